@@ -1,35 +1,14 @@
-use criterion::{criterion_group, criterion_main, Criterion};
-use fips204::traits::{Signer, Verifier};
-use fips204::{ml_dsa_44, ml_dsa_65, ml_dsa_87};
-use rand_core::{CryptoRng, RngCore};
-
-
-// Test RNG to supply incremented values when 'asked'
-#[repr(align(8))]
-struct TestRng {
-    value: u32,
-}
-
-impl RngCore for TestRng {
-    fn next_u32(&mut self) -> u32 { unimplemented!() }
-
-    fn next_u64(&mut self) -> u64 { unimplemented!() }
-
-    fn fill_bytes(&mut self, _out: &mut [u8]) { unimplemented!() }
-
-    fn try_fill_bytes(&mut self, out: &mut [u8]) -> Result<(), rand_core::Error> {
-        out.iter_mut().for_each(|b| *b = self.value.to_le_bytes()[0]);
-        out[0..4].copy_from_slice(&self.value.to_be_bytes());
-        self.value = self.value.wrapping_add(1);
-        Ok(())
-    }
-}
+// Run via `cargo bench --features bench-utils`; see `fips204::bench_utils` for the workloads.
 
-impl CryptoRng for TestRng {}
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use fips204::bench_utils::{self, fill_message, BenchRng, BATCH_SIZES, MESSAGE_SIZES};
+use fips204::traits::{KeyGen, SerDes, Signer, Verifier};
+use fips204::{ml_dsa_44, ml_dsa_65, ml_dsa_87};
 
 
+// Keygen, sign and verify with an 8-byte message; the historical figures in README.md
 pub fn criterion_benchmark(c: &mut Criterion) {
-    let mut rng = TestRng { value: 0 };
+    let mut rng = BenchRng::hedged(0);
     let msg = [0u8, 1, 2, 3, 4, 5, 6, 7];
 
     let (pk44, sk44) = ml_dsa_44::try_keygen_with_rng(&mut rng).unwrap();
@@ -60,5 +39,110 @@ pub fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("ml_dsa_87 pk verify", |b| b.iter(|| pk87.verify(&msg, &sig87, &[])));
 }
 
+
+// Sign and verify across message sizes, deterministic vs hedged signing, and from already
+// expanded keys vs from serialized (byte array) keys which must first be decoded/expanded
+macro_rules! param_set_benchmarks {
+    ($name:ident, $ml_dsa:ident, $label:literal) => {
+        pub fn $name(c: &mut Criterion) {
+            let mut rng = BenchRng::hedged(0);
+            let (pk, sk) = $ml_dsa::KG::keygen_from_seed(&[0x42; 32]);
+            let (pk_bytes, sk_bytes) = (pk.clone().into_bytes(), sk.clone().into_bytes());
+
+            let mut group = c.benchmark_group(concat!($label, " sign"));
+            for size in MESSAGE_SIZES {
+                let mut msg = vec![0u8; size];
+                fill_message(&mut msg);
+                group.throughput(Throughput::Bytes(size as u64));
+                group.bench_with_input(BenchmarkId::new("hedged", size), &msg, |b, msg| {
+                    b.iter(|| sk.try_sign_with_rng(&mut rng, msg, &[]))
+                });
+                group.bench_with_input(BenchmarkId::new("deterministic", size), &msg, |b, msg| {
+                    b.iter(|| sk.try_sign_with_rng(&mut BenchRng::deterministic(), msg, &[]))
+                });
+            }
+            group.finish();
+
+            let mut group = c.benchmark_group(concat!($label, " verify"));
+            for size in MESSAGE_SIZES {
+                let mut msg = vec![0u8; size];
+                fill_message(&mut msg);
+                let sig = sk.try_sign_with_rng(&mut rng, &msg, &[]).unwrap();
+                group.throughput(Throughput::Bytes(size as u64));
+                group.bench_with_input(BenchmarkId::from_parameter(size), &msg, |b, msg| {
+                    b.iter(|| pk.verify(msg, &sig, &[]))
+                });
+            }
+            group.finish();
+
+            let mut msg = [0u8; 32];
+            fill_message(&mut msg);
+            let sig = sk.try_sign_with_rng(&mut rng, &msg, &[]).unwrap();
+            let mut group = c.benchmark_group(concat!($label, " key form"));
+            group.bench_function("sign expanded", |b| b.iter(|| sk.try_sign_with_rng(&mut rng, &msg, &[])));
+            group.bench_function("sign from bytes", |b| {
+                b.iter(|| {
+                    let sk = $ml_dsa::PrivateKey::try_from_bytes(sk_bytes).unwrap();
+                    sk.try_sign_with_rng(&mut rng, &msg, &[])
+                })
+            });
+            group.bench_function("verify expanded", |b| b.iter(|| pk.verify(&msg, &sig, &[])));
+            group.bench_function("verify from bytes", |b| {
+                b.iter(|| {
+                    let pk = $ml_dsa::PublicKey::try_from_bytes(pk_bytes).unwrap();
+                    pk.verify(&msg, &sig, &[])
+                })
+            });
+            group.finish();
+
+            let mut group = c.benchmark_group(concat!($label, " batch sign"));
+            for batch in BATCH_SIZES {
+                let storage: Vec<[u8; 32]> = (0..batch).map(|i| [i as u8; 32]).collect();
+                let messages: Vec<&[u8]> = storage.iter().map(<[u8; 32]>::as_slice).collect();
+                group.throughput(Throughput::Elements(batch as u64));
+                group.bench_with_input(BenchmarkId::new("try_sign_many", batch), &messages, |b, m| {
+                    b.iter(|| sk.try_sign_many_with_rng(&mut rng, m, &[]))
+                });
+                group.bench_with_input(BenchmarkId::new("try_sign loop", batch), &messages, |b, m| {
+                    b.iter(|| {
+                        m.iter().map(|m| sk.try_sign_with_rng(&mut rng, m, &[])).collect::<Vec<_>>()
+                    })
+                });
+            }
+            group.finish();
+        }
+    };
+}
+
+param_set_benchmarks!(ml_dsa_44_benchmark, ml_dsa_44, "ml_dsa_44");
+param_set_benchmarks!(ml_dsa_65_benchmark, ml_dsa_65, "ml_dsa_65");
+param_set_benchmarks!(ml_dsa_87_benchmark, ml_dsa_87, "ml_dsa_87");
+
+
+// Decompose/MakeHint/UseHint in isolation, which otherwise hide within sign and verify
+pub fn hint_benchmark(c: &mut Criterion) {
+    const Q: i32 = 8_380_417;
+    let r: [i32; 256] = core::array::from_fn(|n| (n as i32 * 32_771) % Q);
+    let z: [i32; 256] = core::array::from_fn(|n| (n as i32 * 1_021) % 200_000 - 100_000);
+    let h: [i32; 256] = core::array::from_fn(|n| (n % 7 == 0).into());
+
+    let mut group = c.benchmark_group("hints");
+    for (label, gamma2) in [("gamma2 (q-1)/88", (Q - 1) / 88), ("gamma2 (q-1)/32", (Q - 1) / 32)] {
+        group.bench_function(BenchmarkId::new("high_bits", label), |b| {
+            b.iter(|| bench_utils::high_bits(gamma2, &r))
+        });
+        group.bench_function(BenchmarkId::new("make_hint", label), |b| {
+            b.iter(|| bench_utils::make_hint(gamma2, &z, &r))
+        });
+        group.bench_function(BenchmarkId::new("use_hint", label), |b| {
+            b.iter(|| bench_utils::use_hint(gamma2, &h, &r))
+        });
+    }
+    group.finish();
+}
+
+
 criterion_group!(benches, criterion_benchmark);
-criterion_main!(benches);
+criterion_group!(sweeps, ml_dsa_44_benchmark, ml_dsa_65_benchmark, ml_dsa_87_benchmark);
+criterion_group!(hints, hint_benchmark);
+criterion_main!(benches, sweeps, hints);
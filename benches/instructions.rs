@@ -0,0 +1,55 @@
+// Deterministic instruction-count benchmarks, measured with Cachegrind (valgrind must be installed):
+// $ cargo bench --features bench-utils --bench instructions
+//
+// Unlike the wall-clock figures of `benchmark.rs`, the counts are reproducible across runs and
+// machines (of the same target and compiler), so can be compared numerically to gate regressions.
+// Each function runs in a fresh process and is measured in full, so the operations are stacked:
+// the cost of signing is `keygen_sign_*` less `keygen_*`, and that of verification is
+// `keygen_sign_verify_*` less `keygen_sign_*`. Fixed seeds (and `BenchRng`) make every run
+// perform exactly the same work, including the number of signing rejection iterations.
+
+use fips204::bench_utils::BenchRng;
+use fips204::traits::{KeyGen, Signer, Verifier};
+use fips204::{ml_dsa_44, ml_dsa_65, ml_dsa_87};
+use iai::black_box;
+
+const SEED: [u8; 32] = [0x42; 32];
+const MESSAGE: [u8; 8] = [0u8, 1, 2, 3, 4, 5, 6, 7];
+
+
+macro_rules! instruction_benches {
+    ($ml_dsa:ident, $keygen:ident, $keygen_sign:ident, $keygen_sign_verify:ident) => {
+        fn $keygen() -> ($ml_dsa::PublicKey, $ml_dsa::PrivateKey) {
+            $ml_dsa::KG::keygen_from_seed(black_box(&SEED))
+        }
+
+        fn $keygen_sign() -> [u8; $ml_dsa::SIG_LEN] {
+            let (_pk, sk) = $ml_dsa::KG::keygen_from_seed(black_box(&SEED));
+            sk.try_sign_with_rng(&mut BenchRng::hedged(0), black_box(&MESSAGE), &[]).unwrap()
+        }
+
+        fn $keygen_sign_verify() -> bool {
+            let (pk, sk) = $ml_dsa::KG::keygen_from_seed(black_box(&SEED));
+            let sig = sk.try_sign_with_rng(&mut BenchRng::hedged(0), black_box(&MESSAGE), &[]).unwrap();
+            pk.verify(black_box(&MESSAGE), &sig, &[])
+        }
+    };
+}
+
+
+instruction_benches!(ml_dsa_44, keygen_44, keygen_sign_44, keygen_sign_verify_44);
+instruction_benches!(ml_dsa_65, keygen_65, keygen_sign_65, keygen_sign_verify_65);
+instruction_benches!(ml_dsa_87, keygen_87, keygen_sign_87, keygen_sign_verify_87);
+
+
+iai::main!(
+    keygen_44,
+    keygen_sign_44,
+    keygen_sign_verify_44,
+    keygen_65,
+    keygen_sign_65,
+    keygen_sign_verify_65,
+    keygen_87,
+    keygen_sign_87,
+    keygen_sign_verify_87
+);
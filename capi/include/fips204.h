@@ -0,0 +1,63 @@
+/*
+ * Stable C ABI for the FIPS 204 ML-DSA code (libfips204_capi), ABI version 1.
+ *
+ * Every symbol carries the ABI version in its name; an incompatible ABI will be exported under
+ * new (fips204_v2_*) names alongside these. Keys and signatures use the FIPS 204 encodings, and
+ * msg/ctx may be NULL when their length is zero. Functions return one of the status codes below.
+ */
+#ifndef FIPS204_H
+#define FIPS204_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define FIPS204_V1_ABI_VERSION 1
+
+#define FIPS204_V1_OK 0             /* Success; for verification, the signature is valid */
+#define FIPS204_V1_BAD_SIGNATURE 1  /* Verification only: the signature is invalid */
+#define FIPS204_V1_ERR_NULL (-1)    /* A required pointer was NULL */
+#define FIPS204_V1_ERR_INVALID (-2) /* Malformed key, or context longer than 255 bytes */
+#define FIPS204_V1_ERR_RNG (-3)     /* The random number generator failed */
+#define FIPS204_V1_ERR_SELF_TEST (-4) /* keygen_from_seed only: FIPS mode self-test failed */
+
+uint32_t fips204_v1_abi_version(void);
+
+size_t fips204_v1_ml_dsa_44_pk_len(void);
+size_t fips204_v1_ml_dsa_44_sk_len(void);
+size_t fips204_v1_ml_dsa_44_sig_len(void);
+int fips204_v1_ml_dsa_44_keygen(uint8_t *pk, uint8_t *sk);
+int fips204_v1_ml_dsa_44_keygen_from_seed(const uint8_t *seed, uint8_t *pk, uint8_t *sk);
+int fips204_v1_ml_dsa_44_sign(const uint8_t *sk, const uint8_t *msg, size_t msg_len,
+                              const uint8_t *ctx, size_t ctx_len, uint8_t *sig);
+int fips204_v1_ml_dsa_44_verify(const uint8_t *pk, const uint8_t *msg, size_t msg_len,
+                                const uint8_t *ctx, size_t ctx_len, const uint8_t *sig);
+
+size_t fips204_v1_ml_dsa_65_pk_len(void);
+size_t fips204_v1_ml_dsa_65_sk_len(void);
+size_t fips204_v1_ml_dsa_65_sig_len(void);
+int fips204_v1_ml_dsa_65_keygen(uint8_t *pk, uint8_t *sk);
+int fips204_v1_ml_dsa_65_keygen_from_seed(const uint8_t *seed, uint8_t *pk, uint8_t *sk);
+int fips204_v1_ml_dsa_65_sign(const uint8_t *sk, const uint8_t *msg, size_t msg_len,
+                              const uint8_t *ctx, size_t ctx_len, uint8_t *sig);
+int fips204_v1_ml_dsa_65_verify(const uint8_t *pk, const uint8_t *msg, size_t msg_len,
+                                const uint8_t *ctx, size_t ctx_len, const uint8_t *sig);
+
+size_t fips204_v1_ml_dsa_87_pk_len(void);
+size_t fips204_v1_ml_dsa_87_sk_len(void);
+size_t fips204_v1_ml_dsa_87_sig_len(void);
+int fips204_v1_ml_dsa_87_keygen(uint8_t *pk, uint8_t *sk);
+int fips204_v1_ml_dsa_87_keygen_from_seed(const uint8_t *seed, uint8_t *pk, uint8_t *sk);
+int fips204_v1_ml_dsa_87_sign(const uint8_t *sk, const uint8_t *msg, size_t msg_len,
+                              const uint8_t *ctx, size_t ctx_len, uint8_t *sig);
+int fips204_v1_ml_dsa_87_verify(const uint8_t *pk, const uint8_t *msg, size_t msg_len,
+                                const uint8_t *ctx, size_t ctx_len, const uint8_t *sig);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* FIPS204_H */
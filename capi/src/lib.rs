@@ -0,0 +1,260 @@
+#![deny(clippy::pedantic, warnings, missing_docs)]
+//! Stable C ABI for the FIPS 204 ML-DSA code, suitable for packaging as a shared library.
+//!
+//! Every exported symbol carries the ABI version in its name (`fips204_v1_*`). A future,
+//! incompatible ABI will be exported under new names (`fips204_v2_*`) alongside the old ones, so
+//! a packaged `libfips204_capi` can be upgraded without breaking existing callers. The version
+//! actually implemented is returned by `fips204_v1_abi_version()`. The declarations are in
+//! `include/fips204.h`.
+//!
+//! For each parameter set (`ml_dsa_44`, `ml_dsa_65` and `ml_dsa_87`) the following are exported:
+//!
+//! * `fips204_v1_<set>_pk_len()`, `_sk_len()` and `_sig_len()`: the encoding lengths in bytes;
+//! * `fips204_v1_<set>_keygen(pk, sk)`: key generation with the OS random number generator;
+//! * `fips204_v1_<set>_keygen_from_seed(seed, pk, sk)`: deterministic key generation from a
+//!   32-byte seed, as per `ML-DSA.KeyGen_internal()`;
+//! * `fips204_v1_<set>_sign(sk, msg, msg_len, ctx, ctx_len, sig)`: hedged signing;
+//! * `fips204_v1_<set>_verify(pk, msg, msg_len, ctx, ctx_len, sig)`: verification.
+//!
+//! Keys and signatures use the FIPS 204 encodings. All functions return one of the
+//! `FIPS204_V1_*` status codes below; `msg` and `ctx` may be null when their length is zero.
+
+// Raw pointers from C are only dereferenced in the three helpers below, after a null check.
+#![allow(unsafe_code)]
+
+use core::ffi::c_int;
+use fips204::traits::{SerDes, Verifier};
+use rand_core::{CryptoRng, OsRng, RngCore};
+
+
+/// The ABI version implemented, as returned by `fips204_v1_abi_version()`.
+pub const FIPS204_V1_ABI_VERSION: u32 = 1;
+
+/// Success; for verification, the signature is valid.
+pub const FIPS204_V1_OK: c_int = 0;
+
+/// Verification only: the signature is invalid.
+pub const FIPS204_V1_BAD_SIGNATURE: c_int = 1;
+
+/// A required pointer was null.
+pub const FIPS204_V1_ERR_NULL: c_int = -1;
+
+/// An input was malformed: an improperly encoded key, or a context longer than 255 bytes.
+pub const FIPS204_V1_ERR_INVALID: c_int = -2;
+
+/// The random number generator failed.
+pub const FIPS204_V1_ERR_RNG: c_int = -3;
+
+/// Key generation from a seed only: with `fips-mode`, the self-test failed and the module is in
+/// the error state.
+pub const FIPS204_V1_ERR_SELF_TEST: c_int = -4;
+
+
+/// Returns the ABI version implemented by this library.
+#[no_mangle]
+pub extern "C" fn fips204_v1_abi_version() -> u32 { FIPS204_V1_ABI_VERSION }
+
+
+/// # Safety
+/// When `len` is non-zero, `ptr` must be null or valid for reads of `len` bytes.
+unsafe fn bytes<'a>(ptr: *const u8, len: usize) -> Option<&'a [u8]> {
+    if len == 0 {
+        Some(&[])
+    } else if ptr.is_null() {
+        None
+    } else {
+        Some(core::slice::from_raw_parts(ptr, len))
+    }
+}
+
+
+/// # Safety
+/// `ptr` must be null or valid for reads of `N` bytes.
+unsafe fn array<'a, const N: usize>(ptr: *const u8) -> Option<&'a [u8; N]> { ptr.cast::<[u8; N]>().as_ref() }
+
+
+/// # Safety
+/// `ptr` must be null or valid for writes of `N` bytes, and not alias any other argument.
+unsafe fn array_mut<'a, const N: usize>(ptr: *mut u8) -> Option<&'a mut [u8; N]> {
+    ptr.cast::<[u8; N]>().as_mut()
+}
+
+
+/// The OS random number generator, recording whether it failed so that signing can report
+/// `FIPS204_V1_ERR_RNG` rather than `FIPS204_V1_ERR_INVALID`.
+struct OsRngStatus {
+    failed: bool,
+}
+
+
+impl RngCore for OsRngStatus {
+    fn next_u32(&mut self) -> u32 { OsRng.next_u32() }
+
+    fn next_u64(&mut self) -> u64 { OsRng.next_u64() }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) { OsRng.fill_bytes(dest); }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        let res = OsRng.try_fill_bytes(dest);
+        self.failed |= res.is_err();
+        res
+    }
+}
+
+
+impl CryptoRng for OsRngStatus {}
+
+
+macro_rules! status {
+    ($e:expr) => {
+        match $e {
+            Some(value) => value,
+            None => return FIPS204_V1_ERR_NULL,
+        }
+    };
+}
+
+
+macro_rules! capi {
+    ($set:ident, $pk_len:ident, $sk_len:ident, $sig_len:ident, $keygen:ident, $keygen_from_seed:ident,
+     $sign:ident, $verify:ident) => {
+        /// Returns the public key length in bytes.
+        #[no_mangle]
+        pub extern "C" fn $pk_len() -> usize { fips204::$set::PK_LEN }
+
+
+        /// Returns the private key length in bytes.
+        #[no_mangle]
+        pub extern "C" fn $sk_len() -> usize { fips204::$set::SK_LEN }
+
+
+        /// Returns the signature length in bytes.
+        #[no_mangle]
+        pub extern "C" fn $sig_len() -> usize { fips204::$set::SIG_LEN }
+
+
+        /// Generates a key pair using the OS random number generator, writing the encoded keys
+        /// into `pk` and `sk`.
+        ///
+        /// # Safety
+        /// `pk` and `sk` must be valid for writes of the public and private key lengths.
+        #[no_mangle]
+        pub unsafe extern "C" fn $keygen(pk: *mut u8, sk: *mut u8) -> c_int {
+            let pk = status!(array_mut(pk));
+            let sk = status!(array_mut(sk));
+            match fips204::$set::try_keygen_into(pk, sk) {
+                Ok(()) => FIPS204_V1_OK,
+                Err(_) => FIPS204_V1_ERR_RNG,
+            }
+        }
+
+
+        /// Generates a key pair from the 32-byte `seed`, writing the encoded keys into `pk`
+        /// and `sk`.
+        ///
+        /// # Safety
+        /// `seed` must be valid for reads of 32 bytes, and `pk` and `sk` valid for writes of
+        /// the public and private key lengths.
+        #[no_mangle]
+        pub unsafe extern "C" fn $keygen_from_seed(seed: *const u8, pk: *mut u8, sk: *mut u8) -> c_int {
+            let seed = status!(array(seed));
+            let pk = status!(array_mut(pk));
+            let sk = status!(array_mut(sk));
+            match fips204::$set::try_keygen_from_seed_into(seed, pk, sk) {
+                Ok(()) => FIPS204_V1_OK,
+                Err(_) => FIPS204_V1_ERR_SELF_TEST,
+            }
+        }
+
+
+        /// Signs `msg` under the context string `ctx` using the OS random number generator,
+        /// writing the signature into `sig`.
+        ///
+        /// # Safety
+        /// `sk` must be valid for reads of the private key length, `msg` and `ctx` for reads
+        /// of `msg_len` and `ctx_len` bytes, and `sig` valid for writes of the signature length.
+        #[no_mangle]
+        pub unsafe extern "C" fn $sign(
+            sk: *const u8, msg: *const u8, msg_len: usize, ctx: *const u8, ctx_len: usize, sig: *mut u8,
+        ) -> c_int {
+            let sk = status!(array(sk));
+            let msg = status!(bytes(msg, msg_len));
+            let ctx = status!(bytes(ctx, ctx_len));
+            let sig = status!(array_mut(sig));
+            if ctx.len() > 255 {
+                return FIPS204_V1_ERR_INVALID;
+            }
+            // The private key is expanded from the caller's buffer without copying it
+            let mut rng = OsRngStatus { failed: false };
+            match fips204::$set::try_sign_from_bytes_with_rng(&mut rng, sk, msg, ctx) {
+                Ok(signature) => {
+                    *sig = signature;
+                    FIPS204_V1_OK
+                }
+                Err(_) if rng.failed => FIPS204_V1_ERR_RNG,
+                Err(_) => FIPS204_V1_ERR_INVALID,
+            }
+        }
+
+
+        /// Verifies the signature `sig` over `msg` under the context string `ctx`.
+        ///
+        /// # Safety
+        /// `pk` and `sig` must be valid for reads of the public key and signature lengths, and
+        /// `msg` and `ctx` for reads of `msg_len` and `ctx_len` bytes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $verify(
+            pk: *const u8, msg: *const u8, msg_len: usize, ctx: *const u8, ctx_len: usize, sig: *const u8,
+        ) -> c_int {
+            let pk = status!(array(pk));
+            let msg = status!(bytes(msg, msg_len));
+            let ctx = status!(bytes(ctx, ctx_len));
+            let sig = status!(array(sig));
+            if ctx.len() > 255 {
+                return FIPS204_V1_ERR_INVALID;
+            }
+            let Ok(epk) = fips204::$set::PublicKey::try_from_bytes(*pk) else {
+                return FIPS204_V1_ERR_INVALID;
+            };
+            if epk.verify(msg, sig, ctx) {
+                FIPS204_V1_OK
+            } else {
+                FIPS204_V1_BAD_SIGNATURE
+            }
+        }
+    };
+}
+
+
+capi!(
+    ml_dsa_44,
+    fips204_v1_ml_dsa_44_pk_len,
+    fips204_v1_ml_dsa_44_sk_len,
+    fips204_v1_ml_dsa_44_sig_len,
+    fips204_v1_ml_dsa_44_keygen,
+    fips204_v1_ml_dsa_44_keygen_from_seed,
+    fips204_v1_ml_dsa_44_sign,
+    fips204_v1_ml_dsa_44_verify
+);
+
+capi!(
+    ml_dsa_65,
+    fips204_v1_ml_dsa_65_pk_len,
+    fips204_v1_ml_dsa_65_sk_len,
+    fips204_v1_ml_dsa_65_sig_len,
+    fips204_v1_ml_dsa_65_keygen,
+    fips204_v1_ml_dsa_65_keygen_from_seed,
+    fips204_v1_ml_dsa_65_sign,
+    fips204_v1_ml_dsa_65_verify
+);
+
+capi!(
+    ml_dsa_87,
+    fips204_v1_ml_dsa_87_pk_len,
+    fips204_v1_ml_dsa_87_sk_len,
+    fips204_v1_ml_dsa_87_sig_len,
+    fips204_v1_ml_dsa_87_keygen,
+    fips204_v1_ml_dsa_87_keygen_from_seed,
+    fips204_v1_ml_dsa_87_sign,
+    fips204_v1_ml_dsa_87_verify
+);
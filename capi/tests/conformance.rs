@@ -0,0 +1,158 @@
+// Loads the built shared library (not the rlib) and exercises every exported function through
+// the C ABI, checking the results against the Rust API. The header is checked to declare
+// exactly the exported symbols.
+
+#![allow(unsafe_code)]
+
+use libloading::{Library, Symbol};
+use std::collections::BTreeSet;
+use std::ffi::c_int;
+use std::path::PathBuf;
+
+type LenFn = unsafe extern "C" fn() -> usize;
+type KeyGenFn = unsafe extern "C" fn(*mut u8, *mut u8) -> c_int;
+type KeyGenFromSeedFn = unsafe extern "C" fn(*const u8, *mut u8, *mut u8) -> c_int;
+type SignFn = unsafe extern "C" fn(*const u8, *const u8, usize, *const u8, usize, *mut u8) -> c_int;
+type VerifyFn = unsafe extern "C" fn(*const u8, *const u8, usize, *const u8, usize, *const u8) -> c_int;
+
+const OK: c_int = 0;
+const BAD_SIGNATURE: c_int = 1;
+const ERR_NULL: c_int = -1;
+const ERR_INVALID: c_int = -2;
+
+
+/// The cdylib built alongside this test binary (in `deps/`), or else in the target profile
+/// directory above it (as left by `cargo build`).
+fn library_path() -> PathBuf {
+    let exe = std::env::current_exe().unwrap();
+    let deps = exe.parent().unwrap();
+    let name = libloading::library_filename("fips204_capi");
+    let path = deps.join(&name);
+    if path.exists() {
+        path
+    } else {
+        deps.parent().unwrap().join(name)
+    }
+}
+
+
+fn library() -> Library {
+    let path = library_path();
+    unsafe { Library::new(&path) }.unwrap_or_else(|e| panic!("cannot load {}: {e}", path.display()))
+}
+
+
+/// The function names declared in `include/fips204.h`.
+fn header_symbols() -> BTreeSet<String> {
+    let header = include_str!("../include/fips204.h");
+    // The identifier immediately preceding each '('
+    let segments: Vec<&str> = header.split('(').collect();
+    segments[..segments.len() - 1]
+        .iter()
+        .filter_map(|segment| segment.rsplit(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).next())
+        .filter(|name| name.starts_with("fips204_v1_"))
+        .map(String::from)
+        .collect()
+}
+
+
+macro_rules! check_set {
+    ($lib:expr, $set:ident, $prefix:literal, $tested:expr) => {{
+        use fips204::$set::{KG, PK_LEN, SIG_LEN, SK_LEN};
+        use fips204::traits::{KeyGen, SerDes, Signer, Verifier};
+
+        let lib: &Library = $lib;
+        let tested: &mut BTreeSet<String> = $tested;
+        macro_rules! sym {
+            ($name:literal, $ty:ty) => {{
+                let name = concat!($prefix, $name);
+                assert!(tested.insert(name.to_string()));
+                let f: Symbol<'_, $ty> = unsafe { lib.get(name.as_bytes()) }.unwrap();
+                f
+            }};
+        }
+        let pk_len = sym!("pk_len", LenFn);
+        let sk_len = sym!("sk_len", LenFn);
+        let sig_len = sym!("sig_len", LenFn);
+        let keygen = sym!("keygen", KeyGenFn);
+        let keygen_from_seed = sym!("keygen_from_seed", KeyGenFromSeedFn);
+        let sign = sym!("sign", SignFn);
+        let verify = sym!("verify", VerifyFn);
+
+        unsafe {
+            assert_eq!((pk_len(), sk_len(), sig_len()), (PK_LEN, SK_LEN, SIG_LEN));
+
+            // Seeded key generation matches the Rust API
+            let seed = [0x42u8; 32];
+            let (mut pk, mut sk) = ([0u8; PK_LEN], [0u8; SK_LEN]);
+            assert_eq!(keygen_from_seed(seed.as_ptr(), pk.as_mut_ptr(), sk.as_mut_ptr()), OK);
+            let (rust_pk, rust_sk) = KG::keygen_from_seed(&seed);
+            assert_eq!(pk, rust_pk.clone().into_bytes());
+            assert_eq!(sk, rust_sk.into_bytes());
+            assert_eq!(keygen_from_seed(std::ptr::null(), pk.as_mut_ptr(), sk.as_mut_ptr()), ERR_NULL);
+
+            // Random key generation, signing and verification
+            let (mut pk, mut sk, mut sig) = ([0u8; PK_LEN], [0u8; SK_LEN], [0u8; SIG_LEN]);
+            assert_eq!(keygen(pk.as_mut_ptr(), sk.as_mut_ptr()), OK);
+            assert_eq!(keygen(pk.as_mut_ptr(), std::ptr::null_mut()), ERR_NULL);
+            let (msg, ctx) = (b"message", b"ctx");
+            assert_eq!(sign(sk.as_ptr(), msg.as_ptr(), msg.len(), ctx.as_ptr(), ctx.len(), sig.as_mut_ptr()), OK);
+            assert_eq!(verify(pk.as_ptr(), msg.as_ptr(), msg.len(), ctx.as_ptr(), ctx.len(), sig.as_ptr()), OK);
+            let epk = <fips204::$set::PublicKey as SerDes>::try_from_bytes(pk).unwrap();
+            assert!(epk.verify(msg, &sig, ctx));
+            assert_eq!(verify(pk.as_ptr(), msg.as_ptr(), 6, ctx.as_ptr(), ctx.len(), sig.as_ptr()), BAD_SIGNATURE);
+
+            // Signatures from the Rust API verify through the C ABI, with a null empty message
+            let rust_sig = KG::keygen_from_seed(&seed).1.try_sign(&[], &[]).unwrap();
+            let rust_pk = rust_pk.into_bytes();
+            let null = std::ptr::null();
+            assert_eq!(verify(rust_pk.as_ptr(), null, 0, null, 0, rust_sig.as_ptr()), OK);
+
+            // Invalid inputs
+            let long_ctx = [0u8; 256];
+            assert_eq!(sign(sk.as_ptr(), null, 0, long_ctx.as_ptr(), 256, sig.as_mut_ptr()), ERR_INVALID);
+            assert_eq!(verify(pk.as_ptr(), null, 0, long_ctx.as_ptr(), 256, sig.as_ptr()), ERR_INVALID);
+            assert_eq!(sign([0xFFu8; SK_LEN].as_ptr(), null, 0, null, 0, sig.as_mut_ptr()), ERR_INVALID);
+            assert_eq!(sign(sk.as_ptr(), null, 1, null, 0, sig.as_mut_ptr()), ERR_NULL);
+            assert_eq!(verify(pk.as_ptr(), null, 0, null, 0, null), ERR_NULL);
+        }
+    }};
+}
+
+
+#[test]
+fn conformance() {
+    let lib = library();
+    let mut tested = BTreeSet::new();
+
+    let abi_version: Symbol<'_, unsafe extern "C" fn() -> u32> = unsafe { lib.get(b"fips204_v1_abi_version") }.unwrap();
+    assert_eq!(unsafe { abi_version() }, 1);
+    assert!(tested.insert("fips204_v1_abi_version".to_string()));
+
+    check_set!(&lib, ml_dsa_44, "fips204_v1_ml_dsa_44_", &mut tested);
+    check_set!(&lib, ml_dsa_65, "fips204_v1_ml_dsa_65_", &mut tested);
+    check_set!(&lib, ml_dsa_87, "fips204_v1_ml_dsa_87_", &mut tested);
+
+    assert_eq!(tested, header_symbols(), "include/fips204.h and the tested symbols differ");
+}
+
+
+/// Every `fips204*` symbol exported by the shared library is versioned and declared in the
+/// header. Skipped when `nm` is not available.
+#[test]
+fn exported_symbols_are_versioned() {
+    let path = library_path();
+    let Ok(output) = std::process::Command::new("nm").args(["-D", "--defined-only"]).arg(&path).output() else {
+        return;
+    };
+    if !output.status.success() {
+        return;
+    }
+    let exported: BTreeSet<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_whitespace().last())
+        .filter(|name| name.starts_with("fips204"))
+        .map(String::from)
+        .collect();
+    assert_eq!(exported, header_symbols());
+}
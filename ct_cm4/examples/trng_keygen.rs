@@ -0,0 +1,46 @@
+#![no_std]
+#![no_main]
+
+// This example feeds the nRF52833 hardware TRNG into key generation and signing, via the
+// `fips204::rng::HalRng` adapter (`embedded-hal` feature) over the HAL's `Rng` driver.
+//
+// $ cargo embed --example trng_keygen
+
+use cortex_m::asm;
+use cortex_m_rt::entry;
+use fips204::ml_dsa_44;
+use fips204::rng::HalRng;
+use fips204::traits::{Signer, Verifier};
+use microbit::{board::Board, hal::{pac::DWT, rng::Rng}};
+use panic_rtt_target as _;
+use rtt_target::{rprintln, rtt_init_print};
+
+
+#[entry]
+fn main() -> ! {
+    let mut board = Board::take().unwrap();
+    board.DCB.enable_trace();
+    board.DWT.enable_cycle_counter();
+    rtt_init_print!();
+
+    // The TRNG peripheral (with bias correction enabled by the HAL) supplies `ξ` and each `rnd`
+    let mut rng = HalRng::new(Rng::new(board.RNG));
+
+    let start = DWT::cycle_count();
+    let (pk, sk) = ml_dsa_44::try_keygen_with_rng(&mut rng).unwrap();
+    let finish = DWT::cycle_count();
+    rprintln!("Keygen cycle count: {}", finish - start);
+
+    let mut i = 0u32;
+    loop {
+        let message = i.to_le_bytes();
+        asm::isb();
+        let start = DWT::cycle_count();
+        let sig = sk.try_sign_with_rng(&mut rng, &message, &[]).unwrap();
+        let finish = DWT::cycle_count();
+        asm::isb();
+        assert!(pk.verify(&message, &sig, &[]));
+        rprintln!("Sign cycle count: {}", finish - start);
+        i += 1;
+    }
+}
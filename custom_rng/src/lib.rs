@@ -0,0 +1,46 @@
+#![no_std]
+#![deny(clippy::pedantic, warnings, missing_docs)]
+//! Demonstrates, and checks in CI by building for a bare-metal target, that the `fips204`
+//! `default-rng` feature composes with a `getrandom` custom backend. On targets without OS
+//! support (e.g., `thumbv7em-none-eabi` running an RTOS), `getrandom` calls the function
+//! registered below, and so does every `fips204` function using the OS default random number
+//! generator (e.g., `ml_dsa_44::try_keygen()` and `try_sign()`).
+//!
+//! The registration must appear exactly once in the final binary (typically in the firmware's
+//! board support code); a library should not register a backend on behalf of its users.
+
+use fips204::ml_dsa_44;
+use fips204::traits::Signer;
+use getrandom::{register_custom_getrandom, Error};
+
+
+extern "C" {
+    // Stand-in for the RTOS entropy system call, returning zero on success
+    fn rtos_entropy(buf: *mut u8, len: usize) -> i32;
+}
+
+
+/// Fills `buf` from the RTOS entropy system call.
+///
+/// # Errors
+/// Returns an error when the system call fails.
+#[allow(unsafe_code)] // The foreign function writes at most `buf.len()` bytes into `buf`
+pub fn rtos_getrandom(buf: &mut [u8]) -> Result<(), Error> {
+    match unsafe { rtos_entropy(buf.as_mut_ptr(), buf.len()) } {
+        0 => Ok(()),
+        _ => Err(Error::UNEXPECTED),
+    }
+}
+
+
+register_custom_getrandom!(rtos_getrandom);
+
+
+/// Generates a key pair and signs `message`, drawing all randomness from the RTOS.
+///
+/// # Errors
+/// Returns an error when the RTOS entropy source fails.
+pub fn keygen_and_sign(message: &[u8]) -> Result<[u8; ml_dsa_44::SIG_LEN], &'static str> {
+    let (_pk, sk) = ml_dsa_44::try_keygen()?;
+    sk.try_sign(message, &[])
+}
@@ -0,0 +1,132 @@
+// An end-to-end file signing example using ML-DSA-65: key generation to PEM/DER, signing a
+// file and (streaming) verification with progress reporting.
+//
+// $ cargo run --example fips204-sign --features std -- keygen <dir>
+// $ cargo run --example fips204-sign --features std -- sign <dir>/sk.bin <file> <file>.sig
+// $ cargo run --example fips204-sign --features std -- verify <dir>/pk.pem <file> <file>.sig
+//
+// With no arguments, the above is run as a demonstration within a temporary directory.
+//
+// The public key is written as a DER `SubjectPublicKeyInfo` (pk.der) and its PEM encoding
+// (pk.pem), as used in X.509 certificates. The private key is written in the FIPS 204 byte
+// encoding (sk.bin); protect it accordingly. Signing reads the file into memory, whereas
+// verification streams it in chunks so that multi-GB images can be checked.
+
+use fips204::ml_dsa_65;
+use fips204::traits::{SerDes, Signer};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+const CTX: &[u8] = b"fips204-sign example";
+const PEM_LABEL: &str = "PUBLIC KEY";
+
+
+fn keygen(dir: &Path) -> Result<(), Box<dyn Error>> {
+    let (pk, sk) = ml_dsa_65::try_keygen()?;
+    let der = pk.to_spki_der();
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(dir.join("pk.der"), der)?;
+    std::fs::write(dir.join("pk.pem"), pem_encode(PEM_LABEL, &der))?;
+    std::fs::write(dir.join("sk.bin"), sk.into_bytes())?;
+    println!("wrote {}/{{pk.der, pk.pem, sk.bin}}", dir.display());
+    Ok(())
+}
+
+
+fn sign(sk_path: &Path, file: &Path, sig_path: &Path) -> Result<(), Box<dyn Error>> {
+    let sk_bytes = std::fs::read(sk_path)?;
+    let sk = ml_dsa_65::PrivateKey::try_from_bytes(
+        sk_bytes.try_into().map_err(|_| "private key has the wrong length")?,
+    )?;
+    let sig = sk.try_sign(&std::fs::read(file)?, CTX)?;
+    std::fs::write(sig_path, sig)?;
+    println!("signed {} (key {})", file.display(), hex(&sk.public_fingerprint()[..8]));
+    Ok(())
+}
+
+
+fn verify(pem_path: &Path, file: &Path, sig_path: &Path) -> Result<bool, Box<dyn Error>> {
+    let der = pem_decode(PEM_LABEL, &std::fs::read_to_string(pem_path)?)?;
+    let pk = ml_dsa_65::PublicKey::try_from_spki_der(&der)?;
+    let sig: [u8; ml_dsa_65::SIG_LEN] =
+        std::fs::read(sig_path)?.try_into().map_err(|_| "signature has the wrong length")?;
+    let ok = pk.verify_file(file, &sig, CTX, |done, total| {
+        eprint!("\rverifying {}: {done}/{total} bytes", file.display());
+    })?;
+    eprintln!();
+    println!("signature {}", if ok { "OK" } else { "INVALID" });
+    Ok(ok)
+}
+
+
+fn demo() -> Result<(), Box<dyn Error>> {
+    let dir = std::env::temp_dir().join("fips204-sign-example");
+    keygen(&dir)?;
+    let file = dir.join("firmware.img");
+    std::fs::write(&file, vec![0xA5u8; 5 * 1024 * 1024])?;
+    let sig = dir.join("firmware.img.sig");
+    sign(&dir.join("sk.bin"), &file, &sig)?;
+    assert!(verify(&dir.join("pk.pem"), &file, &sig)?);
+
+    // Any change to the file is detected
+    std::fs::write(&file, vec![0xA4u8; 5 * 1024 * 1024])?;
+    assert!(!verify(&dir.join("pk.pem"), &file, &sig)?);
+    Ok(())
+}
+
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<PathBuf> = std::env::args_os().skip(1).map(PathBuf::from).collect();
+    let args: Vec<&Path> = args.iter().map(PathBuf::as_path).collect();
+    match (args.first().and_then(|cmd| cmd.to_str()), &args[..]) {
+        (None, _) => demo(),
+        (Some("keygen"), [_, dir]) => keygen(dir),
+        (Some("sign"), [_, sk, file, sig]) => sign(sk, file, sig),
+        (Some("verify"), [_, pk, file, sig]) => {
+            if verify(pk, file, sig)? {
+                Ok(())
+            } else {
+                std::process::exit(1)
+            }
+        }
+        _ => Err("usage: fips204-sign [keygen <dir> | sign <sk.bin> <file> <sig> | verify <pk.pem> <file> <sig>]".into()),
+    }
+}
+
+
+// Minimal PEM (RFC 7468) support, so that the example needs no further dependencies
+
+const B64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn pem_encode(label: &str, der: &[u8]) -> String {
+    let mut b64 = String::new();
+    for chunk in der.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |acc, (i, b)| acc | u32::from(*b) << (16 - 8 * i));
+        for i in 0..4 {
+            b64.push(if i <= chunk.len() { char::from(B64[(n >> (18 - 6 * i) & 0x3F) as usize]) } else { '=' });
+        }
+    }
+    let lines: Vec<&str> = b64.as_bytes().chunks(64).map(|l| std::str::from_utf8(l).unwrap()).collect();
+    format!("-----BEGIN {label}-----\n{}\n-----END {label}-----\n", lines.join("\n"))
+}
+
+fn pem_decode(label: &str, pem: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    let body = pem
+        .trim()
+        .strip_prefix(&format!("-----BEGIN {label}-----"))
+        .and_then(|rest| rest.strip_suffix(&format!("-----END {label}-----")))
+        .ok_or("not a PEM public key")?;
+    let sextets = body
+        .bytes()
+        .filter(|b| !b.is_ascii_whitespace() && *b != b'=')
+        .map(|b| B64.iter().position(|&c| c == b).map(|p| p as u32).ok_or("bad base64"))
+        .collect::<Result<Vec<u32>, _>>()?;
+    let mut der = Vec::new();
+    for chunk in sextets.chunks(4) {
+        let n = chunk.iter().enumerate().fold(0u32, |acc, (i, s)| acc | s << (18 - 6 * i));
+        der.extend_from_slice(&n.to_be_bytes()[1..chunk.len()]);
+    }
+    Ok(der)
+}
+
+fn hex(bytes: &[u8]) -> String { bytes.iter().map(|b| format!("{b:02x}")).collect() }
@@ -0,0 +1,33 @@
+// A minimal ML-DSA-65 key generation, signing and verification program whose binary is inspected
+// by `tests/size.rs`, which builds it with only the `ml-dsa-65` feature enabled.
+
+use fips204::ml_dsa_65;
+use fips204::traits::{KeyGen, Signer, Verifier};
+use rand_core::{CryptoRng, RngCore};
+
+
+// Supplies a fixed `rnd`, so the probe needs no OS random number generator
+struct FixedRng;
+
+impl RngCore for FixedRng {
+    fn next_u32(&mut self) -> u32 { 0 }
+
+    fn next_u64(&mut self) -> u64 { 0 }
+
+    fn fill_bytes(&mut self, out: &mut [u8]) { out.fill(0x5A) }
+
+    fn try_fill_bytes(&mut self, out: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(out);
+        Ok(())
+    }
+}
+
+impl CryptoRng for FixedRng {}
+
+
+fn main() {
+    let seed = std::env::args().len().to_le_bytes()[0]; // defeat constant folding
+    let (pk, sk) = ml_dsa_65::KG::keygen_from_seed(&[seed; 32]);
+    let sig = sk.try_sign_with_rng(&mut FixedRng, b"size probe", &[]).unwrap();
+    assert!(pk.verify(b"size probe", &sig, &[]));
+}
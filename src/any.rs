@@ -0,0 +1,324 @@
+// This file implements parameter-set agnostic public key and signature wrappers, so that
+// applications supporting several parameter sets can share a single verification code path.
+
+//! Parameter-set agnostic public keys and signatures.
+//!
+//! [`AnyPublicKey`] and [`AnySignature`] wrap the public keys and signatures of each enabled
+//! parameter set, and are typically constructed via `From` (or from bytes alongside a
+//! [`ParamSet`] identifier carried by the protocol). A signature only ever verifies against a
+//! public key of the same parameter set.
+//!
+//! # Examples
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! # #[cfg(all(feature = "ml-dsa-44", feature = "ml-dsa-87", feature = "default-rng"))] {
+//! use fips204::traits::{SerDes, Signer};
+//! use fips204::{ml_dsa_44, ml_dsa_87, AnyPublicKey, AnySignature, ParamSet};
+//!
+//! let (pk44, sk44) = ml_dsa_44::try_keygen()?;
+//! let (pk87, sk87) = ml_dsa_87::try_keygen()?;
+//! let keys = [AnyPublicKey::from(pk44), AnyPublicKey::from(pk87)];
+//!
+//! let sig44 = AnySignature::from(sk44.try_sign(b"msg", b"ctx")?);
+//! let sig87 = AnySignature::try_from_bytes(ParamSet::MlDsa87, &sk87.try_sign(b"msg", b"ctx")?)?;
+//!
+//! // One code path regardless of the parameter set
+//! assert!(keys[0].verify(b"msg", &sig44, b"ctx"));
+//! assert!(keys[1].verify(b"msg", &sig87, b"ctx"));
+//! assert!(!keys[0].verify(b"msg", &sig87, b"ctx"));
+//! # }
+//! # Ok(())}
+//! ```
+
+use crate::helpers::ensure;
+use crate::traits::{SerDes, Verifier};
+use crate::types::{ParamSet, Ph};
+#[cfg(feature = "ml-dsa-44")]
+use crate::ml_dsa_44;
+#[cfg(feature = "ml-dsa-65")]
+use crate::ml_dsa_65;
+#[cfg(feature = "ml-dsa-87")]
+use crate::ml_dsa_87;
+
+
+/// A public key of any enabled parameter set.
+#[derive(Clone)]
+#[allow(clippy::large_enum_variant)] // no_std, so variants are not boxed
+#[non_exhaustive]
+pub enum AnyPublicKey {
+    /// An ML-DSA-44 public key
+    #[cfg(feature = "ml-dsa-44")]
+    MlDsa44(ml_dsa_44::PublicKey),
+    /// An ML-DSA-65 public key
+    #[cfg(feature = "ml-dsa-65")]
+    MlDsa65(ml_dsa_65::PublicKey),
+    /// An ML-DSA-87 public key
+    #[cfg(feature = "ml-dsa-87")]
+    MlDsa87(ml_dsa_87::PublicKey),
+}
+
+
+/// A signature of any enabled parameter set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[allow(clippy::large_enum_variant)] // no_std, so variants are not boxed
+#[non_exhaustive]
+pub enum AnySignature {
+    /// An ML-DSA-44 signature
+    #[cfg(feature = "ml-dsa-44")]
+    MlDsa44([u8; ml_dsa_44::SIG_LEN]),
+    /// An ML-DSA-65 signature
+    #[cfg(feature = "ml-dsa-65")]
+    MlDsa65([u8; ml_dsa_65::SIG_LEN]),
+    /// An ML-DSA-87 signature
+    #[cfg(feature = "ml-dsa-87")]
+    MlDsa87([u8; ml_dsa_87::SIG_LEN]),
+}
+
+
+impl AnyPublicKey {
+    /// Deserializes (and expands) a public key of the given parameter set.
+    ///
+    /// # Errors
+    /// Returns an error when the parameter set is not enabled, `bytes` has the wrong length,
+    /// or the public key is malformed.
+    pub fn try_from_bytes(param_set: ParamSet, bytes: &[u8]) -> Result<Self, &'static str> {
+        ensure!(bytes.len() == param_set.pk_len(), "AnyPublicKey: wrong length");
+        match param_set {
+            #[cfg(feature = "ml-dsa-44")]
+            ParamSet::MlDsa44 => Ok(Self::MlDsa44(ml_dsa_44::PublicKey::try_from_bytes(
+                bytes.try_into().map_err(|_| "AnyPublicKey: wrong length")?,
+            )?)),
+            #[cfg(feature = "ml-dsa-65")]
+            ParamSet::MlDsa65 => Ok(Self::MlDsa65(ml_dsa_65::PublicKey::try_from_bytes(
+                bytes.try_into().map_err(|_| "AnyPublicKey: wrong length")?,
+            )?)),
+            #[cfg(feature = "ml-dsa-87")]
+            ParamSet::MlDsa87 => Ok(Self::MlDsa87(ml_dsa_87::PublicKey::try_from_bytes(
+                bytes.try_into().map_err(|_| "AnyPublicKey: wrong length")?,
+            )?)),
+            #[allow(unreachable_patterns)]
+            _ => Err("AnyPublicKey: parameter set not enabled"),
+        }
+    }
+
+
+    /// Returns the parameter set of this public key.
+    #[must_use]
+    pub fn param_set(&self) -> ParamSet {
+        match self {
+            #[cfg(feature = "ml-dsa-44")]
+            Self::MlDsa44(_) => ParamSet::MlDsa44,
+            #[cfg(feature = "ml-dsa-65")]
+            Self::MlDsa65(_) => ParamSet::MlDsa65,
+            #[cfg(feature = "ml-dsa-87")]
+            Self::MlDsa87(_) => ParamSet::MlDsa87,
+        }
+    }
+
+
+    /// Verifies a signature of the message with the context string, per
+    /// [`crate::traits::Verifier::verify()`]. Returns `false` when the signature belongs to a
+    /// different parameter set than the public key. As this function operates on purely public
+    /// data, it need/does not provide constant-time assurances.
+    #[must_use]
+    pub fn verify(&self, message: &[u8], sig: &AnySignature, ctx: &[u8]) -> bool {
+        #[allow(unreachable_patterns)]
+        match (self, sig) {
+            #[cfg(feature = "ml-dsa-44")]
+            (Self::MlDsa44(pk), AnySignature::MlDsa44(sig)) => pk.verify(message, sig, ctx),
+            #[cfg(feature = "ml-dsa-65")]
+            (Self::MlDsa65(pk), AnySignature::MlDsa65(sig)) => pk.verify(message, sig, ctx),
+            #[cfg(feature = "ml-dsa-87")]
+            (Self::MlDsa87(pk), AnySignature::MlDsa87(sig)) => pk.verify(message, sig, ctx),
+            _ => false,
+        }
+    }
+
+
+    /// Verifies a HashML-DSA signature of the message with the context string and pre-hash
+    /// function, per [`crate::traits::Verifier::hash_verify()`]. Returns `false` when the
+    /// signature belongs to a different parameter set than the public key.
+    #[must_use]
+    pub fn hash_verify(&self, message: &[u8], sig: &AnySignature, ctx: &[u8], ph: &Ph) -> bool {
+        #[allow(unreachable_patterns)]
+        match (self, sig) {
+            #[cfg(feature = "ml-dsa-44")]
+            (Self::MlDsa44(pk), AnySignature::MlDsa44(sig)) => pk.hash_verify(message, sig, ctx, ph),
+            #[cfg(feature = "ml-dsa-65")]
+            (Self::MlDsa65(pk), AnySignature::MlDsa65(sig)) => pk.hash_verify(message, sig, ctx, ph),
+            #[cfg(feature = "ml-dsa-87")]
+            (Self::MlDsa87(pk), AnySignature::MlDsa87(sig)) => pk.hash_verify(message, sig, ctx, ph),
+            _ => false,
+        }
+    }
+
+
+    /// Returns the 32-byte key fingerprint `H(pk, 32)`; see e.g.
+    /// `ml_dsa_65::PublicKey::fingerprint()`.
+    #[must_use]
+    pub fn fingerprint(&self) -> [u8; 32] {
+        match self {
+            #[cfg(feature = "ml-dsa-44")]
+            Self::MlDsa44(pk) => pk.fingerprint(),
+            #[cfg(feature = "ml-dsa-65")]
+            Self::MlDsa65(pk) => pk.fingerprint(),
+            #[cfg(feature = "ml-dsa-87")]
+            Self::MlDsa87(pk) => pk.fingerprint(),
+        }
+    }
+}
+
+
+impl AnySignature {
+    /// Copies a signature of the given parameter set from `bytes`.
+    ///
+    /// # Errors
+    /// Returns an error when the parameter set is not enabled or `bytes` has the wrong length.
+    pub fn try_from_bytes(param_set: ParamSet, bytes: &[u8]) -> Result<Self, &'static str> {
+        ensure!(bytes.len() == param_set.sig_len(), "AnySignature: wrong length");
+        match param_set {
+            #[cfg(feature = "ml-dsa-44")]
+            ParamSet::MlDsa44 => bytes.try_into().map(Self::MlDsa44),
+            #[cfg(feature = "ml-dsa-65")]
+            ParamSet::MlDsa65 => bytes.try_into().map(Self::MlDsa65),
+            #[cfg(feature = "ml-dsa-87")]
+            ParamSet::MlDsa87 => bytes.try_into().map(Self::MlDsa87),
+            #[allow(unreachable_patterns)]
+            _ => return Err("AnySignature: parameter set not enabled"),
+        }
+        .map_err(|_| "AnySignature: wrong length")
+    }
+
+
+    /// Returns the parameter set of this signature.
+    #[must_use]
+    pub fn param_set(&self) -> ParamSet {
+        match self {
+            #[cfg(feature = "ml-dsa-44")]
+            Self::MlDsa44(_) => ParamSet::MlDsa44,
+            #[cfg(feature = "ml-dsa-65")]
+            Self::MlDsa65(_) => ParamSet::MlDsa65,
+            #[cfg(feature = "ml-dsa-87")]
+            Self::MlDsa87(_) => ParamSet::MlDsa87,
+        }
+    }
+}
+
+
+impl AsRef<[u8]> for AnySignature {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            #[cfg(feature = "ml-dsa-44")]
+            Self::MlDsa44(sig) => sig,
+            #[cfg(feature = "ml-dsa-65")]
+            Self::MlDsa65(sig) => sig,
+            #[cfg(feature = "ml-dsa-87")]
+            Self::MlDsa87(sig) => sig,
+        }
+    }
+}
+
+
+impl TryFrom<&[u8]> for AnyPublicKey {
+    type Error = &'static str;
+
+    /// Deserializes (and expands) a public key, identifying the parameter set from the length
+    /// of `bytes` (the public key lengths of the parameter sets are distinct).
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let param_set = crate::enabled_paramsets().iter().find(|ps| ps.pk_len() == bytes.len());
+        Self::try_from_bytes(*param_set.ok_or("AnyPublicKey: wrong length")?, bytes)
+    }
+}
+
+
+impl TryFrom<&[u8]> for AnySignature {
+    type Error = &'static str;
+
+    /// Copies a signature, identifying the parameter set from the length of `bytes` (the
+    /// signature lengths of the parameter sets are distinct).
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let param_set = crate::enabled_paramsets().iter().find(|ps| ps.sig_len() == bytes.len());
+        Self::try_from_bytes(*param_set.ok_or("AnySignature: wrong length")?, bytes)
+    }
+}
+
+
+#[cfg(feature = "ml-dsa-44")]
+impl From<ml_dsa_44::PublicKey> for AnyPublicKey {
+    fn from(pk: ml_dsa_44::PublicKey) -> Self { Self::MlDsa44(pk) }
+}
+
+#[cfg(feature = "ml-dsa-65")]
+impl From<ml_dsa_65::PublicKey> for AnyPublicKey {
+    fn from(pk: ml_dsa_65::PublicKey) -> Self { Self::MlDsa65(pk) }
+}
+
+#[cfg(feature = "ml-dsa-87")]
+impl From<ml_dsa_87::PublicKey> for AnyPublicKey {
+    fn from(pk: ml_dsa_87::PublicKey) -> Self { Self::MlDsa87(pk) }
+}
+
+#[cfg(feature = "ml-dsa-44")]
+impl From<[u8; ml_dsa_44::SIG_LEN]> for AnySignature {
+    fn from(sig: [u8; ml_dsa_44::SIG_LEN]) -> Self { Self::MlDsa44(sig) }
+}
+
+#[cfg(feature = "ml-dsa-65")]
+impl From<[u8; ml_dsa_65::SIG_LEN]> for AnySignature {
+    fn from(sig: [u8; ml_dsa_65::SIG_LEN]) -> Self { Self::MlDsa65(sig) }
+}
+
+#[cfg(feature = "ml-dsa-87")]
+impl From<[u8; ml_dsa_87::SIG_LEN]> for AnySignature {
+    fn from(sig: [u8; ml_dsa_87::SIG_LEN]) -> Self { Self::MlDsa87(sig) }
+}
+
+
+#[cfg(all(test, feature = "ml-dsa-44", feature = "ml-dsa-65", feature = "ml-dsa-87"))]
+mod tests {
+    use super::*;
+    use crate::traits::Signer;
+    use rand_chacha::rand_core::SeedableRng;
+
+    #[test]
+    #[allow(clippy::large_stack_arrays)]
+    fn test_any_verify() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+        let (pk44, sk44) = ml_dsa_44::try_keygen_with_rng(&mut rng).unwrap();
+        let (pk65, sk65) = ml_dsa_65::try_keygen_with_rng(&mut rng).unwrap();
+        let (pk87, sk87) = ml_dsa_87::try_keygen_with_rng(&mut rng).unwrap();
+
+        let pks = [
+            AnyPublicKey::from(pk44.clone()),
+            AnyPublicKey::try_from_bytes(ParamSet::MlDsa65, &pk65.into_bytes()).unwrap(),
+            AnyPublicKey::from(pk87),
+        ];
+        let sigs = [
+            AnySignature::from(sk44.try_sign_with_rng(&mut rng, b"msg", b"ctx").unwrap()),
+            AnySignature::from(sk65.try_sign_with_rng(&mut rng, b"msg", b"ctx").unwrap()),
+            AnySignature::from(sk87.try_sign_with_rng(&mut rng, b"msg", b"ctx").unwrap()),
+        ];
+
+        for (i, pk) in pks.iter().enumerate() {
+            assert_eq!(pk.param_set(), sigs[i].param_set());
+            for (j, sig) in sigs.iter().enumerate() {
+                assert_eq!(pk.verify(b"msg", sig, b"ctx"), i == j);
+            }
+            assert!(!pk.verify(b"msg", &sigs[i], b"other ctx"));
+        }
+
+        let sig = AnySignature::try_from_bytes(ParamSet::MlDsa44, sigs[0].as_ref()).unwrap();
+        assert_eq!(sig, sigs[0]);
+        assert!(AnySignature::try_from_bytes(ParamSet::MlDsa65, sigs[0].as_ref()).is_err());
+        assert!(AnyPublicKey::try_from_bytes(ParamSet::MlDsa87, &pk44.clone().into_bytes()).is_err());
+
+        for sig in &sigs {
+            assert_eq!(&AnySignature::try_from(sig.as_ref()).unwrap(), sig);
+        }
+        let pk_bytes = pk44.into_bytes();
+        assert_eq!(AnyPublicKey::try_from(&pk_bytes[..]).unwrap().param_set(), ParamSet::MlDsa44);
+        assert!(AnyPublicKey::try_from(&pk_bytes[1..]).is_err());
+        assert!(AnySignature::try_from(&sigs[0].as_ref()[1..]).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+// This file implements an audit log hook receiving non-secret metadata about key generation and
+// signing operations, for security audit trails.
+
+//! Audit logging of key generation and signing, only exposed when the `audit` feature is enabled.
+//!
+//! The audited functions of each parameter set (e.g., `ml_dsa_44::try_keygen_audited()` and
+//! `ml_dsa_44::PrivateKey::try_sign_audited()`) behave exactly as their plain counterparts,
+//! and additionally report each successful operation to an [`AuditLog`] as an [`AuditEvent`].
+//! Events carry only non-secret metadata: the parameter set, a timestamp taken from the log's
+//! injected clock, the public key fingerprint and the number of signing iterations. Failed
+//! operations are not recorded; their error is returned to the caller as usual.
+//!
+//! # Examples
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+//! use fips204::audit::{AuditEvent, AuditLog, AuditOperation};
+//! use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+//! use fips204::Context;
+//!
+//! struct Trail(Vec<AuditEvent>);
+//!
+//! impl AuditLog for Trail {
+//!     fn now(&mut self) -> u64 { 1_750_000_000 } // E.g., seconds since the Unix epoch
+//!     fn record(&mut self, event: &AuditEvent) { self.0.push(*event) }
+//! }
+//!
+//! let mut trail = Trail(Vec::new());
+//! let (pk, sk) = ml_dsa_44::try_keygen_audited(&mut trail)?;
+//! let _sig = sk.try_sign_audited(b"message", &Context::new(b"ctx"), &mut trail)?;
+//!
+//! assert_eq!(trail.0[0].operation, AuditOperation::KeyGen);
+//! assert_eq!(trail.0[1].operation, AuditOperation::Sign);
+//! assert!(trail.0.iter().all(|event| event.fingerprint == pk.fingerprint()));
+//! # }
+//! # Ok(())}
+//! ```
+
+use crate::types::ParamSet;
+
+
+/// The operation an [`AuditEvent`] records.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum AuditOperation {
+    /// A key pair was generated.
+    KeyGen,
+    /// A message was signed with pure ML-DSA.
+    Sign,
+    /// A message was signed with HashML-DSA.
+    HashSign,
+}
+
+
+/// The non-secret metadata of a completed key generation or signing operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AuditEvent {
+    /// The operation performed.
+    pub operation: AuditOperation,
+    /// The parameter set of the key.
+    pub param_set: ParamSet,
+    /// The time at which the operation started, per [`AuditLog::now()`].
+    pub timestamp: u64,
+    /// The 32-byte public key fingerprint `H(pk, 32)` of the key generated or used.
+    pub fingerprint: [u8; 32],
+    /// The number of iterations of the signing rejection sampling loop, which depends only on
+    /// public and per-signature values; zero for key generation.
+    pub iterations: u32,
+}
+
+
+/// Receives an [`AuditEvent`] for every audited operation, e.g., to append it to a security
+/// audit trail.
+pub trait AuditLog {
+    /// Returns the current time (in whatever unit the log uses, e.g., seconds since the Unix
+    /// epoch), as `no_std` targets have no common clock.
+    fn now(&mut self) -> u64;
+
+
+    /// Records a completed operation.
+    fn record(&mut self, event: &AuditEvent);
+}
+
+
+#[cfg(all(test, feature = "ml-dsa-44"))]
+mod tests {
+    use super::*;
+    use crate::ml_dsa_44;
+    use crate::traits::{Signer, Verifier};
+    use crate::types::{Context, Ph};
+    use rand_chacha::rand_core::SeedableRng;
+
+    struct Trail {
+        clock: u64,
+        events: [Option<AuditEvent>; 4],
+        len: usize,
+    }
+
+    impl AuditLog for Trail {
+        fn now(&mut self) -> u64 {
+            self.clock += 10;
+            self.clock
+        }
+
+        fn record(&mut self, event: &AuditEvent) {
+            self.events[self.len] = Some(*event);
+            self.len += 1;
+        }
+    }
+
+    #[test]
+    fn test_audit_events() {
+        let mut trail = Trail { clock: 0, events: [None; 4], len: 0 };
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+        let (pk, sk) = ml_dsa_44::try_keygen_audited_with_rng(&mut rng, &mut trail).unwrap();
+
+        // The signatures are those of the plain signing functions with the same randomness
+        let mut rng2 = rng.clone();
+        let pure = Context::new(b"ctx");
+        let sig = sk.try_sign_audited_with_rng(&mut rng, b"msg", &pure, &mut trail).unwrap();
+        assert_eq!(sig, sk.try_sign_with_rng(&mut rng2, b"msg", b"ctx").unwrap());
+        let prehashed = Context::new(b"ctx").with_hash(Ph::SHA512);
+        let sig = sk.try_sign_audited_with_rng(&mut rng, b"msg", &prehashed, &mut trail).unwrap();
+        assert_eq!(sig, sk.try_hash_sign_with_rng(&mut rng2, b"msg", b"ctx", &Ph::SHA512).unwrap());
+        assert!(pk.hash_verify(b"msg", &sig, b"ctx", &Ph::SHA512));
+
+        // Failures are not recorded
+        assert!(sk.try_sign_audited_with_rng(&mut rng, b"msg", &Context::new(&[0u8; 256]), &mut trail).is_err());
+
+        assert_eq!(trail.len, 3);
+        let operations = [AuditOperation::KeyGen, AuditOperation::Sign, AuditOperation::HashSign];
+        for (i, event) in trail.events.iter().flatten().enumerate() {
+            assert_eq!(event.operation, operations[i]);
+            assert_eq!(event.param_set, ParamSet::MlDsa44);
+            assert_eq!(event.fingerprint, pk.fingerprint());
+            assert_eq!(event.timestamp, 10 * (i as u64 + 1));
+            assert_eq!(event.iterations == 0, i == 0);
+        }
+    }
+}
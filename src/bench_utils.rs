@@ -0,0 +1,120 @@
+// This file implements the shared workload definitions behind `benches/benchmark.rs`, so that
+// downstream users can reproduce (or extend) the published numbers on their own hardware.
+
+//! Benchmark workloads, only exposed when the `bench-utils` feature is enabled.
+//!
+//! The crate's own criterion suite is run via `cargo bench --features bench-utils`. The message
+//! sizes, batch sizes and random number generators below are exactly those used there.
+//!
+//! **These random number generators are NOT secure and must never be used outside of
+//! benchmarking.**
+
+use crate::high_low::{high_bits_poly, make_hint_poly, use_hint_poly};
+use crate::types::R;
+use rand_core::{CryptoRng, RngCore};
+
+
+/// Message sizes (in bytes) swept by the signing and verification benchmarks: 32B to 1MiB.
+pub const MESSAGE_SIZES: [usize; 5] = [32, 1024, 16 * 1024, 256 * 1024, 1024 * 1024];
+
+/// Batch sizes used by the batch signing benchmarks.
+pub const BATCH_SIZES: [usize; 4] = [1, 4, 16, 64];
+
+
+/// Fills `msg` with a fixed, non-trivial pattern so that runs are comparable across machines.
+pub fn fill_message(msg: &mut [u8]) {
+    for (i, b) in msg.iter_mut().enumerate() {
+        #[allow(clippy::cast_possible_truncation)] // intentional
+        let byte = (i as u8).wrapping_mul(31).wrapping_add(7);
+        *b = byte;
+    }
+}
+
+
+/// A fast, reproducible random number generator for benchmarking. The hedged variant supplies
+/// an incrementing counter, while the deterministic variant supplies all zeros, which matches
+/// the `rnd` value of the FIPS 204 deterministic signing variant.
+///
+/// **This is NOT a secure random number generator.**
+#[derive(Clone, Debug)]
+pub struct BenchRng {
+    value: u32,
+    deterministic: bool,
+}
+
+
+impl BenchRng {
+    /// Returns a generator that supplies fresh (counter-based) values on every request.
+    #[must_use]
+    pub fn hedged(seed: u32) -> Self { Self { value: seed, deterministic: false } }
+
+
+    /// Returns a generator that always supplies zeros, i.e., deterministic signing.
+    #[must_use]
+    pub fn deterministic() -> Self { Self { value: 0, deterministic: true } }
+}
+
+
+impl RngCore for BenchRng {
+    fn next_u32(&mut self) -> u32 { rand_core::impls::next_u32_via_fill(self) }
+
+    fn next_u64(&mut self) -> u64 { rand_core::impls::next_u64_via_fill(self) }
+
+    fn fill_bytes(&mut self, out: &mut [u8]) {
+        if self.deterministic {
+            out.fill(0);
+            return;
+        }
+        out.fill(self.value.to_le_bytes()[0]);
+        let len = out.len().min(4);
+        out[..len].copy_from_slice(&self.value.to_be_bytes()[..len]);
+        self.value = self.value.wrapping_add(1);
+    }
+
+    fn try_fill_bytes(&mut self, out: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(out);
+        Ok(())
+    }
+}
+
+
+impl CryptoRng for BenchRng {}
+
+
+/// `HighBits` (i.e., `Decompose`) over one polynomial, isolated from signing. `gamma2` is
+/// `(q-1)/88` for ML-DSA-44 and `(q-1)/32` otherwise; coefficients are expected in `[0, q)`.
+#[must_use]
+pub fn high_bits(gamma2: i32, r: &[i32; 256]) -> [i32; 256] { high_bits_poly(gamma2, &[R(*r)])[0].0 }
+
+
+/// `MakeHint` over one polynomial, isolated from signing; see [`high_bits()`].
+#[must_use]
+pub fn make_hint(gamma2: i32, z: &[i32; 256], r: &[i32; 256]) -> [i32; 256] {
+    make_hint_poly(gamma2, &[R(*z)], &[R(*r)])[0].0
+}
+
+
+/// `UseHint` over one polynomial, isolated from verification; `h` holds 0/1 hint bits. See
+/// [`high_bits()`].
+#[must_use]
+pub fn use_hint(gamma2: i32, h: &[i32; 256], r: &[i32; 256]) -> [i32; 256] {
+    use_hint_poly(gamma2, &[R(*h)], &[R(*r)])[0].0
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bench_rng() {
+        let (mut a, mut b) = ([1u8; 32], [1u8; 32]);
+        BenchRng::deterministic().fill_bytes(&mut a);
+        assert_eq!(a, [0u8; 32]);
+        let mut rng = BenchRng::hedged(5);
+        rng.fill_bytes(&mut a);
+        rng.fill_bytes(&mut b);
+        assert_ne!(a, b);
+        assert_eq!(a[..4], 5u32.to_be_bytes());
+    }
+}
@@ -0,0 +1,297 @@
+// This file implements a small self-describing container for a signature together with what is
+// needed to verify it: the parameter set, the public key or its fingerprint, the context string
+// and the (optional) HashML-DSA pre-hash function.
+
+//! Verification bundles: a signature together with its verification parameters.
+//!
+//! A [`Bundle`] carries a signature alongside the parameter set, the public key (or its 32-byte
+//! fingerprint), the context string and, for HashML-DSA, the pre-hash function, so that
+//! applications need not invent their own envelopes around raw byte arrays. The encoding is:
+//!
+//! | Field       | Length          | Contents                                                    |
+//! |-------------|-----------------|-------------------------------------------------------------|
+//! | magic       | 4               | `MLDB`                                                      |
+//! | version     | 1               | `1`                                                         |
+//! | parameters  | 1               | Final NIST OID arc: `0x11`, `0x12` or `0x13` for ML-DSA-44/65/87 |
+//! | flags       | 1               | Bit 0: full public key (else fingerprint); bit 1: pre-hash  |
+//! | key         | `PK_LEN` or 32  | The encoded public key, or its fingerprint `H(pk, 32)`      |
+//! | context     | 1 + `len`       | Length byte, then the context string                        |
+//! | pre-hash    | 11 (if flagged) | DER object identifier of the pre-hash function, per [`Ph::oid()`] |
+//! | signature   | `SIG_LEN`       | The encoded signature                                       |
+//!
+//! Parsing is strict: unknown versions, flags or parameter sets, and trailing bytes, are
+//! rejected. A public key embedded in a bundle is **not** trusted by verification; the verifier
+//! supplies the public key it trusts, which must match the bundle's key or fingerprint.
+//!
+//! # Examples
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! # #[cfg(all(feature = "ml-dsa-65", feature = "default-rng"))] {
+//! use fips204::bundle::{Bundle, BundleKey, MAX_LEN};
+//! use fips204::traits::Signer;
+//! use fips204::{ml_dsa_65, AnyPublicKey, ParamSet};
+//!
+//! let (pk, sk) = ml_dsa_65::try_keygen()?;
+//! let sig = sk.try_sign(b"release notes", b"releases")?;
+//! let bundle = Bundle {
+//!     param_set: ParamSet::MlDsa65,
+//!     key: BundleKey::Fingerprint(pk.fingerprint()),
+//!     ctx: b"releases",
+//!     ph: None,
+//!     sig: &sig,
+//! };
+//! let mut buf = [0u8; MAX_LEN];
+//! let len = bundle.encode(&mut buf)?;
+//!
+//! // The recipient trusts `pk`, and checks the bundle against it
+//! let parsed = Bundle::parse(&buf[..len])?;
+//! assert!(parsed.verify(&AnyPublicKey::from(pk), b"release notes"));
+//! # }
+//! # Ok(())}
+//! ```
+
+use crate::any::{AnyPublicKey, AnySignature};
+use crate::hashing::h256_xof;
+use crate::helpers::ensure;
+use crate::types::{ParamSet, Ph};
+use sha3::digest::XofReader;
+
+
+const MAGIC: [u8; 4] = *b"MLDB";
+const VERSION: u8 = 1;
+const FLAG_PUBLIC_KEY: u8 = 0x01;
+const FLAG_PREHASH: u8 = 0x02;
+const HEADER_LEN: usize = 7;
+
+/// The maximum encoded length of a bundle, over all parameter sets.
+pub const MAX_LEN: usize = HEADER_LEN + 2592 + 1 + 255 + 11 + 4627;
+
+
+/// The key a [`Bundle`] was signed with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BundleKey<'a> {
+    /// The encoded public key.
+    PublicKey(&'a [u8]),
+    /// The public key fingerprint `H(pk, 32)`.
+    Fingerprint([u8; 32]),
+}
+
+
+/// A signature together with its verification parameters; see the
+/// [module documentation](self) for the encoding.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bundle<'a> {
+    /// The parameter set of the key and signature.
+    pub param_set: ParamSet,
+    /// The public key, or its fingerprint.
+    pub key: BundleKey<'a>,
+    /// The context string (255 bytes or fewer).
+    pub ctx: &'a [u8],
+    /// The pre-hash function for HashML-DSA, or `None` for ML-DSA.
+    pub ph: Option<Ph>,
+    /// The encoded signature.
+    pub sig: &'a [u8],
+}
+
+
+impl<'a> Bundle<'a> {
+    /// Returns the encoded length of the bundle.
+    #[must_use]
+    pub fn encoded_len(&self) -> usize {
+        let key_len = match self.key {
+            BundleKey::PublicKey(pk) => pk.len(),
+            BundleKey::Fingerprint(_) => 32,
+        };
+        HEADER_LEN + key_len + 1 + self.ctx.len() + if self.ph.is_some() { 11 } else { 0 } + self.sig.len()
+    }
+
+
+    /// Encodes the bundle into `out`, returning the number of bytes written.
+    ///
+    /// # Errors
+    /// Returns an error when the public key or signature length does not match the parameter
+    /// set, the context string is too long, or `out` is too short.
+    #[allow(clippy::cast_possible_truncation)] // ctx length checked
+    pub fn encode(&self, out: &mut [u8]) -> Result<usize, &'static str> {
+        ensure!(self.sig.len() == self.param_set.sig_len(), "Bundle: wrong signature length");
+        ensure!(self.ctx.len() < 256, "Bundle: ctx too long");
+        if let BundleKey::PublicKey(pk) = self.key {
+            ensure!(pk.len() == self.param_set.pk_len(), "Bundle: wrong public key length");
+        }
+        let len = self.encoded_len();
+        ensure!(out.len() >= len, "Bundle: output too short");
+
+        let mut flags = 0;
+        if matches!(self.key, BundleKey::PublicKey(_)) {
+            flags |= FLAG_PUBLIC_KEY;
+        }
+        if self.ph.is_some() {
+            flags |= FLAG_PREHASH;
+        }
+        let mut pos = 0;
+        let mut put = |bytes: &[u8]| {
+            out[pos..pos + bytes.len()].copy_from_slice(bytes);
+            pos += bytes.len();
+        };
+        put(&MAGIC);
+        put(&[VERSION, param_set_id(self.param_set), flags]);
+        match &self.key {
+            BundleKey::PublicKey(pk) => put(pk),
+            BundleKey::Fingerprint(fp) => put(fp),
+        }
+        put(&[self.ctx.len() as u8]);
+        put(self.ctx);
+        if let Some(ph) = &self.ph {
+            put(&ph.oid());
+        }
+        put(self.sig);
+        Ok(len)
+    }
+
+
+    /// Parses an encoded bundle, borrowing the key, context string and signature from `bytes`.
+    ///
+    /// # Errors
+    /// Returns an error when `bytes` is not exactly a well-formed bundle.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, &'static str> {
+        let mut rest = bytes;
+        let mut take = |len: usize| -> Result<&'a [u8], &'static str> {
+            ensure!(rest.len() >= len, "Bundle: truncated");
+            let (head, tail) = rest.split_at(len);
+            rest = tail;
+            Ok(head)
+        };
+        ensure!(take(4)? == MAGIC, "Bundle: bad magic");
+        let [version, id, flags] = <[u8; 3]>::try_from(take(3)?).map_err(|_| "Bundle: truncated")?;
+        ensure!(version == VERSION, "Bundle: unsupported version");
+        ensure!(flags & !(FLAG_PUBLIC_KEY | FLAG_PREHASH) == 0, "Bundle: unknown flags");
+        let param_set = match id {
+            0x11 => ParamSet::MlDsa44,
+            0x12 => ParamSet::MlDsa65,
+            0x13 => ParamSet::MlDsa87,
+            _ => return Err("Bundle: unknown parameter set"),
+        };
+        let key = if flags & FLAG_PUBLIC_KEY == 0 {
+            BundleKey::Fingerprint(take(32)?.try_into().map_err(|_| "Bundle: truncated")?)
+        } else {
+            BundleKey::PublicKey(take(param_set.pk_len())?)
+        };
+        let ctx_len = take(1)?[0];
+        let ctx = take(usize::from(ctx_len))?;
+        let ph = if flags & FLAG_PREHASH == 0 {
+            None
+        } else {
+            Some(Ph::from_oid(take(11)?).ok_or("Bundle: unknown pre-hash function")?)
+        };
+        let sig = take(param_set.sig_len())?;
+        ensure!(rest.is_empty(), "Bundle: trailing bytes");
+        Ok(Self { param_set, key, ctx, ph, sig })
+    }
+
+
+    /// Returns the fingerprint `H(pk, 32)` of the bundle's key.
+    #[must_use]
+    pub fn fingerprint(&self) -> [u8; 32] {
+        match self.key {
+            BundleKey::PublicKey(pk) => {
+                let mut fp = [0u8; 32];
+                h256_xof(&[pk]).read(&mut fp);
+                fp
+            }
+            BundleKey::Fingerprint(fp) => fp,
+        }
+    }
+
+
+    /// Verifies the bundle's signature of the message under the trusted public key `pk`, with
+    /// the bundle's context string and pre-hash function. Returns `false` when `pk` does not
+    /// match the bundle's parameter set and key (or fingerprint), or the parameter set is not
+    /// enabled. As this function operates on purely public data, it need/does not provide
+    /// constant-time assurances.
+    #[must_use]
+    pub fn verify(&self, pk: &AnyPublicKey, message: &[u8]) -> bool {
+        if pk.param_set() != self.param_set || pk.fingerprint() != self.fingerprint() {
+            return false;
+        }
+        let Ok(sig) = AnySignature::try_from_bytes(self.param_set, self.sig) else {
+            return false;
+        };
+        match &self.ph {
+            None => pk.verify(message, &sig, self.ctx),
+            Some(ph) => pk.hash_verify(message, &sig, self.ctx, ph),
+        }
+    }
+}
+
+
+const fn param_set_id(param_set: ParamSet) -> u8 {
+    match param_set {
+        ParamSet::MlDsa44 => 0x11,
+        ParamSet::MlDsa65 => 0x12,
+        ParamSet::MlDsa87 => 0x13,
+    }
+}
+
+
+#[cfg(test)]
+#[cfg(all(feature = "ml-dsa-44", feature = "ml-dsa-65"))]
+mod tests {
+    use super::*;
+    use crate::traits::{SerDes, Signer};
+    use crate::{ml_dsa_44, ml_dsa_65};
+    use rand_chacha::rand_core::SeedableRng;
+
+    #[test]
+    fn test_bundle_roundtrip() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+        let (pk, sk) = ml_dsa_44::try_keygen_with_rng(&mut rng).unwrap();
+        let (other_pk, _) = ml_dsa_65::try_keygen_with_rng(&mut rng).unwrap();
+        let pk_bytes = pk.clone().into_bytes();
+        let sig = sk.try_sign_with_rng(&mut rng, b"msg", b"ctx").unwrap();
+        let hash_sig = sk.try_hash_sign_with_rng(&mut rng, b"msg", &[], &Ph::SHAKE128).unwrap();
+        let (pk, other_pk) = (AnyPublicKey::from(pk), AnyPublicKey::from(other_pk));
+
+        let bundles = [
+            Bundle { param_set: ParamSet::MlDsa44, key: BundleKey::PublicKey(&pk_bytes), ctx: b"ctx", ph: None, sig: &sig },
+            Bundle {
+                param_set: ParamSet::MlDsa44,
+                key: BundleKey::Fingerprint(pk.fingerprint()),
+                ctx: &[],
+                ph: Some(Ph::SHAKE128),
+                sig: &hash_sig,
+            },
+        ];
+        let mut buf = [0u8; MAX_LEN];
+        for bundle in &bundles {
+            let len = bundle.encode(&mut buf).unwrap();
+            assert_eq!(len, bundle.encoded_len());
+            let parsed = Bundle::parse(&buf[..len]).unwrap();
+            assert_eq!(&parsed, bundle);
+            assert!(parsed.verify(&pk, b"msg"));
+            assert!(!parsed.verify(&pk, b"other msg"));
+            assert!(!parsed.verify(&other_pk, b"msg"));
+
+            // Strict parsing
+            assert_eq!(Bundle::parse(&buf[..len - 1]), Err("Bundle: truncated"));
+            assert_eq!(Bundle::parse(&buf[..=len]), Err("Bundle: trailing bytes"));
+            for (pos, value, err) in [
+                (0, b'X', "Bundle: bad magic"),
+                (4, 2, "Bundle: unsupported version"),
+                (5, 0x14, "Bundle: unknown parameter set"),
+                (6, 0x04, "Bundle: unknown flags"),
+            ] {
+                let mut bad = buf;
+                bad[pos] = value;
+                assert_eq!(Bundle::parse(&bad[..len]), Err(err));
+            }
+        }
+
+        // A fingerprint that does not match the trusted key
+        let wrong = Bundle { key: BundleKey::Fingerprint([0u8; 32]), ..bundles[0].clone() };
+        assert!(!wrong.verify(&pk, b"msg"));
+        let short = Bundle { sig: &sig[1..], ..bundles[0].clone() };
+        assert_eq!(short.encode(&mut buf), Err("Bundle: wrong signature length"));
+        assert_eq!(bundles[0].encode(&mut buf[..100]), Err("Bundle: output too short"));
+    }
+}
@@ -0,0 +1,64 @@
+// This file implements deterministic CBOR (RFC 8949 section 4.2.1) encodings of ML-DSA public
+// keys and signatures, as used by CTAP2/WebAuthn authenticators. A public key is a COSE_Key
+// map per the IETF COSE ML-DSA draft, `{1: 7 (kty: AKP), 3: alg, -1: pub}`, with the requested
+// (provisional) algorithm identifiers -48, -49 and -50 for ML-DSA-44/65/87. As in CTAP2 and
+// COSE, a signature is carried as a byte string. The map keys are in deterministic (bytewise
+// encoded) order and all lengths are fixed per parameter set, so each encoding is a fixed
+// prefix followed by the raw key or signature; only this deterministic form is decoded.
+
+use crate::types::ParamSet;
+
+/// Length of the `COSE_Key` header preceding the public key bytes, for all parameter sets.
+pub(crate) const KEY_PREFIX_LEN: usize = 10;
+
+/// Length of the byte string header preceding the signature bytes, for all parameter sets.
+pub(crate) const SIG_PREFIX_LEN: usize = 3;
+
+
+/// Returns the (provisional) COSE algorithm identifier of the parameter set.
+pub(crate) const fn cose_alg(param_set: ParamSet) -> i32 {
+    match param_set {
+        ParamSet::MlDsa44 => -48,
+        ParamSet::MlDsa65 => -49,
+        ParamSet::MlDsa87 => -50,
+    }
+}
+
+
+/// Returns the `COSE_Key` header preceding the public key bytes for the given parameter set.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // lengths are all < 64KiB
+pub(crate) const fn key_prefix(param_set: ParamSet) -> [u8; KEY_PREFIX_LEN] {
+    let pk_len = param_set.pk_len();
+    let alg = (-1 - cose_alg(param_set)) as u8; // negative integer argument, all < 256
+    [
+        0xa3, // map(3)
+        0x01, 0x07, // kty: AKP
+        0x03, 0x38, alg, // alg
+        0x20, 0x59, (pk_len >> 8) as u8, pk_len as u8, // pub: bytes(pk_len)
+    ]
+}
+
+
+/// Returns the byte string header preceding the signature bytes for the given parameter set.
+#[allow(clippy::cast_possible_truncation)] // lengths are all < 64KiB
+pub(crate) const fn sig_prefix(param_set: ParamSet) -> [u8; SIG_PREFIX_LEN] {
+    let sig_len = param_set.sig_len();
+    [0x59, (sig_len >> 8) as u8, sig_len as u8]
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefixes() {
+        // {1: 7, 3: -48, -1: h'...'} with a 1312 byte public key
+        let expected = [0xa3, 0x01, 0x07, 0x03, 0x38, 0x2f, 0x20, 0x59, 0x05, 0x20];
+        assert_eq!(key_prefix(ParamSet::MlDsa44), expected);
+        assert_eq!(key_prefix(ParamSet::MlDsa65)[5], 0x30);
+        assert_eq!(key_prefix(ParamSet::MlDsa87)[5], 0x31);
+        assert_eq!(sig_prefix(ParamSet::MlDsa44), [0x59, 0x09, 0x74]); // 2420 bytes
+        assert_eq!(sig_prefix(ParamSet::MlDsa87), [0x59, 0x12, 0x13]); // 4627 bytes
+    }
+}
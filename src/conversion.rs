@@ -225,6 +225,18 @@ pub(crate) fn simple_bit_unpack(v: &[u8], b: i32) -> Result<R, &'static str> {
 /// # Errors
 /// Returns an error on `w` out of range.
 pub(crate) fn bit_unpack(v: &[u8], a: i32, b: i32) -> Result<R, &'static str> {
+    let w_out = bit_unpack_unchecked(v, a, b);
+    let bitlen = bit_length(a + b);
+    let bot = i32::abs(b - (1 << bitlen) + 1); // b − 2^c + 1 (as abs)
+    ensure!(is_in_range(&w_out, bot, b), "Alg 19: w out of range");
+    Ok(w_out)
+}
+
+
+/// As [`bit_unpack()`], but without the final range check, which is left to the caller. This
+/// allows secret data to be range checked in constant time across many polynomials, with a
+/// single aggregated (secret-independent) error decision; see `sk_decode()`.
+pub(crate) fn bit_unpack_unchecked(v: &[u8], a: i32, b: i32) -> R {
     debug_assert!((0..(1024 * 1024)).contains(&a), "Alg 19: a out of range");
     debug_assert!((1..(1024 * 1024)).contains(&b), "Alg 19: b out of range");
     debug_assert_eq!(v.len(), 32 * bit_length(a + b), "Alg 19: bad output size");
@@ -237,17 +249,22 @@ pub(crate) fn bit_unpack(v: &[u8], a: i32, b: i32) -> Result<R, &'static str> {
     // 5: end for
     // 6: return 𝑤
 
-    let bitlen = bit_length(a + b).try_into().expect("Alg 19: try_into fail");
+    // Bytes are inserted on the left/MSB of an unsigned accumulator and coefficients popped
+    // from the right/LSB, mirroring `bit_pack()`; the byte order is fixed by the shifts alone,
+    // so the result is independent of the platform endianness.
+    let bitlen = bit_length(a + b);
+    let mask = (1u32 << bitlen) - 1;
     let mut w_out = R([0i32; 256]);
-    let mut temp = 0i32;
+    let mut temp = 0u32;
     let mut r_index = 0;
     let mut bit_index = 0;
 
     for byte in v {
-        temp |= i32::from(*byte) << bit_index;
+        temp |= u32::from(*byte) << bit_index;
         bit_index += 8;
         while bit_index >= bitlen {
-            let tmask = temp & ((1 << bitlen) - 1);
+            // At most 20 bits, so the conversion cannot fail
+            let tmask = i32::try_from(temp & mask).expect("Alg 19: try_from fail");
             // choice fixed by security parameter, so CT
             w_out.0[r_index] = if a == 0 { tmask } else { b - tmask };
             bit_index -= bitlen;
@@ -256,9 +273,7 @@ pub(crate) fn bit_unpack(v: &[u8], a: i32, b: i32) -> Result<R, &'static str> {
         }
     }
 
-    let bot = i32::abs(b - (1 << bitlen) + 1); // b − 2^c + 1 (as abs)
-    ensure!(is_in_range(&w_out, bot, b), "Alg 19: w out of range");
-    Ok(w_out)
+    w_out
 }
 
 
@@ -287,7 +302,9 @@ pub(crate) fn hint_bit_pack<const CTEST: bool, const K: usize>(
     );
 
     // 1: y ∈ B^{ω+k} ← 0^{ω+k}
-    y_bytes.iter_mut().for_each(|e| *e = 0);
+    for e in y_bytes.iter_mut() {
+        *e = 0;
+    }
 
     // 2: Index ← 0    ▷ Index for writing the first 𝜔 bytes of 𝑦
     let mut index = 0;
@@ -302,7 +319,7 @@ pub(crate) fn hint_bit_pack<const CTEST: bool, const K: usize>(
             // CT patch path
             if CTEST && (index > (y_bytes.len() - 1)) {
                 continue;
-            };
+            }
             // CT patch path
             if CTEST || (h[i].0[j] != 0) {
                 //
@@ -404,8 +421,10 @@ pub(crate) fn hint_bit_unpack<const K: usize>(
         // 19: end for
     }
 
-    debug_assert!(
-        h.iter().all(|r| r.0.iter().filter(|&&e| e == 1).sum::<i32>() <= omega),
+    // Checked in release builds too, as `y` is untrusted; this is implied by the
+    // step 4 bound on Index
+    ensure!(
+        h.iter().map(|r| r.0.iter().sum::<i32>()).sum::<i32>() <= omega,
         "Alg 21: too many 1's in h"
     );
 
@@ -414,6 +433,34 @@ pub(crate) fn hint_bit_unpack<const K: usize>(
 }
 
 
+
+/// A lenient variant of Algorithm 21 `HintBitUnpack(y)` that tolerates unsorted (or repeated)
+/// indices within each polynomial and non-zero padding bytes, both of which are rejected by
+/// the strict `hint_bit_unpack()`. Used only to canonicalize signatures, so that the resulting
+/// `h` can be re-encoded into its unique canonical form.
+///
+/// # Errors
+/// Returns an error when the per-polynomial cumulative counts are decreasing or exceed `ω`.
+pub(crate) fn hint_bit_unpack_lenient<const K: usize>(
+    omega: i32, y_bytes: &[u8],
+) -> Result<[R; K], &'static str> {
+    let omega_u = usize::try_from(omega).expect("Alg 21: omega try_into fail");
+    debug_assert_eq!(y_bytes.len(), omega_u + K, "Alg 21: bad output size");
+    let mut h: [R; K] = [R0; K];
+    let mut index = 0;
+    for i in 0..K {
+        let end = usize::from(y_bytes[omega_u + i]);
+        if (end < index) || (end > omega_u) {
+            return Err("Alg 21: malformed hint counts");
+        }
+        for &j in &y_bytes[index..end] {
+            h[i].0[usize::from(j)] = 1;
+        }
+        index = end;
+    }
+    Ok(h)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -462,6 +509,7 @@ mod tests {
         assert_eq!(-4, res);
     }
 
+    #[cfg(debug_assertions)]
     #[should_panic]
     #[allow(clippy::should_panic_without_expect)]
     #[test]
@@ -471,6 +519,7 @@ mod tests {
         assert!(res.is_err());
     }
 
+    #[cfg(debug_assertions)]
     #[should_panic]
     #[allow(clippy::should_panic_without_expect)]
     #[test]
@@ -520,6 +569,103 @@ mod tests {
         assert!(res.is_err());
     }
 
+    // The tests below are deterministic and small enough to run under MIRI:
+    // $ cargo +nightly miri test --lib -- conversion encodings
+
+    /// Algorithms 9 and 12 (`IntegerToBits` then `BitsToBytes`) applied literally, bit by bit,
+    /// as a reference for `bit_pack()`.
+    fn reference_bit_pack(w: &R, a: i32, b: i32, bytes_out: &mut [u8]) {
+        let bitlen = bit_length(a + b);
+        bytes_out.fill(0);
+        for (i, &coeff) in w.0.iter().enumerate() {
+            let value = if a == 0 { coeff } else { b - coeff };
+            for j in 0..bitlen {
+                let bit = u8::from((value >> j) & 1 == 1);
+                bytes_out[(i * bitlen + j) / 8] |= bit << ((i * bitlen + j) % 8);
+            }
+        }
+    }
+
+    /// A deterministic polynomial with coefficients spread across `[−a, b]`.
+    fn spread(a: i32, b: i32, seed: i32) -> R {
+        let span = i64::from(a) + i64::from(b) + 1;
+        R(core::array::from_fn(|i| {
+            let step = (i64::try_from(i).unwrap() * 7919 + i64::from(seed) * 104_729) % span;
+            i32::try_from(step - i64::from(a)).unwrap()
+        }))
+    }
+
+    #[test]
+    fn test_bit_pack_known_answer() {
+        // Coefficients 0, 1, 2, 3 in 10 bits each are packed least significant bit first
+        let w = R(core::array::from_fn(|i| i32::try_from(i % 1024).unwrap()));
+        let mut bytes = [0u8; 32 * 10];
+        simple_bit_pack(&w, 1023, &mut bytes);
+        assert_eq!(bytes[0..5], [0x00, 0x04, 0x20, 0xC0, 0x00]);
+        assert_eq!(simple_bit_unpack(&bytes, 1023).unwrap(), w);
+
+        // BitPack stores b − w, so w = b encodes as zero and w = −a as all ones
+        let mut bytes = [0u8; 32 * 3];
+        bit_pack(&R([2; 256]), 2, 2, &mut bytes);
+        assert!(bytes.iter().all(|&byte| byte == 0));
+        bit_pack(&R([-2; 256]), 2, 2, &mut bytes);
+        assert_eq!(bytes[0..3], [0x24, 0x49, 0x92]); // 4 = 0b100 repeated
+    }
+
+    #[test]
+    fn test_bit_pack_matches_reference() {
+        // Every (a, b) used by the encodings: w1, t1, s1/s2, t0 and z
+        let params = [
+            (0, 43),
+            (0, 15),
+            (0, 1023),
+            (2, 2),
+            (4, 4),
+            ((1 << 12) - 1, 1 << 12),
+            ((1 << 17) - 1, 1 << 17),
+            ((1 << 19) - 1, 1 << 19),
+        ];
+        let mut packed = [0u8; 32 * 20];
+        let mut expected = [0u8; 32 * 20];
+        for (a, b) in params {
+            let len = 32 * bit_length(a + b);
+            for seed in 0..3 {
+                let w = spread(a, b, seed);
+                bit_pack(&w, a, b, &mut packed[..len]);
+                reference_bit_pack(&w, a, b, &mut expected[..len]);
+                assert_eq!(packed[..len], expected[..len], "a={a} b={b}");
+                assert_eq!(bit_unpack(&packed[..len], a, b).unwrap(), w, "a={a} b={b}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_hint_bit_pack_roundtrip() {
+        let mut h = [R0, R0, R0, R0];
+        h[0].0[3] = 1;
+        h[0].0[255] = 1;
+        h[2].0[0] = 1;
+        let mut y = [0u8; 80 + 4];
+        hint_bit_pack::<false, 4>(80, &h, &mut y);
+        assert_eq!(y[0..4], [3, 255, 0, 0]);
+        assert_eq!(y[80..], [2, 2, 3, 3]);
+        assert_eq!(hint_bit_unpack::<4>(80, &y).unwrap(), h);
+        assert_eq!(hint_bit_unpack_lenient::<4>(80, &y).unwrap(), h);
+
+        // Unsorted indices and non-zero padding are rejected, except by the lenient variant
+        let mut unsorted = y;
+        unsorted.swap(0, 1);
+        assert!(hint_bit_unpack::<4>(80, &unsorted).is_err());
+        assert_eq!(hint_bit_unpack_lenient::<4>(80, &unsorted).unwrap(), h);
+        let mut padded = y;
+        padded[79] = 1;
+        assert!(hint_bit_unpack::<4>(80, &padded).is_err());
+        let mut overlong = y;
+        overlong[83] = 81;
+        assert!(hint_bit_unpack::<4>(80, &overlong).is_err());
+        assert!(hint_bit_unpack_lenient::<4>(80, &overlong).is_err());
+    }
+
     #[test]
     fn test_simple_bit_pack_validation1() {
         let mut random_bytes = [0u8; 32 * 6];
@@ -0,0 +1,52 @@
+// This file implements verification failure reasons for test tooling and interop debugging.
+
+//! Verification diagnostics, only exposed when the `diagnostics` feature is enabled.
+//!
+//! **Do not enable this feature in production.** Revealing why a signature was rejected can
+//! serve as an oracle to an attacker probing a verifier; the production verification API
+//! therefore returns only success or failure. See e.g. `ml_dsa_44::PublicKey::verify_diagnostic()`.
+
+use core::fmt;
+
+
+/// The reason a signature failed verification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VerifyFailure {
+    /// The context string is longer than 255 bytes.
+    ContextTooLong,
+    /// The hint counts exceed `ω` or are out of order, per Algorithm 21 `HintBitUnpack()`.
+    HintCountExceeded,
+    /// The signature is otherwise malformed, e.g., unsorted hint indices or non-zero padding.
+    DecodeFailure,
+    /// The infinity norm of the response `z` is not below `γ1 − β`.
+    ZNormTooLarge,
+    /// The recomputed challenge hash `c_tilde′` does not match the signature's `c_tilde`.
+    CTildeMismatch,
+}
+
+
+impl VerifyFailure {
+    /// Classifies an internal verification error.
+    pub(crate) fn from_internal(err: &'static str) -> Self {
+        match err {
+            "Alg 21a: returns ⊥ (4)" => VerifyFailure::HintCountExceeded,
+            "Alg 8: z out of range" => VerifyFailure::ZNormTooLarge,
+            "Alg 8: c_tilde mismatch" => VerifyFailure::CTildeMismatch,
+            _ => VerifyFailure::DecodeFailure,
+        }
+    }
+}
+
+
+impl fmt::Display for VerifyFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            VerifyFailure::ContextTooLong => "context string too long",
+            VerifyFailure::HintCountExceeded => "hint count exceeded",
+            VerifyFailure::DecodeFailure => "signature decode failure",
+            VerifyFailure::ZNormTooLarge => "z norm too large",
+            VerifyFailure::CTildeMismatch => "c_tilde mismatch",
+        })
+    }
+}
@@ -1,9 +1,10 @@
 // This file implements functionality from FIPS 204 section 7.2 Encodings of ML-DSA Keys and Signatures
 
 use crate::conversion::{
-    bit_pack, bit_unpack, hint_bit_pack, hint_bit_unpack, simple_bit_pack, simple_bit_unpack,
+    bit_pack, bit_unpack, bit_unpack_unchecked, hint_bit_pack, hint_bit_unpack,
+    hint_bit_unpack_lenient, simple_bit_pack, simple_bit_unpack,
 };
-use crate::helpers::{bit_length, is_in_range};
+use crate::helpers::{bit_length, ensure, is_in_range, out_of_range_mask};
 use crate::types::{R, R0};
 use crate::{D, Q};
 
@@ -94,6 +95,19 @@ pub(crate) fn pk_decode<const K: usize, const PK_LEN: usize>(
 pub(crate) fn sk_encode<const K: usize, const L: usize, const SK_LEN: usize>(
     eta: i32, rho: &[u8; 32], k: &[u8; 32], tr: &[u8; 64], s_1: &[R; L], s_2: &[R; K], t_0: &[R; K],
 ) -> [u8; SK_LEN] {
+    let mut sk = [0u8; SK_LEN];
+    sk_encode_into::<K, L, SK_LEN>(eta, rho, k, tr, s_1, s_2, t_0, &mut sk);
+    sk
+}
+
+
+/// As `sk_encode()`, but writes the private key into the caller's buffer so that the encoding
+/// is never held anywhere else (see `ml_dsa::key_gen_into()`). Every byte of `sk` is written.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn sk_encode_into<const K: usize, const L: usize, const SK_LEN: usize>(
+    eta: i32, rho: &[u8; 32], k: &[u8; 32], tr: &[u8; 64], s_1: &[R; L], s_2: &[R; K], t_0: &[R; K],
+    sk: &mut [u8; SK_LEN],
+) {
     let top = 1 << (D - 1);
     debug_assert!((eta == 2) || (eta == 4), "Alg 24: incorrect eta");
     debug_assert!(s_1.iter().all(|x| is_in_range(x, eta, eta)), "Alg 24: s1 out of range");
@@ -105,8 +119,6 @@ pub(crate) fn sk_encode<const K: usize, const L: usize, const SK_LEN: usize>(
         "Alg 24: bad sk/config size"
     );
 
-    let mut sk = [0u8; SK_LEN];
-
     // 1: sk ← rho || 𝐾 || tr
     sk[0..32].copy_from_slice(rho);
     sk[32..64].copy_from_slice(k);
@@ -148,7 +160,6 @@ pub(crate) fn sk_encode<const K: usize, const L: usize, const SK_LEN: usize>(
     debug_assert_eq!(start + K * step, sk.len(), "Alg 24: length miscalc");
 
     // 11: return sk
-    sk
 }
 
 
@@ -190,7 +201,7 @@ pub(crate) fn sk_decode<const K: usize, const L: usize, const SK_LEN: usize>(
     for i in 0..L {
         //
         // 3: s1[i] ← BitUnpack(yi, η, η)   ▷ This may lie outside [−η, η], if input is malformed
-        s_1[i] = bit_unpack(&sk[start + i * step..start + (i + 1) * step], eta, eta)?;
+        s_1[i] = bit_unpack_unchecked(&sk[start + i * step..start + (i + 1) * step], eta, eta);
 
         // 4: end for
     }
@@ -200,11 +211,16 @@ pub(crate) fn sk_decode<const K: usize, const L: usize, const SK_LEN: usize>(
     for i in 0..K {
         //
         // 6: s2[i] ← BitUnpack(zi, η, η) ▷ This may lie outside [−η, η], if input is malformed
-        s_2[i] = bit_unpack(&sk[start + i * step..start + (i + 1) * step], eta, eta)?;
+        s_2[i] = bit_unpack_unchecked(&sk[start + i * step..start + (i + 1) * step], eta, eta);
 
         // 7: end for
     }
 
+    // The s1 and s2 range checks are made across all coefficients in constant time, with a
+    // single error decision, so that a malformed key does not reveal where it is malformed
+    let bad = s_1.iter().chain(s_2.iter()).fold(0, |acc, r| acc | out_of_range_mask(r, eta, eta));
+    ensure!(bad == 0, "Alg 25: s1 or s2 out of range");
+
     // 8: for i from 0 to k − 1 do
     let start = start + K * step;
     let step = 32 * D as usize;
@@ -328,6 +344,33 @@ pub(crate) fn sig_decode<
 }
 
 
+/// Re-encodes a signature into its unique canonical byte form: hint indices sorted (and
+/// de-duplicated) within each polynomial, and zero padding. Decoding `z` is bijective, so only
+/// the hint portion can differ from its canonical form.
+///
+/// # Errors
+/// Returns an error when the hint counts are malformed (and so no canonical form exists).
+pub(crate) fn sig_canonicalize<
+    const K: usize,
+    const L: usize,
+    const LAMBDA_DIV4: usize,
+    const SIG_LEN: usize,
+>(
+    gamma1: i32, omega: i32, sigma: &[u8; SIG_LEN],
+) -> Result<[u8; SIG_LEN], &'static str> {
+    let start = LAMBDA_DIV4;
+    let step = 32 * (bit_length(gamma1 - 1) + 1);
+    let c_tilde: [u8; LAMBDA_DIV4] =
+        sigma[..LAMBDA_DIV4].try_into().expect("cannot fail; LAMBDA_DIV4 < SIG_LEN");
+    let mut z: [R; L] = [R0; L];
+    for i in 0..L {
+        z[i] = bit_unpack(&sigma[start + i * step..start + (i + 1) * step], gamma1 - 1, gamma1)?;
+    }
+    let h = hint_bit_unpack_lenient::<K>(omega, &sigma[start + L * step..])?;
+    Ok(sig_encode::<false, K, L, LAMBDA_DIV4, SIG_LEN>(gamma1, omega, &c_tilde, &z, &h))
+}
+
+
 /// # Algorithm 28: `w1Encode(w1)` on page 35.
 /// Encodes a polynomial vector `w1` into a bit string.
 ///
@@ -369,7 +412,9 @@ mod tests {
     fn test_pk_encode_decode_roundtrip1() {
         // D=13 K=4 PK_LEN=1312
         let mut random_pk = [0u8; 1312];
-        random_pk.iter_mut().for_each(|a| *a = rand::random::<u8>());
+        for a in &mut random_pk {
+            *a = rand::random::<u8>();
+        }
         //let mut rho = [0u8; 32];
         //let mut t1 = [[0i32; 256]; 4];
         let (rho, t1) = pk_decode::<4, 1312>(&random_pk).unwrap();
@@ -382,7 +427,9 @@ mod tests {
     fn test_pk_encode_decode_roundtrip2() {
         // D=13 K=6 PK_LEN=1952
         let mut random_pk = [0u8; 1952];
-        random_pk.iter_mut().for_each(|a| *a = rand::random::<u8>());
+        for a in &mut random_pk {
+            *a = rand::random::<u8>();
+        }
         //let mut rho = [0u8; 32];
         //let mut t1 = [[0i32; 256]; 6];
         let (rho, t1) = pk_decode::<6, 1952>(&random_pk).unwrap();
@@ -395,7 +442,9 @@ mod tests {
     fn test_pk_encode_decode_roundtrip3() {
         // D=13 K=8 PK_LEN=2592
         let mut random_pk = [0u8; 2592];
-        random_pk.iter_mut().for_each(|a| *a = rand::random::<u8>());
+        for a in &mut random_pk {
+            *a = rand::random::<u8>();
+        }
         //let mut rho = [0u8; 32];
         //let mut t1 = [[0i32; 256]; 8];
         let (rho, t1) = pk_decode::<8, 2592>(&random_pk).unwrap();
@@ -419,7 +468,9 @@ mod tests {
         // D=13 ETA=2 K=4 L=4 SK_LEN=2560
         let (rho, k) = (rand::random::<[u8; 32]>(), rand::random::<[u8; 32]>());
         let mut tr = [0u8; 64];
-        tr.iter_mut().for_each(|e| *e = rand::random::<u8>());
+        for e in &mut tr {
+            *e = rand::random::<u8>();
+        }
         let s1 = [get_vec(2), get_vec(2), get_vec(2), get_vec(2)];
         let s2 = [get_vec(2), get_vec(2), get_vec(2), get_vec(2)];
         let t0 = [
@@ -444,6 +495,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sk_decode_range() {
+        // D=13 ETA=2 K=4 L=4 SK_LEN=2560; each s1/s2 coefficient is 3 bits of 2 − x
+        let (s1, s2, t0) = ([get_vec(2), get_vec(2), get_vec(2), get_vec(2)], [R0; 4], [R0; 4]);
+        let sk = sk_encode::<4, 4, 2560>(2, &[0; 32], &[0; 32], &[0; 64], &s1, &s2, &t0);
+        assert!(sk_decode::<4, 4, 2560>(2, &sk).is_ok());
+
+        // First coefficient of s1 and last coefficient of s2 each set to 2 − 7 = −5
+        for (index, bits) in [(128, 0x07), (128 + 8 * 96 - 1, 0xE0)] {
+            let mut bad = sk;
+            bad[index] |= bits;
+            assert_eq!(sk_decode::<4, 4, 2560>(2, &bad).err(), Some("Alg 25: s1 or s2 out of range"));
+        }
+    }
+
+    // The `bad .../config size` debug assertions of the encoders and decoders, checked for
+    // every parameter set: (K, L, eta, gamma1, omega, lambda/4, param set)
+    #[test]
+    fn test_config_sizes() {
+        use crate::types::ParamSet;
+        for (k, l, eta, gamma1, omega, lambda_div4, ps) in [
+            (4, 4, 2, 1 << 17, 80, 32, ParamSet::MlDsa44),
+            (6, 5, 4, 1 << 19, 55, 48, ParamSet::MlDsa65),
+            (8, 7, 2, 1 << 19, 75, 64, ParamSet::MlDsa87),
+        ] {
+            const BLQD: usize = bit_length(Q - 1) - D as usize;
+            assert_eq!(ps.pk_len(), 32 + 32 * k * BLQD);
+            assert_eq!(ps.sk_len(), 128 + 32 * ((k + l) * bit_length(2 * eta) + D as usize * k));
+            assert_eq!(
+                ps.sig_len(),
+                lambda_div4 + l * 32 * (1 + bit_length(gamma1 - 1)) + usize::try_from(omega).unwrap() + k
+            );
+            assert!((1..256).contains(&(usize::try_from(omega).unwrap() + k)));
+        }
+    }
+
+    fn decode_arbitrary<
+        const K: usize,
+        const L: usize,
+        const PK_LEN: usize,
+        const SK_LEN: usize,
+        const SIG_LEN: usize,
+        const LAMBDA_DIV4: usize,
+    >(
+        eta: i32, gamma1: i32, omega: i32,
+    ) {
+        let mut rng = rand::thread_rng();
+        for fill in [None, Some(0x00), Some(0xFF)] {
+            let (mut pk, mut sk, mut sig) = ([0u8; PK_LEN], [0u8; SK_LEN], [0u8; SIG_LEN]);
+            for bytes in [&mut pk[..], &mut sk[..], &mut sig[..]] {
+                match fill {
+                    None => rng.fill_bytes(bytes),
+                    Some(b) => bytes.fill(b),
+                }
+            }
+            drop(pk_decode::<K, PK_LEN>(&pk));
+            drop(sk_decode::<K, L, SK_LEN>(eta, &sk));
+            drop(sig_decode::<K, L, LAMBDA_DIV4, SIG_LEN>(gamma1, omega, &sig));
+        }
+    }
+
+    // Arbitrary (untrusted) input must only ever produce an error, never a (debug) assertion
+    #[test]
+    fn decoders_never_panic() {
+        for _ in 0..100 {
+            decode_arbitrary::<4, 4, 1312, 2560, 2420, 32>(2, 1 << 17, 80);
+            decode_arbitrary::<6, 5, 1952, 4032, 3309, 48>(4, 1 << 19, 55);
+            decode_arbitrary::<8, 7, 2592, 4896, 4627, 64>(2, 1 << 19, 75);
+        }
+    }
+
     #[test]
     fn test_sig_roundtrip() {
         // GAMMA1=2^17 K=4 L=4 LAMBDA=128 OMEGA=80
@@ -459,4 +581,37 @@ mod tests {
         assert!(z.iter().zip(z_test.iter()).all(|(a, b)| a.0 == b.0));
         assert!(h.iter().zip(h_test.unwrap().iter()).all(|(a, b)| a.0 == b.0));
     }
+
+    #[test]
+    fn test_sig_canonicalize() {
+        // GAMMA1=2^17 K=4 L=4 LAMBDA=128 OMEGA=80
+        let z = [get_vec(2), get_vec(2), get_vec(2), get_vec(2)];
+        let mut h = [R0, R0, R0, R0];
+        h[1].0[9] = 1;
+        h[1].0[200] = 1;
+        let sigma = sig_encode::<false, 4, 4, 32, 2420>(1 << 17, 80, &[7u8; 32], &z, &h);
+        assert_eq!(sig_canonicalize::<4, 4, 32, 2420>(1 << 17, 80, &sigma).unwrap(), sigma);
+
+        // Swapped hint indices are canonicalized back to the original encoding
+        let hints = 32 + 4 * 32 * 18;
+        let mut swapped = sigma;
+        swapped.swap(hints, hints + 1);
+        assert!(sig_decode::<4, 4, 32, 2420>(1 << 17, 80, &swapped).is_err());
+        assert_eq!(sig_canonicalize::<4, 4, 32, 2420>(1 << 17, 80, &swapped).unwrap(), sigma);
+    }
+
+    #[test]
+    fn test_w1_encode() {
+        // GAMMA2=(Q-1)/88 gives 6-bit coefficients; GAMMA2=(Q-1)/32 gives 4-bit coefficients
+        for (gamma2, bits) in [((Q - 1) / 88, 6), ((Q - 1) / 32, 4)] {
+            let max = u32::try_from((Q - 1) / (2 * gamma2)).unwrap();
+            let w1 = [get_vec(max), get_vec(max), get_vec(max), get_vec(max)];
+            let mut w1_tilde = [0u8; 4 * 32 * 6];
+            let w1_tilde = &mut w1_tilde[..4 * 32 * bits];
+            w1_encode::<4>(gamma2, &w1, w1_tilde);
+            for (i, chunk) in w1_tilde.chunks(32 * bits).enumerate() {
+                assert_eq!(simple_bit_unpack(chunk, (Q - 1) / (2 * gamma2) - 1).unwrap(), w1[i]);
+            }
+        }
+    }
 }
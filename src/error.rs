@@ -0,0 +1,130 @@
+// This file implements the typed error returned by the length-erased (runtime dispatch) and
+// validating APIs.
+
+use crate::types::ParamSet;
+use core::fmt;
+
+
+/// Typed error for APIs that accept length-erased (slice) inputs and therefore must decide at
+/// runtime which parameter set the input belongs to (the fixed-size array APIs prevent these
+/// mix-ups at compile time instead), such as e.g. `ml_dsa_44::PublicKey::try_from_slice()`,
+/// and for the deep public key validation of e.g.
+/// `ml_dsa_44::PublicKey::try_from_bytes_validated()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+#[allow(variant_size_differences)] // 24 bytes in all, and `Copy`
+pub enum Error {
+    /// The input belongs to a different parameter set (`found`) than the key it was used with
+    /// (`expected`); `found` is `None` when its length matches no parameter set at all.
+    WrongParamSet {
+        /// The parameter set of the key.
+        expected: ParamSet,
+        /// The parameter set implied by the length of the input, if any.
+        found: Option<ParamSet>,
+    },
+    /// The signature is correctly sized but did not verify.
+    InvalidSignature,
+    /// The public key seed `rho` is degenerate (all bytes equal), which an honest key
+    /// generation produces with negligible probability.
+    DegenerateRho,
+    /// The public key `t1` is entirely zero, for which signatures can be forged without the
+    /// private key.
+    DegenerateT1,
+    /// The expanded (cached NTT) public key values do not re-derive the encoded `t1`.
+    ExpansionMismatch,
+    /// The encoded key or signature is `got` bytes long rather than the `expected` length.
+    /// `likely_paramset` names the parameter set whose encoding of the same kind has length
+    /// `got`, pointing to a misconfigured parameter set rather than a truncated input; it is
+    /// `None` when the length matches no parameter set.
+    WrongLength {
+        /// The length the parser required.
+        expected: usize,
+        /// The length of the input.
+        got: usize,
+        /// The parameter set implied by the length of the input, if any.
+        likely_paramset: Option<ParamSet>,
+    },
+    /// The encoded key is correctly sized but malformed (e.g., a private key coefficient of
+    /// `s_1` or `s_2` is out of range).
+    MalformedKey,
+    /// With the `fips-mode` feature, the self-test failed, so the module is in the error state
+    /// (see `fips::state()`).
+    SelfTestFailed,
+}
+
+
+impl Error {
+    /// Returns a stable numeric code for the error variant, for FFI and logging pipelines that
+    /// must not depend upon the (English, possibly reworded) `Display` text. Codes are never
+    /// reassigned across releases; new variants receive new codes. Zero is reserved for success.
+    ///
+    /// | Code | Variant                         |
+    /// |------|---------------------------------|
+    /// | 1    | [`Error::WrongParamSet`]        |
+    /// | 2    | [`Error::InvalidSignature`]     |
+    /// | 3    | [`Error::DegenerateRho`]        |
+    /// | 4    | [`Error::DegenerateT1`]         |
+    /// | 5    | [`Error::ExpansionMismatch`]    |
+    /// | 6    | [`Error::WrongLength`]          |
+    /// | 7    | [`Error::MalformedKey`]         |
+    /// | 8    | [`Error::SelfTestFailed`]       |
+    #[must_use]
+    pub const fn code(&self) -> u32 {
+        match self {
+            Error::WrongParamSet { .. } => 1,
+            Error::InvalidSignature => 2,
+            Error::DegenerateRho => 3,
+            Error::DegenerateT1 => 4,
+            Error::ExpansionMismatch => 5,
+            Error::WrongLength { .. } => 6,
+            Error::MalformedKey => 7,
+            Error::SelfTestFailed => 8,
+        }
+    }
+}
+
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::WrongParamSet { expected, found: Some(found) } => {
+                write!(f, "wrong parameter set: expected {expected}, found {found}")
+            }
+            Error::WrongParamSet { expected, found: None } => {
+                write!(f, "wrong parameter set: expected {expected}, found unrecognized length")
+            }
+            Error::InvalidSignature => f.write_str("signature did not verify"),
+            Error::DegenerateRho => f.write_str("public key rho is degenerate"),
+            Error::DegenerateT1 => f.write_str("public key t1 is all zero"),
+            Error::ExpansionMismatch => f.write_str("expanded public key does not match encoding"),
+            Error::WrongLength { expected, got, likely_paramset: Some(likely) } => {
+                write!(f, "wrong length: expected {expected} bytes, got {got} (as for {likely})")
+            }
+            Error::WrongLength { expected, got, likely_paramset: None } => {
+                write!(f, "wrong length: expected {expected} bytes, got {got}")
+            }
+            Error::MalformedKey => f.write_str("key encoding is malformed"),
+            Error::SelfTestFailed => f.write_str("self-test failed; the module is in the error state"),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_are_stable() {
+        let wrong = Error::WrongParamSet { expected: ParamSet::MlDsa44, found: None };
+        assert_eq!(wrong.code(), 1);
+        assert_eq!(Error::InvalidSignature.code(), 2);
+        assert_eq!(Error::DegenerateRho.code(), 3);
+        assert_eq!(Error::DegenerateT1.code(), 4);
+        assert_eq!(Error::ExpansionMismatch.code(), 5);
+        let wrong = Error::WrongLength { expected: 1312, got: 1952, likely_paramset: Some(ParamSet::MlDsa65) };
+        assert_eq!(wrong.code(), 6);
+        assert_eq!(Error::MalformedKey.code(), 7);
+        assert_eq!(Error::SelfTestFailed.code(), 8);
+    }
+}
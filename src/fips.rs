@@ -0,0 +1,268 @@
+// This file implements the module state of the `fips-mode` feature: the power-on self-test
+// gating every key generation, signing and verification operation.
+
+//! The module state under the `fips-mode` feature, for integrators of FIPS 140-3 validated
+//! modules. Only exposed when the `fips-mode` feature is enabled.
+//!
+//! The `fips-mode` feature compiles out every non-approved extension, regardless of which other
+//! features are enabled (e.g., by another crate in the dependency graph): the `unstable`
+//! intermediates (including key generation with an injected `rho`), the `hazmat`
+//! start/finish split and the `dudect` measurement entry points. This crate has no legacy
+//! (round 3) Dilithium compatibility to remove.
+//!
+//! It further requires the [`self_test()`](crate::self_test()) known-answer tests to pass
+//! before first use. The self-test runs automatically on the first key generation, signing
+//! or verification operation if it has not been run explicitly. Should it fail, the module
+//! enters the [`ModuleState::Error`] state, in which every such operation fails rather than
+//! produce output: signing and key generation from a random number generator return an error,
+//! and verification rejects. Key generation from a seed has no error to return, so panics.
+//! Re-running [`self_test()`](crate::self_test()) successfully returns the module to
+//! [`ModuleState::Operational`]; [`ensure_operational()`] reports the state as a
+//! [`crate::Error`].
+//!
+//! With the `std` feature, the self-test run on first use runs on a thread of its own, so that
+//! the first operation needs no more stack than any other. Without `std`, it runs on the
+//! caller's stack, which then needs [`SELF_TEST_STACK_BYTES`] beyond the operation's own needs;
+//! call [`self_test()`](crate::self_test()) at start-up to avoid this.
+//!
+//! With the `std` feature, operations invoked on other threads while the self-test is running
+//! wait for it to finish. The self-test's own operations (on its thread) proceed. Without
+//! `std`, the crate cannot tell the self-test's operations from those of other execution
+//! contexts (e.g., interrupt handlers or other cores), so all proceed; integrators wanting no
+//! operation to precede a passing self-test should then call
+//! [`self_test()`](crate::self_test()) at start-up, before anything else uses the crate.
+//!
+//! # Examples
+//! ```rust
+//! use fips204::fips::{state, ModuleState};
+//!
+//! assert!(fips204::self_test().passed());
+//! assert_eq!(state(), ModuleState::Operational);
+//! ```
+
+use core::sync::atomic::{AtomicU8, Ordering};
+
+
+const UNTESTED: u8 = 0;
+const SELF_TESTING: u8 = 1;
+const OPERATIONAL: u8 = 2;
+const ERROR: u8 = 3;
+
+static STATE: AtomicU8 = AtomicU8::new(UNTESTED);
+
+
+/// The state of the module, per FIPS 140-3.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModuleState {
+    /// The self-test has not yet been run.
+    Untested,
+    /// The self-test is running.
+    SelfTesting,
+    /// The self-test passed; all operations are available.
+    Operational,
+    /// The self-test failed; all operations fail.
+    Error,
+}
+
+
+/// Returns the current state of the module.
+#[must_use]
+pub fn state() -> ModuleState {
+    match STATE.load(Ordering::Acquire) {
+        UNTESTED => ModuleState::Untested,
+        SELF_TESTING => ModuleState::SelfTesting,
+        OPERATIONAL => ModuleState::Operational,
+        _ => ModuleState::Error,
+    }
+}
+
+
+/// Runs the known-answer tests `kats` as an explicit self-test (see `self_test()`) on this
+/// thread, then enters the operational or error state per `passed`.
+pub(crate) fn run_self_test<R>(kats: impl FnOnce() -> R, passed: impl FnOnce(&R) -> bool) -> R {
+    STATE.store(SELF_TESTING, Ordering::Release);
+    let guard = ErrorOnUnwind(&STATE);
+    let report = on_self_test_thread(kats);
+    core::mem::forget(guard);
+    STATE.store(if passed(&report) { OPERATIONAL } else { ERROR }, Ordering::Release);
+    report
+}
+
+
+/// Runs the self-test if it has not yet been run (waiting for one running on another thread to
+/// finish), and then returns whether the module is operational.
+///
+/// # Errors
+/// Returns [`crate::Error::SelfTestFailed`] when the module is in the error state.
+pub fn ensure_operational() -> Result<(), crate::Error> {
+    check(&STATE, first_use_self_test)
+}
+
+
+/// Upper bound on the stack used by [`self_test()`](crate::self_test()), in bytes, in optimized
+/// builds (as enforced by `tests/stack.rs`). Without `std`, the first operation runs the
+/// self-test on its caller's stack, which must then have this much room beyond the operation's
+/// own needs; calling [`self_test()`](crate::self_test()) at start-up avoids this.
+pub const SELF_TEST_STACK_BYTES: usize = 480 * 1024;
+
+
+// The stack of the thread running the self-test on first use (with `std`), ample for unoptimized
+// builds too
+#[cfg(feature = "std")]
+const SELF_TEST_THREAD_STACK: usize = 4 << 20;
+
+
+// Runs the self-test on first use. It is kept out of line, so that the frames of the known-answer
+// tests are not part of every operation's stack frame, and with `std` runs on a thread of its
+// own, so that they are not on the caller's stack at all.
+#[cold]
+#[inline(never)]
+fn first_use_self_test() -> bool {
+    #[cfg(feature = "std")]
+    {
+        let spawned = std::thread::Builder::new()
+            .stack_size(SELF_TEST_THREAD_STACK)
+            .spawn(|| on_self_test_thread(|| crate::self_test::run_kats().passed()));
+        if let Ok(handle) = spawned {
+            return handle.join().unwrap_or(false);
+        }
+    }
+    crate::self_test::run_kats().passed()
+}
+
+
+/// Called on entry to every fallible key generation, signing and verification operation; see
+/// [`ensure_operational()`].
+///
+/// # Errors
+/// Returns an error when the module is in the error state.
+pub(crate) fn check_operational() -> Result<(), &'static str> {
+    ensure_operational().map_err(|_| "FIPS mode: the self-test failed; the module is in the error state")
+}
+
+
+/// As [`check_operational()`], for key generation from a seed, which has no error to return
+/// (the fallible `try_keygen_from_seed_into()` checks first).
+pub(crate) fn assert_operational() {
+    if let Err(e) = check_operational() {
+        panic!("{e}");
+    }
+}
+
+
+fn check(state: &AtomicU8, self_test: impl FnOnce() -> bool) -> Result<(), crate::Error> {
+    if state.compare_exchange(UNTESTED, SELF_TESTING, Ordering::AcqRel, Ordering::Acquire).is_ok() {
+        let guard = ErrorOnUnwind(state);
+        let passed = on_self_test_thread(self_test);
+        core::mem::forget(guard);
+        state.store(if passed { OPERATIONAL } else { ERROR }, Ordering::Release);
+    }
+    loop {
+        match state.load(Ordering::Acquire) {
+            OPERATIONAL => return Ok(()),
+            ERROR => return Err(crate::Error::SelfTestFailed),
+            // The self-test's own operations proceed; others wait for it to finish
+            _ if is_self_test_thread() => return Ok(()),
+            _ => core::hint::spin_loop(),
+        }
+    }
+}
+
+
+// Enters the error state when dropped, i.e., unless forgotten once the self-test has returned, so
+// that a panic within the self-test (e.g., of a random number generator) does not leave the state
+// at `SELF_TESTING` and other threads waiting forever
+struct ErrorOnUnwind<'a>(&'a AtomicU8);
+
+impl Drop for ErrorOnUnwind<'_> {
+    fn drop(&mut self) { self.0.store(ERROR, Ordering::Release); }
+}
+
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static SELF_TEST_THREAD: core::cell::Cell<bool> = const { core::cell::Cell::new(false) };
+}
+
+
+// Runs `f` marked as the self-test thread
+fn on_self_test_thread<R>(f: impl FnOnce() -> R) -> R {
+    #[cfg(feature = "std")]
+    let outer = SELF_TEST_THREAD.with(|t| t.replace(true));
+    let res = f();
+    #[cfg(feature = "std")]
+    SELF_TEST_THREAD.with(|t| t.set(outer));
+    res
+}
+
+
+// Without `std`, the self-test's own operations cannot be told apart from others, so all proceed
+fn is_self_test_thread() -> bool {
+    #[cfg(feature = "std")]
+    return SELF_TEST_THREAD.with(core::cell::Cell::get);
+    #[cfg(not(feature = "std"))]
+    true
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_test_on_first_use() {
+        let state = AtomicU8::new(UNTESTED);
+        let mut runs = 0;
+        let res = check(&state, || {
+            runs += 1;
+            // Operations within the self-test proceed without running it again
+            check(&state, || unreachable!()).is_ok()
+        });
+        assert_eq!(res, Ok(()));
+        assert_eq!(check(&state, || unreachable!()), Ok(()));
+        assert_eq!((runs, state.load(Ordering::Relaxed)), (1, OPERATIONAL));
+    }
+
+    #[test]
+    fn test_error_state_fails() {
+        let state = AtomicU8::new(UNTESTED);
+        assert_eq!(check(&state, || false), Err(crate::Error::SelfTestFailed));
+        assert_eq!(check(&state, || unreachable!()), Err(crate::Error::SelfTestFailed));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_other_threads_wait_for_self_test() {
+        use std::sync::atomic::AtomicBool;
+        let (state, finished) = (AtomicU8::new(UNTESTED), AtomicBool::new(false));
+        std::thread::scope(|s| {
+            let mut other = None;
+            let res = check(&state, || {
+                other = Some(s.spawn(|| (check(&state, || unreachable!()), finished.load(Ordering::Acquire))));
+                std::thread::sleep(std::time::Duration::from_millis(50));
+                finished.store(true, Ordering::Release);
+                true
+            });
+            assert_eq!(res, Ok(()));
+            // The other thread's operation completed only after the self-test had
+            assert_eq!(other.unwrap().join().unwrap(), (Ok(()), true));
+        });
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_panicking_self_test_enters_error_state() {
+        let state = AtomicU8::new(UNTESTED);
+        let res = std::panic::catch_unwind(|| check(&state, || panic!("self-test panicked")));
+        assert!(res.is_err());
+        assert_eq!(state.load(Ordering::Relaxed), ERROR);
+        assert_eq!(check(&state, || unreachable!()), Err(crate::Error::SelfTestFailed));
+    }
+
+    #[test]
+    fn test_module_state() {
+        assert!(crate::self_test().passed());
+        // Other tests may be running the self-test concurrently
+        assert!(matches!(state(), ModuleState::Operational | ModuleState::SelfTesting));
+    }
+}
@@ -0,0 +1,193 @@
+// This file implements a verification wrapper that pads every response to a fixed deadline
+// measured by an injected timer, for servers exposing verification as a remote oracle.
+
+//! Fixed-latency verification for authentication servers and other remote verification oracles.
+//!
+//! Verification operates on purely public data and is not constant-time: its duration depends
+//! on, e.g., where a malformed signature is first rejected. Where a remote party can submit
+//! signatures and measure response times, a [`FixedLatencyVerifier`] pads every verification
+//! to the same deadline, measured from **before** any of the signature is examined. The
+//! deadline is the start time plus a configured budget; a verification that overruns its
+//! budget returns at once, and is counted (see [`FixedLatencyVerifier::overruns()`]) so that
+//! the budget can be raised. Sleeping for a fixed period after verifying, or starting the
+//! clock after the length check, does **not** hide the variation, which is why the padding is
+//! done here.
+//!
+//! The clock is injected via the [`Timer`] trait, as `no_std` targets have no common clock;
+//! [`StdTimer`] implements it with `std::time::Instant` when the `std` feature is enabled.
+//!
+//! # Examples
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! # #[cfg(all(feature = "ml-dsa-44", feature = "std", feature = "default-rng"))] {
+//! use fips204::fixed_latency::{FixedLatencyVerifier, StdTimer};
+//! use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+//! use fips204::traits::Signer;
+//!
+//! let (pk, sk) = ml_dsa_44::try_keygen()?;
+//! let sig = sk.try_sign(b"login challenge", b"auth")?;
+//!
+//! // Every response takes (at least) 5 ms, whether the signature is valid, malformed or absent
+//! let mut verifier = FixedLatencyVerifier::new(pk, StdTimer::new(), 5_000_000);
+//! assert!(verifier.verify_bytes(b"login challenge", &sig, b"auth"));
+//! assert!(!verifier.verify_bytes(b"login challenge", &[0u8; 3], b"auth"));
+//! assert_eq!(verifier.overruns(), 0);
+//! # }
+//! # Ok(())}
+//! ```
+
+use crate::traits::DynVerifier;
+
+
+/// A monotonic clock able to wait until a deadline, in a unit of the implementor's choosing
+/// (e.g., nanoseconds or timer ticks) that the budget of a [`FixedLatencyVerifier`] shares.
+pub trait Timer {
+    /// Returns the current time, which never decreases.
+    fn now(&mut self) -> u64;
+
+
+    /// Returns once [`Timer::now()`] has reached `deadline`, at once if it already has.
+    fn wait_until(&mut self, deadline: u64);
+}
+
+
+/// Wraps a public key such that every verification takes the same time, as measured by its
+/// [`Timer`]; see the [module documentation](self).
+#[derive(Clone, Debug)]
+pub struct FixedLatencyVerifier<V, C> {
+    verifier: V,
+    timer: C,
+    budget: u64,
+    overruns: u64,
+}
+
+
+impl<V: DynVerifier, C: Timer> FixedLatencyVerifier<V, C> {
+    /// Wraps the `verifier` (typically a public key), padding each verification to `budget`
+    /// units of the `timer`. The budget should comfortably exceed the slowest verification
+    /// observed on the target.
+    pub fn new(verifier: V, timer: C, budget: u64) -> Self { Self { verifier, timer, budget, overruns: 0 } }
+
+
+    /// Verifies a signature supplied as a byte slice, as [`DynVerifier::verify_bytes()`] does,
+    /// returning only once the budget has elapsed since the call began. A verification
+    /// exceeding the budget is counted as an overrun, and returns without further delay.
+    pub fn verify_bytes(&mut self, message: &[u8], sig: &[u8], ctx: &[u8]) -> bool {
+        let deadline = self.timer.now().saturating_add(self.budget);
+        let valid = self.verifier.verify_bytes(message, sig, ctx);
+        if self.timer.now() > deadline {
+            self.overruns += 1;
+        }
+        self.timer.wait_until(deadline);
+        valid
+    }
+
+
+    /// Returns the number of verifications that exceeded the budget, and so revealed their
+    /// duration.
+    #[must_use]
+    pub fn overruns(&self) -> u64 { self.overruns }
+
+
+    /// Returns the budget, in units of the timer.
+    #[must_use]
+    pub fn budget(&self) -> u64 { self.budget }
+
+
+    /// Returns the wrapped verifier and timer.
+    pub fn into_inner(self) -> (V, C) { (self.verifier, self.timer) }
+}
+
+
+/// A [`Timer`] counting nanoseconds since its creation with `std::time::Instant`, and waiting
+/// with `std::thread::sleep()` (which may oversleep by a scheduler quantum, but never returns
+/// early). Only exposed when the `std` feature is enabled.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug)]
+pub struct StdTimer(std::time::Instant);
+
+
+#[cfg(feature = "std")]
+impl StdTimer {
+    /// Returns a timer whose origin is now.
+    #[must_use]
+    pub fn new() -> Self { Self(std::time::Instant::now()) }
+}
+
+
+#[cfg(feature = "std")]
+impl Default for StdTimer {
+    fn default() -> Self { Self::new() }
+}
+
+
+#[cfg(feature = "std")]
+impl Timer for StdTimer {
+    fn now(&mut self) -> u64 { u64::try_from(self.0.elapsed().as_nanos()).unwrap_or(u64::MAX) }
+
+    fn wait_until(&mut self, deadline: u64) {
+        loop {
+            let now = self.now();
+            if now >= deadline {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_nanos(deadline - now));
+        }
+    }
+}
+
+
+#[cfg(all(test, feature = "ml-dsa-44"))]
+mod tests {
+    use super::*;
+    use crate::ml_dsa_44;
+    use crate::traits::Signer;
+    use rand_chacha::rand_core::SeedableRng;
+
+    // A timer whose clock advances by `cost` per reading, recording the last deadline waited for
+    struct FakeTimer {
+        clock: u64,
+        cost: u64,
+        waited: Option<u64>,
+    }
+
+    impl Timer for FakeTimer {
+        fn now(&mut self) -> u64 {
+            self.clock += self.cost;
+            self.clock
+        }
+
+        fn wait_until(&mut self, deadline: u64) {
+            self.waited = Some(deadline);
+            self.clock = self.clock.max(deadline);
+        }
+    }
+
+    #[test]
+    fn test_fixed_latency() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+        let (pk, sk) = ml_dsa_44::try_keygen_with_rng(&mut rng).unwrap();
+        let sig = sk.try_sign_with_rng(&mut rng, b"msg", b"ctx").unwrap();
+        let timer = FakeTimer { clock: 0, cost: 10, waited: None };
+        let mut verifier = FixedLatencyVerifier::new(pk, timer, 100);
+
+        // Each response ends at the deadline, measured from the first reading of the clock
+        let mut ends = [0u64; 3];
+        for (end, sig) in ends.iter_mut().zip([&sig[..], &sig[1..], &[][..]]) {
+            let valid = verifier.verify_bytes(b"msg", sig, b"ctx");
+            assert_eq!(valid, sig.len() == ml_dsa_44::SIG_LEN);
+            *end = verifier.timer.clock;
+            assert_eq!(verifier.timer.waited, Some(*end));
+        }
+        assert_eq!(ends, [110, 220, 330]);
+        assert_eq!(verifier.overruns(), 0);
+
+        // Overruns return at once, and are counted
+        verifier.timer.cost = 200;
+        assert!(verifier.verify_bytes(b"msg", &sig, b"ctx"));
+        assert_eq!((verifier.timer.clock, verifier.timer.waited), (730, Some(630)));
+        assert_eq!(verifier.overruns(), 1);
+        assert_eq!(verifier.budget(), 100);
+    }
+}
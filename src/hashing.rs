@@ -7,12 +7,20 @@ use sha2::{Digest, Sha256, Sha512};
 use sha3::digest::{ExtendableOutput, Update, XofReader};
 use sha3::{Shake128, Shake256};
 
+// The rates (squeeze block sizes) of SHAKE256 and SHAKE128 in bytes. The rejection samplers
+// squeeze whole blocks into their buffers and consume them in place, rather than calling into
+// the XOF for each few bytes; see each sampler for its block accounting.
+const H_RATE: usize = 136;
+const G_RATE: usize = 168;
+
 /// # Function H(v,d) of section 3.7 item 1 at bottom of page 14.
 /// Takes a reference to a list of byte-slice references and runs them through Shake256.
 /// Returns a xof reader for extracting extendable output.
 pub(crate) fn h256_xof(v: &[&[u8]]) -> impl XofReader {
     let mut hasher = Shake256::default();
-    v.iter().for_each(|b| hasher.update(b));
+    for b in v {
+        hasher.update(b);
+    }
     hasher.finalize_xof()
 }
 
@@ -22,7 +30,9 @@ pub(crate) fn h256_xof(v: &[&[u8]]) -> impl XofReader {
 /// Returns a xof reader for extracting extendable output.
 pub(crate) fn g128_xof(v: &[&[u8]]) -> impl XofReader {
     let mut hasher = Shake128::default();
-    v.iter().for_each(|b| hasher.update(b));
+    for b in v {
+        hasher.update(b);
+    }
     hasher.finalize_xof()
 }
 
@@ -36,7 +46,9 @@ pub(crate) fn g128_xof(v: &[&[u8]]) -> impl XofReader {
 /// time in normal operation. To support constant-time `dudect` measurements through
 /// the `dudect_keygen_sign_with_rng()` function exposed when the `dudect` feature is
 /// enabled, the CTEST value would be set to `true` to effectively bypass some of the
-/// loop decisions.
+/// loop decisions. Regardless, the Fisher-Yates swap of steps 11-12 is performed as a masked
+/// pass over `c[0..=i]`, so the memory access pattern does not depend upon `j`; the pass is
+/// branch-free and vectorizable by the compiler.
 ///
 /// **Input**: A seed `ρ ∈ B^{λ/4}` <br>
 /// **Output**: A polynomial `c` in `Rq`.
@@ -50,37 +62,56 @@ pub(crate) fn sample_in_ball<const CTEST: bool>(tau: i32, rho: &[u8]) -> R {
     // 3: ctx ← H.Absorb(ctx, 𝜌)
     let mut h_ctx = h256_xof(&[rho]); // init and absorb
 
+    // The squeezes of steps 4, 7 and 9 are served from one block of H: 8 bytes for step 5 and
+    // then one byte per candidate `j`, of which τ ≤ 60 are accepted (each with probability at
+    // least (256 − τ)/256), so the block is rarely exhausted; if it is, another is squeezed.
+    let mut block = [0u8; H_RATE];
+    h_ctx.read(&mut block);
+
     // 4: (ctx, 𝑠) ← H.Squeeze(ctx, 8)
     // 5: ℎ ← BytesToBits(𝑠)
-    let mut h = [0u8; 8];
-    h_ctx.read(&mut h); // Save the first 8 bytes for step 9
+    let h: [u8; 8] = block[..8].try_into().expect("cannot fail"); // Save the first 8 bytes for step 9
+    let mut pos = 8;
+    let mut squeeze_byte = || {
+        if pos == H_RATE {
+            h_ctx.read(&mut block);
+            pos = 0;
+        }
+        pos += 1;
+        block[pos - 1]
+    };
 
     // 6: for 𝑖 from 256 − 𝜏 to 255 do
     for i in (256 - tau)..=255 {
         //
         // 7: (ctx, 𝑗) ← H.Squeeze(ctx, 1)
-        let mut j = [i.to_le_bytes()[0]]; // remove timing variability
+        let mut j = i.to_le_bytes()[0]; // remove timing variability
         if !CTEST {
-            h_ctx.read(&mut j);
-        };
+            j = squeeze_byte();
+        }
 
         // 8: while 𝑗 > 𝑖 do
-        while usize::from(j[0]) > i {
+        while usize::from(j) > i {
             //
             // 9: (ctx, 𝑗) ← H.Squeeze(ctx, 1)
-            h_ctx.read(&mut j);
+            j = squeeze_byte();
 
             // 10: end while
         }
 
         // 11: ci ← cj
-        c.0[i] = c.0[usize::from(j[0])];
+        let j = i32::from(j);
+        let eq_j = |idx: i32| ((idx ^ j) - 1) >> 31; // -1 when idx == j, else 0
+        c.0[i] = (0i32..).zip(&c.0[..=i]).fold(0, |c_j, (idx, &c_idx)| c_j | (c_idx & eq_j(idx)));
 
         // 12: c_j ← (−1)^{H(ρ)[i+τ−256]
         let index = i + tau - 256;
         let bite = h[index / 8];
         let shifted = bite >> (index & 0x07);
-        c.0[usize::from(j[0])] = 1 - 2 * i32::from(shifted & 0x01);
+        let sign = 1 - 2 * i32::from(shifted & 0x01);
+        for (idx, c_idx) in (0i32..).zip(&mut c.0[..=i]) {
+            *c_idx ^= (*c_idx ^ sign) & eq_j(idx);
+        }
 
         // 13: end for
     }
@@ -119,23 +150,33 @@ pub(crate) fn rej_ntt_poly<const CTEST: bool>(rhos: &[&[u8]]) -> T {
     // 3: ctx ← G.Absorb(ctx, 𝜌)
     let mut xof = g128_xof(rhos);
 
+    // A block of G holds 56 three-byte samples. Each is accepted with probability q/2^23 ≈
+    // 0.999, so the 256 coefficients typically take 5 blocks (280 samples); any samples left
+    // over in the final block are discarded with the XOF.
+    let mut block = [0u8; G_RATE];
+
     // 4: while j < 256 do
     while j < 256 {
         //
-        // 5: (ctx, 𝑠) ← G.Squeeze(ctx, 3)
-        // 6: 𝑎[𝑗] ← CoeffFromThreeBytes(𝑠[0], 𝑠[1], 𝑠[2])
-        let mut h5 = [0u8; 3];
-        xof.read(&mut h5); // implicit c += 3
-        let a_hat_j = coeff_from_three_bytes::<CTEST>(h5); // gets a result
+        // 5: (ctx, 𝑠) ← G.Squeeze(ctx, 3)    ▷ Three bytes at a time from a squeezed block
+        xof.read(&mut block);
+        for s in block.chunks_exact(3) {
+            //
+            // 6: 𝑎[𝑗] ← CoeffFromThreeBytes(𝑠[0], 𝑠[1], 𝑠[2])
+            let a_hat_j = coeff_from_three_bytes::<CTEST>([s[0], s[1], s[2]]); // gets a result
 
-        // 7: if a_hat[j] != ⊥ then
-        if let Ok(res) = a_hat_j {
-            a_hat.0[j] = res; // Good result, save it and carry on
+            // 7: if a_hat[j] != ⊥ then
+            if let Ok(res) = a_hat_j {
+                a_hat.0[j] = res; // Good result, save it and carry on
 
-            // 8: j ← j + 1
-            j += 1;
+                // 8: j ← j + 1
+                j += 1;
+                if j == 256 {
+                    break;
+                }
 
-            // 9: end if
+                // 9: end if
+            }
         }
 
         // 10: end while
@@ -157,7 +198,6 @@ pub(crate) fn rej_ntt_poly<const CTEST: bool>(rhos: &[&[u8]]) -> T {
 /// **Output**: A polynomial `a ∈ Rq`.
 pub(crate) fn rej_bounded_poly<const CTEST: bool>(eta: i32, rhos: &[&[u8]]) -> R {
     debug_assert_eq!(rhos.iter().map(|&i| i.len()).sum::<usize>(), 528 / 8, "Alg 31: bad rho size");
-    let mut z = [0u8];
     let mut a = R0;
 
     // 1: j ← 0
@@ -167,17 +207,28 @@ pub(crate) fn rej_bounded_poly<const CTEST: bool>(eta: i32, rhos: &[&[u8]]) -> R
     // 3: ctx ← H.Absorb(ctx, 𝜌)
     let mut xof = h256_xof(rhos);
 
+    // A block of H holds 272 half-byte samples, each accepted with probability 15/16 (η = 2)
+    // or 9/16 (η = 4), so the 256 coefficients typically take 2 blocks; any bytes left over in
+    // the final block are discarded with the XOF.
+    let mut block = [0u8; H_RATE];
+    let mut pos = H_RATE;
+
     // 4: while j < 256 do
     while j < 256 {
         //
-        // 5: 𝑧 ← H.Squeeze(ctx, 1)
-        xof.read(&mut z);
+        // 5: 𝑧 ← H.Squeeze(ctx, 1)    ▷ One byte at a time from a squeezed block
+        if pos == H_RATE {
+            xof.read(&mut block);
+            pos = 0;
+        }
+        let z = block[pos];
+        pos += 1;
 
         // 6: z0 ← CoefFromHalfByte(z mod 16, η)
-        let z0 = coeff_from_half_byte::<CTEST>(eta, z[0] & 0x0f);
+        let z0 = coeff_from_half_byte::<CTEST>(eta, z & 0x0f);
 
         // 7: z1 ← CoefFromHalfByte(⌊z/16⌋, η)
-        let z1 = coeff_from_half_byte::<CTEST>(eta, z[0] >> 4);
+        let z1 = coeff_from_half_byte::<CTEST>(eta, z >> 4);
 
         // 8: if z0 != ⊥ then
         if let Ok(z0) = z0 {
@@ -232,13 +283,20 @@ pub(crate) fn expand_a<const CTEST: bool, const K: usize, const L: usize>(
     // 5:   end for
     // 6: end for
 
-    let cap_a_hat: [[T; L]; K] = core::array::from_fn(|r| {
-        core::array::from_fn(|s| rej_ntt_poly::<CTEST>(&[&rho[..], &[s as u8], &[r as u8]]))
-    });
+    let cap_a_hat: [[T; L]; K] =
+        core::array::from_fn(|r| core::array::from_fn(|s| expand_a_element::<CTEST>(rho, r, s)));
     cap_a_hat
 }
 
 
+/// The single element `A_hat[r,s]` of Algorithm 32 (steps 3-4), for callers that consume the
+/// matrix one element at a time rather than holding all of it on the stack.
+#[allow(clippy::cast_possible_truncation)] // s and r
+pub(crate) fn expand_a_element<const CTEST: bool>(rho: &[u8; 32], r: usize, s: usize) -> T {
+    rej_ntt_poly::<CTEST>(&[&rho[..], &[s as u8], &[r as u8]])
+}
+
+
 /// # Algorithm 33: `ExpandS(ρ)` on page 38.
 /// Samples vectors `s1 ∈ R^ℓ_q` and `s2 ∈ R^k_q`, each with coefficients in
 /// the interval `[−η, η]`.
@@ -317,9 +375,7 @@ pub(crate) fn expand_mask<const L: usize>(gamma1: i32, rho: &[u8; 64], mu: u16)
 pub(crate) fn hash_message(message: &[u8], ph: &Ph, phm: &mut [u8; 64]) -> ([u8; 11], usize) {
     match ph {
         Ph::SHA256 => (
-            [
-                0x06u8, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01,
-            ],
+            ph.oid(),
             {
                 let mut hasher = Sha256::new();
                 Digest::update(&mut hasher, message);
@@ -328,9 +384,7 @@ pub(crate) fn hash_message(message: &[u8], ph: &Ph, phm: &mut [u8; 64]) -> ([u8;
             },
         ),
         Ph::SHA512 => (
-            [
-                0x06u8, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03,
-            ],
+            ph.oid(),
             {
                 let mut hasher = Sha512::new();
                 Digest::update(&mut hasher, message);
@@ -339,9 +393,7 @@ pub(crate) fn hash_message(message: &[u8], ph: &Ph, phm: &mut [u8; 64]) -> ([u8;
             },
         ),
         Ph::SHAKE128 => (
-            [
-                0x06u8, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x0B,
-            ],
+            ph.oid(),
             {
                 let mut hasher = Shake128::default();
                 hasher.update(message);
@@ -352,3 +404,94 @@ pub(crate) fn hash_message(message: &[u8], ph: &Ph, phm: &mut [u8; 64]) -> ([u8;
         ),
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The block-wise samplers match squeezing the XOF a few bytes at a time, as FIPS 204 does,
+    // for samplers consuming several blocks
+    #[test]
+    fn test_samplers_match_bytewise_squeezing() {
+        for seed in 0u8..64 {
+            let rho = [seed; 34];
+            let mut xof = g128_xof(&[&rho]);
+            let mut expected = T0;
+            let mut j = 0;
+            while j < 256 {
+                let mut s = [0u8; 3];
+                xof.read(&mut s);
+                if let Ok(a) = coeff_from_three_bytes::<false>(s) {
+                    expected.0[j] = a;
+                    j += 1;
+                }
+            }
+            assert_eq!(rej_ntt_poly::<false>(&[&rho]).0, expected.0);
+
+            let rho = [seed; 66];
+            for eta in [2, 4] {
+                let mut xof = h256_xof(&[&rho]);
+                let mut expected = R0;
+                let mut j = 0;
+                while j < 256 {
+                    let mut z = [0u8];
+                    xof.read(&mut z);
+                    for nibble in [z[0] & 0x0f, z[0] >> 4] {
+                        if let (Ok(a), true) = (coeff_from_half_byte::<false>(eta, nibble), j < 256) {
+                            expected.0[j] = a;
+                            j += 1;
+                        }
+                    }
+                }
+                assert_eq!(rej_bounded_poly::<false>(eta, &[&rho]), expected);
+            }
+            let mut xof = h256_xof(&[&rho]);
+            let mut h = [0u8; 8];
+            xof.read(&mut h);
+            let mut expected = R0;
+            for i in (256 - 60)..=255 {
+                let mut j = [0u8];
+                xof.read(&mut j);
+                while usize::from(j[0]) > i {
+                    xof.read(&mut j);
+                }
+                let j = usize::from(j[0]);
+                expected.0[i] = expected.0[j];
+                let index = i + 60 - 256;
+                expected.0[j] = 1 - 2 * i32::from((h[index / 8] >> (index & 0x07)) & 0x01);
+            }
+            assert_eq!(sample_in_ball::<false>(60, &rho), expected);
+        }
+    }
+
+
+    // Weight, values and distribution of `sample_in_ball()` outputs over many seeds
+    #[test]
+    fn test_sample_in_ball_distribution() {
+        const TRIALS: u32 = 2000;
+        for tau in [39, 49, 60] {
+            let (mut nonzero, mut negative) = ([0u32; 256], 0u32);
+            for trial in 0..TRIALS {
+                let c = sample_in_ball::<false>(tau, &trial.to_le_bytes());
+                assert_eq!(c.0.iter().filter(|&&e| e != 0).count(), usize::try_from(tau).unwrap());
+                assert!(c.0.iter().all(|e| [-1, 0, 1].contains(e)));
+                for (count, &e) in nonzero.iter_mut().zip(&c.0) {
+                    *count += u32::from(e != 0);
+                }
+                negative += u32::try_from(c.0.iter().filter(|&&e| e == -1).count()).unwrap();
+            }
+
+            // Each position is nonzero with probability τ/256; allow ±5σ per position
+            let p = f64::from(tau) / 256.0;
+            let (mean, sigma) = (f64::from(TRIALS) * p, (f64::from(TRIALS) * p * (1.0 - p)).sqrt());
+            for count in nonzero {
+                assert!((f64::from(count) - mean).abs() < 5.0 * sigma, "tau {tau}: count {count}");
+            }
+
+            // Signs are balanced; allow ±5σ overall
+            let total = f64::from(TRIALS) * f64::from(tau);
+            assert!((f64::from(negative) - total / 2.0).abs() < 5.0 * (total / 4.0).sqrt());
+        }
+    }
+}
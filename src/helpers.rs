@@ -4,8 +4,8 @@ use crate::{Q, ZETA};
 // Some arith routines leverage dilithium https://github.com/PQClean/PQClean/tree/master/crypto_sign
 
 
-/// # Algorithm 43 `BitRev8()` is not implemented; zetas are pulled from pre-computed table
-/// `ZETA_TABLE_MONT`; see below (near end)
+// # Algorithm 43 `BitRev8()` is not implemented; zetas are pulled from pre-computed table
+// `ZETA_TABLE_MONT`; see below (near end)
 
 /// # Macro ensure!()
 /// If the condition is not met, return an error Result. Borrowed from the `anyhow` crate.
@@ -20,6 +20,37 @@ macro_rules! ensure {
 pub(crate) use ensure; // make available throughout crate
 
 
+/// # Macro checked!()
+/// Performs `add`, `sub` or `mul` on two integers of the same type. With the `checked-math`
+/// feature enabled, the operation is overflow-checked (in all profiles) and any wraparound
+/// panics with a message naming the call site; otherwise it compiles to the plain operator.
+macro_rules! checked {
+    (add, $a:expr, $b:expr, $site:literal) => { checked!(@op checked_add, +, $a, $b, $site) };
+    (sub, $a:expr, $b:expr, $site:literal) => { checked!(@op checked_sub, -, $a, $b, $site) };
+    (mul, $a:expr, $b:expr, $site:literal) => { checked!(@op checked_mul, *, $a, $b, $site) };
+    (@op $f:ident, $op:tt, $a:expr, $b:expr, $site:literal) => {{
+        #[cfg(feature = "checked-math")]
+        let Some(res) = ($a).$f($b) else {
+            panic!(concat!("checked-math: integer overflow in ", $site))
+        };
+        #[cfg(not(feature = "checked-math"))]
+        let res = $a $op $b;
+        res
+    }};
+}
+
+pub(crate) use checked; // make available throughout crate
+
+
+/// Compile-time check of a fixed context length; referencing `CtxLen::<N>::OK` fails to compile
+/// (at monomorphization) when `N` exceeds the 255 byte limit of FIPS 204.
+pub(crate) struct CtxLen<const N: usize>;
+
+impl<const N: usize> CtxLen<N> {
+    pub(crate) const OK: () = assert!(N < 256, "ctx must be 255 bytes or fewer");
+}
+
+
 /// Ensure all coefficients of polynomial `w` are within -lo to +hi (inclusive)
 /// Note, while both range parameters are i32, they should be both non-negative
 pub(crate) fn is_in_range(w: &R, lo: i32, hi: i32) -> bool {
@@ -27,7 +58,19 @@ pub(crate) fn is_in_range(w: &R, lo: i32, hi: i32) -> bool {
 }
 
 
-/// Partial Barrett-style reduction
+/// Returns zero when all coefficients of polynomial `w` are within -lo to +hi (inclusive), and
+/// -1 otherwise. Unlike `is_in_range()`, every coefficient is examined and there are no
+/// data-dependent branches, so the result can be OR-aggregated across secret polynomials.
+/// Note, while both range parameters are i32, they should be both non-negative (and small).
+pub(crate) fn out_of_range_mask(w: &R, lo: i32, hi: i32) -> i32 {
+    w.0.iter().fold(0, |acc, &e| acc | (((e + lo) | (hi - e)) >> 31))
+}
+
+
+/// Partial Barrett-style reduction of a signed 64-bit value mod Q.
+///
+/// **Input**:  `|a| < 67_058_539·2^32`. <br>
+/// **Output**: `res ≡ a mod q` with `|res| < 2q`. Branch-free, so constant-time.
 // Arguably very slightly faster than single-step i128 below; worth more experimentation
 #[allow(clippy::cast_possible_truncation)]
 pub(crate) const fn partial_reduce64(a: i64) -> i32 {
@@ -57,23 +100,31 @@ pub(crate) const fn partial_reduce64b(a: i64) -> i32 {
 
 
 /// Partially reduce a signed 32-bit value mod Q ---> `-Q <~ result <~ Q`
+///
+/// **Input**:  `|a| < 2^31 − 2^22`, so that `a + 2^22` cannot overflow. <br>
+/// **Output**: `res ≡ a mod q` with `|res| < q`. Branch-free, so constant-time.
 // Considering the positive case for `a`, bits 23 and above can be loosely
 // viewed as the 'number of Q' contained within `a` (with some rounding-down
 // error). So, increment these bits and then subtract off the corresponding
 // number of Q. The result is within (better than) -Q < res < Q.
 pub(crate) const fn partial_reduce32(a: i32) -> i32 {
     debug_assert!(a.abs() < 2_143_289_344, "partial_reduce32 input");
-    let x = (a + (1 << 22)) >> 23;
-    let res = a - x * Q;
+    let x = checked!(add, a, 1 << 22, "partial_reduce32 (a + 2^22)") >> 23;
+    let res = checked!(sub, a, checked!(mul, x, Q, "partial_reduce32 (x·q)"), "partial_reduce32 (a - x·q)");
     debug_assert!(res.abs() < Q, "partial_reduce32 output");
     res
 }
 
 
+/// Fully reduce a signed 32-bit value mod Q.
+///
+/// **Input**:  `|a| < 2^31 − 2^22` (as for [`partial_reduce32()`]). <br>
+/// **Output**: `res = a mod q` with `0 ≤ res < q`. The conditional addition of `q` uses a mask
+/// derived from the sign bit rather than a branch, so this is constant-time.
 pub(crate) const fn full_reduce32(a: i32) -> i32 {
     debug_assert!(a.abs() < 2_143_289_344, "full_reduce32 input");
     let x = partial_reduce32(a); // puts us within better than -Q to +Q
-    let res = x + ((x >> 31) & Q); // add Q if negative
+    let res = checked!(add, x, (x >> 31) & Q, "full_reduce32 (x + q)"); // add Q if negative
     debug_assert!(res < Q, "full_reduce32 output");
     res
 }
@@ -88,6 +139,9 @@ pub(crate) const fn bit_length(x: i32) -> usize { x.ilog2() as usize + 1 }
 /// If `α` is a positive integer and `m ∈ Z` or `m ∈ Z_α` , then m mod± α denotes the unique
 /// element `m′ ∈ Z` in the range `−α/2 < m′ ≤ α/2` such that `m` and `m′` are congruent
 /// modulo `α`.  'ready to optimize'
+///
+/// **Input**:  `|m| < 2^31 − 2^22` (as for [`full_reduce32()`]). <br>
+/// **Output**: `m mod± q`, i.e., `−q/2 < res ≤ q/2`. Branch-free, so constant-time.
 pub(crate) fn center_mod(m: i32) -> i32 {
     debug_assert!(m.abs() < 2_143_289_344, "center_mod input"); // for clarity; caught in full_reduce32
     let t = full_reduce32(m);
@@ -117,6 +171,26 @@ pub(crate) fn mat_vec_mul<const K: usize, const L: usize>(
 }
 
 
+/// As [`mat_vec_mul()`], but with each element of `A_hat` produced on demand by `a_hat(i, j)`
+/// rather than held in memory, which saves `K·L` polynomials of stack (16 KiB for ML-DSA-44).
+#[must_use]
+pub(crate) fn mat_vec_mul_with<const K: usize, const L: usize>(
+    a_hat: impl Fn(usize, usize) -> T, u_hat: &[T; L],
+) -> [T; K] {
+    let mut w_hat = [T0; K];
+    let u_hat_mont = to_mont(u_hat);
+    for (i, w_hat_i) in w_hat.iter_mut().enumerate() {
+        for (j, u_hat_mont_j) in u_hat_mont.iter().enumerate() {
+            let a_hat_ij = a_hat(i, j);
+            w_hat_i.0.iter_mut().enumerate().for_each(|(n, e)| {
+                *e += mont_reduce(i64::from(a_hat_ij.0[n]) * i64::from(u_hat_mont_j.0[n]));
+            });
+        }
+    }
+    w_hat
+}
+
+
 // Note Algorithm 44 has been dissolved into its place of use(s)
 
 /// # Algorithm 46: `AddVectorNTT(v_hat, w_hat)` on page 45.
@@ -138,6 +212,35 @@ pub(crate) fn to_mont<const L: usize>(vec_a: &[T; L]) -> [T; L] {
 }
 
 
+/// Converts a vector of polynomials out of Montgomery form: `a ⋅ 2^{−32} mod q` for each
+/// coefficient, with `−q < r < q`. The inverse of `to_mont()` (up to reduction).
+#[must_use]
+pub(crate) fn from_mont<const L: usize>(vec_a: &[T; L]) -> [T; L] { mont_mul_const(vec_a, 1) }
+
+
+/// Multiplies every coefficient of a vector of polynomials by the constant `c` with Montgomery
+/// reduction: `a ⋅ c ⋅ 2^{−32} mod q`, with `−q < r < q`. The vector is processed as one flat
+/// run of coefficients rather than per polynomial, so the loop auto-vectorizes. Branch-free, so
+/// constant-time.
+#[must_use]
+pub(crate) fn mont_mul_const<const L: usize>(vec_a: &[T; L], c: i32) -> [T; L] {
+    let mut res = vec_a.clone();
+    for a in res.iter_mut().flat_map(|poly| poly.0.iter_mut()) {
+        *a = mont_reduce(i64::from(*a) * i64::from(c));
+    }
+    res
+}
+
+
+/// As `mont_mul_const()`, but in place on a vector over `R`, fully reducing each result into
+/// `[0, q)`. This is the final scaling of `inv_ntt()`, applied across all of its outputs.
+pub(crate) fn mont_mul_const_full<const L: usize>(w: &mut [R; L], c: i32) {
+    for a in w.iter_mut().flat_map(|poly| poly.0.iter_mut()) {
+        *a = full_reduce32(mont_reduce(checked!(mul, i64::from(c), i64::from(*a), "mont_mul_const_full (c·a)")));
+    }
+}
+
+
 pub(crate) fn infinity_norm<const ROW: usize>(w: &[R; ROW]) -> i32 {
     w.iter()
         .flat_map(|row| row.0)
@@ -153,17 +256,20 @@ pub(crate) fn infinity_norm<const ROW: usize>(w: &[R; ROW]) -> i32 {
 /// # Algorithm 49: MontgomeryReduce(𝑎) on page 50.
 /// Computes 𝑎 ⋅ 2−32 mod 𝑞.
 ///
-/// **Input**:  Integer 𝑎 with −2^{31}*𝑞 ≤ 𝑎 ≤ 2^{31}*𝑞.
-/// **Output**: 𝑟 ≡ 𝑎 ⋅ 2^{−32} mod 𝑞.
+/// **Input**:  Integer 𝑎 with −2^{31}*𝑞 ≤ 𝑎 ≤ 2^{31}*𝑞 (precisely, `−17_996_808_479_301_632 ≤ a ≤
+///             17_996_808_470_921_215`). <br>
+/// **Output**: 𝑟 ≡ 𝑎 ⋅ 2^{−32} mod 𝑞 with `−q < r < q`. Branch-free, so constant-time.
 #[allow(clippy::cast_possible_truncation)] // a as i32, res as i32
 pub(crate) const fn mont_reduce(a: i64) -> i32 {
     const QINV: i32 = 58_728_449; // (Q * QINV) % 2**32 = 1
     debug_assert!(a >= -17_996_808_479_301_632, "mont_reduce input (a)");
     debug_assert!(a <= 17_996_808_470_921_215, "mont_reduce input (b)");
-    let t = (a as i32).wrapping_mul(QINV);
-    let res = (a - (t as i64).wrapping_mul(Q as i64)) >> 32;
+    let t = (a as i32).wrapping_mul(QINV); // intentionally wraps; only the low 32 bits matter
+    let res = checked!(sub, a, (t as i64).wrapping_mul(Q as i64), "mont_reduce (a - t·q)") >> 32;
     debug_assert!(res < (Q as i64), "mont_reduce output 1");
     debug_assert!(-(Q as i64) < res, "mont_reduce output 2");
+    #[cfg(feature = "checked-math")]
+    assert!((i32::MIN as i64) <= res && res <= (i32::MAX as i64), "checked-math: mont_reduce output exceeds i32");
     res as i32
 }
 
@@ -184,4 +290,152 @@ const fn gen_zeta_table_mont() -> [i32; 256] {
 }
 
 
+// Any further constant tables must likewise be compile-time `static`s, and be counted in
+// `crate::CONST_TABLE_BYTES`
 pub(crate) static ZETA_TABLE_MONT: [i32; 256] = gen_zeta_table_mont();
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_const_table_bytes() {
+        use core::mem::size_of_val;
+        assert_eq!(crate::CONST_TABLE_BYTES, size_of_val(&ZETA_TABLE_MONT));
+    }
+
+    #[test]
+    fn test_out_of_range_mask() {
+        let mut w = R([0; 256]);
+        for (i, e) in w.0.iter_mut().enumerate() {
+            *e = i32::try_from(i % 9).unwrap() - 4; // -4..=4
+        }
+        assert_eq!(out_of_range_mask(&w, 4, 4), 0);
+        assert_eq!(out_of_range_mask(&w, 3, 4), -1);
+        assert_eq!(out_of_range_mask(&w, 4, 3), -1);
+        assert_eq!(is_in_range(&w, 4, 4), out_of_range_mask(&w, 4, 4) == 0);
+    }
+
+    #[test]
+    fn test_mont_conversions() {
+        let mut a = [T([0; 256]), T([0; 256])];
+        for (i, e) in a.iter_mut().flat_map(|poly| poly.0.iter_mut()).enumerate() {
+            *e = i32::try_from(i * 7919).unwrap() % Q - Q / 2;
+        }
+        let back = from_mont(&to_mont(&a));
+        for (x, y) in a.iter().zip(back.iter()).flat_map(|(x, y)| x.0.iter().zip(y.0.iter())) {
+            assert_eq!((x - y).rem_euclid(Q), 0);
+        }
+
+        // The batched scaling matches the per-coefficient reduction it replaces
+        let mut w = [R(a[0].0), R(a[1].0)];
+        mont_mul_const_full(&mut w, 8_347_681);
+        let scaled = mont_mul_const(&a, 8_347_681);
+        for ((x, y), z) in a.iter().zip(w.iter()).zip(scaled.iter()) {
+            for n in 0..256 {
+                assert_eq!(y.0[n], full_reduce32(mont_reduce(8_347_681 * i64::from(x.0[n]))));
+                assert_eq!(y.0[n], z.0[n].rem_euclid(Q));
+            }
+        }
+    }
+
+    // The documented input bounds of Algorithm 49 are -2^31·q <= a <= 2^31·q (approximately)
+    #[test]
+    fn test_mont_reduce_extremes() {
+        for a in [-17_996_808_479_301_632_i64, 17_996_808_470_921_215, 0, -1, 1] {
+            let res = mont_reduce(a);
+            assert!(-Q < res && res < Q);
+            assert_eq!((i128::from(res) << 32).rem_euclid(i128::from(Q)), i128::from(a).rem_euclid(i128::from(Q)));
+        }
+    }
+
+    #[test]
+    fn test_reduce32_extremes() {
+        for a in [2_143_289_343, -2_143_289_343, Q, -Q, Q - 1, 0] {
+            assert!(partial_reduce32(a).abs() < Q);
+            assert_eq!(full_reduce32(a), a.rem_euclid(Q));
+        }
+    }
+
+    // Checks the documented invariants of the 32-bit reductions for a single input
+    fn check_reduce32(a: i32) {
+        let partial = partial_reduce32(a);
+        assert!(partial.abs() < Q && (partial - a) % Q == 0, "partial_reduce32({a})");
+        assert_eq!(full_reduce32(a), a.rem_euclid(Q), "full_reduce32({a})");
+        let centered = center_mod(a);
+        assert!(centered.abs() <= (Q - 1) / 2 && (centered - a) % Q == 0, "center_mod({a})");
+    }
+
+    const REDUCE32_MAX: i32 = i32::MAX - (1 << 22);
+
+    // Every multiple of Q (and its neighbours) across the domain, plus a prime stride through it
+    #[test]
+    fn test_reduce32_domain() {
+        for k in -(REDUCE32_MAX / Q)..=(REDUCE32_MAX / Q) {
+            for a in [k * Q - 1, k * Q, k * Q + 1, k * Q + Q / 2, k * Q + Q / 2 + 1] {
+                if a.abs() <= REDUCE32_MAX {
+                    check_reduce32(a);
+                }
+            }
+        }
+        for a in (-REDUCE32_MAX..=REDUCE32_MAX).step_by(65_521) {
+            check_reduce32(a);
+        }
+    }
+
+    // Exhaustive over the full input domain; run with `cargo test --release -- --ignored`
+    #[test]
+    #[ignore = "exhaustive over ~2^32 inputs; run in release"]
+    fn test_reduce32_exhaustive() {
+        for a in -REDUCE32_MAX..=REDUCE32_MAX {
+            check_reduce32(a);
+        }
+    }
+
+    #[test]
+    fn test_mont_reduce_domain() {
+        use rand_core::{RngCore, SeedableRng};
+        const MAX: i64 = 17_996_808_470_921_215;
+        let check = |a: i64| {
+            let res = mont_reduce(a);
+            assert!(-Q < res && res < Q, "mont_reduce({a})");
+            assert_eq!(
+                (i128::from(res) << 32).rem_euclid(i128::from(Q)),
+                i128::from(a).rem_euclid(i128::from(Q)),
+                "mont_reduce({a})"
+            );
+        };
+
+        // Multiples of Q reduce to exactly zero, including those nearest the bounds
+        for k in (-(1_i64 << 31)..(1 << 31)).step_by(65_521).chain([MAX / i64::from(Q), -MAX / i64::from(Q)]) {
+            assert_eq!(mont_reduce(k * i64::from(Q)), 0);
+        }
+        for k in 0..1000 {
+            for a in [MAX - (MAX % i64::from(Q)) - k * i64::from(Q), k * i64::from(Q)] {
+                check(a);
+                check(-a);
+            }
+        }
+        for a in [-MAX - 8_380_417, -MAX, MAX, MAX - 1, i64::from(i32::MIN), i64::from(i32::MAX)] {
+            check(a);
+        }
+
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+        for _ in 0..100_000 {
+            #[allow(clippy::cast_possible_wrap)]
+            let a = (rng.next_u64() as i64) % MAX;
+            check(a);
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "mont_reduce input (b)")]
+    fn test_mont_reduce_rejects_i64_max() { let _res = mont_reduce(i64::MAX); }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "mont_reduce input (a)")]
+    fn test_mont_reduce_rejects_i64_min() { let _res = mont_reduce(i64::MIN); }
+}
@@ -1,6 +1,6 @@
 // This file implements functionality from FIPS 204 section 7.4 High-Order and Low-Order Bits and Hints
 
-use crate::helpers::full_reduce32;
+use crate::helpers::{checked, full_reduce32};
 use crate::types::{Zq, R};
 use crate::{D, Q};
 
@@ -76,17 +76,19 @@ pub(crate) fn decompose(gamma2: i32, r: Zq) -> (Zq, Zq) {
     let mut xr1;
     if gamma2 & (1 << 17) == 0 {
         // ml-dsa-44
-        xr1 = (rp + 127) >> 7;
-        xr1 = (xr1 * 11275 + (1 << 23)) >> 24;
+        xr1 = checked!(add, rp, 127, "decompose (r + 127)") >> 7;
+        xr1 = checked!(add, checked!(mul, xr1, 11275, "decompose (r1·11275)"), 1 << 23, "decompose (+2^23)")
+            >> 24;
         xr1 ^= ((43 - xr1) >> 31) & xr1;
     } else {
         // ml-dsa-65 and ml-dsa-87
-        xr1 = (rp + 127) >> 7;
-        xr1 = (xr1 * 1025 + (1 << 21)) >> 22;
+        xr1 = checked!(add, rp, 127, "decompose (r + 127)") >> 7;
+        xr1 = checked!(add, checked!(mul, xr1, 1025, "decompose (r1·1025)"), 1 << 21, "decompose (+2^21)")
+            >> 22;
         xr1 &= 15;
     }
 
-    let xr0 = rp - xr1 * 2 * gamma2;
+    let xr0 = checked!(sub, rp, checked!(mul, xr1 * 2, gamma2, "decompose (r1·2·γ2)"), "decompose (r - r1·2·γ2)");
     let xr0 = xr0 - ((((Q - 1) / 2 - xr0) >> 31) & Q);
 
     // Reconstruct/validate outputs
@@ -147,8 +149,8 @@ pub(crate) fn make_hint(gamma2: i32, z: Zq, r: Zq) -> bool {
 /// # Algorithm 40: `UseHint(h,r)` on page 41.
 /// Returns the high bits of `r` adjusted according to hint `h`.
 ///
-/// This non-CT function uses public data from the signature; thus
-/// does not need to be constant time
+/// This function uses public data from the signature; thus does not need to be constant
+/// time. It is nonetheless branch-free on `h` and `r0`.
 ///
 /// **Input**: Boolean `h` (cast in `Z_q`), `r` ∈ `Z_q` <br>
 /// **Output**: `r_1 ∈ Z` with `0 ≤ r_1 ≤ (q − 1)/(2·γ_2)`
@@ -160,33 +162,73 @@ pub(crate) fn use_hint(gamma2: i32, h: Zq, r: Zq) -> Zq {
     // 2: (r1, r0) ← Decompose(r)
     let (r1, r0) = decompose(gamma2, r);
 
-    // Step 5 here, to simplify later logic
-    if h == 0 {
-        return r1;
-    }
-
     // 3: if h = 1 and r0 > 0 return (r1 + 1) mod m
     // 4: if h = 1 and r0 ≤ 0 return (r1 − 1) mod m
+    // 5: return r1
+    // Branch-free: the +1/-1 step is masked by h
+    let step = 1 + 2 * ((r0 - 1) >> 31);
+    let v1 = r1 + h * step;
     if gamma2 & (1 << 17) == 0 {
-        // ml-dsa-44; explicit r1 + 1 mod m(44)
-        if r0 > 0 {
-            if r1 == 43 {
-                return 0;
+        // ml-dsa-44; explicit mod m(44) for v1 ∈ [-1, 44]
+        let v1 = v1 - 44 * (v1 >> 31);
+        v1 + 44 * ((43 - v1) >> 31)
+    } else {
+        // ml-dsa-65 and ml-dsa-87; mod m(16)
+        v1 & 15
+    }
+}
+
+
+/// `HighBits(r)` (Algorithm 37) over all coefficients of K polynomials.
+pub(crate) fn high_bits_poly<const K: usize>(gamma2: i32, r: &[R; K]) -> [R; K] {
+    core::array::from_fn(|k| R(core::array::from_fn(|n| high_bits(gamma2, r[k].0[n]))))
+}
+
+
+/// `MakeHint(z, r)` (Algorithm 39) over all coefficients of K polynomials, returning hint
+/// bits as 0/1 coefficients.
+pub(crate) fn make_hint_poly<const K: usize>(gamma2: i32, z: &[R; K], r: &[R; K]) -> [R; K] {
+    core::array::from_fn(|k| {
+        R(core::array::from_fn(|n| i32::from(make_hint(gamma2, z[k].0[n], r[k].0[n]))))
+    })
+}
+
+
+/// `UseHint(h, r)` (Algorithm 40) over all coefficients of K polynomials.
+pub(crate) fn use_hint_poly<const K: usize>(gamma2: i32, h: &[R; K], r: &[R; K]) -> [R; K] {
+    core::array::from_fn(|k| R(core::array::from_fn(|n| use_hint(gamma2, h[k].0[n], r[k].0[n]))))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Inputs at the extremes of what `decompose()` receives, for both gamma2 values in use
+    #[test]
+    fn test_decompose_extremes() {
+        for gamma2 in [(Q - 1) / 88, (Q - 1) / 32] {
+            for r in [0, 1, Q - 1, -(Q - 1), 2 * Q - 1, -(2 * Q - 1), 2_143_289_343, -2_143_289_343] {
+                let (r1, r0) = decompose(gamma2, r);
+                assert_eq!(r.rem_euclid(Q), (r1 * 2 * gamma2 + r0).rem_euclid(Q));
+                assert!(0 <= r1 && r1 < (Q - 1) / (2 * gamma2));
+                assert!(-gamma2 <= r0 && r0 <= gamma2);
             }
-            return r1 + 1;
-        } // explicit r1 - 1 mod m(44)
-        if r1 == 0 {
-            return 43;
         }
-        r1 - 1
-    } else {
-        // ml-dsa-65 and ml-dsa-87; explicit r1 + 1 mod m(16)
-        if r0 > 0 {
-            return (r1 + 1) & 15;
-        } // explicit r1 - 1 mod m(16)
-        (r1 - 1) & 15
     }
 
-    // 5: return r1
-    // r1 see first 'if' above
+
+    // The branch-free `use_hint()` against a direct transcription of Algorithm 40
+    #[test]
+    fn test_use_hint_spec() {
+        for gamma2 in [(Q - 1) / 88, (Q - 1) / 32] {
+            let m = (Q - 1) / (2 * gamma2);
+            for r in (0..Q).step_by(997).chain([0, gamma2, 2 * gamma2, Q - gamma2, Q - 1]) {
+                let (r1, r0) = decompose(gamma2, r);
+                assert_eq!(use_hint(gamma2, 0, r), r1);
+                let expected = if r0 > 0 { (r1 + 1).rem_euclid(m) } else { (r1 - 1).rem_euclid(m) };
+                assert_eq!(use_hint(gamma2, 1, r), expected);
+            }
+        }
+    }
 }
@@ -0,0 +1,258 @@
+// This file implements the simple length-framed concatenation of an ML-DSA signature with a
+// classical signature over the same message, as carried by experimental hybrid TLS and SSH
+// deployments. It is deliberately not the ASN.1 composite signature encoding.
+
+//! Hybrid signatures as a length-framed concatenation of an ML-DSA signature and a classical one.
+//!
+//! A hybrid signature is the ML-DSA signature followed by the classical signature (e.g.,
+//! Ed25519 or ECDSA, produced by the application over the same message), each preceded by its
+//! big-endian length:
+//!
+//! | Field     | Length        | Contents                                       |
+//! |-----------|---------------|------------------------------------------------|
+//! | length    | 2 or 4        | Big-endian length of the ML-DSA signature      |
+//! | ML-DSA    | `SIG_LEN`     | The encoded ML-DSA signature                   |
+//! | length    | 2 or 4        | Big-endian length of the classical signature   |
+//! | classical | variable      | The classical signature, opaque to this crate  |
+//!
+//! The width of the length fields is selected by [`Framing`]: two bytes as in a TLS
+//! `opaque<0..2^16-1>` vector, or four bytes as in an SSH `string`. Parsing is strict: lengths
+//! running past the end, and trailing bytes, are rejected. A hybrid signature verifies only when
+//! **both** component signatures verify, so that it remains secure as long as either scheme is.
+//!
+//! # Examples
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! # #[cfg(all(feature = "ml-dsa-65", feature = "default-rng"))] {
+//! use fips204::hybrid::{self, Framing};
+//! use fips204::ml_dsa_65;
+//!
+//! let (pk, sk) = ml_dsa_65::try_keygen()?;
+//! let classical_sig = [0x5Au8; 64]; // E.g., an Ed25519 signature over the same message
+//!
+//! let mut buf = [0u8; hybrid::framed_len(Framing::U16, ml_dsa_65::SIG_LEN, 64)];
+//! let len = hybrid::try_sign_into(&sk, b"message", b"ctx", &classical_sig, Framing::U16, &mut buf)?;
+//!
+//! // The classical verifier is supplied by the application
+//! let v = hybrid::verify(&pk, b"message", b"ctx", &buf[..len], Framing::U16, |sig| sig == classical_sig);
+//! assert!(v);
+//! # }
+//! # Ok(())}
+//! ```
+
+use crate::helpers::ensure;
+use crate::traits::{DynSigner, DynVerifier};
+use rand_core::CryptoRngCore;
+#[cfg(feature = "default-rng")]
+use rand_core::OsRng;
+
+
+/// The width of the big-endian length field preceding each component signature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Framing {
+    /// Two-byte lengths, as in a TLS `opaque<0..2^16-1>` vector.
+    U16,
+    /// Four-byte lengths, as in an SSH `string`.
+    U32,
+}
+
+impl Framing {
+    /// Returns the width in bytes of the length field.
+    #[must_use]
+    pub const fn prefix_len(self) -> usize {
+        match self {
+            Framing::U16 => 2,
+            Framing::U32 => 4,
+        }
+    }
+
+
+    /// Returns the largest component signature length the length field can express.
+    #[must_use]
+    pub const fn max_component_len(self) -> usize {
+        match self {
+            Framing::U16 => u16::MAX as usize,
+            #[allow(clippy::cast_possible_truncation)] // Which is `usize::MAX` on 16-bit targets
+            Framing::U32 => u32::MAX as usize,
+        }
+    }
+}
+
+
+/// Returns the length of a hybrid signature with component signatures of the given lengths,
+/// e.g., for sizing buffers.
+#[must_use]
+pub const fn framed_len(framing: Framing, pq_len: usize, classical_len: usize) -> usize {
+    2 * framing.prefix_len() + pq_len + classical_len
+}
+
+
+/// Writes the hybrid signature framing `pq_sig` and `classical_sig` into the start of `out`,
+/// returning its length.
+///
+/// # Errors
+/// Returns an error when a component is too long for the framing, or `out` is too short.
+pub fn encode(framing: Framing, pq_sig: &[u8], classical_sig: &[u8], out: &mut [u8]) -> Result<usize, &'static str> {
+    let max = framing.max_component_len();
+    ensure!(pq_sig.len() <= max && classical_sig.len() <= max, "Hybrid: component too long for framing");
+    let len = framed_len(framing, pq_sig.len(), classical_sig.len());
+    let out = out.get_mut(..len).ok_or("Hybrid: output buffer too short")?;
+    let (pq_out, classical_out) = out.split_at_mut(framing.prefix_len() + pq_sig.len());
+    put_component(framing, pq_sig, pq_out);
+    put_component(framing, classical_sig, classical_out);
+    Ok(len)
+}
+
+
+/// Splits a hybrid signature into its ML-DSA and classical component signatures, without
+/// verifying either.
+///
+/// # Errors
+/// Returns an error when a length runs past the end of `framed`, or bytes trail the classical
+/// signature.
+pub fn split(framing: Framing, framed: &[u8]) -> Result<(&[u8], &[u8]), &'static str> {
+    let (pq_sig, rest) = take_component(framing, framed)?;
+    let (classical_sig, rest) = take_component(framing, rest)?;
+    ensure!(rest.is_empty(), "Hybrid: trailing bytes");
+    Ok((pq_sig, classical_sig))
+}
+
+
+/// Signs the message with ML-DSA and writes the hybrid signature, framing the ML-DSA signature
+/// with the caller's `classical_sig` over the same message, into the start of `out`, returning
+/// its length. This function utilizes the **provided** random number generator.
+///
+/// # Errors
+/// Returns an error when the random number generator fails, the context is too long, the
+/// classical signature is too long for the framing, or `out` is too short.
+pub fn try_sign_into_with_rng(
+    sk: &dyn DynSigner, rng: &mut dyn CryptoRngCore, message: &[u8], ctx: &[u8], classical_sig: &[u8],
+    framing: Framing, out: &mut [u8],
+) -> Result<usize, &'static str> {
+    let pq_len = sk.signature_len();
+    ensure!(classical_sig.len() <= framing.max_component_len(), "Hybrid: component too long for framing");
+    let len = framed_len(framing, pq_len, classical_sig.len());
+    let out = out.get_mut(..len).ok_or("Hybrid: output buffer too short")?;
+    let (pq_out, classical_out) = out.split_at_mut(framing.prefix_len() + pq_len);
+    let written = sk.try_sign_into_with_rng(rng, message, ctx, &mut pq_out[framing.prefix_len()..])?;
+    debug_assert_eq!(written, pq_len);
+    put_length(framing, pq_len, pq_out);
+    put_component(framing, classical_sig, classical_out);
+    Ok(len)
+}
+
+
+/// Signs the message with ML-DSA and writes the hybrid signature, framing the ML-DSA signature
+/// with the caller's `classical_sig` over the same message, into the start of `out`, returning
+/// its length. This function utilizes the **OS default** random number generator.
+///
+/// # Errors
+/// Returns an error when the random number generator fails, the context is too long, the
+/// classical signature is too long for the framing, or `out` is too short.
+#[cfg(feature = "default-rng")]
+pub fn try_sign_into(
+    sk: &dyn DynSigner, message: &[u8], ctx: &[u8], classical_sig: &[u8], framing: Framing, out: &mut [u8],
+) -> Result<usize, &'static str> {
+    try_sign_into_with_rng(sk, &mut OsRng, message, ctx, classical_sig, framing, out)
+}
+
+
+/// Verifies a hybrid signature on the message: the ML-DSA signature under `pk` and `ctx`, and
+/// the classical signature via `verify_classical` (which is only called once the ML-DSA
+/// signature verifies). Returns `true` only when the framing is well-formed and **both**
+/// signatures verify. As this function operates on purely public data, it need/does not provide
+/// constant-time assurances.
+pub fn verify(
+    pk: &dyn DynVerifier, message: &[u8], ctx: &[u8], framed: &[u8], framing: Framing,
+    verify_classical: impl FnOnce(&[u8]) -> bool,
+) -> bool {
+    let Ok((pq_sig, classical_sig)) = split(framing, framed) else { return false };
+    pk.verify_bytes(message, pq_sig, ctx) && verify_classical(classical_sig)
+}
+
+
+/// Writes the length of a component into the start of `out`, which must be wide enough.
+fn put_length(framing: Framing, len: usize, out: &mut [u8]) {
+    let be = (len as u64).to_be_bytes();
+    let prefix_len = framing.prefix_len();
+    out[..prefix_len].copy_from_slice(&be[8 - prefix_len..]);
+}
+
+
+/// Writes a length-prefixed component into `out`, which must be exactly as long.
+fn put_component(framing: Framing, component: &[u8], out: &mut [u8]) {
+    put_length(framing, component.len(), out);
+    out[framing.prefix_len()..].copy_from_slice(component);
+}
+
+
+/// Takes a length-prefixed component off the front of `bytes`, returning it and the rest.
+fn take_component(framing: Framing, bytes: &[u8]) -> Result<(&[u8], &[u8]), &'static str> {
+    let prefix_len = framing.prefix_len();
+    let prefix = bytes.get(..prefix_len).ok_or("Hybrid: truncated length")?;
+    let len = prefix.iter().fold(0u64, |acc, &b| (acc << 8) | u64::from(b));
+    let len = usize::try_from(len).map_err(|_| "Hybrid: truncated component")?;
+    let rest = &bytes[prefix_len..];
+    ensure!(len <= rest.len(), "Hybrid: truncated component");
+    Ok(rest.split_at(len))
+}
+
+
+#[cfg(all(test, feature = "ml-dsa-44"))]
+mod tests {
+    use super::*;
+    use crate::ml_dsa_44;
+    use crate::traits::Signer;
+    use rand_chacha::rand_core::SeedableRng;
+
+    static LONG: [u8; 65_536] = [0; 65_536];
+
+    #[test]
+    fn test_hybrid_round_trip() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+        let (pk, sk) = ml_dsa_44::try_keygen_with_rng(&mut rng).unwrap();
+        let classical = [0xA5u8; 71]; // E.g., a DER-encoded ECDSA P-256 signature
+
+        let mut buf = [0u8; framed_len(Framing::U32, ml_dsa_44::SIG_LEN, 71) + 1];
+        for framing in [Framing::U16, Framing::U32] {
+            let mut rng2 = rng.clone();
+            let len = try_sign_into_with_rng(&sk, &mut rng, b"msg", b"ctx", &classical, framing, &mut buf).unwrap();
+            assert_eq!(len, framed_len(framing, ml_dsa_44::SIG_LEN, 71));
+
+            // The framing is that of `encode()`, around the plain ML-DSA signature
+            let sig = sk.try_sign_with_rng(&mut rng2, b"msg", b"ctx").unwrap();
+            let mut expected = [0u8; framed_len(Framing::U32, ml_dsa_44::SIG_LEN, 71)];
+            assert_eq!(encode(framing, &sig, &classical, &mut expected), Ok(len));
+            assert_eq!(buf[..len], expected[..len]);
+            assert_eq!(split(framing, &buf[..len]), Ok((&sig[..], &classical[..])));
+            let prefix_len = framing.prefix_len();
+            assert_eq!(buf[..prefix_len], [0, 0, 0x09, 0x74][4 - prefix_len..]); // 2420 bytes
+
+            // Both components must verify
+            let framed = &buf[..len];
+            assert!(verify(&pk, b"msg", b"ctx", framed, framing, |s| s == classical));
+            assert!(!verify(&pk, b"msg", b"ctx", framed, framing, |_| false));
+            assert!(!verify(&pk, b"msg", b"xxx", framed, framing, |_| true));
+            assert!(!verify(&pk, b"xxx", b"ctx", framed, framing, |_| true));
+
+            // Malformed framings are rejected
+            assert!(!verify(&pk, b"msg", b"ctx", &buf[..=len], framing, |_| true));
+            assert!(!verify(&pk, b"msg", b"ctx", &buf[..len - 1], framing, |_| true));
+            let other = if framing == Framing::U16 { Framing::U32 } else { Framing::U16 };
+            assert!(!verify(&pk, b"msg", b"ctx", framed, other, |_| true));
+            assert!(split(framing, &buf[..1]).is_err());
+
+            // Too short output buffers
+            let short = &mut buf[..len - 1];
+            assert!(try_sign_into_with_rng(&sk, &mut rng, b"msg", b"ctx", &classical, framing, short).is_err());
+            assert!(encode(framing, &sig, &classical, short).is_err());
+        }
+
+        // Components exceeding the length field
+        assert!(encode(Framing::U16, &[], &LONG, &mut buf).is_err());
+        assert!(encode(Framing::U16, &LONG, &[], &mut buf).is_err());
+        assert!(try_sign_into_with_rng(&sk, &mut rng, b"msg", b"ctx", &LONG, Framing::U16, &mut buf).is_err());
+        assert_eq!(split(Framing::U32, &[0, 1, 0, 0, 0, 0, 0, 0]), Err("Hybrid: truncated component"));
+    }
+}
@@ -0,0 +1,206 @@
+// This file implements version/algorithm identification for attestations and logs.
+
+use crate::types::ParamSet;
+use core::fmt;
+use core::mem::size_of;
+
+
+/// The arithmetic backend used for the NTT and polynomial arithmetic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Backend {
+    /// Portable scalar Rust (no SIMD, no assembly)
+    Scalar,
+}
+
+
+/// Identification of this implementation, suitable for inclusion in security attestations
+/// and logs of embedding applications. See [`algorithm_id()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AlgorithmId {
+    /// The version of this crate, e.g. `0.4.4`.
+    pub crate_version: &'static str,
+    /// The revision of FIPS 204 that is implemented.
+    pub fips_revision: &'static str,
+    /// The enabled arithmetic backends.
+    pub backends: &'static [Backend],
+    /// The security parameter sets compiled in (per the `ml-dsa-44/65/87` features).
+    pub param_sets: &'static [ParamSet],
+}
+
+
+/// Returns the crate version, implemented FIPS 204 revision, enabled backends and enabled
+/// parameter sets. The `Display` implementation renders this as a single log-friendly line.
+///
+/// # Examples
+/// ```rust
+/// let id = fips204::algorithm_id();
+/// assert_eq!(id.fips_revision, "FIPS 204 (August 13, 2024)");
+/// # #[cfg(feature = "ml-dsa-65")]
+/// assert!(id.param_sets.contains(&fips204::ParamSet::MlDsa65));
+/// let line = id.to_string(); // e.g. "fips204 0.4.4; FIPS 204 (August 13, 2024); ..."
+/// ```
+#[must_use]
+pub const fn algorithm_id() -> AlgorithmId {
+    AlgorithmId {
+        crate_version: env!("CARGO_PKG_VERSION"),
+        fips_revision: "FIPS 204 (August 13, 2024)",
+        backends: &[Backend::Scalar],
+        param_sets: &[
+            #[cfg(feature = "ml-dsa-44")]
+            ParamSet::MlDsa44,
+            #[cfg(feature = "ml-dsa-65")]
+            ParamSet::MlDsa65,
+            #[cfg(feature = "ml-dsa-87")]
+            ParamSet::MlDsa87,
+        ],
+    }
+}
+
+
+/// Whether the ML-DSA-44 parameter set (the `ml-dsa-44` feature) is compiled in.
+///
+/// Dependent crates cannot test another crate's features with `cfg`, but can branch on these
+/// constants (including in `const` contexts) to adapt to the linked build. Code naming a
+/// parameter set module, e.g. `fips204::ml_dsa_87`, still only compiles when it is enabled.
+pub const HAS_ML_DSA_44: bool = cfg!(feature = "ml-dsa-44");
+
+/// Whether the ML-DSA-65 parameter set (the `ml-dsa-65` feature) is compiled in; see
+/// [`HAS_ML_DSA_44`].
+pub const HAS_ML_DSA_65: bool = cfg!(feature = "ml-dsa-65");
+
+/// Whether the ML-DSA-87 parameter set (the `ml-dsa-87` feature) is compiled in; see
+/// [`HAS_ML_DSA_44`].
+pub const HAS_ML_DSA_87: bool = cfg!(feature = "ml-dsa-87");
+
+
+/// Returns the security parameter sets compiled in, in ascending order of strength; this is
+/// `algorithm_id().param_sets`.
+///
+/// # Examples
+/// ```rust
+/// use fips204::{enabled_paramsets, ParamSet};
+///
+/// let sets = enabled_paramsets();
+/// assert_eq!(sets.contains(&ParamSet::MlDsa87), fips204::HAS_ML_DSA_87);
+/// // E.g., a plugin negotiating the strongest parameter set available
+/// let strongest = sets.last().copied();
+/// # assert_eq!(strongest.is_some(), fips204::HAS_ML_DSA_44 || fips204::HAS_ML_DSA_65 || fips204::HAS_ML_DSA_87);
+/// ```
+#[must_use]
+pub const fn enabled_paramsets() -> &'static [ParamSet] { algorithm_id().param_sets }
+
+
+/// Total size in bytes of the constant lookup tables of this crate, which is at present only
+/// the NTT zeta table (256 `i32` values, in bit-reversed order and Montgomery form).
+///
+/// The tables are `static`s evaluated at compile time (by `const fn`), so they are placed in
+/// `.rodata` and need no runtime initialization, lazy statics or RAM copies; a secure-boot
+/// ROM holding a verify-only build can count them against its read-only memory budget. They
+/// are shared by key generation, signing and verification, so a verify-only build needs all of
+/// them. This excludes the round constants of the SHA-2 and SHA-3 dependencies.
+pub const CONST_TABLE_BYTES: usize = size_of::<[i32; 256]>();
+
+
+/// The FIPS 204 Table 1 parameter values of a security parameter set, with the resulting
+/// encoding lengths, as returned by each module's `params()` (e.g., [`crate::ml_dsa_65::params()`]
+/// when the `ml-dsa-65` feature is enabled). The `Display` implementation renders these as a
+/// single line for audit displays.
+///
+/// # Examples
+/// ```rust
+/// # #[cfg(feature = "ml-dsa-65")] {
+/// let params = fips204::ml_dsa_65::params();
+/// assert_eq!((params.k, params.l, params.eta, params.tau), (6, 5, 4, 49));
+/// assert_eq!(params.beta, params.tau * params.eta);
+/// assert_eq!(params.sig_len, fips204::ml_dsa_65::SIG_LEN);
+/// assert!(params.to_string().starts_with("ML-DSA-65: q=8380417 zeta=1753 d=13 tau=49 lambda=192 "));
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Params {
+    /// The parameter set these values belong to.
+    pub param_set: ParamSet,
+    /// The modulus `q`.
+    pub q: u32,
+    /// The 512th root of unity `ζ` modulo `q`.
+    pub zeta: u32,
+    /// The number of dropped bits `d` from `t`.
+    pub d: u32,
+    /// The number of ±1s `τ` in the challenge polynomial.
+    pub tau: u32,
+    /// The collision strength `λ` of the commitment hash `c_tilde`, in bits.
+    pub lambda: usize,
+    /// The coefficient range `γ1` of `y`.
+    pub gamma1: u32,
+    /// The low-order rounding range `γ2`.
+    pub gamma2: u32,
+    /// The number of rows `k` of the matrix `A`.
+    pub k: usize,
+    /// The number of columns `l` of the matrix `A`.
+    pub l: usize,
+    /// The private key range `η`.
+    pub eta: u32,
+    /// `β = τ·η`.
+    pub beta: u32,
+    /// The maximum number `ω` of 1s in the hint `h`.
+    pub omega: u32,
+    /// The public key length in bytes.
+    pub pk_len: usize,
+    /// The private key length in bytes.
+    pub sk_len: usize,
+    /// The signature length in bytes.
+    pub sig_len: usize,
+}
+
+
+impl fmt::Display for Params {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: q={} zeta={} d={} tau={} lambda={} gamma1={} gamma2={} k={} l={} eta={} beta={} omega={}; \
+             pk={} sk={} sig={} bytes",
+            self.param_set,
+            self.q,
+            self.zeta,
+            self.d,
+            self.tau,
+            self.lambda,
+            self.gamma1,
+            self.gamma2,
+            self.k,
+            self.l,
+            self.eta,
+            self.beta,
+            self.omega,
+            self.pk_len,
+            self.sk_len,
+            self.sig_len
+        )
+    }
+}
+
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Backend::Scalar => f.write_str("scalar"),
+        }
+    }
+}
+
+
+impl fmt::Display for AlgorithmId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "fips204 {}; {}; backends:", self.crate_version, self.fips_revision)?;
+        for backend in self.backends {
+            write!(f, " {backend}")?;
+        }
+        f.write_str("; parameter sets:")?;
+        for param_set in self.param_sets {
+            write!(f, " {param_set}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,277 @@
+// This file implements a versioned container for an encoded private key, with optional key
+// identifier and creation time metadata, and a checksum against accidental corruption.
+
+//! Private key containers: an encoded private key together with its inventory metadata.
+//!
+//! A [`KeyContainer`] carries the FIPS 204 private key encoding alongside its parameter set and,
+//! optionally, a key identifier (e.g., a serial number or inventory label) and a creation time,
+//! so that key-management systems can track keys without sidecar files. The encoding is:
+//!
+//! | Field       | Length          | Contents                                                    |
+//! |-------------|-----------------|-------------------------------------------------------------|
+//! | magic       | 4               | `MLDK`                                                      |
+//! | version     | 1               | `1`                                                         |
+//! | parameters  | 1               | Final NIST OID arc: `0x11`, `0x12` or `0x13` for ML-DSA-44/65/87 |
+//! | flags       | 1               | Bit 0: key identifier; bit 1: creation time                 |
+//! | key id      | 1 + `len` (if flagged) | Length byte, then the key identifier                 |
+//! | created     | 8 (if flagged)  | Big-endian seconds since the Unix epoch                     |
+//! | private key | `SK_LEN`        | The encoded private key                                     |
+//! | checksum    | 32              | `H(all preceding bytes, 32)`                                |
+//!
+//! The checksum covers the metadata as well as the key, and detects accidental corruption of
+//! either (e.g., storage or transmission errors). It is an unkeyed hash, so anyone able to
+//! modify the container can recompute it: it provides **no** authentication or tamper
+//! protection. Nor does the container encrypt the private key, which should be stored or
+//! wrapped accordingly (see e.g. `SealedPrivateKey`). Parsing is strict: unknown versions,
+//! flags or parameter sets, trailing bytes and checksum mismatches are rejected.
+//!
+//! # Examples
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! # #[cfg(all(feature = "ml-dsa-65", feature = "default-rng"))] {
+//! use fips204::key_container::{KeyContainer, MAX_LEN};
+//! use fips204::traits::{SerDes, Signer};
+//! use fips204::{ml_dsa_65, ParamSet};
+//!
+//! let (_pk, sk) = ml_dsa_65::try_keygen()?;
+//! let sk_bytes = sk.into_bytes();
+//! let container = KeyContainer {
+//!     param_set: ParamSet::MlDsa65,
+//!     key_id: Some(b"signing-2026-01".as_slice()),
+//!     created: Some(1_767_225_600),
+//!     sk: &sk_bytes,
+//! };
+//! let mut buf = [0u8; MAX_LEN];
+//! let len = container.encode(&mut buf)?;
+//!
+//! let parsed = KeyContainer::parse(&buf[..len])?;
+//! assert_eq!(parsed.key_id, Some(b"signing-2026-01".as_slice()));
+//! let sk = ml_dsa_65::PrivateKey::try_from_bytes(parsed.sk.try_into()?)?;
+//! let _sig = sk.try_sign(b"message", b"ctx")?;
+//! # }
+//! # Ok(())}
+//! ```
+
+use crate::hashing::h256_xof;
+use crate::helpers::ensure;
+use crate::types::ParamSet;
+use sha3::digest::XofReader;
+
+
+const MAGIC: [u8; 4] = *b"MLDK";
+const VERSION: u8 = 1;
+const FLAG_KEY_ID: u8 = 0x01;
+const FLAG_CREATED: u8 = 0x02;
+const HEADER_LEN: usize = 7;
+const CHECKSUM_LEN: usize = 32;
+
+/// The maximum encoded length of a container, over all parameter sets.
+pub const MAX_LEN: usize = HEADER_LEN + 1 + 255 + 8 + 4896 + CHECKSUM_LEN;
+
+
+/// An encoded private key together with its metadata; see the [module documentation](self)
+/// for the encoding. The `Debug` output omits the private key.
+#[derive(Clone, PartialEq, Eq)]
+pub struct KeyContainer<'a> {
+    /// The parameter set of the key.
+    pub param_set: ParamSet,
+    /// The key identifier (255 bytes or fewer), if any.
+    pub key_id: Option<&'a [u8]>,
+    /// The creation time in seconds since the Unix epoch, if recorded.
+    pub created: Option<u64>,
+    /// The encoded private key.
+    pub sk: &'a [u8],
+}
+
+
+impl<'a> KeyContainer<'a> {
+    /// Returns the encoded length of the container.
+    #[must_use]
+    pub fn encoded_len(&self) -> usize {
+        HEADER_LEN
+            + self.key_id.map_or(0, |id| 1 + id.len())
+            + if self.created.is_some() { 8 } else { 0 }
+            + self.sk.len()
+            + CHECKSUM_LEN
+    }
+
+
+    /// Encodes the container into `out`, returning the number of bytes written.
+    ///
+    /// # Errors
+    /// Returns an error when the private key length does not match the parameter set, the key
+    /// identifier is too long, or `out` is too short.
+    #[allow(clippy::cast_possible_truncation)] // key id length checked
+    pub fn encode(&self, out: &mut [u8]) -> Result<usize, &'static str> {
+        ensure!(self.sk.len() == self.param_set.sk_len(), "KeyContainer: wrong private key length");
+        ensure!(self.key_id.map_or(true, |id| id.len() < 256), "KeyContainer: key id too long");
+        let len = self.encoded_len();
+        ensure!(out.len() >= len, "KeyContainer: output too short");
+
+        let mut flags = 0;
+        if self.key_id.is_some() {
+            flags |= FLAG_KEY_ID;
+        }
+        if self.created.is_some() {
+            flags |= FLAG_CREATED;
+        }
+        let mut pos = 0;
+        let mut put = |bytes: &[u8]| {
+            out[pos..pos + bytes.len()].copy_from_slice(bytes);
+            pos += bytes.len();
+        };
+        put(&MAGIC);
+        put(&[VERSION, param_set_id(self.param_set), flags]);
+        if let Some(id) = self.key_id {
+            put(&[id.len() as u8]);
+            put(id);
+        }
+        if let Some(created) = self.created {
+            put(&created.to_be_bytes());
+        }
+        put(self.sk);
+        let body_len = len - CHECKSUM_LEN;
+        let checksum = checksum(&out[..body_len]);
+        out[body_len..len].copy_from_slice(&checksum);
+        Ok(len)
+    }
+
+
+    /// Parses an encoded container, borrowing the key identifier and private key from `bytes`.
+    ///
+    /// # Errors
+    /// Returns an error when `bytes` is not exactly a well-formed container, or its checksum
+    /// does not match.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, &'static str> {
+        ensure!(bytes.len() >= HEADER_LEN + CHECKSUM_LEN, "KeyContainer: truncated");
+        let (mut rest, expected) = bytes.split_at(bytes.len() - CHECKSUM_LEN);
+        let mut take = |len: usize| -> Result<&'a [u8], &'static str> {
+            ensure!(rest.len() >= len, "KeyContainer: truncated");
+            let (head, tail) = rest.split_at(len);
+            rest = tail;
+            Ok(head)
+        };
+        ensure!(take(4)? == MAGIC, "KeyContainer: bad magic");
+        let [version, id, flags] = <[u8; 3]>::try_from(take(3)?).map_err(|_| "KeyContainer: truncated")?;
+        ensure!(version == VERSION, "KeyContainer: unsupported version");
+        ensure!(flags & !(FLAG_KEY_ID | FLAG_CREATED) == 0, "KeyContainer: unknown flags");
+        let param_set = match id {
+            0x11 => ParamSet::MlDsa44,
+            0x12 => ParamSet::MlDsa65,
+            0x13 => ParamSet::MlDsa87,
+            _ => return Err("KeyContainer: unknown parameter set"),
+        };
+        let key_id = if flags & FLAG_KEY_ID == 0 {
+            None
+        } else {
+            let id_len = take(1)?[0];
+            Some(take(usize::from(id_len))?)
+        };
+        let created = if flags & FLAG_CREATED == 0 {
+            None
+        } else {
+            Some(u64::from_be_bytes(take(8)?.try_into().map_err(|_| "KeyContainer: truncated")?))
+        };
+        let sk = take(param_set.sk_len())?;
+        ensure!(rest.is_empty(), "KeyContainer: trailing bytes");
+
+        // The checksum covers the private key, so is compared without early exit
+        let actual = checksum(&bytes[..bytes.len() - CHECKSUM_LEN]);
+        let diff = actual.iter().zip(expected.iter()).fold(0, |acc, (a, b)| acc | (a ^ b));
+        ensure!(diff == 0, "KeyContainer: checksum mismatch");
+        Ok(Self { param_set, key_id, created, sk })
+    }
+}
+
+
+impl core::fmt::Debug for KeyContainer<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("KeyContainer")
+            .field("param_set", &self.param_set)
+            .field("key_id", &self.key_id)
+            .field("created", &self.created)
+            .field("sk", &format_args!("<{} bytes redacted>", self.sk.len()))
+            .finish()
+    }
+}
+
+
+// Unkeyed, so detects corruption but not deliberate modification
+fn checksum(body: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let mut out = [0u8; CHECKSUM_LEN];
+    h256_xof(&[body]).read(&mut out);
+    out
+}
+
+
+const fn param_set_id(param_set: ParamSet) -> u8 {
+    match param_set {
+        ParamSet::MlDsa44 => 0x11,
+        ParamSet::MlDsa65 => 0x12,
+        ParamSet::MlDsa87 => 0x13,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_container_roundtrip() {
+        let sk = [0x5Au8; 2560];
+        let containers = [
+            KeyContainer { param_set: ParamSet::MlDsa44, key_id: None, created: None, sk: &sk },
+            KeyContainer { param_set: ParamSet::MlDsa44, key_id: Some(b"serial-0042".as_slice()), created: None, sk: &sk },
+            KeyContainer { param_set: ParamSet::MlDsa44, key_id: Some(&[][..]), created: Some(1_700_000_000), sk: &sk },
+        ];
+        let mut buf = [0u8; MAX_LEN];
+        for container in &containers {
+            let len = container.encode(&mut buf).unwrap();
+            assert_eq!(len, container.encoded_len());
+            assert_eq!(KeyContainer::parse(&buf[..len]).as_ref(), Ok(container));
+
+            // Strict parsing
+            assert_eq!(KeyContainer::parse(&buf[..len - 1]), Err("KeyContainer: truncated"));
+            assert_eq!(KeyContainer::parse(&buf[..=len]), Err("KeyContainer: trailing bytes"));
+            for (pos, value, err) in [
+                (0, b'X', "KeyContainer: bad magic"),
+                (4, 2, "KeyContainer: unsupported version"),
+                (5, 0x14, "KeyContainer: unknown parameter set"),
+                (6, 0x04, "KeyContainer: unknown flags"),
+            ] {
+                let mut bad = buf;
+                bad[pos] = value;
+                assert_eq!(KeyContainer::parse(&bad[..len]), Err(err));
+            }
+
+            // Any change to the metadata or key is detected
+            for pos in HEADER_LEN..len {
+                let mut bad = buf;
+                bad[pos] ^= 0x01;
+                assert!(KeyContainer::parse(&bad[..len]).is_err(), "{pos}");
+            }
+        }
+
+        // The creation time is big-endian, after the key id
+        assert_eq!(buf[HEADER_LEN..HEADER_LEN + 9], [0, 0, 0, 0, 0, 0x65, 0x53, 0xF1, 0x00]);
+
+        let short = KeyContainer { sk: &sk[1..], ..containers[0].clone() };
+        assert_eq!(short.encode(&mut buf), Err("KeyContainer: wrong private key length"));
+        let long_id = [0u8; 256];
+        let long = KeyContainer { key_id: Some(&long_id[..]), ..containers[0].clone() };
+        assert_eq!(long.encode(&mut buf), Err("KeyContainer: key id too long"));
+        assert_eq!(containers[0].encode(&mut buf[..100]), Err("KeyContainer: output too short"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_key_container_debug_redacts_key() {
+        let sk = [0x5Au8; 2560];
+        let container = KeyContainer { param_set: ParamSet::MlDsa44, key_id: None, created: Some(1), sk: &sk };
+        let debug = std::format!("{container:?}");
+        assert!(debug.contains("<2560 bytes redacted>") && debug.contains("created: Some(1)"));
+        assert!(!debug.contains("90"), "{debug}"); // 0x5A
+    }
+}
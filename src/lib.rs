@@ -1,5 +1,6 @@
 #![no_std]
-#![deny(clippy::pedantic, warnings, missing_docs, unsafe_code)]
+#![deny(clippy::pedantic, warnings, missing_docs)]
+#![forbid(unsafe_code)]
 // Almost all of the 'allow' category...
 #![deny(absolute_paths_not_starting_with_crate, dead_code)]
 #![deny(elided_lifetimes_in_paths, explicit_outlives_requirements, keyword_idents)]
@@ -77,10 +78,20 @@
 
 // Note that debug_assert! statements enforce correct program construction and are not involved
 // in any operational dataflow (so are good fuzz targets). The ensure! statements implement
-// conservative dataflow validation and do not panic. Separately, functions are only generic
-// over security parameters that are directly involved in memory allocation (on the stack).
-// Some coding oddities are driven by 'clippy pedantic' and the fact that Rust doesn't currently
-// do well with arithmetic on generic parameters.
+// conservative dataflow validation and do not panic. For reviewers, the checks on untrusted
+// input that remain in release builds are:
+//   pkDecode    t1 range (via SimpleBitUnpack; cannot fail as 10 bits span the range)
+//   skDecode    s1/s2 range (constant-time, aggregated), t0 range (via BitUnpack)
+//   sigDecode   z range (via BitUnpack), hint counts/order/padding and total ≤ ω (HintBitUnpack)
+//   all         exact input lengths (fixed-size array types), ctx length ≤ 255
+// The remaining debug_assert! statements check (a) buffer lengths against the security
+// parameters, which the const generics fix at compile time and `encodings::tests` verifies
+// for every parameter set, and (b) ranges of internally computed values, which the decoders
+// are exercised against with arbitrary input (in debug builds) by `decoders_never_panic`.
+// Setting `debug-assertions = true` in a release profile re-enables all of them. Separately,
+// functions are only generic over security parameters that are directly involved in memory
+// allocation (on the stack). Some coding oddities are driven by 'clippy pedantic' and the fact
+// that Rust doesn't currently do well with arithmetic on generic parameters.
 
 // Note that the `CTEST` generic parameter supports constant-time measurements by dudect. This
 // is done by minimally removing timing variability of non-secret data (such as the rejection
@@ -88,22 +99,80 @@
 // `false`) except for the single function (per namespace) `dudect_keygen_sign_with_rng()`
 // which is only exposed when the non-default `dudect` feature is enabled.
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
 /// The `rand_core` types are re-exported so that users of fips204 do not
 /// have to worry about using the exact correct version of `rand_core`.
 pub use rand_core::{CryptoRng, Error as RngError, RngCore};
 
+#[cfg(any(feature = "ml-dsa-44", feature = "ml-dsa-65", feature = "ml-dsa-87"))]
+mod any;
+#[cfg(feature = "cbor")]
+mod cbor;
 mod conversion;
 mod encodings;
+mod error;
 mod hashing;
 mod helpers;
 mod high_low;
+mod info;
 mod ml_dsa;
 mod ntt;
+mod self_test;
+mod spki;
 mod types;
 
 /// All functionality is covered by traits, such that consumers can utilize trait objects as desired.
 pub mod traits;
-pub use crate::types::Ph;
+#[cfg(any(feature = "ml-dsa-44", feature = "ml-dsa-65", feature = "ml-dsa-87"))]
+pub use crate::any::{AnyPublicKey, AnySignature};
+pub use crate::error::Error;
+pub use crate::info::{
+    algorithm_id, enabled_paramsets, AlgorithmId, Backend, Params, CONST_TABLE_BYTES, HAS_ML_DSA_44, HAS_ML_DSA_65,
+    HAS_ML_DSA_87,
+};
+pub use crate::self_test::{self_test, SelfTestReport, SelfTestResult};
+pub use crate::types::{Context, Ctx, DigestPh, Msg, ParamSet, Ph, SigningMode, TaggedSignature, VerifyMode};
+/// The `digest` crate, whose `Digest` trait [`DigestPh`] accepts, is re-exported so that
+/// users implementing it use the exact correct version.
+pub use sha2::digest;
+
+#[cfg(feature = "audit")]
+pub mod audit;
+#[cfg(feature = "bench-utils")]
+pub mod bench_utils;
+
+#[cfg(any(feature = "ml-dsa-44", feature = "ml-dsa-65", feature = "ml-dsa-87"))]
+pub mod bundle;
+
+#[cfg(feature = "diagnostics")]
+pub mod diagnostics;
+
+#[cfg(feature = "fips-mode")]
+pub mod fips;
+pub mod fixed_latency;
+pub mod hybrid;
+pub mod key_container;
+
+#[cfg(feature = "merkle")]
+pub mod merkle;
+
+pub mod message;
+#[cfg(feature = "policy")]
+pub mod policy;
+#[cfg(feature = "std")]
+pub mod pool;
+pub mod rng;
+pub mod rollover;
+
+#[cfg(feature = "sss")]
+pub mod sss;
+#[cfg(feature = "vectors")]
+pub mod vectors;
 
 // Applies across all security parameter sets
 const Q: i32 = 8_380_417; // 2^23 - 2^13 + 1 = 0x7FE001; page 15 table 1 first row
@@ -120,6 +189,9 @@ macro_rules! functionality {
         use crate::helpers;
         use crate::ml_dsa;
         use crate::ntt;
+        #[cfg(feature = "cbor")]
+        use crate::cbor;
+        use crate::spki;
         use crate::traits::{KeyGen, SerDes, Signer, Verifier};
         use crate::types;
         use rand_core::CryptoRngCore;
@@ -140,6 +212,12 @@ macro_rules! functionality {
         pub struct KG();
 
 
+        /// Empty struct standing for this security parameter set as a whole, so that generic
+        /// code can swap signature schemes. Implements the [`crate::traits::PqSignature`] trait.
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+        pub struct Scheme;
+
+
         /// Private key specific to the target security parameter set that contains
         /// precomputed elements which improves signature performance.
         ///
@@ -156,9 +234,54 @@ macro_rules! functionality {
         pub type PublicKey = crate::types::PublicKey<K, L>;
 
 
+        /// A public key and its private key, kept together; see the "KEY PAIR" functions.
+        pub type Keypair = crate::types::Keypair<K, L>;
+
+
+        /// An encoded private key, validated on construction, that signs by expanding itself
+        /// on each call; see the "SIGNING WITH ENCODED PRIVATE KEYS" functions. Implements the
+        /// [`crate::traits::Signer`] and [`crate::traits::SerDes`] traits.
+        pub type EncodedPrivateKey = crate::types::EncodedPrivateKey<SK_LEN>;
+
+
         // Note: (public) Signature is just a vanilla fixed-size byte array
 
 
+        // ----- PARAMETERS ---
+
+        /// Returns the FIPS 204 Table 1 parameter values of this security parameter set, and the
+        /// resulting encoding lengths, for audit displays and protocol negotiation metadata.
+        #[must_use]
+        #[allow(clippy::cast_sign_loss)] // The signed constants are all positive
+        pub const fn params() -> crate::Params {
+            crate::Params {
+                param_set: PARAM_SET,
+                q: Q as u32,
+                zeta: crate::ZETA as u32,
+                d: D,
+                tau: TAU as u32,
+                lambda: LAMBDA,
+                gamma1: GAMMA1 as u32,
+                gamma2: GAMMA2 as u32,
+                k: K,
+                l: L,
+                eta: ETA as u32,
+                beta: BETA as u32,
+                omega: OMEGA as u32,
+                pk_len: PK_LEN,
+                sk_len: SK_LEN,
+                sig_len: SIG_LEN,
+            }
+        }
+
+
+        /// The static memory used by key generation, signing and verification, in bytes: the
+        /// read-only constant tables counted by [`crate::CONST_TABLE_BYTES`], as the crate has no
+        /// writable statics. Only exposed when the `memory-report` feature is enabled.
+        #[cfg(feature = "memory-report")]
+        pub const STATIC_BYTES: usize = crate::CONST_TABLE_BYTES;
+
+
         // ----- PRIMARY FUNCTIONS ---
 
         /// # Algorithm 1: `ML-DSA.KeyGen()` on page 17.
@@ -235,6 +358,9 @@ macro_rules! functionality {
         impl KeyGen for KG {
             type PrivateKey = PrivateKey;
             type PublicKey = PublicKey;
+            const PK_LEN: usize = PK_LEN;
+            const SK_LEN: usize = SK_LEN;
+            const SIG_LEN: usize = SIG_LEN;
 
 
             /// # Algorithm 1 in `KeyGen` trait
@@ -254,6 +380,8 @@ macro_rules! functionality {
         impl Signer for PrivateKey {
             type Signature = [u8; SIG_LEN];
             type PublicKey = PublicKey;
+            const SK_LEN: usize = SK_LEN;
+            const SIG_LEN: usize = SIG_LEN;
 
             /// # Algorithm 2: `ML-DSA.Sign(sk, 𝑀 , ctx)` on page 18.
             /// Generates an ML-DSA signature.
@@ -289,7 +417,7 @@ macro_rules! functionality {
                 // 11: 𝜎 ← ML-DSA.Sign_internal(𝑠𝑘, 𝑀 ′ , 𝑟𝑛𝑑)
                 let sig = ml_dsa::sign_internal::<CTEST, K, L, LAMBDA_DIV4, SIG_LEN, SK_LEN, W1_LEN>(
                     BETA, GAMMA1, GAMMA2, OMEGA, TAU, &self, message, ctx, &[], &[], rnd, false
-                );
+                )?;
 
                 // 12: return 𝜎
                 Ok(sig)
@@ -335,7 +463,7 @@ macro_rules! functionality {
                 // 24: 𝜎 ← ML-DSA.Sign_internal(𝑠𝑘, 𝑀 ′ , 𝑟𝑛𝑑)
                 let sig = ml_dsa::sign_internal::<CTEST, K, L, LAMBDA_DIV4, SIG_LEN, SK_LEN, W1_LEN>(
                     BETA, GAMMA1, GAMMA2, OMEGA, TAU, &self, message, ctx, &oid, &phm[0..phm_len], rnd, false
-                );
+                )?;
 
                 // 25: return 𝜎
                 Ok(sig)
@@ -352,6 +480,8 @@ macro_rules! functionality {
 
         impl Verifier for PublicKey {
             type Signature = [u8; SIG_LEN];
+            const PK_LEN: usize = PK_LEN;
+            const SIG_LEN: usize = SIG_LEN;
 
             /// # Algorithm 3: `ML-DSA.Verify(pk, 𝑀, 𝜎, ctx)` on page 18.
             /// Verifies a signature 𝜎 for a message 𝑀.
@@ -375,7 +505,8 @@ macro_rules! functionality {
                 // 5: 𝑀′ ← BytesToBits(IntegerToBytes(0, 1) ∥ IntegerToBytes(|ctx|, 1) ∥ ctx) ∥ 𝑀
                 // 6: return ML-DSA.Verify_internal(pk, 𝑀′, 𝜎)
                 ml_dsa::verify_internal::<CTEST, K, L, LAMBDA_DIV4, PK_LEN, SIG_LEN, W1_LEN>(
-                    BETA, GAMMA1, GAMMA2, OMEGA, TAU, &self, &message, &sig, ctx, &[], &[], false
+                    BETA, GAMMA1, GAMMA2, OMEGA, TAU, &self, &message, &sig, ctx, &[], &[], false,
+                    crate::VerifyMode::default()
                 )
             }
 
@@ -406,270 +537,4209 @@ macro_rules! functionality {
                 // 18: 𝑀′ ← BytesToBits(IntegerToBytes(1, 1) ∥ IntegerToBytes(|ctx|, 1) ∥ ctx ∥ OID ∥ PH𝑀 )
                 // 19: return ML-DSA.Verify_internal(𝑝𝑘, 𝑀′ , 𝜎)
                 ml_dsa::verify_internal::<CTEST, K, L, LAMBDA_DIV4, PK_LEN, SIG_LEN, W1_LEN>(
-                    BETA, GAMMA1, GAMMA2, OMEGA, TAU, &self, &message, &sig, ctx, &oid, &phm[0..phm_len], false
+                    BETA, GAMMA1, GAMMA2, OMEGA, TAU, &self, &message, &sig, ctx, &oid, &phm[0..phm_len], false,
+                    crate::VerifyMode::default()
                 )
             }
         }
 
 
-        // ----- SERIALIZATION AND DESERIALIZATION ---
+        // ----- SCHEME AGILITY ---
 
-        impl SerDes for PrivateKey {
-            type ByteArray = [u8; SK_LEN];
+        impl crate::traits::PqSignature for Scheme {
+            const ALGORITHM: &'static str = PARAM_SET.name();
+            const PK_LEN: usize = PK_LEN;
+            const SK_LEN: usize = SK_LEN;
+            const SIG_LEN: usize = SIG_LEN;
+            type PublicKey = PublicKey;
+            type PrivateKey = PrivateKey;
+            type Signature = [u8; SIG_LEN];
+            type PublicKeyBytes = [u8; PK_LEN];
+            type PrivateKeyBytes = [u8; SK_LEN];
 
 
-            fn try_from_bytes(sk: Self::ByteArray) -> Result<Self, &'static str> {
-                let esk = ml_dsa::expand_private::<K, L, SK_LEN>(ETA, &sk)?;
-                Ok(esk)
+            fn try_keygen_with_rng(rng: &mut impl CryptoRngCore) -> Result<(PublicKey, PrivateKey), &'static str> {
+                KG::try_keygen_with_rng(rng)
             }
 
 
-            fn into_bytes(self) -> Self::ByteArray {
-                // Extract the pre-computes
-                let PrivateKey {rho, cap_k, tr, s_1_hat_mont: s_hat_1_mont, s_2_hat_mont: s_hat_2_mont, t_0_hat_mont: t_hat_0_mont, ..} = &self;
+            fn try_sign_with_rng(
+                sk: &PrivateKey, rng: &mut impl CryptoRngCore, message: &[u8], ctx: &[u8],
+            ) -> Result<[u8; SIG_LEN], &'static str> {
+                sk.try_sign_with_rng(rng, message, ctx)
+            }
 
-                // mont->norm each n coeff, of L entries of T, then inverse NTT
-                let s_1: [types::R; L] = ntt::inv_ntt(
-                    &core::array::from_fn(|l|
-                        types::T(core::array::from_fn(|n|
-                            helpers::mont_reduce(i64::from(s_hat_1_mont[l].0[n]))))));
-                // correct each coeff such that they are centered around 0
-                let s_1: [types::R; L] =
-                    core::array::from_fn(|l|
-                        types::R(core::array::from_fn(|n|
-                            if s_1[l].0[n] > (Q / 2) {s_1[l].0[n] - Q} else {s_1[l].0[n]})));
 
-                let s_2: [types::R; K] = ntt::inv_ntt(
-                    &core::array::from_fn(|k|
-                        types::T(core::array::from_fn(|n|
-                            helpers::mont_reduce(i64::from(s_hat_2_mont[k].0[n]))))));
-                let s_2: [types::R; K] =
-                    core::array::from_fn(|k|
-                        types::R(core::array::from_fn(|n|
-                            if s_2[k].0[n] > (Q / 2) {s_2[k].0[n] - Q} else {s_2[k].0[n]})));
+            fn verify(pk: &PublicKey, message: &[u8], sig: &[u8; SIG_LEN], ctx: &[u8]) -> bool {
+                pk.verify(message, sig, ctx)
+            }
 
 
-                let t_0: [types::R; K] = ntt::inv_ntt(
-                    &core::array::from_fn(|k|
-                        types::T(core::array::from_fn(|n|
-                            helpers::mont_reduce(i64::from(t_hat_0_mont[k].0[n]))))));
-                let t_0: [types::R; K] =
-                    core::array::from_fn(|k|
-                        types::R(core::array::from_fn(|n|
-                            if t_0[k].0[n] > (Q / 2) {t_0[k].0[n] - Q} else {t_0[k].0[n]})));
+            fn public_key_to_bytes(pk: &PublicKey) -> [u8; PK_LEN] { pk.into() }
 
-                // Encode and return
-                encodings::sk_encode::<K, L, SK_LEN>(ETA, rho, cap_k, tr, &s_1, &s_2, &t_0)
-            }
-        }
 
+            fn private_key_to_bytes(sk: &PrivateKey) -> [u8; SK_LEN] { sk.into() }
 
-        impl SerDes for PublicKey {
-            type ByteArray = [u8; PK_LEN];
 
+            fn try_public_key_from_bytes(bytes: &[u8]) -> Result<PublicKey, &'static str> { bytes.try_into() }
 
-            fn try_from_bytes(pk: Self::ByteArray) -> Result<Self, &'static str> {
-                let epk = ml_dsa::expand_public(&pk)?;
-                Ok(epk)
 
+            fn try_private_key_from_bytes(bytes: &[u8]) -> Result<PrivateKey, &'static str> { bytes.try_into() }
+
+
+            fn try_signature_from_bytes(bytes: &[u8]) -> Result<[u8; SIG_LEN], &'static str> {
+                bytes.try_into().map_err(|_| "Signature: wrong length")
             }
+        }
 
 
-            fn into_bytes(self) -> Self::ByteArray {
-                // Extract the pre-computes
-                let PublicKey {rho, tr: _tr, t1_d2_hat_mont} = &self;
+        // ----- VERIFICATION WITH ENCODED PUBLIC KEYS ---
 
-                // reconstruct t1_d2 then t1
-                let t1_d2: [types::R; K] = ntt::inv_ntt(
-                    &core::array::from_fn(|k|
-                        types::T(core::array::from_fn(|n|
-                            helpers::mont_reduce(i64::from(t1_d2_hat_mont[k].0[n]))))));
+        /// The encoded public key byte array also implements [`Verifier`], expanding the key on
+        /// each call, so that generic code bounded on [`Verifier`] accepts either the expanded
+        /// [`PublicKey`] or its encoding. A malformed encoding verifies nothing.
+        impl Verifier for [u8; PK_LEN] {
+            type Signature = [u8; SIG_LEN];
+            const PK_LEN: usize = PK_LEN;
+            const SIG_LEN: usize = SIG_LEN;
 
-                let t1: [types::R; K] = core::array::from_fn(|k|
-                    types::R(core::array::from_fn(|n|
-                        t1_d2[k].0[n] >> D)));
 
-                encodings::pk_encode(rho, &t1)
-             }
+            fn verify(&self, message: &[u8], sig: &Self::Signature, ctx: &[u8]) -> bool {
+                PublicKey::try_from_bytes(*self).is_ok_and(|pk| pk.verify(message, sig, ctx))
+            }
+
+
+            fn hash_verify(&self, message: &[u8], sig: &Self::Signature, ctx: &[u8], ph: &types::Ph) -> bool {
+                PublicKey::try_from_bytes(*self).is_ok_and(|pk| pk.hash_verify(message, sig, ctx, ph))
+            }
         }
 
 
-        #[cfg(test)]
-        mod tests {
-            use super::*;
-            use crate::types::Ph;
-            use rand_chacha::rand_core::SeedableRng;
+        // ----- SIGNING WITH ENCODED PRIVATE KEYS ---
 
-            #[test]
-            fn smoke_test() {
-                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
-                let message1 = [0u8, 1, 2, 3, 4, 5, 6, 7];
-                let message2 = [7u8, 7, 7, 7, 7, 7, 7, 7];
+        // Note: `EncodedPrivateKey` implements `Signer` (and `SerDes`), expanding the key on each
+        // call as `try_sign_from_bytes()` does, so that generic code bounded on `Signer` accepts
+        // either the expanded `PrivateKey` or an encoding, with identical signatures in every
+        // mode. The encoding is validated on construction, so `get_public_key()` cannot fail.
 
-                for _i in 0..32 {
-                    let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
-                    let sig = sk.try_sign_with_rng(&mut rng, &message1, &[]).unwrap();
-                    assert!(pk.verify(&message1, &sig, &[]));
-                    assert!(!pk.verify(&message2, &sig, &[]));
-                    for ph in [Ph::SHA256, Ph::SHA512, Ph::SHAKE128] {
-                        let sig = sk.try_hash_sign_with_rng(&mut rng, &message1, &[], &ph).unwrap();
-                        let v = pk.hash_verify(&message1, &sig, &[], &ph);
-                        assert!(v);
-                    }
-                    assert_eq!(pk.clone().into_bytes(), sk.get_public_key().into_bytes());
-                }
+        impl Signer for EncodedPrivateKey {
+            type Signature = [u8; SIG_LEN];
+            type PublicKey = PublicKey;
+            const SK_LEN: usize = SK_LEN;
+            const SIG_LEN: usize = SIG_LEN;
+
+
+            fn try_sign_with_rng(
+                &self, rng: &mut impl CryptoRngCore, message: &[u8], ctx: &[u8],
+            ) -> Result<Self::Signature, &'static str> {
+                self.expand().try_sign_with_rng(rng, message, ctx)
             }
-        }
 
 
-        // ----- SUPPORT FOR DUDECT CONSTANT TIME MEASUREMENTS ---
+            fn try_hash_sign_with_rng(
+                &self, rng: &mut impl CryptoRngCore, message: &[u8], ctx: &[u8], ph: &types::Ph,
+            ) -> Result<Self::Signature, &'static str> {
+                self.expand().try_hash_sign_with_rng(rng, message, ctx, ph)
+            }
 
-        /// This function supports the dudect constant-time measurement framework, and
-        /// is only exposed with the `dudect` feature is enabled.
-        ///
-        /// # Errors
-        /// Returns an error when the random number generator fails; propagates internal errors.
-        #[deprecated = "Function for constant-time testing; do not use elsewhere"]
-        #[cfg(feature = "dudect")]
-        pub fn dudect_keygen_sign_with_rng(
-            rng: &mut impl CryptoRngCore, message: &[u8],
-        ) -> Result<[u8; SIG_LEN], &'static str> {
-            let (_pk, sk) = ml_dsa::key_gen::<true, K, L, PK_LEN, SK_LEN>(rng, ETA)?;
-            let mut rnd = [0u8; 32];
-            rng.try_fill_bytes(&mut rnd).map_err(|_| "Random number generator failed")?;
-            let sig = ml_dsa::sign_internal::<true, K, L, LAMBDA_DIV4, SIG_LEN, SK_LEN, W1_LEN>(
-                BETA, GAMMA1, GAMMA2, OMEGA, TAU, &sk, message, &[1], &[2], &[3], rnd, true
-            );
-            Ok(sig)
-        }
 
-        #[deprecated = "Temporary function to allow application of internal nist vectors; will be removed"]
-        /// As of Oct 30 2024, the NIST test vectors are applied to the **internal** functions rather than
-        /// the external API.
-        ///
-        /// The primary difference pertains to the prepending of domain, context, OID and
-        /// hash information to the message in the `sign_finish()` and `verify_finish()` functions (follow
-        /// the last `nist=true` function argument). This is expected to change such that the full API can
-        /// be robustly tested - when this happens, this function will no longer be needed.
-        /// # Errors
-        /// Propagate errors from the `sign_finish()` function (for failing RNG).
-        pub fn _internal_sign(
-            sk: &PrivateKey, message: &[u8], ctx: &[u8], rnd: [u8; 32]
-        ) -> Result<[u8; SIG_LEN], &'static str> {
-            helpers::ensure!(ctx.len() < 256, "_internal_sign: ctx too long");
-            let sig = ml_dsa::sign_internal::<CTEST, K, L, LAMBDA_DIV4, SIG_LEN, SK_LEN, W1_LEN>(
-                BETA, GAMMA1, GAMMA2, OMEGA, TAU, sk, message, ctx, &[], &[], rnd, true
-            );
-            Ok(sig)
+            fn get_public_key(&self) -> Self::PublicKey { self.expand().get_public_key() }
         }
 
-        #[deprecated = "Temporary function to allow application of internal nist vectors; will be removed"]
-        #[must_use]
-        /// As of Oct 30 2024, the NIST test vectors are applied to the **internal** functions rather than
-        /// the external API.
-        ///
-        /// The primary difference pertains to the prepending of domain, context, OID and
-        /// hash information to the message in the `sign_finish()` and `verify_finish()` functions (follow
-        /// the last `nist=true` function argument). This is expected to change such that the full API can
-        /// be robustly tested - when this happens, this function will no longer be needed.
-        pub fn _internal_verify(pk: &PublicKey, message: &[u8], sig: &[u8; SIG_LEN], ctx: &[u8]) -> bool {
-            if ctx.len() > 255 {
-                return false;
-            };
-            ml_dsa::verify_internal::<CTEST, K, L, LAMBDA_DIV4, PK_LEN, SIG_LEN, W1_LEN>(
-                BETA, GAMMA1, GAMMA2, OMEGA, TAU, pk, &message, &sig, ctx, &[], &[], true
-            )
-        }
-    };
-}
 
+        impl SerDes for EncodedPrivateKey {
+            type ByteArray = [u8; SK_LEN];
 
-/// # Functionality for the **ML-DSA-44** security parameter set.
-///
-/// This includes specific sizes for the
-/// public key, secret key, and signature along with a number of internal constants. The ML-DSA-44
-/// parameter set is claimed to be in security strength category 2.
-///
-/// **1)** The basic usage is for an originator to start with the [`ml_dsa_44::try_keygen`] function below to
-/// generate both [`ml_dsa_44::PublicKey`] and [`ml_dsa_44::PrivateKey`] structs. The resulting
-/// [`ml_dsa_44::PrivateKey`] struct implements the [`traits::Signer`] trait which supplies a variety of
-/// functions to sign byte-array messages, such as [`traits::Signer::try_sign()`].
-///
-/// **2)** Both of the `PrivateKey` and `PublicKey` structs implement the [`traits::SerDes`] trait.
-/// The originator utilizes the [`traits::SerDes::into_bytes()`] functions to serialize the structs
-/// into byte-arrays for storage and/or transmission, similar to the message. Upon retrieval and/or receipt,
-/// the remote party utilizes the [`traits::SerDes::try_from_bytes()`] functions to deserialize the
-/// byte-arrays into structs.
-///
-/// **3)** Finally, the remote party uses the [`traits::Verifier::verify()`] function implemented on the
-/// [`ml_dsa_44::PublicKey`] struct to verify the message with the `Signature` byte array.
-///
-/// See the top-level [crate] documentation for example code that implements the above flow.
-#[cfg(feature = "ml-dsa-44")]
-pub mod ml_dsa_44 {
-    const TAU: i32 = 39;
-    const LAMBDA: usize = 128;
-    const GAMMA1: i32 = 1 << 17;
-    const GAMMA2: i32 = (Q - 1) / 88;
-    const K: usize = 4;
-    const L: usize = 4;
-    const ETA: i32 = 2;
-    const OMEGA: i32 = 80;
-    /// Private (secret) key length in bytes.
-    pub const SK_LEN: usize = 2560;
-    /// Public key length in bytes.
-    pub const PK_LEN: usize = 1312;
-    /// Signature length in bytes.
-    pub const SIG_LEN: usize = 2420;
 
-    functionality!();
-}
+            /// Validates the encoded private key as [`try_validate_encoded()`] does.
+            fn try_from_bytes(sk: Self::ByteArray) -> Result<Self, &'static str> {
+                let esk = Self(sk); // zeroizes the encoding on error, too
+                try_validate_encoded(&esk.0)?;
+                Ok(esk)
+            }
 
 
-/// # Functionality for the **ML-DSA-65** security parameter set.
-///
-/// This includes specific sizes for the
-/// public key, secret key, and signature along with a number of internal constants. The ML-DSA-65
-/// parameter set is claimed to be in security strength category 3.
-///
-/// **1)** The basic usage is for an originator to start with the [`ml_dsa_44::try_keygen`] function below to
-/// generate both [`ml_dsa_44::PublicKey`] and [`ml_dsa_44::PrivateKey`] structs. The resulting
-/// [`ml_dsa_44::PrivateKey`] struct implements the [`traits::Signer`] trait which supplies a variety of
-/// functions to sign byte-array messages, such as [`traits::Signer::try_sign()`].
-///
-/// **2)** Both of the `PrivateKey` and `PublicKey` structs implement the [`traits::SerDes`] trait
-/// The originator utilizes the [`traits::SerDes::into_bytes()`] functions to serialize the structs
-/// into byte-arrays for storage and/or transmission, similar to the message. Upon retrieval and/or receipt,
-/// the remote party utilizes the [`traits::SerDes::try_from_bytes()`] functions to deserialize the
-/// byte-arrays into structs.
-///
-/// **3)** Finally, the remote party uses the [`traits::Verifier::verify()`] function implemented on the
-/// [`ml_dsa_44::PublicKey`] struct to verify the message with the `Signature` byte array.
-///
-/// See the top-level [crate] documentation for example code that implements the above flow.
-#[cfg(feature = "ml-dsa-65")]
-pub mod ml_dsa_65 {
-    const TAU: i32 = 49;
-    const LAMBDA: usize = 192;
-    const GAMMA1: i32 = 1 << 19;
-    const GAMMA2: i32 = (Q - 1) / 32;
-    const K: usize = 6;
-    const L: usize = 5;
-    const ETA: i32 = 4;
-    const OMEGA: i32 = 55;
-    /// Private (secret) key length in bytes.
-    pub const SK_LEN: usize = 4032;
-    /// Public key length in bytes.
-    pub const PK_LEN: usize = 1952;
-    /// Signature length in bytes.
-    pub const SIG_LEN: usize = 3309;
+            fn into_bytes(self) -> Self::ByteArray { self.0 }
+        }
 
-    functionality!();
-}
 
+        impl EncodedPrivateKey {
+            /// Returns the encoded private key.
+            #[must_use]
+            pub fn as_bytes(&self) -> &[u8; SK_LEN] { &self.0 }
 
-/// # Functionality for the **ML-DSA-87** security parameter set.
-///
+
+            /// Signs the message with the context string as [`PrivateKey::try_sign_tagged()`]
+            /// does. This function utilizes the **OS default** random number generator.
+            ///
+            /// # Errors
+            /// Returns an error when the random number generator fails or the context is too long.
+            #[cfg(feature = "default-rng")]
+            pub fn try_sign_tagged(
+                &self, message: &[u8], ctx: &[u8],
+            ) -> Result<crate::TaggedSignature<[u8; SIG_LEN]>, &'static str> {
+                self.expand().try_sign_tagged(message, ctx)
+            }
+
+
+            /// Signs the message with the context string as
+            /// [`PrivateKey::try_sign_tagged_with_rng()`] does. This function utilizes the
+            /// **provided** random number generator.
+            ///
+            /// # Errors
+            /// Returns an error when the random number generator fails or the context is too long.
+            pub fn try_sign_tagged_with_rng(
+                &self, rng: &mut impl CryptoRngCore, message: &[u8], ctx: &[u8],
+            ) -> Result<crate::TaggedSignature<[u8; SIG_LEN]>, &'static str> {
+                self.expand().try_sign_tagged_with_rng(rng, message, ctx)
+            }
+
+
+            /// Signs the message with the context string using the FIPS 204 deterministic
+            /// variant, as [`PrivateKey::try_sign_deterministic()`] does.
+            ///
+            /// # Errors
+            /// Returns an error when the context is too long.
+            pub fn try_sign_deterministic(
+                &self, message: &[u8], ctx: &[u8],
+            ) -> Result<crate::TaggedSignature<[u8; SIG_LEN]>, &'static str> {
+                self.expand().try_sign_deterministic(message, ctx)
+            }
+
+
+            // The expanded key zeroizes itself on drop
+            fn expand(&self) -> PrivateKey {
+                ml_dsa::expand_private::<K, L, SK_LEN>(ETA, &self.0).expect("validated on construction")
+            }
+        }
+
+
+        // ----- KEY PAIR ---
+
+        impl Keypair {
+            /// Generates a key pair; see [`try_keygen()`]. This function utilizes the **OS
+            /// default** random number generator.
+            ///
+            /// # Errors
+            /// Returns an error if the random number generator fails.
+            ///
+            /// # Examples
+            /// ```rust
+            /// # use std::error::Error;
+            /// # fn main() -> Result<(), Box<dyn Error>> {
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+            /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+            /// use fips204::traits::SerDes;
+            ///
+            /// let keypair = ml_dsa_44::Keypair::try_generate()?;
+            /// let sig = keypair.try_sign(b"message", b"ctx")?;
+            /// assert!(keypair.verify_own(b"message", &sig, b"ctx"));
+            ///
+            /// let (pk_bytes, sk_bytes) = keypair.into_bytes();
+            /// let keypair = ml_dsa_44::Keypair::try_from_bytes((pk_bytes, sk_bytes))?;
+            /// # }
+            /// # Ok(())}
+            /// ```
+            #[cfg(feature = "default-rng")]
+            pub fn try_generate() -> Result<Self, &'static str> { Self::try_generate_with_rng(&mut rand_core::OsRng) }
+
+
+            /// Generates a key pair; see [`try_keygen_with_rng()`]. This function utilizes the
+            /// **provided** random number generator.
+            ///
+            /// # Errors
+            /// Returns an error if the random number generator fails.
+            pub fn try_generate_with_rng(rng: &mut impl CryptoRngCore) -> Result<Self, &'static str> {
+                let (pk, sk) = KG::try_keygen_with_rng(rng)?;
+                Ok(Self { pk, sk })
+            }
+
+
+            /// Deterministically generates a key pair from the seed `xi`; see
+            /// [`crate::traits::KeyGen::keygen_from_seed()`].
+            #[must_use]
+            pub fn from_seed(xi: &[u8; 32]) -> Self {
+                let (pk, sk) = KG::keygen_from_seed(xi);
+                Self { pk, sk }
+            }
+
+
+            /// Combines a public key and private key, checking that they belong together: the
+            /// seed `rho` and hash `tr` must agree, the private key must be internally consistent
+            /// (see [`try_validate_encoded()`]), and re-deriving `t1` from the private key's `s1`
+            /// and `s2` must reproduce the public key.
+            ///
+            /// # Errors
+            /// Returns an error when the public key does not match the private key.
+            pub fn try_from_parts(pk: PublicKey, sk: PrivateKey) -> Result<Self, &'static str> {
+                // The private key carries rho and tr = H(pk), so these are cheap first checks
+                helpers::ensure!(
+                    (pk.rho == sk.rho) & (pk.tr == sk.tr),
+                    "Keypair: public key does not match private key"
+                );
+                // A private key whose rho and tr were transplanted from another key is caught here
+                let mut sk_bytes = sk.duplicate().into_bytes();
+                let consistent = try_validate_encoded(&sk_bytes);
+                sk_bytes.zeroize();
+                consistent?;
+                helpers::ensure!(
+                    ml_dsa::private_to_public_key(&sk) == pk,
+                    "Keypair: public key does not match private key"
+                );
+                Ok(Self { pk, sk })
+            }
+
+
+            /// Splits the key pair into its public and private keys.
+            #[must_use]
+            pub fn into_parts(self) -> (PublicKey, PrivateKey) { (self.pk, self.sk) }
+
+
+            /// Returns the public key.
+            #[must_use]
+            pub fn public(&self) -> &PublicKey { &self.pk }
+
+
+            /// Returns the private key.
+            #[must_use]
+            pub fn private(&self) -> &PrivateKey { &self.sk }
+
+
+            /// Signs the message with the context string; see
+            /// [`crate::traits::Signer::try_sign()`]. This function utilizes the **OS default**
+            /// random number generator.
+            ///
+            /// # Errors
+            /// Returns an error when the random number generator fails or the context is too long.
+            #[cfg(feature = "default-rng")]
+            pub fn try_sign(&self, message: &[u8], ctx: &[u8]) -> Result<[u8; SIG_LEN], &'static str> {
+                self.sk.try_sign(message, ctx)
+            }
+
+
+            /// Signs the message with the context string; see
+            /// [`crate::traits::Signer::try_sign_with_rng()`]. This function utilizes the
+            /// **provided** random number generator.
+            ///
+            /// # Errors
+            /// Returns an error when the random number generator fails or the context is too long.
+            pub fn try_sign_with_rng(
+                &self, rng: &mut impl CryptoRngCore, message: &[u8], ctx: &[u8],
+            ) -> Result<[u8; SIG_LEN], &'static str> {
+                self.sk.try_sign_with_rng(rng, message, ctx)
+            }
+
+
+            /// Verifies a signature under this key pair's own public key, e.g., to check a
+            /// signature before releasing it. See [`crate::traits::Verifier::verify()`].
+            #[must_use]
+            pub fn verify_own(&self, message: &[u8], sig: &[u8; SIG_LEN], ctx: &[u8]) -> bool {
+                self.pk.verify(message, sig, ctx)
+            }
+        }
+
+
+        impl SerDes for Keypair {
+            type ByteArray = ([u8; PK_LEN], [u8; SK_LEN]);
+
+
+            fn try_from_bytes((pk, sk): Self::ByteArray) -> Result<Self, &'static str> {
+                Self::try_from_parts(PublicKey::try_from_bytes(pk)?, PrivateKey::try_from_bytes(sk)?)
+            }
+
+
+            fn into_bytes(self) -> Self::ByteArray { (self.pk.into_bytes(), self.sk.into_bytes()) }
+        }
+
+
+        // ----- EXPLICIT PRIVATE KEY DUPLICATION ---
+
+        // Note: `Clone` on private keys (and key pairs) is only implemented with the on-by-default
+        // `clone-private-key` feature, so that key-handling policies forbidding implicit copies
+        // can disable it. These functions remain available either way, and stand out in review.
+
+        impl PrivateKey {
+            /// Returns a copy of the private key; see [`PrivateKey::duplicate_audited()`]. Each
+            /// copy zeroizes itself on drop.
+            #[must_use]
+            pub fn duplicate(&self) -> Self { self.duplicate_audited(|_| ()) }
+
+
+            /// Returns a copy of the private key, first notifying `audit` with the key's `tr`
+            /// (the public key hash, which identifies the key without revealing it), e.g. to log
+            /// the duplication.
+            ///
+            /// # Examples
+            /// ```rust
+            /// # use std::error::Error;
+            /// # fn main() -> Result<(), Box<dyn Error>> {
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+            /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+            /// use fips204::traits::{Signer, Verifier};
+            ///
+            /// let (pk, sk) = ml_dsa_44::try_keygen()?;
+            /// let mut copies = 0;
+            /// let sk2 = sk.duplicate_audited(|tr| {
+            ///     assert_eq!(tr, &pk.tr());
+            ///     copies += 1;
+            /// });
+            /// assert_eq!(copies, 1);
+            /// assert!(pk.verify(b"message", &sk2.try_sign(b"message", &[])?, &[]));
+            /// # }
+            /// # Ok(())}
+            /// ```
+            #[must_use]
+            pub fn duplicate_audited(&self, audit: impl FnOnce(&[u8; 64])) -> Self {
+                audit(&self.tr);
+                PrivateKey {
+                    rho: self.rho,
+                    cap_k: self.cap_k,
+                    tr: self.tr,
+                    s_1_hat_mont: self.s_1_hat_mont.clone(),
+                    s_2_hat_mont: self.s_2_hat_mont.clone(),
+                    t_0_hat_mont: self.t_0_hat_mont.clone(),
+                }
+            }
+        }
+
+
+        // ----- SEALED (ENCRYPTED AT REST) PRIVATE KEY ---
+
+        /// A private key held only in sealed form (e.g., encrypted under a key held by an HSM or
+        /// OS keystore), together with a callback that unseals it into the FIPS 204 encoding.
+        /// Each operation unseals and expands the key on the stack, uses it, and zeroizes it
+        /// again, so a long-lived process holds no plaintext private key outside that window.
+        ///
+        /// The unsealing and expansion are repeated for every operation, which costs roughly as
+        /// much as deserializing the key (plus the callback) per signature.
+        ///
+        /// # Examples
+        /// ```rust
+        /// # use std::error::Error;
+        /// # fn main() -> Result<(), Box<dyn Error>> {
+        /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+        /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+        /// use fips204::traits::{SerDes, Verifier};
+        ///
+        /// let (pk, sk) = ml_dsa_44::try_keygen()?;
+        /// let wrap = |b: u8| b ^ 0x5A; // Stand-in for real (authenticated) encryption
+        /// let sealed: Vec<u8> = sk.into_bytes().iter().map(|&b| wrap(b)).collect();
+        ///
+        /// let sealed_sk = ml_dsa_44::SealedPrivateKey::new(sealed, |sealed, out| {
+        ///     out.iter_mut().zip(sealed).for_each(|(o, &b)| *o = wrap(b));
+        ///     Ok(())
+        /// });
+        /// let sig = sealed_sk.try_sign(b"message", b"ctx")?;
+        /// assert!(pk.verify(b"message", &sig, b"ctx"));
+        /// # }
+        /// # Ok(())}
+        /// ```
+        pub struct SealedPrivateKey<S, F>
+        where
+            F: Fn(&S, &mut [u8; SK_LEN]) -> Result<(), &'static str>,
+        {
+            sealed: S,
+            unseal: F,
+        }
+
+
+        impl<S, F> SealedPrivateKey<S, F>
+        where
+            F: Fn(&S, &mut [u8; SK_LEN]) -> Result<(), &'static str>,
+        {
+            /// Wraps the sealed private key with its unseal callback, which must write the
+            /// FIPS 204 private key encoding into the supplied buffer. Nothing is unsealed here.
+            pub fn new(sealed: S, unseal: F) -> Self { Self { sealed, unseal } }
+
+
+            /// Unseals and expands the private key, passes it to `f`, then zeroizes it.
+            ///
+            /// # Errors
+            /// Returns an error when the unseal callback fails or the unsealed key is malformed.
+            pub fn try_with_private_key<T>(&self, f: impl FnOnce(&PrivateKey) -> T) -> Result<T, &'static str> {
+                let mut sk_bytes = [0u8; SK_LEN];
+                let res = (self.unseal)(&self.sealed, &mut sk_bytes)
+                    .and_then(|()| PrivateKey::try_from_bytes(sk_bytes));
+                sk_bytes.zeroize();
+                res.map(|sk| f(&sk)) // the expanded key zeroizes itself on drop
+            }
+
+
+            /// Signs the message with the context string; see
+            /// [`crate::traits::Signer::try_sign()`]. This function utilizes the **OS default**
+            /// random number generator.
+            ///
+            /// # Errors
+            /// Returns an error when unsealing fails, the random number generator fails or the
+            /// context is too long.
+            #[cfg(feature = "default-rng")]
+            pub fn try_sign(&self, message: &[u8], ctx: &[u8]) -> Result<[u8; SIG_LEN], &'static str> {
+                self.try_with_private_key(|sk| sk.try_sign(message, ctx))?
+            }
+
+
+            /// Signs the message with the context string; see
+            /// [`crate::traits::Signer::try_sign_with_rng()`]. This function utilizes the
+            /// **provided** random number generator.
+            ///
+            /// # Errors
+            /// Returns an error when unsealing fails, the random number generator fails or the
+            /// context is too long.
+            pub fn try_sign_with_rng(
+                &self, rng: &mut impl CryptoRngCore, message: &[u8], ctx: &[u8],
+            ) -> Result<[u8; SIG_LEN], &'static str> {
+                self.try_with_private_key(|sk| sk.try_sign_with_rng(rng, message, ctx))?
+            }
+        }
+
+
+        // ----- CALLER-PROVIDED (SECURE) MEMORY ---
+
+        // Note: these functions support secure-memory policies where the encoded private key may
+        // only ever reside in a region allocated by the caller (e.g., `mlock`'d or TrustZone
+        // memory); a slice of such a region converts via `<&mut [u8; SK_LEN]>::try_from()`. The
+        // encoded private key is written to, and read from, that region only. The seeds and
+        // secret polynomials computed from it are necessarily held transiently on the stack, and
+        // are zeroized before returning.
+
+        /// Generates a key pair from the seed, writing the encoded public and private keys
+        /// directly into the supplied buffers. The output is identical to
+        /// `keygen_from_seed(xi)` followed by `into_bytes()` on each key.
+        ///
+        /// # Panics
+        /// With the `fips-mode` feature, when the module is in the error state; see
+        /// [`try_keygen_from_seed_into()`].
+        pub fn keygen_from_seed_into(xi: &[u8; 32], pk_out: &mut [u8; PK_LEN], sk_out: &mut [u8; SK_LEN]) {
+            ml_dsa::key_gen_into::<false, K, L, PK_LEN, SK_LEN>(ETA, xi, pk_out, sk_out);
+        }
+
+
+        /// Generates a key pair from the seed as [`keygen_from_seed_into()`] does, but returns
+        /// an error rather than panicking when the `fips-mode` module is in the error state
+        /// (e.g., for callers across an FFI boundary, which a panic must not cross).
+        ///
+        /// # Errors
+        /// Returns an error only with the `fips-mode` feature, when the module is in the error
+        /// state; the buffers are then left untouched.
+        pub fn try_keygen_from_seed_into(
+            xi: &[u8; 32], pk_out: &mut [u8; PK_LEN], sk_out: &mut [u8; SK_LEN],
+        ) -> Result<(), &'static str> {
+            #[cfg(feature = "fips-mode")]
+            crate::fips::check_operational()?;
+            keygen_from_seed_into(xi, pk_out, sk_out);
+            Ok(())
+        }
+
+
+        /// Generates a key pair, writing the encoded public and private keys directly into the
+        /// supplied buffers; see [`keygen_from_seed_into()`]. This function utilizes the
+        /// **OS default** random number generator.
+        ///
+        /// # Errors
+        /// Returns an error when the random number generator fails; the buffers are then
+        /// left untouched.
+        ///
+        /// # Examples
+        /// ```rust
+        /// # use std::error::Error;
+        /// # fn main() -> Result<(), Box<dyn Error>> {
+        /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+        /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+        /// use fips204::traits::Verifier;
+        ///
+        /// let mut region = vec![0u8; 4096]; // Stand-in for a locked memory region
+        /// let sk_buf: &mut [u8; ml_dsa_44::SK_LEN] = (&mut region[..ml_dsa_44::SK_LEN]).try_into()?;
+        /// let mut pk_bytes = [0u8; ml_dsa_44::PK_LEN];
+        /// ml_dsa_44::try_keygen_into(&mut pk_bytes, sk_buf)?;
+        ///
+        /// let sig = ml_dsa_44::try_sign_from_bytes(sk_buf, b"message", b"ctx")?;
+        /// assert!(pk_bytes.verify(b"message", &sig, b"ctx"));
+        /// # }
+        /// # Ok(())}
+        /// ```
+        #[cfg(feature = "default-rng")]
+        pub fn try_keygen_into(pk_out: &mut [u8; PK_LEN], sk_out: &mut [u8; SK_LEN]) -> Result<(), &'static str> {
+            try_keygen_into_with_rng(&mut rand_core::OsRng, pk_out, sk_out)
+        }
+
+
+        /// Generates a key pair, writing the encoded public and private keys directly into the
+        /// supplied buffers; see [`keygen_from_seed_into()`]. This function utilizes the
+        /// **provided** random number generator.
+        ///
+        /// # Errors
+        /// Returns an error when the random number generator fails; the buffers are then
+        /// left untouched.
+        pub fn try_keygen_into_with_rng(
+            rng: &mut impl CryptoRngCore, pk_out: &mut [u8; PK_LEN], sk_out: &mut [u8; SK_LEN],
+        ) -> Result<(), &'static str> {
+            #[cfg(feature = "fips-mode")]
+            crate::fips::check_operational()?;
+            let mut xi = [0u8; 32];
+            let res = rng.try_fill_bytes(&mut xi).map_err(|_| "KeyGen: Random number generator failed");
+            if res.is_ok() {
+                keygen_from_seed_into(&xi, pk_out, sk_out);
+            }
+            xi.zeroize();
+            res
+        }
+
+
+        /// Signs the message with the context string using the encoded private key in place,
+        /// without copying the encoding; see [`crate::traits::Signer::try_sign()`]. The key is
+        /// expanded on the stack and zeroized before returning. This function utilizes the
+        /// **OS default** random number generator.
+        ///
+        /// # Errors
+        /// Returns an error when the private key is malformed, the random number generator
+        /// fails, or the context is too long.
+        #[cfg(feature = "default-rng")]
+        pub fn try_sign_from_bytes(sk: &[u8; SK_LEN], message: &[u8], ctx: &[u8]) -> Result<[u8; SIG_LEN], &'static str> {
+            try_sign_from_bytes_with_rng(&mut rand_core::OsRng, sk, message, ctx)
+        }
+
+
+        /// Signs the message with the context string using the encoded private key in place;
+        /// see [`try_sign_from_bytes()`]. This function utilizes the **provided** random number
+        /// generator.
+        ///
+        /// # Errors
+        /// Returns an error when the private key is malformed, the random number generator
+        /// fails, or the context is too long.
+        pub fn try_sign_from_bytes_with_rng(
+            rng: &mut impl CryptoRngCore, sk: &[u8; SK_LEN], message: &[u8], ctx: &[u8],
+        ) -> Result<[u8; SIG_LEN], &'static str> {
+            let esk = ml_dsa::expand_private::<K, L, SK_LEN>(ETA, sk)?;
+            esk.try_sign_with_rng(rng, message, ctx) // the expanded key zeroizes itself on drop
+        }
+
+
+        // ----- CALLER-PROVIDED EXPANDED KEY STORAGE ---
+
+        // Note: the expanded keys hold the keys' NTT-domain precomputes (there is no stored
+        // matrix `A`; it is regenerated element by element), which run to several KiB. The slots
+        // have `const` constructors so that the storage can be a `static` placed in a chosen
+        // memory region (e.g., external RAM via `#[link_section]`), and `try_load()` expands an
+        // encoded key directly into it, one polynomial at a time. An empty slot holds no usable
+        // key: the slots do not implement `Signer` or `Verifier`, and `get()` only returns a key
+        // once one has been loaded.
+
+        /// Caller-provided storage for an expanded private key, which is empty until a key is
+        /// loaded with [`PrivateKeySlot::try_load()`].
+        pub struct PrivateKeySlot {
+            key: PrivateKey,
+            loaded: bool,
+        }
+
+
+        impl PrivateKeySlot {
+            /// Returns an empty slot.
+            #[must_use]
+            pub const fn empty() -> Self {
+                let key = PrivateKey {
+                    rho: [0u8; 32],
+                    cap_k: [0u8; 32],
+                    tr: [0u8; 64],
+                    s_1_hat_mont: [types::T0; L],
+                    s_2_hat_mont: [types::T0; K],
+                    t_0_hat_mont: [types::T0; K],
+                };
+                Self { key, loaded: false }
+            }
+
+
+            /// Expands the encoded private key into this slot in place, replacing (and thereby
+            /// zeroizing) any previous key. The result is identical to `try_from_bytes(*sk)`,
+            /// without the expanded key ever being held on the stack.
+            ///
+            /// # Errors
+            /// Returns an error on a malformed private key, in which case the slot is zeroized
+            /// and left empty.
+            ///
+            /// # Examples
+            /// ```rust
+            /// # use std::error::Error;
+            /// # fn main() -> Result<(), Box<dyn Error>> {
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+            /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+            /// use fips204::traits::{SerDes, Signer, Verifier};
+            /// use std::sync::Mutex;
+            ///
+            /// // E.g., `#[link_section = ".ext_ram"]` to place the expanded key in external RAM
+            /// static SK: Mutex<ml_dsa_44::PrivateKeySlot> = Mutex::new(ml_dsa_44::PrivateKeySlot::empty());
+            ///
+            /// let (pk, sk) = ml_dsa_44::try_keygen()?;
+            /// SK.lock().unwrap().try_load(&sk.into_bytes())?;
+            /// let sig = SK.lock().unwrap().get().ok_or("no key loaded")?.try_sign(b"message", b"ctx")?;
+            /// assert!(pk.verify(b"message", &sig, b"ctx"));
+            /// # }
+            /// # Ok(())}
+            /// ```
+            pub fn try_load(&mut self, sk: &[u8; SK_LEN]) -> Result<(), &'static str> {
+                self.loaded = false;
+                ml_dsa::expand_private_into::<K, L, SK_LEN>(ETA, sk, &mut self.key)?;
+                self.loaded = true;
+                Ok(())
+            }
+
+
+            /// Returns the loaded private key, or `None` when the slot is empty.
+            #[must_use]
+            pub fn get(&self) -> Option<&PrivateKey> { self.loaded.then_some(&self.key) }
+
+
+            /// Zeroizes the slot and leaves it empty.
+            pub fn clear(&mut self) {
+                self.key.zeroize();
+                self.loaded = false;
+            }
+        }
+
+
+        /// Caller-provided storage for an expanded public key, which is empty until a key is
+        /// loaded with [`PublicKeySlot::try_load()`] (or [`PublicKeySlot::load_stepwise()`]).
+        pub struct PublicKeySlot {
+            key: PublicKey,
+            loaded: bool,
+        }
+
+
+        impl PublicKeySlot {
+            /// Returns an empty slot.
+            #[must_use]
+            pub const fn empty() -> Self {
+                let key = PublicKey { rho: [0u8; 32], tr: [0u8; 64], t1_d2_hat_mont: [types::T0; K] };
+                Self { key, loaded: false }
+            }
+
+
+            /// Expands the encoded public key into this slot in place, replacing any previous
+            /// key. The result is identical to `try_from_bytes(*pk)`, without the expanded key
+            /// ever being held on the stack.
+            ///
+            /// # Errors
+            /// Returns an error on a malformed public key, in which case the slot is left empty.
+            pub fn try_load(&mut self, pk: &[u8; PK_LEN]) -> Result<(), &'static str> {
+                self.loaded = false;
+                ml_dsa::expand_public_into::<K, L, PK_LEN>(pk, &mut self.key)?;
+                self.loaded = true;
+                Ok(())
+            }
+
+
+            /// Returns the loaded public key, or `None` when the slot is empty.
+            #[must_use]
+            pub fn get(&self) -> Option<&PublicKey> { self.loaded.then_some(&self.key) }
+
+
+            /// Leaves the slot empty.
+            pub fn clear(&mut self) { self.loaded = false; }
+        }
+
+
+        // ----- STEPWISE (COOPERATIVE) KEY EXPANSION ---
+
+        /// Expands a public key into caller-provided storage in bounded steps, so that firmware
+        /// running under a watchdog can spread the work across scheduler slices. Each call to
+        /// [`PublicKeyExpansion::step()`] expands one row: when deriving the public key from a
+        /// private key, this regenerates row `r` of `A` (`L` elements) and computes `t_1[r]`,
+        /// and when loading an encoded public key, it decodes and transforms `t_1[r]`. A final
+        /// step sets `rho` and `tr`, for `K + 1` steps in all. The expansion is also a
+        /// [`Future`] performing one step per poll and yielding (waking itself) in between.
+        ///
+        /// Created by `PublicKeySlot::load_stepwise()` or `PrivateKey::public_key_stepwise()`.
+        /// The slot is empty until the expansion completes, and stays empty if the expansion
+        /// is abandoned part-way or fails.
+        ///
+        /// [`Future`]: core::future::Future
+        pub struct PublicKeyExpansion<'a> {
+            source: ExpansionSource<'a>,
+            out: &'a mut PublicKeySlot,
+            row: usize,
+            result: Option<Result<(), &'static str>>,
+        }
+
+
+        enum ExpansionSource<'a> {
+            Encoded(&'a [u8; PK_LEN]),
+            Private(&'a PrivateKey),
+        }
+
+
+        impl PublicKeySlot {
+            /// Prepares to expand the encoded public key into this slot in bounded steps; no
+            /// work is performed until [`PublicKeyExpansion::step()`] is called (or the
+            /// expansion is polled). The result is identical to [`PublicKeySlot::try_load()`].
+            ///
+            /// # Examples
+            /// ```rust
+            /// # use std::error::Error;
+            /// # fn main() -> Result<(), Box<dyn Error>> {
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+            /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+            /// use fips204::traits::{SerDes, Signer, Verifier};
+            ///
+            /// let (pk, sk) = ml_dsa_44::try_keygen()?;
+            /// let sig = sk.try_sign(b"message", b"ctx")?;
+            ///
+            /// let pk_bytes = pk.into_bytes();
+            /// let mut slot = ml_dsa_44::PublicKeySlot::empty();
+            /// let mut expansion = slot.load_stepwise(&pk_bytes);
+            /// let result = loop {
+            ///     if let Some(result) = expansion.step() {
+            ///         break result;
+            ///     }
+            ///     // ...feed the watchdog, or yield to other tasks here
+            /// };
+            /// result?;
+            /// assert!(slot.get().ok_or("no key loaded")?.verify(b"message", &sig, b"ctx"));
+            /// # }
+            /// # Ok(())}
+            /// ```
+            pub fn load_stepwise<'a>(&'a mut self, pk: &'a [u8; PK_LEN]) -> PublicKeyExpansion<'a> {
+                self.loaded = false;
+                PublicKeyExpansion { source: ExpansionSource::Encoded(pk), out: self, row: 0, result: None }
+            }
+        }
+
+
+        impl PrivateKey {
+            /// Prepares to derive the public key of this private key into the `out` slot in
+            /// bounded steps; no work is performed until [`PublicKeyExpansion::step()`] is called
+            /// (or the expansion is polled). The result is identical to
+            /// [`crate::traits::Signer::get_public_key()`].
+            pub fn public_key_stepwise<'a>(&'a self, out: &'a mut PublicKeySlot) -> PublicKeyExpansion<'a> {
+                out.loaded = false;
+                PublicKeyExpansion { source: ExpansionSource::Private(self), out, row: 0, result: None }
+            }
+        }
+
+
+        impl PublicKeyExpansion<'_> {
+            /// Performs the next row of work, returning `None` while work remains and then the
+            /// result (repeatedly, if called again). On error (a malformed encoded public key),
+            /// the slot is left empty. Deriving the public key from a
+            /// private key operates in constant-time relative to secret data, as does
+            /// [`crate::traits::Signer::get_public_key()`].
+            pub fn step(&mut self) -> Option<Result<(), &'static str>> {
+                if self.result.is_some() {
+                    return self.result;
+                }
+                if self.row < K {
+                    let row = match self.source {
+                        ExpansionSource::Encoded(pk) => ml_dsa::expand_public_row(pk, self.row),
+                        ExpansionSource::Private(sk) => Ok(ml_dsa::private_to_public_row(sk, self.row)),
+                    };
+                    match row {
+                        Ok(row) => self.out.key.t1_d2_hat_mont[self.row] = row,
+                        Err(e) => self.result = Some(Err(e)),
+                    }
+                    self.row += 1;
+                } else {
+                    match self.source {
+                        ExpansionSource::Encoded(pk) => {
+                            use sha3::digest::XofReader;
+                            self.out.key.rho.copy_from_slice(&pk[0..32]);
+                            hashing::h256_xof(&[pk]).read(&mut self.out.key.tr);
+                        }
+                        ExpansionSource::Private(sk) => (self.out.key.rho, self.out.key.tr) = (sk.rho, sk.tr),
+                    }
+                    self.out.loaded = true;
+                    self.result = Some(Ok(()));
+                }
+                self.result
+            }
+        }
+
+
+        impl core::future::Future for PublicKeyExpansion<'_> {
+            type Output = Result<(), &'static str>;
+
+            fn poll(
+                self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>,
+            ) -> core::task::Poll<Self::Output> {
+                match self.get_mut().step() {
+                    Some(result) => core::task::Poll::Ready(result),
+                    None => {
+                        cx.waker().wake_by_ref(); // Ready to continue, after others have run
+                        core::task::Poll::Pending
+                    }
+                }
+            }
+        }
+
+
+        // ----- PRIVATE KEY FORMAT MIGRATION ---
+
+        // Note: a private key exists in three forms: the 32-byte seed `ξ` of ML-DSA.KeyGen_internal,
+        // the FIPS 204 encoding of `SK_LEN` bytes, and the expanded `PrivateKey`. These functions
+        // convert seed → encoded → expanded → encoded, validating along the way. The seed cannot
+        // be recovered from the other forms, as it is hashed; a store migrating away from seeds
+        // must keep them if it may ever need them again.
+
+        /// Converts a seed to the encoded private key; identical to `keygen_from_seed(xi)`
+        /// followed by `into_bytes()` on the private key.
+        #[must_use]
+        pub fn seed_to_encoded(xi: &[u8; 32]) -> [u8; SK_LEN] {
+            let (mut pk, mut sk) = ([0u8; PK_LEN], [0u8; SK_LEN]);
+            keygen_from_seed_into(xi, &mut pk, &mut sk);
+            sk
+        }
+
+
+        /// Converts a seed to the expanded private key; identical to the private key of
+        /// `keygen_from_seed(xi)`.
+        #[must_use]
+        pub fn seed_to_expanded(xi: &[u8; 32]) -> PrivateKey {
+            ml_dsa::key_gen_internal::<CTEST, K, L, PK_LEN, SK_LEN>(ETA, xi).1
+        }
+
+
+        /// Checks that the encoded private key is well-formed and internally consistent: beyond
+        /// the coefficient ranges checked by [`crate::traits::SerDes::try_from_bytes()`], its
+        /// `t0` and `tr` must be those derived from its `rho`, `s1` and `s2`. This costs about as
+        /// much as key generation.
+        ///
+        /// # Errors
+        /// Returns an error on a malformed or inconsistent private key.
+        pub fn try_validate_encoded(sk: &[u8; SK_LEN]) -> Result<(), &'static str> {
+            ml_dsa::check_private::<K, L, PK_LEN, SK_LEN>(ETA, sk)
+        }
+
+
+        /// Converts the encoded private key to the expanded private key, after validating it
+        /// with [`try_validate_encoded()`].
+        ///
+        /// # Errors
+        /// Returns an error on a malformed or inconsistent private key.
+        ///
+        /// # Examples
+        /// ```rust
+        /// # use std::error::Error;
+        /// # fn main() -> Result<(), Box<dyn Error>> {
+        /// # #[cfg(feature = "ml-dsa-44")] {
+        /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+        ///
+        /// let xi = [0x42u8; 32]; // A seed from the old key store
+        /// let encoded = ml_dsa_44::seed_to_encoded(&xi);
+        /// assert!(ml_dsa_44::seed_matches_encoded(&xi, &encoded));
+        ///
+        /// let expanded = ml_dsa_44::try_encoded_to_expanded(&encoded)?;
+        /// assert_eq!(ml_dsa_44::expanded_to_encoded(&expanded), encoded);
+        /// # }
+        /// # Ok(())}
+        /// ```
+        pub fn try_encoded_to_expanded(sk: &[u8; SK_LEN]) -> Result<PrivateKey, &'static str> {
+            try_validate_encoded(sk)?;
+            ml_dsa::expand_private::<K, L, SK_LEN>(ETA, sk)
+        }
+
+
+        /// Converts the expanded private key to the encoded private key, without consuming it.
+        #[must_use]
+        pub fn expanded_to_encoded(sk: &PrivateKey) -> [u8; SK_LEN] { sk.duplicate().into_bytes() }
+
+
+        /// Returns whether the encoded private key is the one derived from the seed, comparing in
+        /// constant time; e.g., to confirm a migration before discarding the seeds.
+        #[must_use]
+        pub fn seed_matches_encoded(xi: &[u8; 32], sk: &[u8; SK_LEN]) -> bool {
+            let mut derived = seed_to_encoded(xi);
+            let diff = derived.iter().zip(sk.iter()).fold(0, |acc, (a, b)| acc | (a ^ b));
+            derived.zeroize();
+            diff == 0
+        }
+
+
+        // ----- ATTACHED (SIGNED MESSAGE) MODE ---
+
+        /// Length of the little-endian message length prefix in an attached signed message.
+        #[cfg(feature = "alloc")]
+        const ATTACHED_PREFIX_LEN: usize = 8;
+
+
+        #[cfg(feature = "alloc")]
+        impl PrivateKey {
+            /// Signs the message and returns a single "signed message" blob that embeds it, in
+            /// the style of `NaCl`'s `crypto_sign()`. The blob is laid out as the message length
+            /// (8 bytes, little-endian), the message itself, then the signature. This function
+            /// utilizes the **OS default** random number generator and is only exposed when the
+            /// `alloc` feature is enabled.
+            ///
+            /// # Errors
+            /// Returns an error when the random number generator fails or context too long.
+            ///
+            /// # Examples
+            /// ```rust
+            /// # use std::error::Error;
+            /// # fn main() -> Result<(), Box<dyn Error>> {
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng", feature = "alloc"))] {
+            /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+            ///
+            /// let message = [0u8, 1, 2, 3, 4, 5, 6, 7];
+            ///
+            /// let (pk, sk) = ml_dsa_44::try_keygen()?; // Generate both public and secret keys
+            /// let blob = sk.try_sign_attached(&message, &[0])?; // Sign and embed the message
+            /// let opened = pk.open_attached(&blob, &[0])?; // Verify and extract the message
+            /// assert_eq!(opened, message);
+            /// # }
+            /// # Ok(())}
+            /// ```
+            #[cfg(feature = "default-rng")]
+            pub fn try_sign_attached(&self, message: &[u8], ctx: &[u8]) -> Result<alloc::vec::Vec<u8>, &'static str> {
+                self.try_sign_attached_with_rng(&mut rand_core::OsRng, message, ctx)
+            }
+
+
+            /// Signs the message and returns a single "signed message" blob that embeds it; see
+            /// [`PrivateKey::try_sign_attached()`] for the layout. This function utilizes the
+            /// **provided** random number generator.
+            ///
+            /// # Errors
+            /// Returns an error when the random number generator fails or context too long.
+            pub fn try_sign_attached_with_rng(
+                &self, rng: &mut impl CryptoRngCore, message: &[u8], ctx: &[u8],
+            ) -> Result<alloc::vec::Vec<u8>, &'static str> {
+                let sig = self.try_sign_with_rng(rng, message, ctx)?;
+                let msg_len = u64::try_from(message.len()).map_err(|_| "Attached: message too long")?;
+                let mut blob = alloc::vec::Vec::with_capacity(ATTACHED_PREFIX_LEN + message.len() + SIG_LEN);
+                blob.extend_from_slice(&msg_len.to_le_bytes());
+                blob.extend_from_slice(message);
+                blob.extend_from_slice(&sig);
+                Ok(blob)
+            }
+        }
+
+
+        #[cfg(feature = "alloc")]
+        impl PublicKey {
+            /// Verifies a "signed message" blob produced by [`PrivateKey::try_sign_attached()`]
+            /// and, on success, returns the embedded message. As this function operates on purely
+            /// public data, it need/does not provide constant-time assurances. Only exposed when
+            /// the `alloc` feature is enabled.
+            ///
+            /// # Errors
+            /// Returns an error when the blob is malformed or the signature does not verify.
+            pub fn open_attached(&self, blob: &[u8], ctx: &[u8]) -> Result<alloc::vec::Vec<u8>, &'static str> {
+                helpers::ensure!(blob.len() >= ATTACHED_PREFIX_LEN + SIG_LEN, "Attached: blob too short");
+                let (prefix, rest) = blob.split_at(ATTACHED_PREFIX_LEN);
+                let msg_len = u64::from_le_bytes(prefix.try_into().map_err(|_| "Attached: bad prefix")?);
+                helpers::ensure!(
+                    u64::try_from(rest.len() - SIG_LEN).map_or(false, |len| len == msg_len),
+                    "Attached: message length mismatch"
+                );
+                let (message, sig) = rest.split_at(rest.len() - SIG_LEN);
+                let sig: &[u8; SIG_LEN] = sig.try_into().map_err(|_| "Attached: bad signature length")?;
+                helpers::ensure!(self.verify(message, sig, ctx), "Attached: signature did not verify");
+                Ok(message.to_vec())
+            }
+        }
+
+
+        // ----- IN-PLACE SIGNING ---
+
+        // Note: the message is always borrowed as `&[u8]` and the signature returned by value (or
+        // written only after the message has been fully absorbed), so the borrow checker rejects
+        // any attempt to have a signature output alias its message at compile time. The in-place
+        // functions below support the common embedded pattern of a single buffer holding the
+        // message followed by room for its signature.
+
+        impl PrivateKey {
+            /// Signs `buf[..msg_len]` and writes the signature immediately after it, into
+            /// `buf[msg_len..msg_len + SIG_LEN]`; any remainder of `buf` is left untouched. This
+            /// function utilizes the **OS default** random number generator.
+            ///
+            /// # Errors
+            /// Returns an error when `buf` is too short, the random number generator fails, or
+            /// the context is too long.
+            ///
+            /// # Examples
+            /// ```rust
+            /// # use std::error::Error;
+            /// # fn main() -> Result<(), Box<dyn Error>> {
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+            /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+            ///
+            /// let (pk, sk) = ml_dsa_44::try_keygen()?;
+            /// let mut frame = [0u8; 8 + ml_dsa_44::SIG_LEN]; // e.g., a radio frame
+            /// frame[..8].copy_from_slice(&[0u8, 1, 2, 3, 4, 5, 6, 7]);
+            /// sk.try_sign_in_place(&mut frame, 8, &[])?;
+            /// assert!(pk.verify_in_place(&frame, 8, &[]));
+            /// # }
+            /// # Ok(())}
+            /// ```
+            #[cfg(feature = "default-rng")]
+            pub fn try_sign_in_place(&self, buf: &mut [u8], msg_len: usize, ctx: &[u8]) -> Result<(), &'static str> {
+                self.try_sign_in_place_with_rng(&mut rand_core::OsRng, buf, msg_len, ctx)
+            }
+
+
+            /// Signs `buf[..msg_len]` and writes the signature immediately after it; see
+            /// [`PrivateKey::try_sign_in_place()`]. This function utilizes the **provided**
+            /// random number generator.
+            ///
+            /// # Errors
+            /// Returns an error when `buf` is too short, the random number generator fails, or
+            /// the context is too long.
+            pub fn try_sign_in_place_with_rng(
+                &self, rng: &mut impl CryptoRngCore, buf: &mut [u8], msg_len: usize, ctx: &[u8],
+            ) -> Result<(), &'static str> {
+                helpers::ensure!(
+                    msg_len.checked_add(SIG_LEN).map_or(false, |len| len <= buf.len()),
+                    "In-place: buffer too short"
+                );
+                let (message, rest) = buf.split_at_mut(msg_len);
+                let sig = self.try_sign_with_rng(rng, message, ctx)?;
+                rest[..SIG_LEN].copy_from_slice(&sig);
+                Ok(())
+            }
+        }
+
+
+        impl PublicKey {
+            /// Verifies the signature at `buf[msg_len..msg_len + SIG_LEN]` over the message
+            /// `buf[..msg_len]`, as laid out by [`PrivateKey::try_sign_in_place()`]. Returns
+            /// `false` when `buf` is too short. As this function operates on purely public data,
+            /// it need/does not provide constant-time assurances.
+            #[must_use]
+            pub fn verify_in_place(&self, buf: &[u8], msg_len: usize, ctx: &[u8]) -> bool {
+                let Some(sig) = msg_len.checked_add(SIG_LEN).and_then(|end| buf.get(msg_len..end)) else {
+                    return false;
+                };
+                sig.try_into().map_or(false, |sig| self.verify(&buf[..msg_len], sig, ctx))
+            }
+        }
+
+
+        // ----- BATCH SIGNING ---
+
+        #[cfg(feature = "alloc")]
+        impl PrivateKey {
+            /// Signs a batch of messages under a single context, returning one signature per message
+            /// (in order). The matrix `A` is expanded and the `tr`/context prefix of the message
+            /// representative is hashed only once for the whole batch, and the per-signature
+            /// randomness is drawn with a single call to the **OS default** random number generator.
+            /// Only exposed when the `alloc` feature is enabled.
+            ///
+            /// # Errors
+            /// Returns an error when the random number generator fails or context too long.
+            ///
+            /// # Examples
+            /// ```rust
+            /// # use std::error::Error;
+            /// # fn main() -> Result<(), Box<dyn Error>> {
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng", feature = "alloc"))] {
+            /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+            /// use fips204::traits::Verifier;
+            ///
+            /// let messages: [&[u8]; 3] = [b"first", b"second", b"third"];
+            ///
+            /// let (pk, sk) = ml_dsa_44::try_keygen()?; // Generate both public and secret keys
+            /// let sigs = sk.try_sign_many(&messages, &[0])?; // One signature per message
+            /// for (message, sig) in messages.iter().zip(sigs.iter()) {
+            ///     assert!(pk.verify(message, sig, &[0]));
+            /// }
+            /// # }
+            /// # Ok(())}
+            /// ```
+            #[cfg(feature = "default-rng")]
+            pub fn try_sign_many(
+                &self, messages: &[&[u8]], ctx: &[u8],
+            ) -> Result<alloc::vec::Vec<[u8; SIG_LEN]>, &'static str> {
+                self.try_sign_many_with_rng(&mut rand_core::OsRng, messages, ctx)
+            }
+
+
+            /// Signs a batch of messages under a single context; see [`PrivateKey::try_sign_many()`].
+            /// This function utilizes the **provided** random number generator, which is called
+            /// exactly once for the whole batch.
+            ///
+            /// # Errors
+            /// Returns an error when the random number generator fails or context too long.
+            pub fn try_sign_many_with_rng(
+                &self, rng: &mut impl CryptoRngCore, messages: &[&[u8]], ctx: &[u8],
+            ) -> Result<alloc::vec::Vec<[u8; SIG_LEN]>, &'static str> {
+                helpers::ensure!(ctx.len() < 256, "ML-DSA.Sign: ctx too long");
+                let mut rnds = alloc::vec![0u8; 32 * messages.len()];
+                rng.try_fill_bytes(&mut rnds).map_err(|_| "ML-DSA.Sign: random number generator failed")?;
+                let mut sigs = alloc::vec![[0u8; SIG_LEN]; messages.len()];
+                let res = ml_dsa::sign_many::<CTEST, K, L, LAMBDA_DIV4, SIG_LEN, W1_LEN>(
+                    BETA, GAMMA1, GAMMA2, OMEGA, TAU, self, messages, ctx, &rnds, &mut sigs
+                );
+                rnds.zeroize();
+                res.map(|()| sigs)
+            }
+        }
+
+
+        // ----- SIGNATURE CANONICALIZATION ---
+
+        /// Re-encodes a signature into its unique canonical byte form, with hint indices sorted
+        /// within each polynomial and zero padding. Consensus-critical applications can use this
+        /// to normalize signatures before hashing them. Note that [`crate::traits::Verifier::verify()`]
+        /// already rejects every non-canonical encoding, so a signature that verifies is returned
+        /// unchanged.
+        ///
+        /// # Errors
+        /// Returns an error when the signature is malformed beyond canonicalization, i.e., its
+        /// hint counts are decreasing or exceed `ω`, or a `z` coefficient is out of range.
+        ///
+        /// # Examples
+        /// ```rust
+        /// # use std::error::Error;
+        /// # fn main() -> Result<(), Box<dyn Error>> {
+        /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+        /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+        /// use fips204::traits::Signer;
+        ///
+        /// let (_pk, sk) = ml_dsa_44::try_keygen()?;
+        /// let sig = sk.try_sign(&[0u8, 1, 2, 3], &[])?;
+        /// assert!(ml_dsa_44::is_canonical_signature(&sig));
+        /// assert_eq!(ml_dsa_44::canonicalize_signature(&sig)?, sig);
+        /// # }
+        /// # Ok(())}
+        /// ```
+        pub fn canonicalize_signature(sig: &[u8; SIG_LEN]) -> Result<[u8; SIG_LEN], &'static str> {
+            encodings::sig_canonicalize::<K, L, LAMBDA_DIV4, SIG_LEN>(GAMMA1, OMEGA, sig)
+        }
+
+
+        /// Returns `true` when the signature is in its unique canonical byte form; see
+        /// [`canonicalize_signature()`].
+        #[must_use]
+        pub fn is_canonical_signature(sig: &[u8; SIG_LEN]) -> bool {
+            canonicalize_signature(sig).map_or(false, |canonical| &canonical == sig)
+        }
+
+
+        // ----- SIGNATURE TRIAGE ---
+
+        /// Cheaply screens a signature's structure, e.g., so that a network frontend can drop
+        /// garbage before queuing full verification on worker threads. This decodes the
+        /// signature and checks that:
+        /// * the hint is properly encoded (increasing indices, at most `ω` in total, zero padding),
+        /// * the response `z` satisfies the norm bound `||z||∞ < γ1 − β` of verification.
+        ///
+        /// Every signature rejected here would also be rejected by
+        /// [`crate::traits::Verifier::verify()`], so screening never drops a valid signature.
+        /// A signature that passes carries **no** assurance of authenticity: it is not bound to
+        /// any message, context or public key (which only full verification establishes via the
+        /// challenge hash `c_tilde`), and anyone can construct such signatures at will. The cost
+        /// is a small fraction of verification, as no matrix expansion, NTT or hashing is done.
+        ///
+        /// # Errors
+        /// Returns an error describing the first structural defect found.
+        ///
+        /// # Examples
+        /// ```rust
+        /// # use std::error::Error;
+        /// # fn main() -> Result<(), Box<dyn Error>> {
+        /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+        /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+        /// use fips204::traits::Signer;
+        ///
+        /// let (_pk, sk) = ml_dsa_44::try_keygen()?;
+        /// let sig = sk.try_sign(&[0u8, 1, 2, 3], &[])?;
+        /// assert!(ml_dsa_44::screen_signature(&sig).is_ok());
+        /// assert!(ml_dsa_44::screen_signature(&[0u8; ml_dsa_44::SIG_LEN]).is_err());
+        /// # }
+        /// # Ok(())}
+        /// ```
+        pub fn screen_signature(sig: &[u8; SIG_LEN]) -> Result<(), &'static str> {
+            let (_c_tilde, z, _h) = encodings::sig_decode::<K, L, LAMBDA_DIV4, SIG_LEN>(GAMMA1, OMEGA, sig)?;
+            helpers::ensure!(helpers::infinity_norm(&z) < GAMMA1 - BETA, "Screen: z out of range");
+            Ok(())
+        }
+
+
+        // ----- LENGTH-ERASED (RUNTIME DISPATCH) VERIFICATION ---
+
+        impl PublicKey {
+            /// Verifies a signature supplied as a byte slice, such as when the parameter set is
+            /// only known at runtime. A signature of another parameter set (or of no parameter set
+            /// at all) is rejected with [`crate::Error::WrongParamSet`] before any decoding takes
+            /// place. Note that the fixed-size signature arrays used by
+            /// [`crate::traits::Verifier::verify()`] already prevent such mix-ups at compile time.
+            ///
+            /// # Errors
+            /// Returns [`crate::Error::WrongParamSet`] on a signature length mismatch, or
+            /// [`crate::Error::InvalidSignature`] when the signature does not verify.
+            ///
+            /// # Examples
+            /// ```rust
+            /// # use std::error::Error;
+            /// # fn main() -> Result<(), Box<dyn Error>> {
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "ml-dsa-65", feature = "default-rng"))] {
+            /// use fips204::{ml_dsa_44, ml_dsa_65, ParamSet};
+            /// use fips204::traits::Signer;
+            ///
+            /// let message = [0u8, 1, 2, 3, 4, 5, 6, 7];
+            /// let (_pk44, sk44) = ml_dsa_44::try_keygen()?;
+            /// let (pk65, _sk65) = ml_dsa_65::try_keygen()?;
+            /// let sig44 = sk44.try_sign(&message, &[])?;
+            ///
+            /// let res = pk65.try_verify_slice(&message, &sig44, &[]);
+            /// assert_eq!(
+            ///     res,
+            ///     Err(fips204::Error::WrongParamSet {
+            ///         expected: ParamSet::MlDsa65,
+            ///         found: Some(ParamSet::MlDsa44)
+            ///     })
+            /// );
+            /// # }
+            /// # Ok(())}
+            /// ```
+            ///
+            /// Whereas the fixed-size API refuses to compile:
+            /// ```rust,compile_fail
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "ml-dsa-65"))] {
+            /// use fips204::{ml_dsa_44, ml_dsa_65};
+            /// use fips204::traits::{Signer, Verifier};
+            ///
+            /// let (_pk44, sk44) = ml_dsa_44::try_keygen().unwrap();
+            /// let (pk65, _sk65) = ml_dsa_65::try_keygen().unwrap();
+            /// let sig44 = sk44.try_sign(&[], &[]).unwrap();
+            /// let _ = pk65.verify(&[], &sig44, &[]); // expected `[u8; 3309]`, found `[u8; 2420]`
+            /// # }
+            /// ```
+            pub fn try_verify_slice(&self, message: &[u8], sig: &[u8], ctx: &[u8]) -> Result<(), crate::Error> {
+                let Ok(sig) = <&[u8; SIG_LEN]>::try_from(sig) else {
+                    return Err(crate::Error::WrongParamSet {
+                        expected: PARAM_SET,
+                        found: crate::ParamSet::from_sig_len(sig.len()),
+                    });
+                };
+                if self.verify(message, sig, ctx) {
+                    Ok(())
+                } else {
+                    Err(crate::Error::InvalidSignature)
+                }
+            }
+        }
+
+
+        impl PublicKey {
+            /// Deserializes (and expands) a public key from a slice, such as when the parameter
+            /// set is read from configuration. A key of another parameter set is reported with
+            /// that parameter set, rather than just as a length mismatch.
+            ///
+            /// # Errors
+            /// Returns [`crate::Error::WrongLength`] when `pk` is not `PK_LEN` bytes long, or
+            /// [`crate::Error::MalformedKey`] when it does not decode.
+            ///
+            /// # Examples
+            /// ```rust
+            /// # use std::error::Error;
+            /// # fn main() -> Result<(), Box<dyn Error>> {
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "ml-dsa-65", feature = "default-rng"))] {
+            /// use fips204::traits::SerDes;
+            /// use fips204::{ml_dsa_44, ml_dsa_65, ParamSet};
+            ///
+            /// let (pk65, _sk65) = ml_dsa_65::try_keygen()?;
+            /// let res = ml_dsa_44::PublicKey::try_from_slice(&pk65.into_bytes());
+            /// assert_eq!(
+            ///     res.err(),
+            ///     Some(fips204::Error::WrongLength {
+            ///         expected: ml_dsa_44::PK_LEN,
+            ///         got: ml_dsa_65::PK_LEN,
+            ///         likely_paramset: Some(ParamSet::MlDsa65)
+            ///     })
+            /// );
+            /// # }
+            /// # Ok(())}
+            /// ```
+            pub fn try_from_slice(pk: &[u8]) -> Result<Self, crate::Error> {
+                let Ok(pk) = <&[u8; PK_LEN]>::try_from(pk) else {
+                    return Err(crate::Error::WrongLength {
+                        expected: PK_LEN,
+                        got: pk.len(),
+                        likely_paramset: crate::ParamSet::from_pk_len(pk.len()),
+                    });
+                };
+                ml_dsa::expand_public(pk).map_err(|_| crate::Error::MalformedKey)
+            }
+        }
+
+
+        impl PrivateKey {
+            /// Deserializes (and expands) a private key from a slice, in place; see
+            /// `PublicKey::try_from_slice()`.
+            ///
+            /// # Errors
+            /// Returns [`crate::Error::WrongLength`] when `sk` is not `SK_LEN` bytes long, or
+            /// [`crate::Error::MalformedKey`] when it does not decode.
+            pub fn try_from_slice(sk: &[u8]) -> Result<Self, crate::Error> {
+                let Ok(sk) = <&[u8; SK_LEN]>::try_from(sk) else {
+                    return Err(crate::Error::WrongLength {
+                        expected: SK_LEN,
+                        got: sk.len(),
+                        likely_paramset: crate::ParamSet::from_sk_len(sk.len()),
+                    });
+                };
+                ml_dsa::expand_private::<K, L, SK_LEN>(ETA, sk).map_err(|_| crate::Error::MalformedKey)
+            }
+        }
+
+
+        /// Copies a signature from a slice; see `PublicKey::try_from_slice()`.
+        ///
+        /// # Errors
+        /// Returns [`crate::Error::WrongLength`] when `sig` is not `SIG_LEN` bytes long.
+        pub fn try_sig_from_slice(sig: &[u8]) -> Result<[u8; SIG_LEN], crate::Error> {
+            sig.try_into().map_err(|_| crate::Error::WrongLength {
+                expected: SIG_LEN,
+                got: sig.len(),
+                likely_paramset: crate::ParamSet::from_sig_len(sig.len()),
+            })
+        }
+
+
+        // ----- FIXED-LENGTH CONTEXT ---
+
+        impl PrivateKey {
+            /// Signs a message under a compile-time fixed context, such as a constant protocol
+            /// label. The `N ≤ 255` length limit is enforced at compile time, so the only
+            /// remaining error is a random number generator failure. This function utilizes the
+            /// **OS default** random number generator.
+            ///
+            /// # Errors
+            /// Returns an error when the random number generator fails.
+            ///
+            /// # Examples
+            /// ```rust
+            /// # use std::error::Error;
+            /// # fn main() -> Result<(), Box<dyn Error>> {
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+            /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+            ///
+            /// const LABEL: &[u8; 8] = b"my-proto";
+            ///
+            /// let (pk, sk) = ml_dsa_44::try_keygen()?;
+            /// let sig = sk.try_sign_fixed_ctx(&[0u8, 1, 2, 3], LABEL)?;
+            /// assert!(pk.verify_fixed_ctx(&[0u8, 1, 2, 3], &sig, LABEL));
+            /// # }
+            /// # Ok(())}
+            /// ```
+            ///
+            /// Whereas an over-long context refuses to compile:
+            /// ```rust,compile_fail
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+            /// use fips204::ml_dsa_44;
+            ///
+            /// let (_pk, sk) = ml_dsa_44::try_keygen().unwrap();
+            /// let _ = sk.try_sign_fixed_ctx(&[0u8, 1, 2, 3], &[0u8; 256]);
+            /// # }
+            /// ```
+            #[cfg(feature = "default-rng")]
+            pub fn try_sign_fixed_ctx<const N: usize>(
+                &self, message: &[u8], ctx: &[u8; N],
+            ) -> Result<[u8; SIG_LEN], &'static str> {
+                self.try_sign_fixed_ctx_with_rng(&mut rand_core::OsRng, message, ctx)
+            }
+
+
+            /// Signs a message under a compile-time fixed context; see
+            /// [`PrivateKey::try_sign_fixed_ctx()`]. This function utilizes the **provided**
+            /// random number generator.
+            ///
+            /// # Errors
+            /// Returns an error when the random number generator fails.
+            pub fn try_sign_fixed_ctx_with_rng<const N: usize>(
+                &self, rng: &mut impl CryptoRngCore, message: &[u8], ctx: &[u8; N],
+            ) -> Result<[u8; SIG_LEN], &'static str> {
+                let () = helpers::CtxLen::<N>::OK;
+                let mut rnd = [0u8; 32];
+                rng.try_fill_bytes(&mut rnd).map_err(|_| "ML-DSA.Sign: random number generator failed")?;
+                ml_dsa::sign_internal::<CTEST, K, L, LAMBDA_DIV4, SIG_LEN, SK_LEN, W1_LEN>(
+                    BETA, GAMMA1, GAMMA2, OMEGA, TAU, self, message, ctx, &[], &[], rnd, false
+                )
+            }
+        }
+
+
+        impl PublicKey {
+            /// Verifies a signature made under a compile-time fixed context; see
+            /// [`PrivateKey::try_sign_fixed_ctx()`]. The `N ≤ 255` length limit is enforced at
+            /// compile time. As this function operates on purely public data, it need/does not
+            /// provide constant-time assurances.
+            #[must_use]
+            pub fn verify_fixed_ctx<const N: usize>(&self, message: &[u8], sig: &[u8; SIG_LEN], ctx: &[u8; N]) -> bool {
+                let () = helpers::CtxLen::<N>::OK;
+                ml_dsa::verify_internal::<CTEST, K, L, LAMBDA_DIV4, PK_LEN, SIG_LEN, W1_LEN>(
+                    BETA, GAMMA1, GAMMA2, OMEGA, TAU, self, message, sig, ctx, &[], &[], false,
+                    crate::VerifyMode::default()
+                )
+            }
+        }
+
+
+        // ----- HEDGED SIGNING WITH AUXILIARY ENTROPY ---
+
+        impl PrivateKey {
+            /// Signs the message with the context string as [`crate::traits::Signer::try_sign()`]
+            /// does, but with the signing randomness `rnd` derived as `H(rnd′ ∥ aux, 32)` from
+            /// 32 bytes `rnd′` drawn from the **provided** random number generator and 32 bytes of
+            /// caller-supplied auxiliary entropy `aux`. The signature is hedged as long as either
+            /// source is unpredictable, e.g., when the primary random number generator may repeat
+            /// its output after a virtual machine snapshot is restored, but the application keeps
+            /// its own entropy pool.
+            ///
+            /// # Errors
+            /// Returns an error when the random number generator fails or the context is too long.
+            ///
+            /// # Examples
+            /// ```rust
+            /// # use std::error::Error;
+            /// # fn main() -> Result<(), Box<dyn Error>> {
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+            /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+            /// use fips204::traits::Verifier;
+            /// use rand_core::OsRng;
+            ///
+            /// let (pk, sk) = ml_dsa_44::try_keygen()?;
+            /// let aux = [0x42u8; 32]; // From the application's own entropy pool
+            /// let sig = sk.try_sign_with_aux_rand(&mut OsRng, &aux, b"message", b"ctx")?;
+            /// assert!(pk.verify(b"message", &sig, b"ctx"));
+            /// # }
+            /// # Ok(())}
+            /// ```
+            pub fn try_sign_with_aux_rand(
+                &self, rng: &mut impl CryptoRngCore, aux: &[u8; 32], message: &[u8], ctx: &[u8],
+            ) -> Result<[u8; SIG_LEN], &'static str> {
+                use sha3::digest::XofReader;
+                helpers::ensure!(ctx.len() < 256, "ML-DSA.Sign: ctx too long");
+                let mut rnd_prime = [0u8; 32];
+                rng.try_fill_bytes(&mut rnd_prime).map_err(|_| "ML-DSA.Sign: random number generator failed")?;
+                let mut rnd = [0u8; 32];
+                hashing::h256_xof(&[&rnd_prime, aux]).read(&mut rnd);
+                rnd_prime.zeroize();
+                let sig = ml_dsa::sign_internal::<CTEST, K, L, LAMBDA_DIV4, SIG_LEN, SK_LEN, W1_LEN>(
+                    BETA, GAMMA1, GAMMA2, OMEGA, TAU, self, message, ctx, &[], &[], rnd, false
+                );
+                rnd.zeroize();
+                sig
+            }
+        }
+
+
+        // ----- SIGNING MODE TAGGING ---
+
+        impl PrivateKey {
+            /// Signs the message with the context string as [`crate::traits::Signer::try_sign()`]
+            /// does, returning the signature tagged as [`crate::SigningMode::Hedged`]. This
+            /// function utilizes the **OS default** random number generator.
+            ///
+            /// # Errors
+            /// Returns an error when the random number generator fails or the context is too long.
+            #[cfg(feature = "default-rng")]
+            pub fn try_sign_tagged(
+                &self, message: &[u8], ctx: &[u8],
+            ) -> Result<crate::TaggedSignature<[u8; SIG_LEN]>, &'static str> {
+                self.try_sign_tagged_with_rng(&mut rand_core::OsRng, message, ctx)
+            }
+
+
+            /// Signs the message with the context string as
+            /// [`crate::traits::Signer::try_sign_with_rng()`] does, returning the signature
+            /// tagged as [`crate::SigningMode::Hedged`]. This function utilizes the **provided**
+            /// random number generator.
+            ///
+            /// # Errors
+            /// Returns an error when the random number generator fails or the context is too long.
+            pub fn try_sign_tagged_with_rng(
+                &self, rng: &mut impl CryptoRngCore, message: &[u8], ctx: &[u8],
+            ) -> Result<crate::TaggedSignature<[u8; SIG_LEN]>, &'static str> {
+                let sig = self.try_sign_with_rng(rng, message, ctx)?;
+                Ok(crate::TaggedSignature::new(sig, crate::SigningMode::Hedged))
+            }
+
+
+            /// Signs the message with the context string using the FIPS 204 deterministic
+            /// variant (with `rnd` all zeros), returning the signature tagged as
+            /// [`crate::SigningMode::Deterministic`]. Deterministic signatures forgo the hedging
+            /// against fault and side-channel attacks, and so should be confined to testing or
+            /// to platforms without a random number generator.
+            ///
+            /// # Errors
+            /// Returns an error when the context is too long.
+            ///
+            /// # Examples
+            /// ```rust
+            /// # use std::error::Error;
+            /// # fn main() -> Result<(), Box<dyn Error>> {
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+            /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+            /// use fips204::traits::Verifier;
+            /// use fips204::SigningMode;
+            ///
+            /// let (pk, sk) = ml_dsa_44::try_keygen()?;
+            /// let sig = sk.try_sign_deterministic(b"message", b"ctx")?;
+            /// assert_eq!(sig.mode(), SigningMode::Deterministic);
+            /// assert!(pk.verify(b"message", sig.signature(), b"ctx"));
+            ///
+            /// // E.g., in production, where only hedged signatures may be released
+            /// assert!(sig.require_hedged().is_err());
+            /// assert!(sk.try_sign_tagged(b"message", b"ctx")?.require_hedged().is_ok());
+            /// # }
+            /// # Ok(())}
+            /// ```
+            pub fn try_sign_deterministic(
+                &self, message: &[u8], ctx: &[u8],
+            ) -> Result<crate::TaggedSignature<[u8; SIG_LEN]>, &'static str> {
+                helpers::ensure!(ctx.len() < 256, "ML-DSA.Sign: ctx too long");
+                let sig = ml_dsa::sign_internal::<CTEST, K, L, LAMBDA_DIV4, SIG_LEN, SK_LEN, W1_LEN>(
+                    BETA, GAMMA1, GAMMA2, OMEGA, TAU, self, message, ctx, &[], &[], [0u8; 32], false
+                )?;
+                Ok(crate::TaggedSignature::new(sig, crate::SigningMode::Deterministic))
+            }
+
+
+            /// Signs the message with the context string and pre-hash function using the
+            /// FIPS 204 deterministic variant of HashML-DSA (with `rnd` all zeros), as
+            /// [`PrivateKey::try_sign_deterministic()`] does for ML-DSA.
+            ///
+            /// # Errors
+            /// Returns an error when the context is too long.
+            pub fn try_hash_sign_deterministic(
+                &self, message: &[u8], ctx: &[u8], ph: &types::Ph,
+            ) -> Result<crate::TaggedSignature<[u8; SIG_LEN]>, &'static str> {
+                helpers::ensure!(ctx.len() < 256, "HashML-DSA.Sign: ctx too long");
+                let mut phm = [0u8; 64];
+                let (oid, phm_len) = hashing::hash_message(message, ph, &mut phm);
+                let sig = ml_dsa::sign_internal::<CTEST, K, L, LAMBDA_DIV4, SIG_LEN, SK_LEN, W1_LEN>(
+                    BETA, GAMMA1, GAMMA2, OMEGA, TAU, self, message, ctx, &oid, &phm[0..phm_len], [0u8; 32], false
+                )?;
+                Ok(crate::TaggedSignature::new(sig, crate::SigningMode::Deterministic))
+            }
+        }
+
+
+        // ----- HASHML-DSA WITH ANY DIGEST ---
+
+        impl PrivateKey {
+            /// Generates a HashML-DSA signature as
+            /// [`crate::traits::Signer::try_hash_sign()`] does, with any pre-hash function
+            /// given as a [`crate::DigestPh`]. This function utilizes the **OS default** random
+            /// number generator.
+            ///
+            /// # Errors
+            /// Returns an error when the random number generator fails or context too long.
+            #[cfg(feature = "default-rng")]
+            pub fn try_hash_sign_digest<D: sha2::Digest>(
+                &self, message: &[u8], ctx: &[u8], ph: &types::DigestPh<'_, D>,
+            ) -> Result<[u8; SIG_LEN], &'static str> {
+                self.try_hash_sign_digest_with_rng(&mut rand_core::OsRng, message, ctx, ph)
+            }
+
+
+            /// Generates a HashML-DSA signature with any pre-hash function; see
+            /// [`PrivateKey::try_hash_sign_digest()`]. This function utilizes the **provided**
+            /// random number generator.
+            ///
+            /// # Errors
+            /// Returns an error when the random number generator fails or context too long.
+            pub fn try_hash_sign_digest_with_rng<D: sha2::Digest>(
+                &self, rng: &mut impl CryptoRngCore, message: &[u8], ctx: &[u8], ph: &types::DigestPh<'_, D>,
+            ) -> Result<[u8; SIG_LEN], &'static str> {
+                helpers::ensure!(ctx.len() < 256, "HashML-DSA.Sign: ctx too long");
+                let mut rnd = [0u8; 32];
+                rng.try_fill_bytes(&mut rnd).map_err(|_| "HashML-DSA.Sign: random number generator failed")?;
+                let phm = D::digest(message);
+                ml_dsa::sign_internal::<CTEST, K, L, LAMBDA_DIV4, SIG_LEN, SK_LEN, W1_LEN>(
+                    BETA, GAMMA1, GAMMA2, OMEGA, TAU, self, message, ctx, ph.oid(), &phm, rnd, false,
+                )
+            }
+        }
+
+
+        impl PublicKey {
+            /// Verifies a HashML-DSA signature as [`crate::traits::Verifier::hash_verify()`]
+            /// does, with any pre-hash function given as a [`crate::DigestPh`]. As this function
+            /// operates on purely public data, it need/does not provide constant-time assurances.
+            #[must_use]
+            pub fn hash_verify_digest<D: sha2::Digest>(
+                &self, message: &[u8], sig: &[u8; SIG_LEN], ctx: &[u8], ph: &types::DigestPh<'_, D>,
+            ) -> bool {
+                if ctx.len() > 255 {
+                    return false;
+                };
+                let phm = D::digest(message);
+                ml_dsa::verify_internal::<CTEST, K, L, LAMBDA_DIV4, PK_LEN, SIG_LEN, W1_LEN>(
+                    BETA, GAMMA1, GAMMA2, OMEGA, TAU, self, message, sig, ctx, ph.oid(), &phm, false,
+                    crate::VerifyMode::default(),
+                )
+            }
+        }
+
+
+        // ----- CONFIGURABLE REJECTION ---
+
+        impl PublicKey {
+            /// Verifies a signature as [`crate::traits::Verifier::verify()`] does, but with
+            /// control over how an invalid signature is rejected; see [`crate::VerifyMode`].
+            /// `verify()` itself uses the default [`crate::VerifyMode::Uniform`]. As this function
+            /// operates on purely public data, it need/does not provide constant-time assurances.
+            ///
+            /// # Examples
+            /// ```rust
+            /// # use std::error::Error;
+            /// # fn main() -> Result<(), Box<dyn Error>> {
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+            /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+            /// use fips204::traits::Signer;
+            /// use fips204::VerifyMode;
+            ///
+            /// let (pk, sk) = ml_dsa_44::try_keygen()?;
+            /// let mut sig = sk.try_sign(&[0u8, 1, 2, 3], &[])?;
+            /// assert!(pk.verify_with_mode(&[0u8, 1, 2, 3], &sig, &[], VerifyMode::FastReject));
+            /// sig[0] ^= 0x01;
+            /// assert!(!pk.verify_with_mode(&[0u8, 1, 2, 3], &sig, &[], VerifyMode::FastReject));
+            /// # }
+            /// # Ok(())}
+            /// ```
+            #[must_use]
+            pub fn verify_with_mode(
+                &self, message: &[u8], sig: &[u8; SIG_LEN], ctx: &[u8], mode: crate::VerifyMode,
+            ) -> bool {
+                if ctx.len() > 255 {
+                    return false;
+                };
+                ml_dsa::verify_internal::<CTEST, K, L, LAMBDA_DIV4, PK_LEN, SIG_LEN, W1_LEN>(
+                    BETA, GAMMA1, GAMMA2, OMEGA, TAU, self, message, sig, ctx, &[], &[], false, mode
+                )
+            }
+        }
+
+
+        // ----- VERIFICATION AGAINST CANDIDATE KEYS ---
+
+        /// Verifies a signature against each of several candidate public keys (e.g., the current
+        /// and previous keys of a rotation window), returning the index within `pks` of the first
+        /// key under which it verifies, or `None` if there is none. This is equivalent to calling
+        /// [`crate::traits::Verifier::verify()`] with each key in turn, but the signature is
+        /// decoded, and its key-independent transforms computed, just once. The message
+        /// representative `µ` binds the key's hash `tr`, so it is only shared between
+        /// consecutive candidates with the same key. As this function operates on purely
+        /// public data, it need/does not provide constant-time assurances.
+        ///
+        /// # Examples
+        /// ```rust
+        /// # use std::error::Error;
+        /// # fn main() -> Result<(), Box<dyn Error>> {
+        /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+        /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+        /// use fips204::traits::Signer;
+        ///
+        /// let (previous_pk, previous_sk) = ml_dsa_44::try_keygen()?;
+        /// let (current_pk, current_sk) = ml_dsa_44::try_keygen()?;
+        /// let candidates = [&current_pk, &previous_pk];
+        ///
+        /// let sig = previous_sk.try_sign(b"zone data", b"")?;
+        /// assert_eq!(ml_dsa_44::verify_any(&candidates, b"zone data", &sig, b""), Some(1));
+        /// let sig = current_sk.try_sign(b"zone data", b"")?;
+        /// assert_eq!(ml_dsa_44::verify_any(&candidates, b"zone data", &sig, b""), Some(0));
+        /// assert_eq!(ml_dsa_44::verify_any(&candidates, b"other data", &sig, b""), None);
+        /// # }
+        /// # Ok(())}
+        /// ```
+        #[must_use]
+        pub fn verify_any(pks: &[&PublicKey], message: &[u8], sig: &[u8; SIG_LEN], ctx: &[u8]) -> Option<usize> {
+            if ctx.len() > 255 {
+                return None;
+            };
+            ml_dsa::verify_any::<K, L, LAMBDA_DIV4, SIG_LEN, W1_LEN>(
+                BETA, GAMMA1, GAMMA2, OMEGA, TAU, pks, message, sig, ctx,
+            )
+        }
+
+
+        // ----- SIGNING LATENCY BUDGETING ---
+
+        /// Returns the probability that signing needs more than `n` iterations of its rejection
+        /// sampling loop. Each iteration succeeds independently with probability
+        /// `1 / EXPECTED_SIGN_ITERATIONS` (to a close approximation), so the iteration count is
+        /// geometrically distributed, with no worst case; `n = 0` gives `1.0`. Multiply by the
+        /// cost of one iteration (e.g., as measured on the target) to budget signing latency.
+        ///
+        /// # Examples
+        /// ```rust
+        /// # #[cfg(feature = "ml-dsa-44")] {
+        /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+        ///
+        /// assert_eq!(ml_dsa_44::EXPECTED_SIGN_ITERATIONS, 4.25);
+        /// assert!(ml_dsa_44::sign_iterations_tail(20) < 0.005); // 20 iterations cover > 99.5%
+        /// let n = ml_dsa_44::sign_iterations_bound(1.0 / 1_000_000.0); // One signature in a million
+        /// assert!(ml_dsa_44::sign_iterations_tail(n) <= 1.0 / 1_000_000.0);
+        /// # }
+        /// ```
+        #[must_use]
+        pub fn sign_iterations_tail(n: u32) -> f64 {
+            let reject = 1.0 - 1.0 / EXPECTED_SIGN_ITERATIONS;
+            let mut tail = 1.0;
+            for _ in 0..n {
+                tail *= reject;
+            }
+            tail
+        }
+
+
+        /// Returns the smallest number of iterations `n` of the signing rejection sampling loop
+        /// such that more are needed with probability at most `p`; see
+        /// [`sign_iterations_tail()`]. Returns `u32::MAX` when `p` is not positive (or is NaN).
+        #[must_use]
+        pub fn sign_iterations_bound(p: f64) -> u32 {
+            if p.is_nan() || p <= 0.0 {
+                return u32::MAX;
+            }
+            let reject = 1.0 - 1.0 / EXPECTED_SIGN_ITERATIONS;
+            let (mut n, mut tail) = (0u32, 1.0);
+            while tail > p {
+                tail *= reject;
+                n += 1;
+            }
+            n
+        }
+
+
+        /// Fills `samples` with the number of rejection sampling iterations taken by real
+        /// signing operations, each of a random 32-byte message with fresh randomness, under a
+        /// key pair generated from `rng`. This supports validating [`sign_iterations_tail()`]
+        /// on a target, or simulating the latency of a workload.
+        ///
+        /// # Errors
+        /// Returns an error when the random number generator fails.
+        pub fn simulate_sign_iterations(rng: &mut impl CryptoRngCore, samples: &mut [u32]) -> Result<(), &'static str> {
+            let (_pk, esk) = KG::try_keygen_with_rng(rng)?;
+            let cap_a_hat = hashing::expand_a::<CTEST, K, L>(&esk.rho);
+            let rng_err = "ML-DSA.Sign: random number generator failed";
+            for sample in samples {
+                let (mut message, mut rnd) = ([0u8; 32], [0u8; 32]);
+                rng.try_fill_bytes(&mut message).map_err(|_| rng_err)?;
+                rng.try_fill_bytes(&mut rnd).map_err(|_| rng_err)?;
+                let mu = crate::message::mu(&esk.tr, &message, &[], None)?;
+                *sample = ml_dsa::sign_mu_counted::<CTEST, K, L, LAMBDA_DIV4, SIG_LEN, W1_LEN>(
+                    BETA, GAMMA1, GAMMA2, OMEGA, TAU, &esk, &cap_a_hat, &mu, rnd,
+                )?
+                .1;
+            }
+            Ok(())
+        }
+
+
+        // ----- AUDIT LOGGING ---
+
+        /// Generates a key pair as [`try_keygen()`] does, then records the key generation in
+        /// `log`; see [`crate::audit`]. This function utilizes the **OS default** random number
+        /// generator.
+        ///
+        /// # Errors
+        /// Returns an error when the random number generator fails.
+        #[cfg(all(feature = "audit", feature = "default-rng"))]
+        pub fn try_keygen_audited(log: &mut impl crate::audit::AuditLog) -> Result<(PublicKey, PrivateKey), &'static str> {
+            try_keygen_audited_with_rng(&mut rand_core::OsRng, log)
+        }
+
+
+        /// Generates a key pair as [`try_keygen_with_rng()`] does, then records the key
+        /// generation in `log`; see [`crate::audit`]. This function utilizes the **provided**
+        /// random number generator.
+        ///
+        /// # Errors
+        /// Returns an error when the random number generator fails.
+        #[cfg(feature = "audit")]
+        pub fn try_keygen_audited_with_rng(
+            rng: &mut impl CryptoRngCore, log: &mut impl crate::audit::AuditLog,
+        ) -> Result<(PublicKey, PrivateKey), &'static str> {
+            let timestamp = log.now();
+            let (pk, sk) = KG::try_keygen_with_rng(rng)?;
+            log.record(&crate::audit::AuditEvent {
+                operation: crate::audit::AuditOperation::KeyGen,
+                param_set: PARAM_SET,
+                timestamp,
+                fingerprint: pk.fingerprint(),
+                iterations: 0,
+            });
+            Ok((pk, sk))
+        }
+
+
+        #[cfg(feature = "audit")]
+        impl PrivateKey {
+            /// Signs the message under the context (with ML-DSA or HashML-DSA, as selected), as
+            /// [`crate::traits::Signer::try_sign_with_context()`] does, then records the
+            /// signature in `log`; see [`crate::audit`]. This function utilizes the **OS
+            /// default** random number generator.
+            ///
+            /// # Errors
+            /// Returns an error when the random number generator fails or the context string is
+            /// too long.
+            #[cfg(feature = "default-rng")]
+            pub fn try_sign_audited(
+                &self, message: &[u8], context: &types::Context<'_>, log: &mut impl crate::audit::AuditLog,
+            ) -> Result<[u8; SIG_LEN], &'static str> {
+                self.try_sign_audited_with_rng(&mut rand_core::OsRng, message, context, log)
+            }
+
+
+            /// Signs the message under the context, then records the signature in `log`; see
+            /// [`PrivateKey::try_sign_audited()`]. This function utilizes the **provided**
+            /// random number generator, drawing `rnd` as the plain signing functions do.
+            ///
+            /// # Errors
+            /// Returns an error when the random number generator fails or the context string is
+            /// too long.
+            pub fn try_sign_audited_with_rng(
+                &self, rng: &mut impl CryptoRngCore, message: &[u8], context: &types::Context<'_>,
+                log: &mut impl crate::audit::AuditLog,
+            ) -> Result<[u8; SIG_LEN], &'static str> {
+                let timestamp = log.now();
+                let mu = crate::message::mu(&self.tr, message, context.ctx(), context.hash())?;
+                let mut rnd = [0u8; 32];
+                rng.try_fill_bytes(&mut rnd).map_err(|_| "ML-DSA.Sign: random number generator failed")?;
+                let cap_a_hat = hashing::expand_a::<CTEST, K, L>(&self.rho);
+                let (sig, iterations) = ml_dsa::sign_mu_counted::<CTEST, K, L, LAMBDA_DIV4, SIG_LEN, W1_LEN>(
+                    BETA, GAMMA1, GAMMA2, OMEGA, TAU, self, &cap_a_hat, &mu, rnd,
+                )?;
+                log.record(&crate::audit::AuditEvent {
+                    operation: if context.hash().is_some() {
+                        crate::audit::AuditOperation::HashSign
+                    } else {
+                        crate::audit::AuditOperation::Sign
+                    },
+                    param_set: PARAM_SET,
+                    timestamp,
+                    fingerprint: self.public_fingerprint(),
+                    iterations,
+                });
+                Ok(sig)
+            }
+        }
+
+
+        // ----- DIAGNOSTICS ---
+
+        #[cfg(feature = "diagnostics")]
+        impl PublicKey {
+            /// Verifies a signature as [`crate::traits::Verifier::verify()`] does, but returns
+            /// the reason for any rejection. Only exposed when the `diagnostics` feature is
+            /// enabled, which is intended for test tooling and interop debugging; see the
+            /// warning in [`crate::diagnostics`].
+            ///
+            /// # Errors
+            /// Returns the [`crate::diagnostics::VerifyFailure`] reason when verification fails.
+            ///
+            /// # Examples
+            /// ```rust
+            /// # use std::error::Error;
+            /// # fn main() -> Result<(), Box<dyn Error>> {
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+            /// use fips204::diagnostics::VerifyFailure;
+            /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+            /// use fips204::traits::Signer;
+            ///
+            /// let (pk, sk) = ml_dsa_44::try_keygen()?;
+            /// let sig = sk.try_sign(&[0u8, 1, 2, 3], &[])?;
+            /// assert_eq!(pk.verify_diagnostic(&[0u8, 1, 2, 3], &sig, &[]), Ok(()));
+            /// let res = pk.verify_diagnostic(&[0u8, 1, 2, 4], &sig, &[]);
+            /// assert_eq!(res, Err(VerifyFailure::CTildeMismatch));
+            /// # }
+            /// # Ok(())}
+            /// ```
+            pub fn verify_diagnostic(
+                &self, message: &[u8], sig: &[u8; SIG_LEN], ctx: &[u8],
+            ) -> Result<(), crate::diagnostics::VerifyFailure> {
+                if ctx.len() > 255 {
+                    return Err(crate::diagnostics::VerifyFailure::ContextTooLong);
+                };
+                ml_dsa::try_verify_internal::<CTEST, K, L, LAMBDA_DIV4, PK_LEN, SIG_LEN, W1_LEN>(
+                    BETA, GAMMA1, GAMMA2, OMEGA, TAU, self, message, sig, ctx, &[], &[], false,
+                    crate::VerifyMode::default()
+                ).map_err(crate::diagnostics::VerifyFailure::from_internal)
+            }
+        }
+
+
+        // ----- STREAMING VERIFICATION ---
+
+        /// Verifies a (pure ML-DSA) signature over a message supplied in chunks, so that large
+        /// messages such as firmware images need not be held in memory. Created by
+        /// `PublicKey::try_verify_stream()`; the result is identical to
+        /// [`crate::traits::Verifier::verify()`] over the concatenated chunks.
+        #[derive(Clone)]
+        pub struct StreamVerifier<'a> {
+            pk: &'a PublicKey,
+            hasher: sha3::Shake256,
+        }
+
+
+        impl PublicKey {
+            /// Starts verifying a signature over a message that will be supplied incrementally
+            /// via [`StreamVerifier::update()`].
+            ///
+            /// # Errors
+            /// Returns an error when the `ctx` is longer than 255 bytes.
+            ///
+            /// # Examples
+            /// ```rust
+            /// # use std::error::Error;
+            /// # fn main() -> Result<(), Box<dyn Error>> {
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+            /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+            /// use fips204::traits::Signer;
+            ///
+            /// let (pk, sk) = ml_dsa_44::try_keygen()?;
+            /// let sig = sk.try_sign(b"a large message", &[])?;
+            ///
+            /// let mut verifier = pk.try_verify_stream(&[])?;
+            /// verifier.update(b"a large ");
+            /// verifier.update(b"message");
+            /// assert!(verifier.verify(&sig));
+            /// # }
+            /// # Ok(())}
+            /// ```
+            pub fn try_verify_stream(&self, ctx: &[u8]) -> Result<StreamVerifier<'_>, &'static str> {
+                helpers::ensure!(ctx.len() < 256, "ML-DSA.Verify: ctx too long");
+                Ok(StreamVerifier { pk: self, hasher: ml_dsa::mu_hasher(&self.tr, ctx, &[], false) })
+            }
+        }
+
+
+        impl StreamVerifier<'_> {
+            /// Absorbs the next chunk of the message.
+            pub fn update(&mut self, chunk: &[u8]) {
+                use sha3::digest::Update;
+                self.hasher.update(chunk);
+            }
+
+
+            /// Completes verification of the signature over all of the chunks supplied. As this
+            /// function operates on purely public data, it need/does not provide constant-time
+            /// assurances.
+            #[must_use]
+            pub fn verify(self, sig: &[u8; SIG_LEN]) -> bool {
+                use sha3::digest::{ExtendableOutput, XofReader};
+                let mut mu = [0u8; 64];
+                self.hasher.finalize_xof().read(&mut mu);
+                ml_dsa::try_verify_mu::<K, L, LAMBDA_DIV4, SIG_LEN, W1_LEN>(
+                    BETA, GAMMA1, GAMMA2, OMEGA, TAU, self.pk, &mu, sig, crate::VerifyMode::default()
+                ).is_ok()
+            }
+        }
+
+
+        /// Encoded length of one polynomial of the signature response `z`.
+        const Z_POLY_LEN: usize = (SIG_LEN - LAMBDA_DIV4 - OMEGA.unsigned_abs() as usize - K) / L;
+
+
+        /// Verifies a (pure ML-DSA) signature where both the message **and the signature** are
+        /// supplied in chunks, in any interleaving, using constant memory and no heap. Suited to
+        /// DMA-fed verification on microcontrollers where neither is ever resident in full; each
+        /// polynomial of the signature is folded into the verification as soon as its bytes are
+        /// complete. Created by `PublicKey::try_verify_chunked()`; the result is identical to
+        /// [`crate::traits::Verifier::verify()`] over the concatenated chunks.
+        #[derive(Clone)]
+        pub struct ChunkedVerifier<'a> {
+            pk: &'a PublicKey,
+            hasher: sha3::Shake256,
+            sig: ml_dsa::IncrementalSig<K, L, LAMBDA_DIV4, Z_POLY_LEN, { OMEGA.unsigned_abs() as usize + K }>,
+            malformed: bool,
+        }
+
+
+        impl PublicKey {
+            /// Starts verifying a signature whose message and signature bytes will both be
+            /// supplied incrementally, via [`ChunkedVerifier::update_message()`] and
+            /// [`ChunkedVerifier::update_signature()`].
+            ///
+            /// # Errors
+            /// Returns an error when the `ctx` is longer than 255 bytes.
+            ///
+            /// # Examples
+            /// ```rust
+            /// # use std::error::Error;
+            /// # fn main() -> Result<(), Box<dyn Error>> {
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+            /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+            /// use fips204::traits::Signer;
+            ///
+            /// let (pk, sk) = ml_dsa_44::try_keygen()?;
+            /// let sig = sk.try_sign(b"a large message", &[])?;
+            ///
+            /// let mut verifier = pk.try_verify_chunked(&[])?;
+            /// for block in sig.chunks(512) { // e.g., as each DMA transfer completes
+            ///     verifier.update_signature(block);
+            /// }
+            /// verifier.update_message(b"a large ");
+            /// verifier.update_message(b"message");
+            /// assert!(verifier.verify());
+            /// # }
+            /// # Ok(())}
+            /// ```
+            pub fn try_verify_chunked(&self, ctx: &[u8]) -> Result<ChunkedVerifier<'_>, &'static str> {
+                helpers::ensure!(ctx.len() < 256, "ML-DSA.Verify: ctx too long");
+                Ok(ChunkedVerifier {
+                    pk: self,
+                    hasher: ml_dsa::mu_hasher(&self.tr, ctx, &[], false),
+                    sig: ml_dsa::IncrementalSig::new(),
+                    malformed: false,
+                })
+            }
+        }
+
+
+        impl ChunkedVerifier<'_> {
+            /// Absorbs the next chunk of the message.
+            pub fn update_message(&mut self, chunk: &[u8]) {
+                use sha3::digest::Update;
+                self.hasher.update(chunk);
+            }
+
+
+            /// Absorbs the next chunk of the signature. Returns `false` (and the signature will
+            /// not verify) once more than `SIG_LEN` bytes have been supplied or a polynomial of
+            /// the signature is malformed.
+            pub fn update_signature(&mut self, chunk: &[u8]) -> bool {
+                self.malformed |= self.sig.update(GAMMA1, &self.pk.rho, chunk).is_err();
+                !self.malformed
+            }
+
+
+            /// Completes verification over all of the chunks supplied; exactly `SIG_LEN`
+            /// signature bytes must have been supplied. As this function operates on purely
+            /// public data, it need/does not provide constant-time assurances.
+            #[must_use]
+            pub fn verify(self) -> bool { self.result() }
+
+
+            fn result(&self) -> bool {
+                use sha3::digest::{ExtendableOutput, XofReader};
+                let mut mu = [0u8; 64];
+                self.hasher.clone().finalize_xof().read(&mut mu);
+                !self.malformed
+                    && self.sig.finish::<W1_LEN>(BETA, GAMMA1, GAMMA2, OMEGA, TAU, self.pk, &mu).is_ok()
+            }
+        }
+
+
+        // ----- STEPWISE (COOPERATIVE) VERIFICATION ---
+
+        /// Maximum number of message bytes absorbed by a single [`StepwiseVerifier::step()`].
+        pub const STEP_MESSAGE_LEN: usize = 4096;
+
+
+        /// Verifies a (pure ML-DSA) signature in bounded slices of work, so that a flood of
+        /// verifications cannot monopolize a cooperative scheduler thread. Each call to
+        /// [`StepwiseVerifier::step()`] absorbs at most [`STEP_MESSAGE_LEN`] message bytes, or
+        /// folds in one polynomial of the signature (`K` matrix elements), or performs the final
+        /// checks; roughly `L + 2` steps follow the message. The verifier is also a [`Future`]
+        /// performing one step per poll and yielding (waking itself) in between.
+        ///
+        /// Created by `PublicKey::try_verify_stepwise()`; the result is identical to
+        /// [`crate::traits::Verifier::verify()`].
+        ///
+        /// [`Future`]: core::future::Future
+        #[derive(Clone)]
+        pub struct StepwiseVerifier<'a> {
+            inner: ChunkedVerifier<'a>,
+            message: &'a [u8],
+            sig: &'a [u8; SIG_LEN],
+            sig_pos: usize,
+            result: Option<bool>,
+        }
+
+
+        impl PublicKey {
+            /// Prepares to verify the signature over the message in bounded steps; no work is
+            /// performed until [`StepwiseVerifier::step()`] is called (or the verifier is polled).
+            ///
+            /// # Errors
+            /// Returns an error when the `ctx` is longer than 255 bytes.
+            ///
+            /// # Examples
+            /// ```rust
+            /// # use std::error::Error;
+            /// # fn main() -> Result<(), Box<dyn Error>> {
+            /// # #[cfg(all(feature = "ml-dsa-87", feature = "default-rng"))] {
+            /// use fips204::ml_dsa_87; // Could also be ml_dsa_44 or ml_dsa_65.
+            /// use fips204::traits::Signer;
+            ///
+            /// let (pk, sk) = ml_dsa_87::try_keygen()?;
+            /// let sig = sk.try_sign(b"message", &[])?;
+            ///
+            /// let mut verifier = pk.try_verify_stepwise(b"message", &sig, &[])?;
+            /// let valid = loop {
+            ///     if let Some(valid) = verifier.step() {
+            ///         break valid;
+            ///     }
+            ///     // ...yield to other tasks here (or just `.await` the verifier instead)
+            /// };
+            /// assert!(valid);
+            /// # }
+            /// # Ok(())}
+            /// ```
+            pub fn try_verify_stepwise<'a>(
+                &'a self, message: &'a [u8], sig: &'a [u8; SIG_LEN], ctx: &[u8],
+            ) -> Result<StepwiseVerifier<'a>, &'static str> {
+                Ok(StepwiseVerifier { inner: self.try_verify_chunked(ctx)?, message, sig, sig_pos: 0, result: None })
+            }
+        }
+
+
+        impl StepwiseVerifier<'_> {
+            /// Performs the next bounded slice of work, returning `None` while work remains and
+            /// then the verification result (repeatedly, if called again). As this function
+            /// operates on purely public data, it need/does not provide constant-time assurances.
+            pub fn step(&mut self) -> Option<bool> {
+                if self.result.is_some() {
+                    return self.result;
+                }
+                if !self.message.is_empty() {
+                    let (chunk, rest) = self.message.split_at(self.message.len().min(STEP_MESSAGE_LEN));
+                    self.inner.update_message(chunk);
+                    self.message = rest;
+                } else if self.sig_pos < SIG_LEN {
+                    // A chunk of one polynomial's length completes at most one polynomial
+                    let end = SIG_LEN.min(self.sig_pos + Z_POLY_LEN);
+                    let _accepted = self.inner.update_signature(&self.sig[self.sig_pos..end]);
+                    self.sig_pos = end;
+                } else {
+                    self.result = Some(self.inner.result());
+                }
+                self.result
+            }
+        }
+
+
+        impl core::future::Future for StepwiseVerifier<'_> {
+            type Output = bool;
+
+            fn poll(self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<bool> {
+                match self.get_mut().step() {
+                    Some(valid) => core::task::Poll::Ready(valid),
+                    None => {
+                        cx.waker().wake_by_ref(); // Ready to continue, after others have run
+                        core::task::Poll::Pending
+                    }
+                }
+            }
+        }
+
+
+        #[cfg(feature = "std")]
+        impl PublicKey {
+            /// Verifies a signature over the contents of a file, streaming it through a
+            /// [`StreamVerifier`] in 1 MiB chunks so that multi-GB images need not be held in
+            /// memory. After each chunk, `progress(bytes_done, total_bytes)` is called, with the
+            /// total taken from the file metadata at the outset. Only exposed when the `std`
+            /// feature is enabled.
+            ///
+            /// Returns `Ok(false)` when the signature does not verify (including when the `ctx` is
+            /// longer than 255 bytes).
+            ///
+            /// # Errors
+            /// Returns an I/O error when the file cannot be opened or read.
+            pub fn verify_file(
+                &self, path: impl AsRef<std::path::Path>, sig: &[u8; SIG_LEN], ctx: &[u8],
+                mut progress: impl FnMut(u64, u64),
+            ) -> std::io::Result<bool> {
+                use std::io::Read;
+                const CHUNK_LEN: usize = 1 << 20;
+                let Ok(mut stream) = self.try_verify_stream(ctx) else { return Ok(false) };
+                let mut file = std::fs::File::open(path)?;
+                let total = file.metadata()?.len();
+                let mut buf = alloc::vec![0u8; CHUNK_LEN];
+                let mut done = 0u64;
+                loop {
+                    let len = match file.read(&mut buf) {
+                        Ok(0) => break,
+                        Ok(len) => len,
+                        Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                        Err(e) => return Err(e),
+                    };
+                    stream.update(&buf[..len]);
+                    done += len as u64;
+                    progress(done, total);
+                }
+                Ok(stream.verify(sig))
+            }
+        }
+
+
+        // ----- UNSTABLE INTERMEDIATES ---
+
+        /// Length in bytes of the `w1Encode(w1)` commitment encoding for this parameter set.
+        /// Only exposed when the `unstable` feature is enabled.
+        #[cfg(all(feature = "unstable", not(feature = "fips-mode")))]
+        pub const W1_ENCODED_LEN: usize = W1_LEN;
+
+
+        /// # Algorithm 28: `w1Encode(w1)` on page 35.
+        /// Encodes the commitment `w1` into the caller's buffer exactly as used when signing
+        /// and verifying, so that experimental aggregation/proof systems need not duplicate the
+        /// encoding logic. This is **unstable** and only exposed when the `unstable` feature is
+        /// enabled.
+        ///
+        /// **Input**: `w1 ∈ R^k` with coefficients in `[0, (q − 1)/(2γ_2) − 1]`. <br>
+        /// **Output**: `w1_tilde` written into `out`.
+        ///
+        /// # Errors
+        /// Returns an error when a coefficient of `w1` is out of range.
+        #[cfg(all(feature = "unstable", not(feature = "fips-mode")))]
+        pub fn w1_encode_into(w1: &[[i32; 256]; K], out: &mut [u8; W1_ENCODED_LEN]) -> Result<(), &'static str> {
+            let w1: [types::R; K] = core::array::from_fn(|k| types::R(w1[k]));
+            helpers::ensure!(
+                w1.iter().all(|r| helpers::is_in_range(r, 0, (Q - 1) / (2 * GAMMA2) - 1)),
+                "w1Encode: w1 out of range"
+            );
+            encodings::w1_encode::<K>(GAMMA2, &w1, out);
+            Ok(())
+        }
+
+
+        #[cfg(all(feature = "unstable", not(feature = "fips-mode")))]
+        impl PublicKey {
+            /// Reconstructs the signer's commitment `w′_1` from a signature (steps 2-11 of
+            /// Algorithm 8 `ML-DSA.Verify_internal()`) and writes `w1Encode(w′_1)` into the
+            /// caller's buffer. For a valid signature this is identical to the encoding hashed by
+            /// the signer, i.e., `c_tilde = H(µ || out)`. No other verification is performed. This
+            /// is **unstable** and only exposed when the `unstable` feature is enabled.
+            ///
+            /// # Errors
+            /// Returns an error when the signature is malformed.
+            pub fn try_reconstruct_w1_encoded(
+                &self, sig: &[u8; SIG_LEN], out: &mut [u8; W1_ENCODED_LEN],
+            ) -> Result<(), &'static str> {
+                ml_dsa::reconstruct_w1::<K, L, LAMBDA_DIV4, SIG_LEN, W1_LEN>(
+                    GAMMA1, GAMMA2, OMEGA, TAU, self, sig, None, out
+                ).map(|_| ())
+            }
+        }
+
+
+        /// Generates a key pair from the seed `xi` as per Algorithm 6 `ML-DSA.KeyGen_internal()`,
+        /// except that the public matrix seed `rho` is supplied by the caller rather than derived
+        /// from `xi`. This supports systems deriving `rho` from an identity (or sharing it across a
+        /// population of keys) so that it need not be transmitted. This is **unstable** and only
+        /// exposed when the `unstable` feature is enabled.
+        ///
+        /// **Security**: this is not FIPS 204 key generation, and a standard implementation will
+        /// not reproduce the key pair from `xi`. A `rho` shared across keys or known in advance
+        /// allows an attacker to amortize precomputation on the matrix `A` over every key using
+        /// it (a multi-target setting the ML-DSA security analysis does not assume), and an
+        /// attacker-chosen `rho` may yield a weak `A`. The secrets still derive only from `xi`, which
+        /// must be uniformly random and kept secret. Do not use this unless the protocol has been
+        /// analyzed for it.
+        #[cfg(all(feature = "unstable", not(feature = "fips-mode")))]
+        #[must_use]
+        pub fn keygen_from_seed_and_rho(xi: &[u8; 32], rho: &[u8; 32]) -> (PublicKey, PrivateKey) {
+            use sha3::digest::XofReader;
+            let mut h = hashing::h256_xof(&[xi, &[K.to_le_bytes()[0]], &[L.to_le_bytes()[0]]]);
+            let (mut derived_rho, mut rho_prime, mut cap_k) = ([0u8; 32], [0u8; 64], [0u8; 32]);
+            h.read(&mut derived_rho); // Discarded in favor of the caller's rho
+            h.read(&mut rho_prime);
+            h.read(&mut cap_k);
+            let keys = ml_dsa::key_gen_from_parts::<CTEST, K, L, PK_LEN>(ETA, rho, &rho_prime, &cap_k);
+            rho_prime.zeroize();
+            cap_k.zeroize();
+            keys
+        }
+
+
+        /// Generates a key pair with the caller-supplied public matrix seed `rho`, using the
+        /// **provided** random number generator for the seed `xi`. See
+        /// [`keygen_from_seed_and_rho()`] for the security implications. This is **unstable**
+        /// and only exposed when the `unstable` feature is enabled.
+        ///
+        /// # Errors
+        /// Returns an error when the random number generator fails.
+        #[cfg(all(feature = "unstable", not(feature = "fips-mode")))]
+        pub fn try_keygen_with_rho_with_rng(
+            rng: &mut impl CryptoRngCore, rho: &[u8; 32],
+        ) -> Result<(PublicKey, PrivateKey), &'static str> {
+            let mut xi = [0u8; 32];
+            rng.try_fill_bytes(&mut xi).map_err(|_| "KeyGen: Random number generator failed")?;
+            let keys = keygen_from_seed_and_rho(&xi, rho);
+            xi.zeroize();
+            Ok(keys)
+        }
+
+
+        // ----- HAZMAT (START/FINISH SPLIT) ---
+
+        /// The start/finish split of signing and verification, exposed for protocol research and
+        /// HSM integration. This is only available when the `hazmat` feature is enabled.
+        ///
+        /// The `*_start()` functions decode a key and precompute its NTT-domain elements (the
+        /// expanded key, as held by [`PrivateKey`] and [`PublicKey`]); the `*_finish()`
+        /// functions then operate on the expanded key and a message representative `µ`, which
+        /// the caller computes (e.g., with [`crate::message::mu()`] from the key's `tr`).
+        ///
+        /// **Security**: these functions perform none of the checks of the regular API. It is
+        /// the caller's responsibility to bind `µ` to the correct `tr`, context and pre-hash,
+        /// and to supply `rnd` from an approved random bit generator (or all zeros for the
+        /// deterministic variant). Reusing `rnd` with a different `µ` is harmless, but a
+        /// predictable `rnd` forfeits the hedging against fault attacks.
+        ///
+        /// # Examples
+        /// ```rust
+        /// # use std::error::Error;
+        /// # fn main() -> Result<(), Box<dyn Error>> {
+        /// # #[cfg(all(feature = "ml-dsa-44", feature = "hazmat", feature = "default-rng"))] {
+        /// use fips204::ml_dsa_44::{self, hazmat}; // Could also be ml_dsa_65 or ml_dsa_87.
+        /// use fips204::message;
+        /// use fips204::traits::{SerDes, Verifier};
+        ///
+        /// let (pk, sk) = ml_dsa_44::try_keygen()?;
+        /// let esk = hazmat::sign_start(&sk.into_bytes())?;
+        /// let mu = message::mu(&hazmat::private_key_tr(&esk), b"message", b"ctx", None)?;
+        /// let sig = hazmat::sign_finish(&esk, &mu, [0u8; 32]); // Deterministic variant
+        ///
+        /// let epk = hazmat::verify_start(&pk.clone().into_bytes())?;
+        /// assert!(hazmat::verify_finish(&epk, &mu, &sig));
+        /// assert!(pk.verify(b"message", &sig, b"ctx"));
+        /// # }
+        /// # Ok(())}
+        /// ```
+        #[cfg(all(feature = "hazmat", not(feature = "fips-mode")))]
+        pub mod hazmat {
+            use super::{ml_dsa, PrivateKey, PublicKey, BETA, CTEST, ETA, GAMMA1, GAMMA2};
+            use super::{hashing, K, L, LAMBDA_DIV4, OMEGA, PK_LEN, SIG_LEN, SK_LEN, TAU, W1_LEN};
+
+
+            /// Steps 1-4 of Algorithm 7 `ML-DSA.Sign_internal()`: decodes the private key and
+            /// precomputes `NTT(s_1)`, `NTT(s_2)` and `NTT(t_0)`.
+            ///
+            /// # Errors
+            /// Returns an error on a malformed private key.
+            pub fn sign_start(sk: &[u8; SK_LEN]) -> Result<PrivateKey, &'static str> {
+                ml_dsa::expand_private::<K, L, SK_LEN>(ETA, sk)
+            }
+
+
+            /// Returns the public key hash `tr` held within the private key, as needed to
+            /// compute `µ` for [`sign_finish()`].
+            #[must_use]
+            pub fn private_key_tr(esk: &PrivateKey) -> [u8; 64] { esk.tr }
+
+
+            /// Steps 5 and 7-34 of Algorithm 7 `ML-DSA.Sign_internal()`: expands `A` and runs the
+            /// rejection sampling loop over the message representative `µ`, with the per-message
+            /// randomness `rnd`.
+            #[must_use]
+            pub fn sign_finish(esk: &PrivateKey, mu: &[u8; 64], rnd: [u8; 32]) -> [u8; SIG_LEN] {
+                let cap_a_hat = hashing::expand_a::<CTEST, K, L>(&esk.rho);
+                // Signing only fails in the fips-mode error state, and fips-mode compiles out hazmat
+                ml_dsa::sign_mu::<CTEST, K, L, LAMBDA_DIV4, SIG_LEN, W1_LEN>(
+                    BETA, GAMMA1, GAMMA2, OMEGA, TAU, esk, &cap_a_hat, mu, rnd,
+                )
+                .unwrap_or_else(|_| unreachable!())
+            }
+
+
+            /// Step 1 of Algorithm 8 `ML-DSA.Verify_internal()`: decodes the public key and
+            /// precomputes `tr` and `NTT(t_1 · 2^d)`.
+            ///
+            /// # Errors
+            /// Returns an error on a malformed public key.
+            pub fn verify_start(pk: &[u8; PK_LEN]) -> Result<PublicKey, &'static str> {
+                ml_dsa::expand_public::<K, L, PK_LEN>(pk)
+            }
+
+
+            /// Steps 2-5 and 8-13 of Algorithm 8 `ML-DSA.Verify_internal()` over the message
+            /// representative `µ`. As this function operates on purely public data, it need/does
+            /// not provide constant-time assurances.
+            #[must_use]
+            pub fn verify_finish(epk: &PublicKey, mu: &[u8; 64], sig: &[u8; SIG_LEN]) -> bool {
+                ml_dsa::try_verify_mu::<K, L, LAMBDA_DIV4, SIG_LEN, W1_LEN>(
+                    BETA, GAMMA1, GAMMA2, OMEGA, TAU, epk, mu, sig, crate::VerifyMode::Uniform,
+                )
+                .is_ok()
+            }
+
+
+            /// The recomputed quantities of a verification, as returned by [`verify_residual()`].
+            /// A signature is valid exactly when `c_tilde == c_tilde_prime` and `z_in_range`.
+            #[cfg(all(feature = "unstable", not(feature = "fips-mode")))]
+            #[derive(Clone, Debug, PartialEq, Eq)]
+            pub struct VerifyResidual {
+                /// The encoding `w1Encode(w′_1)` of the reconstructed signer's commitment.
+                pub w1_encoded: [u8; W1_LEN],
+                /// The commitment hash `c_tilde` carried by the signature.
+                pub c_tilde: [u8; LAMBDA_DIV4],
+                /// The recomputed commitment hash `c_tilde_′ = H(µ ∥ w1Encode(w′_1), λ/4)`.
+                pub c_tilde_prime: [u8; LAMBDA_DIV4],
+                /// Whether the response satisfies `||z||∞ < γ1 − β`.
+                pub z_in_range: bool,
+            }
+
+
+            /// Steps 2-5 and 8-12 of Algorithm 8 `ML-DSA.Verify_internal()` over the message
+            /// representative `µ`, returning the recomputed `w1Encode(w′_1)` and challenge hash
+            /// rather than the outcome of step 13, so that experimental batch or aggregate
+            /// verification schemes can combine the checks of several signatures. This is
+            /// **not** part of FIPS 204, is **unstable**, and is only exposed when both the
+            /// `hazmat` and `unstable` features are enabled; a FIPS-conformant verifier must use
+            /// [`verify_finish()`] (or the regular API) instead.
+            ///
+            /// # Errors
+            /// Returns an error when the signature (or its hint) is malformed.
+            #[cfg(all(feature = "unstable", not(feature = "fips-mode")))]
+            pub fn verify_residual(
+                epk: &PublicKey, mu: &[u8; 64], sig: &[u8; SIG_LEN],
+            ) -> Result<VerifyResidual, &'static str> {
+                let (w1_encoded, c_tilde, c_tilde_prime, z_in_range) =
+                    ml_dsa::verify_residual::<K, L, LAMBDA_DIV4, SIG_LEN, W1_LEN>(
+                        BETA, GAMMA1, GAMMA2, OMEGA, TAU, epk, mu, sig,
+                    )?;
+                Ok(VerifyResidual { w1_encoded, c_tilde, c_tilde_prime, z_in_range })
+            }
+        }
+
+
+        // ----- PUBLIC KEY HASH ---
+
+        impl PublicKey {
+            /// Returns `tr = H(pk, 64)`, the SHAKE256 hash of the encoded public key that the
+            /// private key embeds and that prefixes every signed message (Algorithm 7 step 6).
+            /// It is computed once when the public key is generated or deserialized.
+            ///
+            /// This supports cross-checking an externally produced private key against its
+            /// public key (the encoded private key carries `tr` in bytes `64..128`), and
+            /// computing `µ = H(tr || M′, 64)` outside the signer in an external-µ flow.
+            ///
+            /// # Examples
+            /// ```rust
+            /// # use std::error::Error;
+            /// # fn main() -> Result<(), Box<dyn Error>> {
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+            /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+            /// use fips204::traits::SerDes;
+            ///
+            /// let (pk, sk) = ml_dsa_44::try_keygen()?;
+            /// assert_eq!(pk.tr()[..], sk.into_bytes()[64..128]);
+            /// # }
+            /// # Ok(())}
+            /// ```
+            #[must_use]
+            pub fn tr(&self) -> [u8; 64] { self.tr }
+
+
+            /// Returns the 32-byte key fingerprint `H(pk, 32)`, identical to
+            /// [`PrivateKey::public_fingerprint()`] of the corresponding private key.
+            #[must_use]
+            pub fn fingerprint(&self) -> [u8; 32] { fingerprint(&self.tr) }
+        }
+
+
+        impl PrivateKey {
+            /// Returns a 32-byte fingerprint of the corresponding public key, `H(pk, 32)` (the
+            /// SHAKE256 hash of the encoded public key), so that a signing service can log which
+            /// key signed what without handling public key objects or risking secret bytes in
+            /// its logs. As SHAKE256 output is prefix-consistent, this is simply the first 32
+            /// bytes of `tr`, which is computed once when the key is generated or deserialized.
+            ///
+            /// # Examples
+            /// ```rust
+            /// # use std::error::Error;
+            /// # fn main() -> Result<(), Box<dyn Error>> {
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+            /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+            ///
+            /// let (pk, sk) = ml_dsa_44::try_keygen()?;
+            /// assert_eq!(sk.public_fingerprint(), pk.fingerprint());
+            /// # }
+            /// # Ok(())}
+            /// ```
+            #[must_use]
+            pub fn public_fingerprint(&self) -> [u8; 32] { fingerprint(&self.tr) }
+        }
+
+
+        fn fingerprint(tr: &[u8; 64]) -> [u8; 32] {
+            let mut fp = [0u8; 32];
+            fp.copy_from_slice(&tr[..32]);
+            fp
+        }
+
+
+        // Note: the expanded public key compares by its contents (`rho` and the NTT-domain `t1`,
+        // from which the encoding is recovered exactly), and hashes by `tr`, which equal keys
+        // share. `Debug` shows only the parameter set and fingerprint, rather than the
+        // precomputed polynomials.
+
+        impl PartialEq for PublicKey {
+            fn eq(&self, other: &Self) -> bool {
+                self.rho == other.rho
+                    && self.t1_d2_hat_mont.iter().zip(other.t1_d2_hat_mont.iter()).all(|(a, b)| a.0 == b.0)
+            }
+        }
+
+
+        impl Eq for PublicKey {}
+
+
+        impl core::hash::Hash for PublicKey {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) { self.tr.hash(state); }
+        }
+
+
+        impl core::fmt::Debug for PublicKey {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "PublicKey({PARAM_SET}, fingerprint ")?;
+                self.fingerprint().iter().try_for_each(|b| write!(f, "{b:02x}"))?;
+                f.write_str(")")
+            }
+        }
+
+
+        // ----- PINNED VERIFICATION ---
+
+        /// A verifier pinned to a single public key by its 32-byte fingerprint (see
+        /// `PublicKey::fingerprint()`), e.g., the release key that an update client ships
+        /// with. Verification with any other public key fails, whatever the signature. The
+        /// fingerprints are compared in constant time.
+        ///
+        /// # Examples
+        /// ```rust
+        /// # use std::error::Error;
+        /// # fn main() -> Result<(), Box<dyn Error>> {
+        /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+        /// use fips204::ml_dsa_44::{self, PinnedVerifier}; // Could also be ml_dsa_65 or ml_dsa_87.
+        /// use fips204::traits::{SerDes, Signer};
+        ///
+        /// let (pk, sk) = ml_dsa_44::try_keygen()?;
+        /// let pinned = PinnedVerifier::new(pk.fingerprint()); // Typically a compiled-in constant
+        ///
+        /// // The public key arrives alongside the update
+        /// let sig = sk.try_sign(b"image", b"update")?;
+        /// let received = ml_dsa_44::PublicKey::try_from_bytes(pk.into_bytes())?;
+        /// assert!(pinned.verify(&received, b"image", &sig, b"update"));
+        ///
+        /// let (other_pk, other_sk) = ml_dsa_44::try_keygen()?;
+        /// let sig = other_sk.try_sign(b"image", b"update")?;
+        /// assert!(!pinned.verify(&other_pk, b"image", &sig, b"update"));
+        /// # }
+        /// # Ok(())}
+        /// ```
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub struct PinnedVerifier {
+            fingerprint: [u8; 32],
+        }
+
+
+        impl PinnedVerifier {
+            /// Pins the public key with this fingerprint.
+            #[must_use]
+            pub const fn new(fingerprint: [u8; 32]) -> Self { Self { fingerprint } }
+
+
+            /// Returns the pinned fingerprint.
+            #[must_use]
+            pub const fn fingerprint(&self) -> [u8; 32] { self.fingerprint }
+
+
+            /// Returns whether `pk` is the pinned public key, comparing in constant time.
+            #[must_use]
+            pub fn is_pinned(&self, pk: &PublicKey) -> bool {
+                let diff = self.fingerprint.iter().zip(pk.fingerprint().iter()).fold(0, |acc, (a, b)| acc | (a ^ b));
+                diff == 0
+            }
+
+
+            /// Verifies the signature as [`crate::traits::Verifier::verify()`] does, but only
+            /// when `pk` is the pinned public key.
+            #[must_use]
+            pub fn verify(&self, pk: &PublicKey, message: &[u8], sig: &[u8; SIG_LEN], ctx: &[u8]) -> bool {
+                self.is_pinned(pk) && pk.verify(message, sig, ctx)
+            }
+
+
+            /// Verifies the signature as [`crate::traits::Verifier::hash_verify()`] does, but only
+            /// when `pk` is the pinned public key.
+            #[must_use]
+            pub fn hash_verify(
+                &self, pk: &PublicKey, message: &[u8], sig: &[u8; SIG_LEN], ctx: &[u8], ph: &types::Ph,
+            ) -> bool {
+                self.is_pinned(pk) && pk.hash_verify(message, sig, ctx, ph)
+            }
+        }
+
+
+        impl From<&PublicKey> for PinnedVerifier {
+            fn from(pk: &PublicKey) -> Self { Self::new(pk.fingerprint()) }
+        }
+
+
+        // ----- DEEP PUBLIC KEY VALIDATION ---
+
+        impl PublicKey {
+            /// Deserializes and expands a public key as [`crate::traits::SerDes::try_from_bytes()`]
+            /// does, then performs additional validation suited to building long-lived verifier
+            /// state from untrusted bytes. FIPS 204 requires no checks beyond the length, so
+            /// these are optional hardening:
+            /// * the cached NTT-domain `t1` values are converted back and re-encoded, which must
+            ///   reproduce `pk` exactly (guarding against faults during expansion);
+            /// * `rho` must not be degenerate (all bytes equal);
+            /// * `t1` must not be all zero, as signatures could then be forged without the
+            ///   private key.
+            ///
+            /// # Errors
+            /// Returns [`crate::Error::DegenerateRho`], [`crate::Error::DegenerateT1`] or
+            /// [`crate::Error::ExpansionMismatch`] when the respective check fails.
+            pub fn try_from_bytes_validated(pk: [u8; PK_LEN]) -> Result<Self, crate::Error> {
+                // The t1 encoding always decodes in range, so expansion cannot fail
+                let epk = ml_dsa::expand_public(&pk).map_err(|_| crate::Error::ExpansionMismatch)?;
+                if epk.clone().into_bytes() != pk {
+                    return Err(crate::Error::ExpansionMismatch);
+                }
+                if pk[..32].iter().all(|b| *b == pk[0]) {
+                    return Err(crate::Error::DegenerateRho);
+                }
+                if pk[32..].iter().all(|b| *b == 0) {
+                    return Err(crate::Error::DegenerateT1);
+                }
+                Ok(epk)
+            }
+        }
+
+
+        // ----- SUBJECTPUBLICKEYINFO (SPKI) DER ---
+
+        /// Length in bytes of the DER-encoded `SubjectPublicKeyInfo` of a public key.
+        pub const SPKI_LEN: usize = spki::PREFIX_LEN + PK_LEN;
+
+
+        impl PublicKey {
+            /// Encodes the public key as a DER `SubjectPublicKeyInfo`, with the NIST ML-DSA
+            /// algorithm identifier (and absent parameters) per the IETF X.509 profile.
+            #[must_use]
+            pub fn to_spki_der(&self) -> [u8; SPKI_LEN] {
+                let mut der = [0u8; SPKI_LEN];
+                der[..spki::PREFIX_LEN].copy_from_slice(&spki::prefix(PARAM_SET));
+                der[spki::PREFIX_LEN..].copy_from_slice(&self.clone().into_bytes());
+                der
+            }
+
+
+            /// Parses a DER `SubjectPublicKeyInfo` and expands the embedded public key in one
+            /// call, borrowing the key bytes directly from `der` rather than first copying them
+            /// into an intermediate byte array.
+            ///
+            /// # Errors
+            /// Returns an error when `der` is not exactly the SPKI of a public key of this
+            /// parameter set, or when the public key is malformed.
+            ///
+            /// # Examples
+            /// ```rust
+            /// # use std::error::Error;
+            /// # fn main() -> Result<(), Box<dyn Error>> {
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+            /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+            /// use fips204::traits::{Signer, Verifier};
+            ///
+            /// let (pk, sk) = ml_dsa_44::try_keygen()?;
+            /// let der = pk.to_spki_der(); // e.g., as found in a certificate
+            ///
+            /// let pk2 = ml_dsa_44::PublicKey::try_from_spki_der(&der)?;
+            /// let sig = sk.try_sign(&[0u8, 1, 2, 3], &[])?;
+            /// assert!(pk2.verify(&[0u8, 1, 2, 3], &sig, &[]));
+            /// # }
+            /// # Ok(())}
+            /// ```
+            pub fn try_from_spki_der(der: &[u8]) -> Result<Self, &'static str> {
+                helpers::ensure!(der.len() == SPKI_LEN, "SPKI: wrong length");
+                let (prefix, pk) = der.split_at(spki::PREFIX_LEN);
+                helpers::ensure!(prefix == spki::prefix(PARAM_SET), "SPKI: not an SPKI for this parameter set");
+                let pk: &[u8; PK_LEN] = pk.try_into().map_err(|_| "SPKI: wrong length")?;
+                ml_dsa::expand_public(pk)
+            }
+        }
+
+
+        // ----- CBOR (COSE_KEY) ENCODING ---
+
+        /// The (provisional) COSE algorithm identifier of this parameter set, per the IETF COSE
+        /// ML-DSA draft.
+        #[cfg(feature = "cbor")]
+        pub const COSE_ALG: i32 = cbor::cose_alg(PARAM_SET);
+
+        /// Length in bytes of the deterministic CBOR `COSE_Key` encoding of a public key.
+        #[cfg(feature = "cbor")]
+        pub const COSE_KEY_LEN: usize = cbor::KEY_PREFIX_LEN + PK_LEN;
+
+        /// Length in bytes of the deterministic CBOR encoding of a signature (a byte string).
+        #[cfg(feature = "cbor")]
+        pub const CBOR_SIG_LEN: usize = cbor::SIG_PREFIX_LEN + SIG_LEN;
+
+
+        #[cfg(feature = "cbor")]
+        impl PublicKey {
+            /// Encodes the public key as a deterministic CBOR `COSE_Key` map,
+            /// `{1: 7 (kty: AKP), 3: COSE_ALG, -1: pk}`, for CTAP2/WebAuthn authenticators.
+            ///
+            /// # Examples
+            /// ```rust
+            /// # use std::error::Error;
+            /// # fn main() -> Result<(), Box<dyn Error>> {
+            /// # #[cfg(all(feature = "ml-dsa-44", feature = "cbor", feature = "default-rng"))] {
+            /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+            /// use fips204::traits::{Signer, Verifier};
+            ///
+            /// let (pk, sk) = ml_dsa_44::try_keygen()?;
+            /// let cose_key = pk.to_cose_key(); // e.g., in the attested credential data
+            /// let sig = ml_dsa_44::sig_to_cbor(&sk.try_sign(b"client data", &[])?);
+            ///
+            /// let pk2 = ml_dsa_44::PublicKey::try_from_cose_key(&cose_key)?;
+            /// assert!(pk2.verify(b"client data", &ml_dsa_44::try_sig_from_cbor(&sig)?, &[]));
+            /// # }
+            /// # Ok(())}
+            /// ```
+            #[must_use]
+            pub fn to_cose_key(&self) -> [u8; COSE_KEY_LEN] {
+                let mut cbor = [0u8; COSE_KEY_LEN];
+                cbor[..cbor::KEY_PREFIX_LEN].copy_from_slice(&cbor::key_prefix(PARAM_SET));
+                cbor[cbor::KEY_PREFIX_LEN..].copy_from_slice(&self.clone().into_bytes());
+                cbor
+            }
+
+
+            /// Parses a deterministic CBOR `COSE_Key` map (see [`PublicKey::to_cose_key()`]) and
+            /// expands the embedded public key.
+            ///
+            /// # Errors
+            /// Returns an error when `cbor` is not exactly the deterministic `COSE_Key` encoding of
+            /// a public key of this parameter set, or when the public key is malformed.
+            pub fn try_from_cose_key(cbor: &[u8]) -> Result<Self, &'static str> {
+                helpers::ensure!(cbor.len() == COSE_KEY_LEN, "CBOR: wrong length");
+                let (prefix, pk) = cbor.split_at(cbor::KEY_PREFIX_LEN);
+                helpers::ensure!(prefix == cbor::key_prefix(PARAM_SET), "CBOR: not a COSE_Key for this parameter set");
+                let pk: &[u8; PK_LEN] = pk.try_into().map_err(|_| "CBOR: wrong length")?;
+                ml_dsa::expand_public(pk)
+            }
+        }
+
+
+        /// Encodes the signature as a deterministic CBOR byte string, as carried in CTAP2
+        /// responses and COSE structures.
+        #[cfg(feature = "cbor")]
+        #[must_use]
+        pub fn sig_to_cbor(sig: &[u8; SIG_LEN]) -> [u8; CBOR_SIG_LEN] {
+            let mut cbor = [0u8; CBOR_SIG_LEN];
+            cbor[..cbor::SIG_PREFIX_LEN].copy_from_slice(&cbor::sig_prefix(PARAM_SET));
+            cbor[cbor::SIG_PREFIX_LEN..].copy_from_slice(sig);
+            cbor
+        }
+
+
+        /// Parses a deterministic CBOR byte string holding a signature of this parameter set.
+        ///
+        /// # Errors
+        /// Returns an error when `cbor` is not exactly the deterministic encoding of a byte
+        /// string of `SIG_LEN` bytes.
+        #[cfg(feature = "cbor")]
+        pub fn try_sig_from_cbor(cbor: &[u8]) -> Result<[u8; SIG_LEN], &'static str> {
+            helpers::ensure!(cbor.len() == CBOR_SIG_LEN, "CBOR: wrong length");
+            let (prefix, sig) = cbor.split_at(cbor::SIG_PREFIX_LEN);
+            helpers::ensure!(prefix == cbor::sig_prefix(PARAM_SET), "CBOR: not a signature for this parameter set");
+            sig.try_into().map_err(|_| "CBOR: wrong length")
+        }
+
+
+        // ----- SERIALIZATION AND DESERIALIZATION ---
+
+        impl SerDes for PrivateKey {
+            type ByteArray = [u8; SK_LEN];
+
+
+            fn try_from_bytes(sk: Self::ByteArray) -> Result<Self, &'static str> {
+                let esk = ml_dsa::expand_private::<K, L, SK_LEN>(ETA, &sk)?;
+                Ok(esk)
+            }
+
+
+            fn into_bytes(self) -> Self::ByteArray {
+                // Extract the pre-computes
+                let PrivateKey {rho, cap_k, tr, s_1_hat_mont: s_hat_1_mont, s_2_hat_mont: s_hat_2_mont, t_0_hat_mont: t_hat_0_mont, ..} = &self;
+
+                // mont->norm each n coeff, of L entries of T, then inverse NTT
+                let s_1: [types::R; L] = ntt::inv_ntt(&helpers::from_mont(s_hat_1_mont));
+                // correct each coeff such that they are centered around 0
+                let s_1: [types::R; L] =
+                    core::array::from_fn(|l|
+                        types::R(core::array::from_fn(|n|
+                            if s_1[l].0[n] > (Q / 2) {s_1[l].0[n] - Q} else {s_1[l].0[n]})));
+
+                let s_2: [types::R; K] = ntt::inv_ntt(&helpers::from_mont(s_hat_2_mont));
+                let s_2: [types::R; K] =
+                    core::array::from_fn(|k|
+                        types::R(core::array::from_fn(|n|
+                            if s_2[k].0[n] > (Q / 2) {s_2[k].0[n] - Q} else {s_2[k].0[n]})));
+
+
+                let t_0: [types::R; K] = ntt::inv_ntt(&helpers::from_mont(t_hat_0_mont));
+                let t_0: [types::R; K] =
+                    core::array::from_fn(|k|
+                        types::R(core::array::from_fn(|n|
+                            if t_0[k].0[n] > (Q / 2) {t_0[k].0[n] - Q} else {t_0[k].0[n]})));
+
+                // Encode and return
+                encodings::sk_encode::<K, L, SK_LEN>(ETA, rho, cap_k, tr, &s_1, &s_2, &t_0)
+            }
+        }
+
+
+        impl SerDes for PublicKey {
+            type ByteArray = [u8; PK_LEN];
+
+
+            fn try_from_bytes(pk: Self::ByteArray) -> Result<Self, &'static str> {
+                let epk = ml_dsa::expand_public(&pk)?;
+                Ok(epk)
+
+            }
+
+
+            fn into_bytes(self) -> Self::ByteArray {
+                // Extract the pre-computes
+                let PublicKey {rho, tr: _tr, t1_d2_hat_mont} = &self;
+
+                // reconstruct t1_d2 then t1
+                let t1_d2: [types::R; K] = ntt::inv_ntt(&helpers::from_mont(t1_d2_hat_mont));
+
+                let t1: [types::R; K] = core::array::from_fn(|k|
+                    types::R(core::array::from_fn(|n|
+                        t1_d2[k].0[n] >> D)));
+
+                encodings::pk_encode(rho, &t1)
+             }
+        }
+
+
+        // ----- STANDARD CONVERSION TRAITS ---
+
+        // Note: the keys are held in expanded (NTT) form, so there is no byte encoding to
+        // reference; `AsRef<[u8]>` is not implemented, and the conversions to bytes below
+        // re-encode the key without consuming it.
+
+        impl TryFrom<&[u8]> for PublicKey {
+            type Error = &'static str;
+
+            /// Deserializes (and expands) a public key from a slice of exactly `PK_LEN` bytes.
+            fn try_from(pk: &[u8]) -> Result<Self, Self::Error> {
+                Self::try_from_bytes(pk.try_into().map_err(|_| "PublicKey: wrong length")?)
+            }
+        }
+
+
+        impl TryFrom<&[u8]> for PrivateKey {
+            type Error = &'static str;
+
+            /// Deserializes (and expands) a private key from a slice of exactly `SK_LEN` bytes.
+            fn try_from(sk: &[u8]) -> Result<Self, Self::Error> {
+                let mut bytes: [u8; SK_LEN] = sk.try_into().map_err(|_| "PrivateKey: wrong length")?;
+                let result = Self::try_from_bytes(bytes);
+                bytes.zeroize();
+                result
+            }
+        }
+
+
+        impl From<&PublicKey> for [u8; PK_LEN] {
+            fn from(pk: &PublicKey) -> Self { pk.clone().into_bytes() }
+        }
+
+
+        impl From<&PrivateKey> for [u8; SK_LEN] {
+            fn from(sk: &PrivateKey) -> Self { sk.duplicate().into_bytes() }
+        }
+
+
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+            use crate::types::Ph;
+            use rand_chacha::rand_core::SeedableRng;
+
+            #[test]
+            fn smoke_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let message1 = [0u8, 1, 2, 3, 4, 5, 6, 7];
+                let message2 = [7u8, 7, 7, 7, 7, 7, 7, 7];
+
+                for _i in 0..32 {
+                    let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                    let sig = sk.try_sign_with_rng(&mut rng, &message1, &[]).unwrap();
+                    assert!(pk.verify(&message1, &sig, &[]));
+                    assert!(!pk.verify(&message2, &sig, &[]));
+                    for ph in [Ph::SHA256, Ph::SHA512, Ph::SHAKE128] {
+                        let sig = sk.try_hash_sign_with_rng(&mut rng, &message1, &[], &ph).unwrap();
+                        let v = pk.hash_verify(&message1, &sig, &[], &ph);
+                        assert!(v);
+                    }
+                    assert_eq!(pk.clone().into_bytes(), sk.get_public_key().into_bytes());
+                }
+            }
+
+
+            #[test]
+            fn screen_signature_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let sig = sk.try_sign_with_rng(&mut rng, b"msg", &[]).unwrap();
+                assert!(screen_signature(&sig).is_ok());
+
+                // z coefficients are encoded as γ1 − z, so zero bytes give z = γ1 (too large)
+                let mut big_z = sig;
+                big_z[LAMBDA_DIV4..LAMBDA_DIV4 + 8].fill(0);
+                // Decreasing hint counts are malformed
+                let mut bad_hint = sig;
+                bad_hint[SIG_LEN - K] = bad_hint[SIG_LEN - K + 1].wrapping_add(1);
+                for bad in [big_z, bad_hint] {
+                    assert!(screen_signature(&bad).is_err());
+                    assert!(!pk.verify(b"msg", &bad, &[]));
+                }
+
+                // Structurally sound but unrelated content passes screening, yet fails verification
+                let mut tampered = sig;
+                tampered[0] ^= 1;
+                assert!(screen_signature(&tampered).is_ok());
+                assert!(!pk.verify(b"msg", &tampered, &[]));
+            }
+
+
+            #[test]
+            fn canonicalize_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let message = [0u8, 1, 2, 3, 4, 5, 6, 7];
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let sig = sk.try_sign_with_rng(&mut rng, &message, &[]).unwrap();
+                assert!(is_canonical_signature(&sig));
+                assert_eq!(canonicalize_signature(&sig).unwrap(), sig);
+
+                // Swap the first two hint indices of the first polynomial holding two or more
+                let omega = usize::try_from(OMEGA).unwrap();
+                let hints = SIG_LEN - omega - K;
+                let mut first = 0;
+                let i = (0..K).find(|&i| {
+                    let count = usize::from(sig[hints + omega + i]) - first;
+                    first = usize::from(sig[hints + omega + i]);
+                    count >= 2
+                });
+                if let Some(i) = i {
+                    let first = if i == 0 { 0 } else { usize::from(sig[hints + omega + i - 1]) };
+                    let mut swapped = sig;
+                    swapped.swap(hints + first, hints + first + 1);
+                    assert!(!is_canonical_signature(&swapped));
+                    assert!(!pk.verify(&message, &swapped, &[]));
+                    assert_eq!(canonicalize_signature(&swapped).unwrap(), sig);
+                }
+
+                // Non-zero padding (when there is any) is cleared
+                let used = usize::from(sig[SIG_LEN - 1]);
+                if used < omega {
+                    let mut padded = sig;
+                    padded[hints + omega - 1] = 0x01;
+                    assert!(!is_canonical_signature(&padded));
+                    assert_eq!(canonicalize_signature(&padded).unwrap(), sig);
+                }
+
+                // Decreasing counts have no canonical form
+                let mut bad = sig;
+                bad[SIG_LEN - 1] = 0;
+                bad[SIG_LEN - 2] = 1;
+                assert!(canonicalize_signature(&bad).is_err());
+            }
+
+
+            #[cfg(feature = "diagnostics")]
+            #[test]
+            fn diagnostic_test() {
+                use crate::diagnostics::VerifyFailure;
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let message = [0u8, 1, 2, 3, 4, 5, 6, 7];
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let sig = sk.try_sign_with_rng(&mut rng, &message, &[]).unwrap();
+                let hints = SIG_LEN - K - OMEGA as usize; // Start of the hint encoding
+
+                assert_eq!(pk.verify_diagnostic(&message, &sig, &[]), Ok(()));
+                assert_eq!(pk.verify_diagnostic(&message, &sig, &[0u8; 256]), Err(VerifyFailure::ContextTooLong));
+                assert_eq!(pk.verify_diagnostic(&message, &sig, &[1]), Err(VerifyFailure::CTildeMismatch));
+
+                let mut bad = sig;
+                bad[LAMBDA_DIV4..LAMBDA_DIV4 + 256].fill(0xFF);
+                assert_eq!(pk.verify_diagnostic(&message, &bad, &[]), Err(VerifyFailure::ZNormTooLarge));
+
+                let mut bad = sig;
+                bad[SIG_LEN - 1] = u8::try_from(OMEGA + 1).unwrap();
+                assert_eq!(pk.verify_diagnostic(&message, &bad, &[]), Err(VerifyFailure::HintCountExceeded));
+
+                let mut bad = sig;
+                bad[SIG_LEN - K..].fill(0);
+                bad[hints] = 1; // non-zero padding
+                assert_eq!(pk.verify_diagnostic(&message, &bad, &[]), Err(VerifyFailure::DecodeFailure));
+                assert!(!pk.verify(&message, &bad, &[]));
+            }
+
+
+            #[cfg(all(feature = "unstable", not(feature = "fips-mode")))]
+            #[test]
+            fn external_rho_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let rho = [0x5au8; 32];
+                let (pk1, sk1) = try_keygen_with_rho_with_rng(&mut rng, &rho).unwrap();
+                let (pk2, _sk2) = try_keygen_with_rho_with_rng(&mut rng, &rho).unwrap();
+                let (pk1_bytes, pk2_bytes) = (pk1.clone().into_bytes(), pk2.into_bytes());
+                assert_eq!(pk1_bytes[..32], rho);
+                assert_eq!(pk2_bytes[..32], rho);
+                assert_ne!(pk1_bytes, pk2_bytes);
+
+                // Keys are otherwise ordinary and survive serialization
+                let sk1 = PrivateKey::try_from_bytes(sk1.into_bytes()).unwrap();
+                let sig = sk1.try_sign_with_rng(&mut rng, &[1, 2, 3], &[]).unwrap();
+                assert!(PublicKey::try_from_bytes(pk1_bytes).unwrap().verify(&[1, 2, 3], &sig, &[]));
+
+                // Supplying the rho derived from xi reproduces standard key generation
+                let (pk, _sk) = KG::keygen_from_seed(&[7u8; 32]);
+                let pk_bytes = pk.into_bytes();
+                let (pk3, _sk3) = keygen_from_seed_and_rho(&[7u8; 32], &pk_bytes[..32].try_into().unwrap());
+                assert_eq!(pk3.into_bytes(), pk_bytes);
+            }
+
+
+            #[test]
+            fn wire_sizes_test() {
+                // Generic code sizes its buffers from the traits alone
+                fn sign_into_buf<S: Signer>(sk: &S, msg: &[u8]) -> ([u8; 8192], usize)
+                where S::Signature: AsRef<[u8]> {
+                    let mut buf = [0u8; 8192];
+                    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                    let sig = sk.try_sign_with_rng(&mut rng, msg, &[]).unwrap();
+                    buf[..S::SIG_LEN].copy_from_slice(sig.as_ref());
+                    (buf, S::SIG_LEN)
+                }
+
+                assert_eq!((KG::PK_LEN, KG::SK_LEN, KG::SIG_LEN), (PK_LEN, SK_LEN, SIG_LEN));
+                assert_eq!((PrivateKey::SK_LEN, PrivateKey::SIG_LEN), (SK_LEN, SIG_LEN));
+                assert_eq!((PublicKey::PK_LEN, PublicKey::SIG_LEN), (PK_LEN, SIG_LEN));
+                assert_eq!(PARAM_SET.sig_len(), <PublicKey as Verifier>::SIG_LEN);
+
+                let (pk, sk) = KG::keygen_from_seed(&[7u8; 32]);
+                let (buf, len) = sign_into_buf(&sk, &[1, 2, 3]);
+                assert!(pk.verify(&[1, 2, 3], &buf[..len].try_into().unwrap(), &[]));
+            }
+
+
+            #[test]
+            fn in_place_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                for msg_len in [0, 1, 100] {
+                    let mut buf = [0xa5u8; 100 + SIG_LEN + 3];
+                    sk.try_sign_in_place_with_rng(&mut rng, &mut buf, msg_len, &[9]).unwrap();
+                    assert!(buf[..msg_len].iter().all(|b| *b == 0xa5)); // Message untouched
+                    assert!(buf[msg_len + SIG_LEN..].iter().all(|b| *b == 0xa5)); // As is the remainder
+                    let sig: [u8; SIG_LEN] = buf[msg_len..msg_len + SIG_LEN].try_into().unwrap();
+                    assert!(pk.verify(&buf[..msg_len], &sig, &[9]));
+                    assert!(pk.verify_in_place(&buf, msg_len, &[9]));
+                    assert!(!pk.verify_in_place(&buf, msg_len, &[8]));
+                    assert!(!pk.verify_in_place(&buf[..msg_len + SIG_LEN - 1], msg_len, &[9]));
+                }
+                let mut short = [0u8; SIG_LEN + 9];
+                assert!(sk.try_sign_in_place_with_rng(&mut rng, &mut short, 10, &[]).is_err());
+                assert!(sk.try_sign_in_place_with_rng(&mut rng, &mut short, usize::MAX, &[]).is_err());
+                assert!(!pk.verify_in_place(&short, usize::MAX, &[]));
+            }
+
+
+            #[test]
+            fn chunked_verify_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let message = [0x5au8; 1000];
+                let sig = sk.try_sign_with_rng(&mut rng, &message, &[1, 2]).unwrap();
+
+                for chunk_len in [1, 7, 64, Z_POLY_LEN, SIG_LEN] {
+                    let mut verifier = pk.try_verify_chunked(&[1, 2]).unwrap();
+                    for (msg_chunk, sig_chunk) in message.chunks(chunk_len).zip(sig.chunks(chunk_len)) {
+                        verifier.update_message(msg_chunk);
+                        assert!(verifier.update_signature(sig_chunk));
+                    }
+                    // Whichever of the two is longer is completed on its own
+                    let pairs = message.chunks(chunk_len).zip(sig.chunks(chunk_len)).count();
+                    message.chunks(chunk_len).skip(pairs).for_each(|chunk| verifier.update_message(chunk));
+                    sig.chunks(chunk_len).skip(pairs).for_each(|chunk| assert!(verifier.update_signature(chunk)));
+                    assert!(verifier.clone().verify());
+                    assert!(!verifier.update_signature(&[0]));
+                    assert!(!verifier.verify());
+                }
+
+                // Incomplete, altered, or with a different context
+                let mut verifier = pk.try_verify_chunked(&[1, 2]).unwrap();
+                verifier.update_message(&message);
+                assert!(verifier.update_signature(&sig[..SIG_LEN - 1]));
+                assert!(!verifier.verify());
+                for i in [0, LAMBDA_DIV4 + 3, SIG_LEN - 1] {
+                    let mut bad = sig;
+                    bad[i] ^= 1;
+                    let mut verifier = pk.try_verify_chunked(&[1, 2]).unwrap();
+                    verifier.update_message(&message);
+                    let _accepted = verifier.update_signature(&bad);
+                    assert!(!verifier.verify());
+                }
+                let mut verifier = pk.try_verify_chunked(&[1]).unwrap();
+                verifier.update_message(&message);
+                assert!(verifier.update_signature(&sig));
+                assert!(!verifier.verify());
+                assert!(pk.try_verify_chunked(&[0u8; 256]).is_err());
+            }
+
+
+            #[test]
+            fn stepwise_verify_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let message = [0x5au8; 10_000];
+                let sig = sk.try_sign_with_rng(&mut rng, &message, &[1]).unwrap();
+
+                let mut verifier = pk.try_verify_stepwise(&message, &sig, &[1]).unwrap();
+                let mut steps = 1;
+                while verifier.step().is_none() {
+                    steps += 1;
+                }
+                assert_eq!(verifier.step(), Some(true));
+                let expected = (message.len() + STEP_MESSAGE_LEN - 1) / STEP_MESSAGE_LEN + (SIG_LEN + Z_POLY_LEN - 1) / Z_POLY_LEN + 1;
+                assert_eq!(steps, expected);
+
+                let mut bad = sig;
+                bad[LAMBDA_DIV4 + 3] ^= 1;
+                let mut verifier = pk.try_verify_stepwise(&message, &bad, &[1]).unwrap();
+                assert_eq!(core::iter::from_fn(|| Some(verifier.step())).flatten().next(), Some(false));
+                let mut verifier = pk.try_verify_stepwise(&[], &sig, &[1]).unwrap();
+                assert_eq!(core::iter::from_fn(|| Some(verifier.step())).flatten().next(), Some(false));
+                assert!(pk.try_verify_stepwise(&message, &sig, &[0u8; 256]).is_err());
+            }
+
+
+            #[cfg(feature = "std")]
+            #[test]
+            fn stepwise_verify_future_test() {
+                use core::future::Future;
+                use core::sync::atomic::{AtomicUsize, Ordering};
+                use std::sync::Arc;
+                use std::task::{Context, Poll, Wake, Waker};
+
+                struct CountingWaker(AtomicUsize);
+                impl Wake for CountingWaker {
+                    fn wake(self: Arc<Self>) { let _prior = self.0.fetch_add(1, Ordering::Relaxed); }
+                }
+
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let sig = sk.try_sign_with_rng(&mut rng, b"msg", &[]).unwrap();
+                let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+                let waker = Waker::from(Arc::clone(&counter));
+                let mut cx = Context::from_waker(&waker);
+                let mut verifier = pk.try_verify_stepwise(b"msg", &sig, &[]).unwrap();
+                let mut polls = 1;
+                while core::pin::Pin::new(&mut verifier).poll(&mut cx) == Poll::Pending {
+                    polls += 1;
+                }
+                assert_eq!(counter.0.load(Ordering::Relaxed), polls - 1); // Woken before each yield
+                assert!(polls > L);
+            }
+
+
+            #[test]
+            fn stream_verify_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let message = [0x5au8; 1000];
+                let sig = sk.try_sign_with_rng(&mut rng, &message, &[1, 2]).unwrap();
+
+                for chunk_len in [1, 7, 64, 1000] {
+                    let mut verifier = pk.try_verify_stream(&[1, 2]).unwrap();
+                    message.chunks(chunk_len).for_each(|chunk| verifier.update(chunk));
+                    assert!(verifier.clone().verify(&sig));
+                    verifier.update(&[0]);
+                    assert!(!verifier.verify(&sig));
+                }
+                let mut verifier = pk.try_verify_stream(&[1]).unwrap();
+                verifier.update(&message);
+                assert!(!verifier.verify(&sig));
+                assert!(pk.try_verify_stream(&[0u8; 256]).is_err());
+            }
+
+
+            #[cfg(feature = "std")]
+            #[test]
+            fn verify_file_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let image: std::vec::Vec<u8> = (0..3_000_000u32).map(|i| i.to_le_bytes()[0]).collect();
+                let sig = sk.try_sign_with_rng(&mut rng, &image, &[]).unwrap();
+                let path = std::env::temp_dir().join(std::format!("fips204-verify-file-{PK_LEN}.bin"));
+                std::fs::write(&path, &image).unwrap();
+
+                let mut calls = std::vec::Vec::new();
+                assert!(pk.verify_file(&path, &sig, &[], |done, total| calls.push((done, total))).unwrap());
+                assert_eq!(calls.len(), 3);
+                assert_eq!(calls[2], (3_000_000, 3_000_000));
+                assert!(calls.windows(2).all(|w| w[0].0 < w[1].0));
+                assert!(!pk.verify_file(&path, &sig, &[1], |_, _| {}).unwrap());
+                std::fs::remove_file(&path).unwrap();
+                assert!(pk.verify_file(&path, &sig, &[], |_, _| {}).is_err());
+            }
+
+
+            #[test]
+            fn tr_test() {
+                use sha3::digest::XofReader;
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let mut expected = [0u8; 64];
+                hashing::h256_xof(&[&pk.clone().into_bytes()]).read(&mut expected);
+                assert_eq!(pk.tr(), expected);
+                assert_eq!(sk.get_public_key().tr(), expected);
+                assert_eq!(PublicKey::try_from_bytes(pk.into_bytes()).unwrap().tr(), expected);
+                assert_eq!(sk.into_bytes()[64..128], expected);
+            }
+
+
+            #[test]
+            fn validated_public_key_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let pk_bytes = pk.into_bytes();
+                let pk = PublicKey::try_from_bytes_validated(pk_bytes).unwrap();
+                let sig = sk.try_sign_with_rng(&mut rng, &[1], &[]).unwrap();
+                assert!(pk.verify(&[1], &sig, &[]));
+
+                let mut bad = pk_bytes;
+                bad[..32].fill(0x42);
+                assert_eq!(PublicKey::try_from_bytes_validated(bad).err(), Some(crate::Error::DegenerateRho));
+                let mut bad = pk_bytes;
+                bad[32..].fill(0);
+                assert_eq!(PublicKey::try_from_bytes_validated(bad).err(), Some(crate::Error::DegenerateT1));
+                assert!(PublicKey::try_from_bytes(bad).is_ok()); // Plain deserialization is unchanged
+            }
+
+
+            #[test]
+            fn fingerprint_test() {
+                use sha3::digest::XofReader;
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let (pk2, _sk2) = try_keygen_with_rng(&mut rng).unwrap();
+                let mut expected = [0u8; 32];
+                hashing::h256_xof(&[&pk.clone().into_bytes()]).read(&mut expected);
+                assert_eq!(sk.public_fingerprint(), expected);
+                assert_eq!(pk.fingerprint(), expected);
+                assert_ne!(pk2.fingerprint(), expected);
+                let sk = PrivateKey::try_from_bytes(sk.into_bytes()).unwrap();
+                assert_eq!(sk.public_fingerprint(), expected);
+            }
+
+
+            #[test]
+            fn keypair_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let keypair = Keypair::try_generate_with_rng(&mut rng).unwrap();
+                let sig = keypair.try_sign_with_rng(&mut rng, b"msg", b"ctx").unwrap();
+                assert!(keypair.verify_own(b"msg", &sig, b"ctx"));
+                assert!(keypair.public().verify(b"msg", &sig, b"ctx"));
+                assert!(!keypair.verify_own(b"msg", &sig, b"other ctx"));
+
+                let (pk_bytes, sk_bytes) = (keypair.public().clone().into_bytes(), keypair.private().duplicate().into_bytes());
+                let keypair2 = Keypair::try_from_bytes((pk_bytes, sk_bytes)).unwrap();
+                assert_eq!(keypair2.private().duplicate().into_bytes(), sk_bytes);
+
+                // Mixing keys from different key pairs is rejected
+                let other = Keypair::from_seed(&[9; 32]);
+                let other_sk_bytes = other.private().duplicate().into_bytes();
+                let (other_pk, _) = other.into_parts();
+                let (_, sk) = keypair.into_parts();
+                assert!(Keypair::try_from_parts(other_pk.clone(), sk.duplicate()).is_err());
+                assert!(Keypair::try_from_bytes((other_pk.clone().into_bytes(), sk_bytes)).is_err());
+
+                // As is a private key carrying the other key's rho and tr, but its own s1 and s2
+                let mut forged = sk_bytes;
+                forged[..128].copy_from_slice(&other_sk_bytes[..128]);
+                assert!(Keypair::try_from_bytes((other_pk.into_bytes(), forged)).is_err());
+                assert!(Keypair::try_from_parts(sk.get_public_key(), sk).is_ok());
+            }
+
+
+            #[test]
+            fn sealed_private_key_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let sealed: [u8; SK_LEN] = sk.into_bytes().map(|b| b ^ 0xA5);
+                let sealed_sk = SealedPrivateKey::new(sealed, |sealed, out| {
+                    *out = sealed.map(|b| b ^ 0xA5);
+                    Ok(())
+                });
+                let sig = sealed_sk.try_sign_with_rng(&mut rng, b"msg", b"ctx").unwrap();
+                assert!(pk.verify(b"msg", &sig, b"ctx"));
+                let pk2 = sealed_sk.try_with_private_key(Signer::get_public_key).unwrap();
+                assert_eq!(pk2.into_bytes(), pk.into_bytes());
+
+                let failing = SealedPrivateKey::new((), |(), _out| Err("unseal failed"));
+                assert_eq!(failing.try_sign_with_rng(&mut rng, b"msg", b"ctx"), Err("unseal failed"));
+            }
+
+
+            #[test]
+            fn sign_iterations_test() {
+                assert!((sign_iterations_tail(0) - 1.0).abs() < f64::EPSILON);
+                assert!(sign_iterations_tail(10) > sign_iterations_tail(11));
+                assert_eq!(sign_iterations_bound(1.0), 0);
+                assert_eq!(sign_iterations_bound(0.0), u32::MAX);
+                assert_eq!(sign_iterations_bound(f64::NAN), u32::MAX);
+                let n = sign_iterations_bound(1e-9);
+                assert!(sign_iterations_tail(n) <= 1e-9 && sign_iterations_tail(n - 1) > 1e-9);
+
+                // The observed mean is near the expected number of iterations
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let mut samples = [0u32; 200];
+                simulate_sign_iterations(&mut rng, &mut samples).unwrap();
+                assert!(samples.iter().all(|&n| n >= 1));
+                let mean = f64::from(samples.iter().sum::<u32>()) / 200.0;
+                assert!((mean - EXPECTED_SIGN_ITERATIONS).abs() < 1.5, "mean {mean}");
+            }
+
+
+            #[test]
+            fn params_test() {
+                const P: crate::Params = params(); // Available at compile time
+                assert_eq!((P.k, P.l, P.lambda), (K, L, LAMBDA));
+                assert_eq!((P.pk_len, P.sk_len, P.sig_len), (PARAM_SET.pk_len(), PARAM_SET.sk_len(), PARAM_SET.sig_len()));
+                assert_eq!(P.beta, P.tau * P.eta);
+                assert_eq!(P.sig_len, P.lambda / 4 + P.l * 32 * helpers::bit_length(2 * GAMMA1 - 1) + P.omega as usize + P.k);
+                assert_eq!((P.q - 1) % (2 * P.gamma2), 0);
+            }
+
+
+            #[test]
+            fn keygen_into_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                for xi in [[0u8; 32], [0x5Au8; 32]] {
+                    let (mut pk_bytes, mut sk_bytes) = ([0xFFu8; PK_LEN], [0xFFu8; SK_LEN]);
+                    keygen_from_seed_into(&xi, &mut pk_bytes, &mut sk_bytes);
+                    let (pk, sk) = KG::keygen_from_seed(&xi);
+                    assert_eq!(pk_bytes, pk.into_bytes());
+                    assert_eq!(sk_bytes, sk.into_bytes());
+                    let (mut pk_try, mut sk_try) = ([0u8; PK_LEN], [0u8; SK_LEN]);
+                    try_keygen_from_seed_into(&xi, &mut pk_try, &mut sk_try).unwrap();
+                    assert_eq!((pk_try, sk_try), (pk_bytes, sk_bytes));
+                }
+
+                let (mut pk_bytes, mut sk_bytes) = ([0u8; PK_LEN], [0u8; SK_LEN]);
+                try_keygen_into_with_rng(&mut rng, &mut pk_bytes, &mut sk_bytes).unwrap();
+                let sig = try_sign_from_bytes_with_rng(&mut rng, &sk_bytes, b"msg", b"ctx").unwrap();
+                assert!(pk_bytes.verify(b"msg", &sig, b"ctx"));
+                assert!(try_sign_from_bytes_with_rng(&mut rng, &[0xFFu8; SK_LEN], b"msg", b"ctx").is_err());
+            }
+
+
+            #[test]
+            fn encoded_private_key_signer_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let esk = EncodedPrivateKey::try_from_bytes(sk.duplicate().into_bytes()).unwrap();
+                assert_eq!(esk.get_public_key(), pk);
+                assert_eq!(*esk.as_bytes(), sk.duplicate().into_bytes());
+
+                // Each form consumes the same randomness, so produces the same signature
+                let contexts = [crate::Context::new(b"ctx"), crate::Context::new(b"ctx").with_hash(types::Ph::SHAKE128)];
+                for context in &contexts {
+                    let mut rng2 = rng.clone();
+                    let sig = esk.try_sign_with_context_and_rng(&mut rng, b"msg", context).unwrap();
+                    assert_eq!(sig, sk.try_sign_with_context_and_rng(&mut rng2, b"msg", context).unwrap());
+                    assert!(pk.verify_with_context(b"msg", &sig, context));
+                }
+                let mut rng2 = rng.clone();
+                let sig = esk.try_sign_msg_with_rng(&mut rng, "msg", crate::Ctx::new(b"ctx")).unwrap();
+                assert_eq!(sig, sk.try_sign_msg_with_rng(&mut rng2, "msg", crate::Ctx::new(b"ctx")).unwrap());
+                let mut rng2 = rng.clone();
+                let tagged = esk.try_sign_tagged_with_rng(&mut rng, b"msg", b"ctx").unwrap();
+                assert_eq!(tagged, sk.try_sign_tagged_with_rng(&mut rng2, b"msg", b"ctx").unwrap());
+                let deterministic = esk.try_sign_deterministic(b"msg", b"ctx").unwrap();
+                assert_eq!(deterministic, sk.try_sign_deterministic(b"msg", b"ctx").unwrap());
+
+                assert!(esk.try_sign_with_rng(&mut rng, b"msg", &[0u8; 256]).is_err());
+                assert!(esk.try_sign_deterministic(b"msg", &[0u8; 256]).is_err());
+
+                // Malformed or inconsistent encodings are refused up front
+                assert!(EncodedPrivateKey::try_from_bytes([0xFFu8; SK_LEN]).is_err());
+                let mut bad = sk.duplicate().into_bytes();
+                bad[SK_LEN - 1] ^= 0x01;
+                assert!(EncodedPrivateKey::try_from_bytes(bad).is_err());
+            }
+
+
+            #[cfg(feature = "std")]
+            #[test]
+            fn public_key_eq_hash_debug_test() {
+                use core::hash::{Hash, Hasher};
+                use std::collections::HashSet;
+
+                let (pk1, _sk1) = KG::keygen_from_seed(&[1u8; 32]);
+                let (pk2, sk2) = KG::keygen_from_seed(&[2u8; 32]);
+                assert_eq!(pk1, pk1.clone());
+                assert_eq!(pk2, sk2.get_public_key());
+                assert_eq!(pk2, PublicKey::try_from_bytes(pk2.clone().into_bytes()).unwrap());
+                assert_ne!(pk1, pk2);
+
+                // Keys differing only in `t1` are unequal
+                let mut pk3 = pk1.clone();
+                pk3.t1_d2_hat_mont[K - 1].0[255] ^= 1;
+                assert_ne!(pk1, pk3);
+
+                let hash = |pk: &PublicKey| {
+                    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                    pk.hash(&mut hasher);
+                    hasher.finish()
+                };
+                assert_eq!(hash(&pk2), hash(&sk2.get_public_key()));
+                let mut set = HashSet::new();
+                assert!(set.insert(pk1.clone()) && set.insert(pk2.clone()));
+                assert!(!set.insert(pk1.clone()));
+
+                let debug = std::format!("{pk1:?}");
+                assert!(debug.starts_with(&std::format!("PublicKey({PARAM_SET}, fingerprint ")));
+                assert!(debug.len() == "PublicKey(, fingerprint )".len() + PARAM_SET.name().len() + 64);
+            }
+
+
+            #[test]
+            fn signing_mode_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+
+                let mut rng2 = rng.clone();
+                let hedged = sk.try_sign_tagged_with_rng(&mut rng, b"msg", b"ctx").unwrap();
+                assert_eq!(hedged.mode(), crate::SigningMode::Hedged);
+                assert_eq!(*hedged.signature(), sk.try_sign_with_rng(&mut rng2, b"msg", b"ctx").unwrap());
+
+                // The deterministic variant is that of an all-zero `rnd`
+                let deterministic = sk.try_sign_deterministic(b"msg", b"ctx").unwrap();
+                assert_eq!(deterministic.mode(), crate::SigningMode::Deterministic);
+                assert_eq!(deterministic, sk.try_sign_deterministic(b"msg", b"ctx").unwrap());
+                let zero_rnd = ml_dsa::sign_internal::<CTEST, K, L, LAMBDA_DIV4, SIG_LEN, SK_LEN, W1_LEN>(
+                    BETA, GAMMA1, GAMMA2, OMEGA, TAU, &sk, b"msg", b"ctx", &[], &[], [0u8; 32], false
+                ).unwrap();
+                assert_eq!(*deterministic.signature(), zero_rnd);
+
+                let hashed = sk.try_hash_sign_deterministic(b"msg", b"ctx", &Ph::SHA256).unwrap();
+                assert_eq!(hashed.mode(), crate::SigningMode::Deterministic);
+                assert_eq!(hashed, sk.try_hash_sign_deterministic(b"msg", b"ctx", &Ph::SHA256).unwrap());
+                assert!(pk.hash_verify(b"msg", hashed.signature(), b"ctx", &Ph::SHA256));
+                assert!(sk.try_hash_sign_deterministic(b"msg", &[0u8; 256], &Ph::SHA256).is_err());
+                assert!(pk.verify(b"msg", deterministic.signature(), b"ctx"));
+
+                assert_eq!(hedged.clone().require_hedged(), Ok(hedged.into_signature()));
+                assert!(deterministic.require_hedged().is_err());
+                assert!(sk.try_sign_deterministic(b"msg", &[0u8; 256]).is_err());
+            }
+
+
+            #[test]
+            fn aux_rand_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let sign = |aux: &[u8; 32]| {
+                    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(456);
+                    sk.try_sign_with_aux_rand(&mut rng, aux, b"msg", b"ctx").unwrap()
+                };
+                let (sig1, sig2) = (sign(&[1u8; 32]), sign(&[2u8; 32]));
+                assert!(pk.verify(b"msg", &sig1, b"ctx") && pk.verify(b"msg", &sig2, b"ctx"));
+                assert_eq!(sig1, sign(&[1u8; 32]));
+                assert_ne!(sig1, sig2); // The auxiliary entropy alone changes rnd
+
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(456);
+                let plain = sk.try_sign_with_rng(&mut rng, b"msg", b"ctx").unwrap();
+                assert_ne!(plain, sig1);
+                assert!(sk.try_sign_with_aux_rand(&mut rng, &[1u8; 32], b"msg", &[0u8; 256]).is_err());
+            }
+
+
+            #[test]
+            fn duplicate_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let mut audited = [0u8; 64];
+                let sk2 = sk.duplicate_audited(|tr| audited = *tr);
+                assert_eq!(audited, pk.tr());
+                assert_eq!(sk.duplicate().into_bytes(), sk2.into_bytes());
+            }
+
+
+            #[test]
+            fn pq_signature_test() {
+                use crate::traits::PqSignature;
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = Scheme::try_keygen_with_rng(&mut rng).unwrap();
+                let sig = Scheme::try_sign_with_rng(&sk, &mut rng, b"msg", b"ctx").unwrap();
+                assert!(Scheme::verify(&pk, b"msg", &sig, b"ctx"));
+                assert!(!Scheme::verify(&pk, b"msg", &sig, b""));
+
+                let pk2 = Scheme::try_public_key_from_bytes(&Scheme::public_key_to_bytes(&pk)).unwrap();
+                let sk2 = Scheme::try_private_key_from_bytes(&Scheme::private_key_to_bytes(&sk)).unwrap();
+                let sig2 = Scheme::try_signature_from_bytes(&sig).unwrap();
+                assert!(Scheme::verify(&pk2, b"msg", &sig2, b"ctx"));
+                assert_eq!(sk2.into_bytes(), sk.into_bytes());
+                assert!(Scheme::try_signature_from_bytes(&sig[1..]).is_err());
+                assert!(Scheme::try_public_key_from_bytes(&[0u8; 3]).is_err());
+                assert_eq!(Scheme::ALGORITHM, PARAM_SET.name());
+            }
+
+
+            #[test]
+            fn digest_ph_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+
+                // The canned SHA-256 and SHA-512 match the fixed list, with the same randomness
+                let mut rng2 = rng.clone();
+                let sig = sk.try_hash_sign_digest_with_rng(&mut rng, b"msg", b"ctx", &types::DigestPh::sha512()).unwrap();
+                assert_eq!(sig, sk.try_hash_sign_with_rng(&mut rng2, b"msg", b"ctx", &Ph::SHA512).unwrap());
+                assert!(pk.hash_verify_digest(b"msg", &sig, b"ctx", &types::DigestPh::sha512()));
+                let sig = sk.try_hash_sign_with_rng(&mut rng, b"msg", b"ctx", &Ph::SHA256).unwrap();
+                assert!(pk.hash_verify_digest(b"msg", &sig, b"ctx", &types::DigestPh::sha256()));
+
+                // The OID is bound into the signature, as is the digest
+                let ph = types::DigestPh::sha3_384();
+                let sig = sk.try_hash_sign_digest_with_rng(&mut rng, b"msg", b"ctx", &ph).unwrap();
+                assert!(pk.hash_verify_digest(b"msg", &sig, b"ctx", &ph));
+                assert!(!pk.hash_verify_digest(b"msg", &sig, b"ctx", &types::DigestPh::sha384()));
+                let other_oid = types::DigestPh::<sha3::Sha3_384>::try_new(types::DigestPh::sha3_512().oid()).unwrap();
+                assert!(!pk.hash_verify_digest(b"msg", &sig, b"ctx", &other_oid));
+                assert!(!pk.hash_verify_digest(b"msg2", &sig, b"ctx", &ph));
+                assert!(!pk.verify(b"msg", &sig, b"ctx"));
+                assert!(sk.try_hash_sign_digest_with_rng(&mut rng, b"msg", &[0u8; 256], &ph).is_err());
+
+                for bad in [&[][..], &[0x06, 0x01], &[0x05, 0x01, 0x01], &[0x06, 0x02, 0x01], &[0x06, 0x01, 0x81]] {
+                    assert!(types::DigestPh::<sha2::Sha256>::try_new(bad).is_err());
+                }
+            }
+
+
+            #[test]
+            fn msg_ctx_test() {
+                use crate::{Context, Ctx, Msg};
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let ctx = Ctx::new(b"ctx");
+
+                // The same signature as the byte slice interface, from any message form
+                let mut rng2 = rng.clone();
+                let sig = sk.try_sign_msg_with_rng(&mut rng, b"msg", ctx).unwrap();
+                assert_eq!(sig, sk.try_sign_with_rng(&mut rng2, b"msg", b"ctx").unwrap());
+                assert!(pk.verify_msg(&b"msg"[..], &sig, ctx));
+                assert!(pk.verify_msg("msg", &sig, ctx));
+                assert!(pk.verify_msg(Msg::new(b"msg"), &sig, ctx));
+                assert!(pk.verify_with_context(b"msg", &sig, &Context::from(ctx)));
+                assert!(!pk.verify_msg(b"ctx", &sig, Ctx::new(b"msg")));
+                assert!(!pk.verify_msg(b"msg", &sig, Ctx::EMPTY));
+                assert!(sk.try_sign_msg_with_rng(&mut rng, b"msg", Ctx::new(&[0u8; 256])).is_err());
+            }
+
+
+            #[test]
+            fn conversion_traits_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let pk_bytes = <[u8; PK_LEN]>::from(&pk);
+                let sk_bytes = <[u8; SK_LEN]>::from(&sk);
+                assert_eq!(pk_bytes, pk.clone().into_bytes());
+                assert_eq!(sk_bytes, sk.duplicate().into_bytes());
+
+                let pk2 = PublicKey::try_from(&pk_bytes[..]).unwrap();
+                let sk2 = PrivateKey::try_from(&sk_bytes[..]).unwrap();
+                assert_eq!(pk2.into_bytes(), pk_bytes);
+                assert_eq!(sk2.into_bytes(), sk_bytes);
+                assert!(PublicKey::try_from(&pk_bytes[1..]).is_err());
+                assert!(PrivateKey::try_from(&[&sk_bytes[..], &[0u8]].concat()[..]).is_err());
+            }
+
+
+            #[test]
+            fn migration_test() {
+                let xi = [0x5Au8; 32];
+                let (_pk, sk) = KG::keygen_from_seed(&xi);
+                let encoded = seed_to_encoded(&xi);
+                assert_eq!(encoded, sk.duplicate().into_bytes());
+                assert_eq!(seed_to_expanded(&xi).into_bytes(), encoded);
+                assert_eq!(expanded_to_encoded(&try_encoded_to_expanded(&encoded).unwrap()), encoded);
+                assert!(seed_matches_encoded(&xi, &encoded));
+                assert!(!seed_matches_encoded(&[0u8; 32], &encoded));
+
+                // Well-formed but inconsistent encodings are rejected: K is free, but tr and t0
+                // (here its last coefficient) are determined by the rest of the key
+                for (pos, valid) in [(40, true), (100, false), (SK_LEN - 1, false)] {
+                    let mut modified = encoded;
+                    modified[pos] ^= 0x01;
+                    assert!(PrivateKey::try_from_bytes(modified).is_ok());
+                    assert_eq!(try_validate_encoded(&modified).is_ok(), valid);
+                    assert_eq!(try_encoded_to_expanded(&modified).is_ok(), valid);
+                }
+                assert!(try_validate_encoded(&[0xFFu8; SK_LEN]).is_err());
+            }
+
+
+            #[test]
+            fn try_load_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (mut pk_slot, mut sk_slot) = (PublicKeySlot::empty(), PrivateKeySlot::empty());
+                assert!(pk_slot.get().is_none() && sk_slot.get().is_none());
+                for _ in 0..2 {
+                    let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                    pk_slot.try_load(&pk.clone().into_bytes()).unwrap();
+                    sk_slot.try_load(&sk.duplicate().into_bytes()).unwrap();
+                    let (epk, esk) = (pk_slot.get().unwrap(), sk_slot.get().unwrap());
+                    assert_eq!((epk.rho, epk.tr), (pk.rho, pk.tr));
+                    assert!(epk.t1_d2_hat_mont.iter().zip(pk.t1_d2_hat_mont.iter()).all(|(a, b)| a.0 == b.0));
+                    assert_eq!((esk.rho, esk.cap_k, esk.tr), (sk.rho, sk.cap_k, sk.tr));
+                    assert!(esk.s_1_hat_mont.iter().zip(sk.s_1_hat_mont.iter()).all(|(a, b)| a.0 == b.0));
+                    assert!(esk.s_2_hat_mont.iter().zip(sk.s_2_hat_mont.iter()).all(|(a, b)| a.0 == b.0));
+                    assert!(esk.t_0_hat_mont.iter().zip(sk.t_0_hat_mont.iter()).all(|(a, b)| a.0 == b.0));
+                    let sig = esk.try_sign_with_rng(&mut rng, b"msg", b"ctx").unwrap();
+                    assert!(epk.verify(b"msg", &sig, b"ctx"));
+                }
+
+                // A malformed private key leaves the slot zeroized and empty
+                assert!(sk_slot.try_load(&[0xFFu8; SK_LEN]).is_err());
+                assert!(sk_slot.get().is_none());
+                assert_eq!((sk_slot.key.rho, sk_slot.key.cap_k), ([0u8; 32], [0u8; 32]));
+                assert!(sk_slot.key.s_1_hat_mont.iter().all(|s| s.0 == [0i32; 256]));
+
+                // Clearing leaves a slot empty
+                pk_slot.clear();
+                assert!(pk_slot.get().is_none());
+            }
+
+
+            #[test]
+            fn stepwise_expansion_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let mut slot = PublicKeySlot::empty();
+                for _ in 0..2 {
+                    let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                    let sig = sk.try_sign_with_rng(&mut rng, b"msg", b"ctx").unwrap();
+                    let pk_bytes = pk.clone().into_bytes();
+                    for from_private in [false, true] {
+                        let mut expansion =
+                            if from_private { sk.public_key_stepwise(&mut slot) } else { slot.load_stepwise(&pk_bytes) };
+                        let mut steps = 1;
+                        while expansion.step().is_none() {
+                            steps += 1;
+                        }
+                        assert_eq!(expansion.step(), Some(Ok(())));
+                        assert_eq!(steps, K + 1);
+                        let epk = slot.get().unwrap();
+                        assert_eq!((epk.rho, epk.tr), (pk.rho, pk.tr));
+                        assert!(epk.t1_d2_hat_mont.iter().zip(pk.t1_d2_hat_mont.iter()).all(|(a, b)| a.0 == b.0));
+                        assert!(epk.verify(b"msg", &sig, b"ctx"));
+                    }
+
+                    // An expansion abandoned part-way leaves the slot empty
+                    assert!(slot.load_stepwise(&pk_bytes).step().is_none());
+                    assert!(slot.get().is_none());
+                }
+            }
+
+
+            #[cfg(all(feature = "hazmat", not(feature = "fips-mode")))]
+            #[test]
+            fn hazmat_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let esk = hazmat::sign_start(&sk.clone().into_bytes()).unwrap();
+                let epk = hazmat::verify_start(&pk.clone().into_bytes()).unwrap();
+                assert_eq!(hazmat::private_key_tr(&esk), pk.tr());
+
+                for ph in [None, Some(&Ph::SHA512)] {
+                    let mu = crate::message::mu(&pk.tr(), b"msg", b"ctx", ph).unwrap();
+                    let sig = hazmat::sign_finish(&esk, &mu, [7u8; 32]);
+                    assert!(hazmat::verify_finish(&epk, &mu, &sig));
+                    assert!(!hazmat::verify_finish(&epk, &[0u8; 64], &sig));
+                    match ph {
+                        None => assert!(pk.verify(b"msg", &sig, b"ctx")),
+                        Some(ph) => assert!(pk.hash_verify(b"msg", &sig, b"ctx", ph)),
+                    }
+                }
+                let mu = crate::message::mu(&pk.tr(), b"msg", &[], None).unwrap();
+                let sig = ml_dsa::sign_internal::<CTEST, K, L, LAMBDA_DIV4, SIG_LEN, SK_LEN, W1_LEN>(
+                    BETA, GAMMA1, GAMMA2, OMEGA, TAU, &sk, b"msg", &[], &[], &[], [0u8; 32], false
+                )
+                .unwrap();
+                assert_eq!(hazmat::sign_finish(&esk, &mu, [0u8; 32]), sig);
+            }
+
+
+            #[cfg(all(feature = "hazmat", feature = "unstable", not(feature = "fips-mode")))]
+            #[test]
+            fn verify_residual_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let epk = hazmat::verify_start(&pk.clone().into_bytes()).unwrap();
+                let mu = crate::message::mu(&pk.tr(), b"msg", b"ctx", None).unwrap();
+                let sig = sk.try_sign_with_rng(&mut rng, b"msg", b"ctx").unwrap();
+
+                let res = hazmat::verify_residual(&epk, &mu, &sig).unwrap();
+                assert_eq!(res.c_tilde, sig[..LAMBDA_DIV4]);
+                assert_eq!(res.c_tilde, res.c_tilde_prime);
+                assert!(res.z_in_range);
+
+                // Another µ leaves w′_1 unchanged (it depends on the signature alone), but not c_tilde_′
+                let other = hazmat::verify_residual(&epk, &[0u8; 64], &sig).unwrap();
+                assert_eq!(other.w1_encoded, res.w1_encoded);
+                assert_ne!(other.c_tilde_prime, res.c_tilde_prime);
+                assert!(!hazmat::verify_finish(&epk, &[0u8; 64], &sig));
+
+                // A malformed hint is an error
+                let mut bad = sig;
+                bad[SIG_LEN - 1] = 0xFF;
+                assert!(hazmat::verify_residual(&epk, &mu, &bad).is_err());
+            }
+
+
+            #[test]
+            fn verify_any_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk1, sk1) = try_keygen_with_rng(&mut rng).unwrap();
+                let (pk2, sk2) = try_keygen_with_rng(&mut rng).unwrap();
+                let sig1 = sk1.try_sign_with_rng(&mut rng, b"msg", b"ctx").unwrap();
+                let sig2 = sk2.try_sign_with_rng(&mut rng, b"msg", b"ctx").unwrap();
+
+                // The first matching key is reported, as verifying with each in turn would
+                for (pks, expected) in [
+                    (&[&pk1, &pk2][..], [Some(0), Some(1)]),
+                    (&[&pk2, &pk1][..], [Some(1), Some(0)]),
+                    (&[&pk2, &pk2, &pk1, &pk1][..], [Some(2), Some(0)]),
+                    (&[&pk2][..], [None, Some(0)]),
+                    (&[][..], [None, None]),
+                ] {
+                    for (sig, expected) in [&sig1, &sig2].into_iter().zip(expected) {
+                        assert_eq!(verify_any(pks, b"msg", sig, b"ctx"), expected);
+                        let each = pks.iter().position(|pk| pk.verify(b"msg", sig, b"ctx"));
+                        assert_eq!(each, expected);
+                    }
+                }
+                assert_eq!(verify_any(&[&pk1, &pk2], b"msg", &sig1, b"other ctx"), None);
+                let mut bad = sig1;
+                bad[0] ^= 0x01;
+                assert_eq!(verify_any(&[&pk1, &pk2], b"msg", &bad, b"ctx"), None);
+            }
+
+
+            #[test]
+            fn pinned_verifier_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let (other_pk, other_sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let pinned = PinnedVerifier::from(&pk);
+                assert_eq!(pinned, PinnedVerifier::new(pk.fingerprint()));
+                assert!(pinned.is_pinned(&pk) && !pinned.is_pinned(&other_pk));
+
+                let sig = sk.try_sign_with_rng(&mut rng, b"msg", b"ctx").unwrap();
+                let other_sig = other_sk.try_sign_with_rng(&mut rng, b"msg", b"ctx").unwrap();
+                assert!(pinned.verify(&pk, b"msg", &sig, b"ctx"));
+                assert!(!pinned.verify(&pk, b"msg", &other_sig, b"ctx"));
+                assert!(other_pk.verify(b"msg", &other_sig, b"ctx"));
+                assert!(!pinned.verify(&other_pk, b"msg", &other_sig, b"ctx"));
+
+                let sig = sk.try_hash_sign_with_rng(&mut rng, b"msg", b"ctx", &Ph::SHA256).unwrap();
+                assert!(pinned.hash_verify(&pk, b"msg", &sig, b"ctx", &Ph::SHA256));
+                let sig = other_sk.try_hash_sign_with_rng(&mut rng, b"msg", b"ctx", &Ph::SHA256).unwrap();
+                assert!(!pinned.hash_verify(&other_pk, b"msg", &sig, b"ctx", &Ph::SHA256));
+            }
+
+
+            #[cfg(feature = "cbor")]
+            #[test]
+            fn cbor_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let cose_key = pk.to_cose_key();
+                assert_eq!(cose_key[cbor::KEY_PREFIX_LEN..], pk.clone().into_bytes());
+                let pk2 = PublicKey::try_from_cose_key(&cose_key).unwrap();
+                let sig = sk.try_sign_with_rng(&mut rng, b"msg", b"ctx").unwrap();
+                let cbor_sig = sig_to_cbor(&sig);
+                assert_eq!(try_sig_from_cbor(&cbor_sig).unwrap(), sig);
+                assert!(pk2.verify(b"msg", &sig, b"ctx"));
+
+                let mut bad = cose_key;
+                bad[5] ^= 0x01; // Another algorithm identifier
+                assert!(PublicKey::try_from_cose_key(&bad).is_err());
+                assert!(PublicKey::try_from_cose_key(&cose_key[1..]).is_err());
+                assert!(try_sig_from_cbor(&cbor_sig[..CBOR_SIG_LEN - 1]).is_err());
+                let mut bad = cbor_sig;
+                bad[0] = 0x5a; // Non-deterministic 4-byte length
+                assert!(try_sig_from_cbor(&bad).is_err());
+            }
+
+
+            #[test]
+            fn spki_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, _sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let der = pk.to_spki_der();
+                assert_eq!(der[spki::PREFIX_LEN..], pk.clone().into_bytes());
+                let pk2 = PublicKey::try_from_spki_der(&der).unwrap();
+                assert_eq!(pk2.into_bytes(), pk.into_bytes());
+
+                assert!(PublicKey::try_from_spki_der(&der[1..]).is_err());
+                let mut bad = der;
+                bad[16] ^= 0x07; // Another parameter set's OID
+                assert!(PublicKey::try_from_spki_der(&bad).is_err());
+            }
+
+
+            #[test]
+            fn fixed_ctx_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let message = [0u8, 1, 2, 3, 4, 5, 6, 7];
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let ctx = [7u8; 255];
+
+                // Interoperates with the slice-based API in both directions
+                let sig = sk.try_sign_fixed_ctx_with_rng(&mut rng, &message, &ctx).unwrap();
+                assert!(pk.verify(&message, &sig, &ctx));
+                assert!(!pk.verify_fixed_ctx(&message, &sig, &[7u8; 254]));
+                let sig = sk.try_sign_with_rng(&mut rng, &message, &[]).unwrap();
+                assert!(pk.verify_fixed_ctx(&message, &sig, &[]));
+            }
+
+
+            #[test]
+            fn verify_mode_test() {
+                use crate::VerifyMode::{FastReject, Uniform};
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let message = [0u8, 1, 2, 3, 4, 5, 6, 7];
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let sig = sk.try_sign_with_rng(&mut rng, &message, &[]).unwrap();
+
+                for mode in [Uniform, FastReject] {
+                    assert!(pk.verify_with_mode(&message, &sig, &[], mode));
+                    assert!(!pk.verify_with_mode(&message, &sig, &[1], mode));
+                    let mut bad = sig;
+                    bad[0] ^= 0x01; // c_tilde mismatch
+                    assert!(!pk.verify_with_mode(&message, &bad, &[], mode));
+                    bad = sig;
+                    bad[LAMBDA_DIV4..LAMBDA_DIV4 + 256].fill(0xFF); // z out of range
+                    assert!(!pk.verify_with_mode(&message, &bad, &[], mode));
+                }
+            }
+
+
+            #[test]
+            fn verify_slice_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let message = [0u8, 1, 2, 3, 4, 5, 6, 7];
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let sig = sk.try_sign_with_rng(&mut rng, &message, &[]).unwrap();
+
+                assert_eq!(PARAM_SET.pk_len(), PK_LEN);
+                assert_eq!(PARAM_SET.sk_len(), SK_LEN);
+                assert_eq!(PARAM_SET.sig_len(), SIG_LEN);
+                assert_eq!(crate::ParamSet::from_sig_len(SIG_LEN), Some(PARAM_SET));
+
+                assert_eq!(pk.try_verify_slice(&message, &sig, &[]), Ok(()));
+                assert_eq!(pk.try_verify_slice(&message, &sig, &[1]), Err(crate::Error::InvalidSignature));
+                assert_eq!(
+                    pk.try_verify_slice(&message, &sig[1..], &[]),
+                    Err(crate::Error::WrongParamSet { expected: PARAM_SET, found: None })
+                );
+            }
+
+
+            #[test]
+            fn slice_parsers_test() {
+                use crate::ParamSet;
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let sig = sk.try_sign_with_rng(&mut rng, b"msg", b"ctx").unwrap();
+                let (pk_bytes, sk_bytes) = (pk.clone().into_bytes(), sk.duplicate().into_bytes());
+
+                assert_eq!(ParamSet::from_pk_len(PK_LEN), Some(PARAM_SET));
+                assert_eq!(ParamSet::from_sk_len(SK_LEN), Some(PARAM_SET));
+                assert_eq!(PublicKey::try_from_slice(&pk_bytes).unwrap(), pk);
+                assert_eq!(PrivateKey::try_from_slice(&sk_bytes).unwrap().into_bytes(), sk_bytes);
+                assert_eq!(try_sig_from_slice(&sig), Ok(sig));
+                assert_eq!(PrivateKey::try_from_slice(&[0xFFu8; SK_LEN]).err(), Some(crate::Error::MalformedKey));
+
+                // Every other parameter set's length is identified as such
+                let buf = [0u8; 4896];
+                for other in [ParamSet::MlDsa44, ParamSet::MlDsa65, ParamSet::MlDsa87] {
+                    if other == PARAM_SET {
+                        continue;
+                    }
+                    let wrong = |expected, got| crate::Error::WrongLength { expected, got, likely_paramset: Some(other) };
+                    assert_eq!(PublicKey::try_from_slice(&buf[..other.pk_len()]).err(), Some(wrong(PK_LEN, other.pk_len())));
+                    assert_eq!(PrivateKey::try_from_slice(&buf[..other.sk_len()]).err(), Some(wrong(SK_LEN, other.sk_len())));
+                    assert_eq!(try_sig_from_slice(&buf[..other.sig_len()]), Err(wrong(SIG_LEN, other.sig_len())));
+                }
+                let truncated = crate::Error::WrongLength { expected: PK_LEN, got: PK_LEN - 1, likely_paramset: None };
+                assert_eq!(PublicKey::try_from_slice(&pk_bytes[1..]).err(), Some(truncated));
+                assert_eq!(PrivateKey::try_from_slice(&[]).err().map(|e| e.code()), Some(6));
+            }
+
+
+            #[cfg(all(feature = "unstable", not(feature = "fips-mode")))]
+            #[test]
+            fn w1_encoded_test() {
+                use sha3::digest::XofReader;
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let message = [0u8, 1, 2, 3, 4, 5, 6, 7];
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+                let sig = sk.try_sign_with_rng(&mut rng, &message, &[5]).unwrap();
+
+                // c_tilde = H(µ || w1Encode(w′_1)) where µ = H(tr || 0 || |ctx| || ctx || M)
+                let mut w1_tilde = [0u8; W1_ENCODED_LEN];
+                pk.try_reconstruct_w1_encoded(&sig, &mut w1_tilde).unwrap();
+                let mut mu = [0u8; 64];
+                hashing::h256_xof(&[&pk.tr, &[0u8, 1, 5], &message]).read(&mut mu);
+                let mut c_tilde = [0u8; LAMBDA_DIV4];
+                hashing::h256_xof(&[&mu, &w1_tilde]).read(&mut c_tilde);
+                assert_eq!(c_tilde, sig[..LAMBDA_DIV4]);
+
+                let mut bad_sig = sig;
+                bad_sig[SIG_LEN - 1] = 0xFF; // hint index counts out of range
+                assert!(pk.try_reconstruct_w1_encoded(&bad_sig, &mut w1_tilde).is_err());
+
+                let mut w1 = [[0i32; 256]; K];
+                assert!(w1_encode_into(&w1, &mut w1_tilde).is_ok());
+                assert!(w1_tilde.iter().all(|&b| b == 0));
+                w1[0][0] = (Q - 1) / (2 * GAMMA2);
+                assert!(w1_encode_into(&w1, &mut w1_tilde).is_err());
+            }
+
+
+            #[cfg(feature = "alloc")]
+            #[test]
+            fn sign_many_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let messages: [&[u8]; 4] = [&[], &[0u8, 1, 2], &[7u8; 100], &[0u8, 1, 2]];
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+
+                let sigs = sk.try_sign_many_with_rng(&mut rng, &messages, &[3]).unwrap();
+                assert_eq!(sigs.len(), messages.len());
+                for (message, sig) in messages.iter().zip(sigs.iter()) {
+                    assert!(pk.verify(message, sig, &[3]));
+                    assert!(!pk.verify(message, sig, &[]));
+                }
+                assert_ne!(sigs[1], sigs[3]); // Fresh randomness per signature
+                assert!(sk.try_sign_many_with_rng(&mut rng, &[], &[]).unwrap().is_empty());
+                assert!(sk.try_sign_many_with_rng(&mut rng, &messages, &[0u8; 256]).is_err());
+            }
+
+
+            #[cfg(feature = "alloc")]
+            #[test]
+            fn attached_test() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                let message = [0u8, 1, 2, 3, 4, 5, 6, 7];
+                let (pk, sk) = try_keygen_with_rng(&mut rng).unwrap();
+
+                let blob = sk.try_sign_attached_with_rng(&mut rng, &message, &[1]).unwrap();
+                assert_eq!(blob.len(), ATTACHED_PREFIX_LEN + message.len() + SIG_LEN);
+                assert_eq!(pk.open_attached(&blob, &[1]).unwrap(), message);
+                assert!(pk.open_attached(&blob, &[2]).is_err());
+                assert!(pk.open_attached(&blob[..blob.len() - 1], &[1]).is_err());
+
+                let mut bad = blob.clone();
+                bad[ATTACHED_PREFIX_LEN] ^= 1;
+                assert!(pk.open_attached(&bad, &[1]).is_err());
+                let mut bad = blob;
+                bad[0] ^= 1;
+                assert!(pk.open_attached(&bad, &[1]).is_err());
+            }
+        }
+
+
+        // ----- SUPPORT FOR DUDECT CONSTANT TIME MEASUREMENTS ---
+
+        /// This function supports the dudect constant-time measurement framework, and
+        /// is only exposed with the `dudect` feature is enabled.
+        ///
+        /// # Errors
+        /// Returns an error when the random number generator fails; propagates internal errors.
+        #[deprecated = "Function for constant-time testing; do not use elsewhere"]
+        #[cfg(all(feature = "dudect", not(feature = "fips-mode")))]
+        pub fn dudect_keygen_sign_with_rng(
+            rng: &mut impl CryptoRngCore, message: &[u8],
+        ) -> Result<[u8; SIG_LEN], &'static str> {
+            let (_pk, sk) = ml_dsa::key_gen::<true, K, L, PK_LEN, SK_LEN>(rng, ETA)?;
+            let mut rnd = [0u8; 32];
+            rng.try_fill_bytes(&mut rnd).map_err(|_| "Random number generator failed")?;
+            let sig = ml_dsa::sign_internal::<true, K, L, LAMBDA_DIV4, SIG_LEN, SK_LEN, W1_LEN>(
+                BETA, GAMMA1, GAMMA2, OMEGA, TAU, &sk, message, &[1], &[2], &[3], rnd, true
+            )?;
+            Ok(sig)
+        }
+
+        #[deprecated = "Temporary function to allow application of internal nist vectors; will be removed"]
+        /// As of Oct 30 2024, the NIST test vectors are applied to the **internal** functions rather than
+        /// the external API.
+        ///
+        /// The primary difference pertains to the prepending of domain, context, OID and
+        /// hash information to the message in the `sign_finish()` and `verify_finish()` functions (follow
+        /// the last `nist=true` function argument). This is expected to change such that the full API can
+        /// be robustly tested - when this happens, this function will no longer be needed.
+        /// # Errors
+        /// Propagate errors from the `sign_finish()` function (for failing RNG).
+        pub fn _internal_sign(
+            sk: &PrivateKey, message: &[u8], ctx: &[u8], rnd: [u8; 32]
+        ) -> Result<[u8; SIG_LEN], &'static str> {
+            helpers::ensure!(ctx.len() < 256, "_internal_sign: ctx too long");
+            let sig = ml_dsa::sign_internal::<CTEST, K, L, LAMBDA_DIV4, SIG_LEN, SK_LEN, W1_LEN>(
+                BETA, GAMMA1, GAMMA2, OMEGA, TAU, sk, message, ctx, &[], &[], rnd, true
+            )?;
+            Ok(sig)
+        }
+
+        #[deprecated = "Temporary function to allow application of internal nist vectors; will be removed"]
+        #[must_use]
+        /// As of Oct 30 2024, the NIST test vectors are applied to the **internal** functions rather than
+        /// the external API.
+        ///
+        /// The primary difference pertains to the prepending of domain, context, OID and
+        /// hash information to the message in the `sign_finish()` and `verify_finish()` functions (follow
+        /// the last `nist=true` function argument). This is expected to change such that the full API can
+        /// be robustly tested - when this happens, this function will no longer be needed.
+        pub fn _internal_verify(pk: &PublicKey, message: &[u8], sig: &[u8; SIG_LEN], ctx: &[u8]) -> bool {
+            if ctx.len() > 255 {
+                return false;
+            };
+            ml_dsa::verify_internal::<CTEST, K, L, LAMBDA_DIV4, PK_LEN, SIG_LEN, W1_LEN>(
+                BETA, GAMMA1, GAMMA2, OMEGA, TAU, pk, &message, &sig, ctx, &[], &[], true,
+                crate::VerifyMode::default()
+            )
+        }
+    };
+}
+
+
+/// # Functionality for the **ML-DSA-44** security parameter set.
+///
+/// This includes specific sizes for the
+/// public key, secret key, and signature along with a number of internal constants. The ML-DSA-44
+/// parameter set is claimed to be in security strength category 2.
+///
+/// **1)** The basic usage is for an originator to start with the [`ml_dsa_44::try_keygen`] function below to
+/// generate both [`ml_dsa_44::PublicKey`] and [`ml_dsa_44::PrivateKey`] structs. The resulting
+/// [`ml_dsa_44::PrivateKey`] struct implements the [`traits::Signer`] trait which supplies a variety of
+/// functions to sign byte-array messages, such as [`traits::Signer::try_sign()`].
+///
+/// **2)** Both of the `PrivateKey` and `PublicKey` structs implement the [`traits::SerDes`] trait.
+/// The originator utilizes the [`traits::SerDes::into_bytes()`] functions to serialize the structs
+/// into byte-arrays for storage and/or transmission, similar to the message. Upon retrieval and/or receipt,
+/// the remote party utilizes the [`traits::SerDes::try_from_bytes()`] functions to deserialize the
+/// byte-arrays into structs.
+///
+/// **3)** Finally, the remote party uses the [`traits::Verifier::verify()`] function implemented on the
+/// [`ml_dsa_44::PublicKey`] struct to verify the message with the `Signature` byte array.
+///
+/// See the top-level [crate] documentation for example code that implements the above flow.
+#[cfg(feature = "ml-dsa-44")]
+pub mod ml_dsa_44 {
+    /// The security parameter set implemented by this module.
+    pub const PARAM_SET: crate::ParamSet = crate::ParamSet::MlDsa44;
+    const TAU: i32 = 39;
+    const LAMBDA: usize = 128;
+    const GAMMA1: i32 = 1 << 17;
+    const GAMMA2: i32 = (Q - 1) / 88;
+    const K: usize = 4;
+    const L: usize = 4;
+    const ETA: i32 = 2;
+    const OMEGA: i32 = 80;
+    /// Private (secret) key length in bytes.
+    pub const SK_LEN: usize = 2560;
+    /// Public key length in bytes.
+    pub const PK_LEN: usize = 1312;
+    /// Signature length in bytes.
+    pub const SIG_LEN: usize = 2420;
+
+    /// Expected number of iterations of the signing rejection sampling loop (FIPS 204 Table 1).
+    pub const EXPECTED_SIGN_ITERATIONS: f64 = 4.25;
+
+    /// Upper bound on the peak stack used by key generation, in bytes, in optimized builds (as
+    /// enforced by `tests/stack.rs`). Only exposed when the `memory-report` feature is enabled.
+    #[cfg(feature = "memory-report")]
+    pub const KEYGEN_STACK_BYTES: usize = 144 * 1024;
+    /// Upper bound on the peak stack used by signing, in bytes, in optimized builds.
+    #[cfg(feature = "memory-report")]
+    pub const SIGN_STACK_BYTES: usize = 128 * 1024;
+    /// Upper bound on the peak stack used by verification, in bytes, in optimized builds.
+    #[cfg(feature = "memory-report")]
+    pub const VERIFY_STACK_BYTES: usize = 60 * 1024;
+
+    functionality!();
+}
+
+
+/// # Functionality for the **ML-DSA-65** security parameter set.
+///
+/// This includes specific sizes for the
+/// public key, secret key, and signature along with a number of internal constants. The ML-DSA-65
+/// parameter set is claimed to be in security strength category 3.
+///
+/// **1)** The basic usage is for an originator to start with the [`ml_dsa_44::try_keygen`] function below to
+/// generate both [`ml_dsa_44::PublicKey`] and [`ml_dsa_44::PrivateKey`] structs. The resulting
+/// [`ml_dsa_44::PrivateKey`] struct implements the [`traits::Signer`] trait which supplies a variety of
+/// functions to sign byte-array messages, such as [`traits::Signer::try_sign()`].
+///
+/// **2)** Both of the `PrivateKey` and `PublicKey` structs implement the [`traits::SerDes`] trait
+/// The originator utilizes the [`traits::SerDes::into_bytes()`] functions to serialize the structs
+/// into byte-arrays for storage and/or transmission, similar to the message. Upon retrieval and/or receipt,
+/// the remote party utilizes the [`traits::SerDes::try_from_bytes()`] functions to deserialize the
+/// byte-arrays into structs.
+///
+/// **3)** Finally, the remote party uses the [`traits::Verifier::verify()`] function implemented on the
+/// [`ml_dsa_44::PublicKey`] struct to verify the message with the `Signature` byte array.
+///
+/// See the top-level [crate] documentation for example code that implements the above flow.
+#[cfg(feature = "ml-dsa-65")]
+pub mod ml_dsa_65 {
+    /// The security parameter set implemented by this module.
+    pub const PARAM_SET: crate::ParamSet = crate::ParamSet::MlDsa65;
+    const TAU: i32 = 49;
+    const LAMBDA: usize = 192;
+    const GAMMA1: i32 = 1 << 19;
+    const GAMMA2: i32 = (Q - 1) / 32;
+    const K: usize = 6;
+    const L: usize = 5;
+    const ETA: i32 = 4;
+    const OMEGA: i32 = 55;
+    /// Private (secret) key length in bytes.
+    pub const SK_LEN: usize = 4032;
+    /// Public key length in bytes.
+    pub const PK_LEN: usize = 1952;
+    /// Signature length in bytes.
+    pub const SIG_LEN: usize = 3309;
+
+    /// Expected number of iterations of the signing rejection sampling loop (FIPS 204 Table 1).
+    pub const EXPECTED_SIGN_ITERATIONS: f64 = 5.1;
+
+    /// Upper bound on the peak stack used by key generation, in bytes, in optimized builds (as
+    /// enforced by `tests/stack.rs`). Only exposed when the `memory-report` feature is enabled.
+    #[cfg(feature = "memory-report")]
+    pub const KEYGEN_STACK_BYTES: usize = 216 * 1024;
+    /// Upper bound on the peak stack used by signing, in bytes, in optimized builds.
+    #[cfg(feature = "memory-report")]
+    pub const SIGN_STACK_BYTES: usize = 192 * 1024;
+    /// Upper bound on the peak stack used by verification, in bytes, in optimized builds.
+    #[cfg(feature = "memory-report")]
+    pub const VERIFY_STACK_BYTES: usize = 80 * 1024;
+
+    functionality!();
+}
+
+
+/// # Functionality for the **ML-DSA-87** security parameter set.
+///
 /// This includes specific sizes for the
 /// public key, secret key, and signature along with a number of internal constants. The ML-DSA-87
 /// parameter set is claimed to be in security strength category 5.
@@ -691,6 +4761,8 @@ pub mod ml_dsa_65 {
 /// See the top-level [crate] documentation for example code that implements the above flow.
 #[cfg(feature = "ml-dsa-87")]
 pub mod ml_dsa_87 {
+    /// The security parameter set implemented by this module.
+    pub const PARAM_SET: crate::ParamSet = crate::ParamSet::MlDsa87;
     const TAU: i32 = 60;
     const LAMBDA: usize = 256;
     const GAMMA1: i32 = 1 << 19;
@@ -706,5 +4778,19 @@ pub mod ml_dsa_87 {
     /// Signature length in bytes.
     pub const SIG_LEN: usize = 4627;
 
+    /// Expected number of iterations of the signing rejection sampling loop (FIPS 204 Table 1).
+    pub const EXPECTED_SIGN_ITERATIONS: f64 = 3.85;
+
+    /// Upper bound on the peak stack used by key generation, in bytes, in optimized builds (as
+    /// enforced by `tests/stack.rs`). Only exposed when the `memory-report` feature is enabled.
+    #[cfg(feature = "memory-report")]
+    pub const KEYGEN_STACK_BYTES: usize = 320 * 1024;
+    /// Upper bound on the peak stack used by signing, in bytes, in optimized builds.
+    #[cfg(feature = "memory-report")]
+    pub const SIGN_STACK_BYTES: usize = 288 * 1024;
+    /// Upper bound on the peak stack used by verification, in bytes, in optimized builds.
+    #[cfg(feature = "memory-report")]
+    pub const VERIFY_STACK_BYTES: usize = 104 * 1024;
+
     functionality!();
 }
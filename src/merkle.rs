@@ -0,0 +1,227 @@
+// This file implements Merkle-batched signing: a SHAKE256 Merkle tree is built over a batch of
+// messages, only the root is signed with ML-DSA, and each message receives an inclusion proof.
+
+//! Merkle-batched signing, only exposed when the `merkle` feature is enabled.
+//!
+//! A SHAKE256 Merkle tree is built over a batch of messages and only its root is signed with
+//! ML-DSA. Each message then travels with a compact [`InclusionProof`] (of `32·⌈log2(n)⌉` bytes)
+//! alongside the single shared signature. Leaves and interior nodes are domain separated
+//! (`0x00` and `0x01` prefixes respectively), and an unpaired node at the end of a level is
+//! promoted unchanged rather than duplicated. The signed root message also binds the number of
+//! leaves, so a proof cannot be replayed against a tree of a different shape.
+//!
+//! # Examples
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+//! use fips204::merkle;
+//! use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+//!
+//! let messages: [&[u8]; 3] = [b"log entry 1", b"log entry 2", b"log entry 3"];
+//!
+//! let (pk, sk) = ml_dsa_44::try_keygen()?; // Generate both public and secret keys
+//! let (sig, proofs) = merkle::try_sign_batch(&sk, &messages, &[0])?; // Sign just the root
+//!
+//! // Each message is verified with its own proof and the shared signature
+//! assert!(merkle::verify_batch(&pk, messages[1], &proofs[1], &sig, &[0]));
+//! assert!(!merkle::verify_batch(&pk, messages[0], &proofs[1], &sig, &[0]));
+//! # }
+//! # Ok(())}
+//! ```
+
+use crate::hashing::h256_xof;
+use crate::helpers::ensure;
+use crate::traits::{Signer, Verifier};
+use alloc::vec::Vec;
+use rand_core::CryptoRngCore;
+#[cfg(feature = "default-rng")]
+use rand_core::OsRng;
+use sha3::digest::XofReader;
+
+/// Domain separation label prepended to the leaf count and root before signing.
+const ROOT_DOMAIN: &[u8] = b"fips204-merkle-root-v1";
+
+
+/// Proof that a single message is included in a Merkle-batched signature.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InclusionProof {
+    /// Position of the message within the signed batch.
+    pub index: u64,
+    /// Number of messages in the signed batch.
+    pub leaf_count: u64,
+    /// Sibling hashes from the leaf level up to (but excluding) the root.
+    pub path: Vec<[u8; 32]>,
+}
+
+
+/// Signs the root of a Merkle tree built over `messages`, returning the single signature
+/// along with one inclusion proof per message (in order). This function utilizes the
+/// **OS default** random number generator.
+///
+/// # Errors
+/// Returns an error when the batch is empty, the random number generator fails or the
+/// context is too long.
+#[cfg(feature = "default-rng")]
+pub fn try_sign_batch<S: Signer>(
+    sk: &S, messages: &[&[u8]], ctx: &[u8],
+) -> Result<(S::Signature, Vec<InclusionProof>), &'static str> {
+    try_sign_batch_with_rng(sk, &mut OsRng, messages, ctx)
+}
+
+
+/// Signs the root of a Merkle tree built over `messages`; see [`try_sign_batch()`]. This
+/// function utilizes the **provided** random number generator.
+///
+/// # Errors
+/// Returns an error when the batch is empty, the random number generator fails or the
+/// context is too long.
+pub fn try_sign_batch_with_rng<S: Signer>(
+    sk: &S, rng: &mut impl CryptoRngCore, messages: &[&[u8]], ctx: &[u8],
+) -> Result<(S::Signature, Vec<InclusionProof>), &'static str> {
+    ensure!(!messages.is_empty(), "Merkle: empty batch");
+    let leaf_count = u64::try_from(messages.len()).map_err(|_| "Merkle: batch too large")?;
+
+    // Build every level of the tree, from the leaves up to the single root
+    let mut levels: Vec<Vec<[u8; 32]>> = Vec::new();
+    levels.push(messages.iter().map(|m| leaf_hash(m)).collect());
+    while levels[levels.len() - 1].len() > 1 {
+        let next = levels[levels.len() - 1]
+            .chunks(2)
+            .map(|pair| if pair.len() == 2 { node_hash(&pair[0], &pair[1]) } else { pair[0] })
+            .collect();
+        levels.push(next);
+    }
+    let root = levels[levels.len() - 1][0];
+
+    let signature = sk.try_sign_with_rng(rng, &root_message(leaf_count, &root), ctx)?;
+
+    // Collect the siblings for each leaf; promoted nodes contribute nothing to the path
+    let proofs = (0..messages.len())
+        .map(|leaf| {
+            let mut index = leaf;
+            let mut path = Vec::new();
+            for level in &levels[..levels.len() - 1] {
+                if let Some(sibling) = level.get(index ^ 1) {
+                    path.push(*sibling);
+                }
+                index >>= 1;
+            }
+            InclusionProof { index: leaf as u64, leaf_count, path }
+        })
+        .collect();
+
+    Ok((signature, proofs))
+}
+
+
+/// Verifies that `message` is included in a Merkle-batched signature via its `proof`. As this
+/// function operates on purely public data, it need/does not provide constant-time assurances.
+pub fn verify_batch<V: Verifier>(
+    pk: &V, message: &[u8], proof: &InclusionProof, sig: &V::Signature, ctx: &[u8],
+) -> bool {
+    if proof.index >= proof.leaf_count {
+        return false;
+    }
+    let mut node = leaf_hash(message);
+    let mut index = proof.index;
+    let mut width = proof.leaf_count;
+    let mut path = proof.path.iter();
+    while width > 1 {
+        if (index ^ 1) < width {
+            let Some(sibling) = path.next() else { return false };
+            node = if index & 1 == 0 { node_hash(&node, sibling) } else { node_hash(sibling, &node) };
+        }
+        index >>= 1;
+        width = width / 2 + (width & 1);
+    }
+    if path.next().is_some() {
+        return false;
+    }
+    pk.verify(&root_message(proof.leaf_count, &node), sig, ctx)
+}
+
+
+fn leaf_hash(message: &[u8]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    h256_xof(&[&[0x00], message]).read(&mut out);
+    out
+}
+
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    h256_xof(&[&[0x01], left, right]).read(&mut out);
+    out
+}
+
+
+fn root_message(leaf_count: u64, root: &[u8; 32]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(ROOT_DOMAIN.len() + 8 + 32);
+    out.extend_from_slice(ROOT_DOMAIN);
+    out.extend_from_slice(&leaf_count.to_le_bytes());
+    out.extend_from_slice(root);
+    out
+}
+
+
+#[cfg(all(test, feature = "ml-dsa-44"))]
+mod tests {
+    use super::*;
+    use crate::ml_dsa_44;
+    use rand_chacha::rand_core::SeedableRng;
+
+    #[test]
+    fn test_batch_sizes() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+        let (pk, sk) = ml_dsa_44::try_keygen_with_rng(&mut rng).unwrap();
+        let storage: Vec<[u8; 4]> = (0..9u32).map(u32::to_le_bytes).collect();
+
+        for n in 1..=storage.len() {
+            let messages: Vec<&[u8]> = storage[..n].iter().map(<[u8; 4]>::as_slice).collect();
+            let (sig, proofs) = try_sign_batch_with_rng(&sk, &mut rng, &messages, &[1]).unwrap();
+            assert_eq!(proofs.len(), n);
+            for (i, proof) in proofs.iter().enumerate() {
+                assert!(verify_batch(&pk, messages[i], proof, &sig, &[1]));
+                assert!(!verify_batch(&pk, messages[i], proof, &sig, &[2]));
+                assert!(!verify_batch(&pk, &[9, 9, 9, 9], proof, &sig, &[1]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_tampered_proofs() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+        let (pk, sk) = ml_dsa_44::try_keygen_with_rng(&mut rng).unwrap();
+        let messages: [&[u8]; 5] = [b"a", b"b", b"c", b"d", b"e"];
+        let (sig, proofs) = try_sign_batch_with_rng(&sk, &mut rng, &messages, &[]).unwrap();
+
+        let mut bad = proofs[2].clone();
+        bad.index = 3;
+        assert!(!verify_batch(&pk, messages[2], &bad, &sig, &[]));
+
+        let mut bad = proofs[2].clone();
+        bad.leaf_count = 4;
+        assert!(!verify_batch(&pk, messages[2], &bad, &sig, &[]));
+
+        let mut bad = proofs[2].clone();
+        bad.path[0][0] ^= 1;
+        assert!(!verify_batch(&pk, messages[2], &bad, &sig, &[]));
+
+        let mut bad = proofs[2].clone();
+        bad.path.push([0u8; 32]);
+        assert!(!verify_batch(&pk, messages[2], &bad, &sig, &[]));
+
+        let mut bad = proofs[4].clone();
+        bad.index = 5;
+        assert!(!verify_batch(&pk, messages[4], &bad, &sig, &[]));
+
+        let mut bad = proofs[4].clone();
+        bad.leaf_count = u64::MAX;
+        assert!(!verify_batch(&pk, messages[4], &bad, &sig, &[]));
+        bad.path = alloc::vec![[0u8; 32]; 64];
+        assert!(!verify_batch(&pk, messages[4], &bad, &sig, &[]));
+
+        assert!(try_sign_batch_with_rng(&sk, &mut rng, &[], &[]).is_err());
+    }
+}
@@ -0,0 +1,171 @@
+// This file exposes the FIPS 204 construction of the formatted message `M′` and the message
+// representative `µ`, so that external parties (e.g., HSM firmware computing `µ` for an external
+// mu flow) can reproduce them bit-exactly without reference to the signing internals.
+
+//! The formatted message `M′` and message representative `µ` (FIPS 204 Algorithms 2-5 and 7).
+//!
+//! Pure ML-DSA signs `M′ = 0 ∥ |ctx| ∥ ctx ∥ M`, while HashML-DSA signs
+//! `M′ = 1 ∥ |ctx| ∥ ctx ∥ OID ∥ PH(M)`, where `|ctx|` is a single byte, `OID` is the DER
+//! encoding of the pre-hash function's object identifier (see [`Ph::oid()`]) and `PH(M)` is its
+//! digest of the message. Both are then hashed as `µ = SHAKE256(tr ∥ M′, 64)`, where `tr` is
+//! the public key hash (see e.g. `ml_dsa_65::PublicKey::tr()`).
+//!
+//! [`encode_prefix()`] produces everything in `M′` ahead of `M` or `PH(M)`, which allows the
+//! message itself to be streamed into the hash, and [`mu()`] computes `µ` in full.
+//!
+//! # Empty versus absent context
+//! FIPS 204 has no notion of an optional context string: a caller supplying "no context" to
+//! `ML-DSA.Sign()` or `HashML-DSA.Sign()` supplies the empty string, which is still encoded as a
+//! zero length byte (`M′ = 0 ∥ 0 ∥ M`). Accordingly, every API of this crate taking a context
+//! (`ctx: &[u8]`, [`crate::Context::default()`], a null pointer with zero length in the C ABI)
+//! treats an absent context as the empty context, and signatures interoperate with
+//! implementations that take an optional context.
+//!
+//! What *is* distinct is omitting `M′`'s prefix altogether, as the internal functions
+//! `ML-DSA.Sign_internal()` and `ML-DSA.Verify_internal()` do for test vectors (e.g., ACVP). A
+//! signature over the bare message verifies under no context string at all, including the empty
+//! one. [`mu_internal()`] computes `µ` for this case, for use with external-µ signing and
+//! verification (e.g., the `hazmat` functions); it is never used implicitly.
+//!
+//! # Examples
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use fips204::message::{encode_prefix, mu, DOMAIN_PURE, MAX_PREFIX_LEN};
+//!
+//! let mut prefix = [0u8; MAX_PREFIX_LEN];
+//! let len = encode_prefix(b"ctx", None, &mut prefix)?;
+//! assert_eq!(prefix[..len], [DOMAIN_PURE, 3, b'c', b't', b'x']);
+//!
+//! let tr = [0x42u8; 64]; // normally from `PublicKey::tr()`
+//! let mu = mu(&tr, b"message", b"ctx", None)?;
+//! # let _ = mu;
+//! # Ok(())}
+//! ```
+
+use crate::helpers::ensure;
+use crate::types::Ph;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+
+
+/// The domain separator byte leading `M′` for pure ML-DSA.
+pub const DOMAIN_PURE: u8 = 0;
+
+/// The domain separator byte leading `M′` for HashML-DSA (pre-hash).
+pub const DOMAIN_PREHASH: u8 = 1;
+
+/// The maximum context string length in bytes.
+pub const MAX_CTX_LEN: usize = 255;
+
+/// The length of the DER-encoded pre-hash function OIDs in bytes.
+pub const OID_LEN: usize = 11;
+
+/// The longest prefix written by [`encode_prefix()`]: domain byte, length byte, a maximal
+/// context string and an OID.
+pub const MAX_PREFIX_LEN: usize = 2 + MAX_CTX_LEN + OID_LEN;
+
+
+/// Writes the portion of `M′` preceding the message (pure ML-DSA, `ph` is `None`) or its pre-hash
+/// digest (HashML-DSA) into `out`, returning the number of bytes written.
+///
+/// # Errors
+/// Returns an error when `ctx` is longer than 255 bytes or `out` is too short.
+pub fn encode_prefix(ctx: &[u8], ph: Option<&Ph>, out: &mut [u8]) -> Result<usize, &'static str> {
+    ensure!(ctx.len() <= MAX_CTX_LEN, "encode_prefix: ctx too long");
+    let len = 2 + ctx.len() + ph.map_or(0, |_| OID_LEN);
+    ensure!(out.len() >= len, "encode_prefix: output too short");
+    out[0] = if ph.is_some() { DOMAIN_PREHASH } else { DOMAIN_PURE };
+    out[1] = ctx.len().to_le_bytes()[0];
+    out[2..2 + ctx.len()].copy_from_slice(ctx);
+    if let Some(ph) = ph {
+        out[2 + ctx.len()..len].copy_from_slice(&ph.oid());
+    }
+    Ok(len)
+}
+
+
+/// Computes the pre-hash digest `PH(M)` into `out`, returning its length (32 or 64 bytes).
+#[must_use]
+pub fn prehash(message: &[u8], ph: &Ph, out: &mut [u8; 64]) -> usize {
+    crate::hashing::hash_message(message, ph, out).1
+}
+
+
+/// Computes the message representative `µ = SHAKE256(tr ∥ M′, 64)` for a message signed under
+/// `ctx` with pure ML-DSA (`ph` is `None`) or HashML-DSA.
+///
+/// # Errors
+/// Returns an error when `ctx` is longer than 255 bytes.
+pub fn mu(tr: &[u8; 64], message: &[u8], ctx: &[u8], ph: Option<&Ph>) -> Result<[u8; 64], &'static str> {
+    let mut prefix = [0u8; MAX_PREFIX_LEN];
+    let len = encode_prefix(ctx, ph, &mut prefix)?;
+    let mut hasher = Shake256::default();
+    hasher.update(tr);
+    hasher.update(&prefix[..len]);
+    if let Some(ph) = ph {
+        let mut phm = [0u8; 64];
+        let phm_len = prehash(message, ph, &mut phm);
+        hasher.update(&phm[..phm_len]);
+    } else {
+        hasher.update(message);
+    }
+    let mut mu = [0u8; 64];
+    hasher.finalize_xof().read(&mut mu);
+    Ok(mu)
+}
+
+
+/// Computes the message representative `µ = SHAKE256(tr ∥ M′, 64)` of the internal interface,
+/// where `M′` is supplied verbatim rather than being formatted with a domain separator and
+/// context string; see the module documentation. Signatures over this `µ` do not verify under
+/// any context string, including the empty one.
+#[must_use]
+pub fn mu_internal(tr: &[u8; 64], m_prime: &[u8]) -> [u8; 64] {
+    let mut hasher = Shake256::default();
+    hasher.update(tr);
+    hasher.update(m_prime);
+    let mut mu = [0u8; 64];
+    hasher.finalize_xof().read(&mut mu);
+    mu
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ml_dsa::mu_hasher;
+
+    // The public construction matches that used internally for signing and verification
+    #[test]
+    fn test_mu_matches_internal() {
+        let tr = [0x42u8; 64];
+        let long_ctx = [7u8; 255];
+        for ctx in [&b""[..], b"ctx", &long_ctx] {
+            let mut expected = [0u8; 64];
+            let mut hasher = mu_hasher(&tr, ctx, &[], false);
+            hasher.update(b"message");
+            hasher.finalize_xof().read(&mut expected);
+            assert_eq!(mu(&tr, b"message", ctx, None).unwrap(), expected);
+
+            for ph in [Ph::SHA256, Ph::SHA512, Ph::SHAKE128] {
+                let mut phm = [0u8; 64];
+                let phm_len = prehash(b"message", &ph, &mut phm);
+                let mut hasher = mu_hasher(&tr, ctx, &ph.oid(), false);
+                hasher.update(&phm[..phm_len]);
+                hasher.finalize_xof().read(&mut expected);
+                assert_eq!(mu(&tr, b"message", ctx, Some(&ph)).unwrap(), expected);
+            }
+        }
+        assert!(mu(&tr, b"message", &[0u8; 256], None).is_err());
+
+        // The empty context is encoded, so differs from the (internal) absent prefix
+        let mut prefix = [0u8; MAX_PREFIX_LEN];
+        assert_eq!(encode_prefix(&[], None, &mut prefix), Ok(2));
+        assert_eq!(prefix[..2], [DOMAIN_PURE, 0]);
+        assert_eq!(mu(&tr, b"message", &[], None).unwrap(), mu_internal(&tr, b"\x00\x00message"));
+        assert_ne!(mu(&tr, b"message", &[], None).unwrap(), mu_internal(&tr, b"message"));
+        assert!(encode_prefix(b"ctx", Some(&Ph::SHA256), &mut [0u8; 15]).is_err());
+        assert_eq!(encode_prefix(b"ctx", Some(&Ph::SHA256), &mut [0u8; 16]), Ok(16));
+    }
+}
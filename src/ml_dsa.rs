@@ -1,17 +1,20 @@
 // This file implements functionality from FIPS 204 sections 6/7: Key Generation, Signing, Verification
 
-use crate::encodings::{pk_decode, pk_encode, sig_decode, sig_encode, sk_decode, w1_encode};
-use crate::hashing::{expand_a, expand_mask, expand_s, h256_xof, sample_in_ball};
+use crate::conversion::{bit_unpack, hint_bit_unpack, simple_bit_unpack};
+use crate::encodings::{pk_decode, pk_encode, sig_decode, sig_encode, sk_decode, sk_encode_into, w1_encode};
+use crate::hashing::{expand_a, expand_a_element, expand_mask, expand_s, h256_xof, sample_in_ball};
 use crate::helpers::{
-    add_vector_ntt, center_mod, full_reduce32, infinity_norm, mat_vec_mul, mont_reduce,
-    partial_reduce32, to_mont,
+    add_vector_ntt, center_mod, ensure, full_reduce32, infinity_norm, mat_vec_mul, mat_vec_mul_with,
+    from_mont, mont_mul_const, mont_reduce, partial_reduce32, to_mont,
 };
-use crate::high_low::{high_bits, low_bits, make_hint, power2round, use_hint};
+use crate::high_low::{high_bits_poly, low_bits, make_hint_poly, power2round, use_hint_poly};
 use crate::ntt::{inv_ntt, ntt};
-use crate::types::{PrivateKey, PublicKey, R, T};
+use crate::types::{PrivateKey, PublicKey, VerifyMode, R, T};
 use crate::{D, Q};
 use rand_core::CryptoRngCore;
-use sha3::digest::XofReader;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+use zeroize::Zeroize;
 
 
 /// # Algorithm: 1 `ML-DSA.KeyGen()` on page 17.
@@ -22,7 +25,8 @@ use sha3::digest::XofReader;
 ///             private key, `sk ∈ B^{32+32+64+32·((ℓ+k)·bitlen(2·η)+d·k)}`
 ///
 /// # Errors
-/// Returns an error when the random number generator fails.
+/// Returns an error when the random number generator fails (or, with the `fips-mode` feature,
+/// the module is in the error state).
 pub(crate) fn key_gen<
     const CTEST: bool,
     const K: usize,
@@ -32,6 +36,8 @@ pub(crate) fn key_gen<
 >(
     rng: &mut impl CryptoRngCore, eta: i32,
 ) -> Result<(PublicKey<K, L>, PrivateKey<K, L>), &'static str> {
+    #[cfg(feature = "fips-mode")]
+    crate::fips::check_operational()?;
     //
     // 1: ξ ← B^{32}    ▷ Choose random seed
     // 2: if ξ = NULL then
@@ -72,11 +78,23 @@ pub(crate) fn key_gen_internal<
     h2.read(&mut rho_prime);
     let mut cap_k = [0u8; 32];
     h2.read(&mut cap_k);
+    key_gen_from_parts::<CTEST, K, L, PK_LEN>(eta, &rho, &rho_prime, &cap_k)
+}
+
+
+/// Steps 3-11 of Algorithm 6 `ML-DSA.KeyGen_internal()` given the seeds derived in step 1.
+/// This split supports (unstable) key generation with an externally supplied `rho`.
+pub(crate) fn key_gen_from_parts<const CTEST: bool, const K: usize, const L: usize, const PK_LEN: usize>(
+    eta: i32, rho: &[u8; 32], rho_prime: &[u8; 64], cap_k: &[u8; 32],
+) -> (PublicKey<K, L>, PrivateKey<K, L>) {
+    #[cfg(feature = "fips-mode")]
+    crate::fips::assert_operational();
+    let (rho, cap_k) = (*rho, *cap_k);
 
     // There is effectively no step 2 due to formatting error in spec
 
     // 4: (s_1, s_2) ← ExpandS(ρ′)
-    let (s_1, s_2): ([R; L], [R; K]) = expand_s::<CTEST, K, L>(eta, &rho_prime);
+    let (s_1, s_2): ([R; L], [R; K]) = expand_s::<CTEST, K, L>(eta, rho_prime);
 
     // 3: cap_a_hat ← ExpandA(ρ)    ▷ A is generated and stored in NTT representation as Â
     // 5: t ← NTT−1(cap_a_hat ◦ NTT(s_1)) + s_2    ▷ Compute t = As1 + s2
@@ -107,9 +125,7 @@ pub(crate) fn key_gen_internal<
     // 9: 𝐰Approx ← NTT (𝐀 ∘ NTT(𝐳) − NTT(𝑐) ∘ NTT(𝐭1 ⋅ 2𝑑 ))    ▷ 𝐰Approx = 𝐀𝐳 − 𝑐𝐭1 ⋅ 2𝑑
     let t1_d2_hat_mont: [T; K] = {
         let t1_hat_mont: [T; K] = to_mont(&ntt(&t_1));
-        to_mont(&core::array::from_fn(|k| {
-            T(core::array::from_fn(|n| mont_reduce(i64::from(t1_hat_mont[k].0[n]) << D)))
-        }))
+        to_mont(&mont_mul_const(&t1_hat_mont, 1 << D))
     };
 
     // Now we have everything we need for public key struct
@@ -134,6 +150,63 @@ pub(crate) fn key_gen_internal<
 }
 
 
+/// Algorithm 6 `ML-DSA.KeyGen_internal()` writing the encoded keys directly into the caller's
+/// buffers, so that the encoded private key is never assembled anywhere else. The seeds and
+/// secret polynomials are necessarily held on the stack while computing; they are zeroized
+/// before returning.
+pub(crate) fn key_gen_into<
+    const CTEST: bool,
+    const K: usize,
+    const L: usize,
+    const PK_LEN: usize,
+    const SK_LEN: usize,
+>(
+    eta: i32, xi: &[u8; 32], pk_out: &mut [u8; PK_LEN], sk_out: &mut [u8; SK_LEN],
+) {
+    #[cfg(feature = "fips-mode")]
+    crate::fips::assert_operational();
+    //
+    // 1: (rho, rho′, 𝐾) ∈ 𝔹^{32} × 𝔹^{64} × 𝔹^{32} ← H(𝜉||IntegerToBytes(𝑘,1)||IntegerToBytes(ℓ,1),128)
+    let mut h2 = h256_xof(&[xi, &[K.to_le_bytes()[0]], &[L.to_le_bytes()[0]]]);
+    let mut rho = [0u8; 32];
+    h2.read(&mut rho);
+    let mut rho_prime = [0u8; 64];
+    h2.read(&mut rho_prime);
+    let mut cap_k = [0u8; 32];
+    h2.read(&mut cap_k);
+
+    // 4: (s_1, s_2) ← ExpandS(ρ′)
+    let (s_1, s_2): ([R; L], [R; K]) = expand_s::<CTEST, K, L>(eta, &rho_prime);
+    rho_prime.zeroize();
+
+    // 3: cap_a_hat ← ExpandA(ρ)
+    // 5: t ← NTT−1(cap_a_hat ◦ NTT(s_1)) + s_2
+    // 6: (t_1, t_0) ← Power2Round(t, d)
+    let (t_1, t_0): ([R; K], [R; K]) = {
+        let cap_a_hat: [[T; L]; K] = expand_a::<CTEST, K, L>(&rho);
+        let s_1_hat: [T; L] = ntt(&s_1);
+        let as1_hat: [T; K] = mat_vec_mul(&cap_a_hat, &s_1_hat);
+        let t_not_reduced: [R; K] = add_vector_ntt(&inv_ntt(&as1_hat), &s_2);
+        let t: [R; K] = core::array::from_fn(|k| {
+            R(core::array::from_fn(|n| full_reduce32(t_not_reduced[k].0[n])))
+        });
+        power2round(&t)
+    };
+
+    // 8: pk ← pkEncode(ρ, t_1)
+    *pk_out = pk_encode::<K, PK_LEN>(&rho, &t_1);
+
+    // 9: tr ← H(BytesToBits(pk), 64)
+    let mut tr = [0u8; 64];
+    let mut h8 = h256_xof(&[pk_out]);
+    h8.read(&mut tr);
+
+    // 10: sk ← skEncode(ρ, K, tr, s_1, s_2, t_0)
+    sk_encode_into::<K, L, SK_LEN>(eta, &rho, &cap_k, &tr, &s_1, &s_2, &t_0, sk_out);
+    cap_k.zeroize();
+}
+
+
 /// # Algorithm 7: ML-DSA.Sign_internal(𝑠𝑘, 𝑀 ′ , 𝑟𝑛𝑑) on page 25.
 /// Deterministic algorithm to generate a signature for a formatted message 𝑀 ′.
 ///
@@ -141,6 +214,9 @@ pub(crate) fn key_gen_internal<
 ///             formatted message 𝑀′ ∈ {0, 1}∗, and
 ///             per message randomness or dummy variable rnd ∈ 𝔹^{32}. <br>
 /// **Output**: Signature 𝜎 ∈ 𝔹^{𝜆/4+ℓ⋅32⋅(1+bitlen(𝛾1−1))+𝜔+𝑘}.
+///
+/// # Errors
+/// Returns an error only with the `fips-mode` feature, when the module is in the error state.
 // Note the M' is assembled here from provided elements, rather than by caller.
 // Further, a deserialized private key struct has a variety of pre-computed
 // elements ready-to-go.
@@ -161,12 +237,11 @@ pub(crate) fn sign_internal<
 >(
     beta: i32, gamma1: i32, gamma2: i32, omega: i32, tau: i32, esk: &PrivateKey<K, L>,
     message: &[u8], ctx: &[u8], oid: &[u8], phm: &[u8], rnd: [u8; 32], nist: bool,
-) -> [u8; SIG_LEN] {
+) -> Result<[u8; SIG_LEN], &'static str> {
     //
     // 1: (ρ, K, tr, s_1, s_2, t_0) ← skDecode(sk)
-    // --> calculated in `expand_private()` near the bottom of this file
-    // Extract elements from private key
-    let PrivateKey { rho, cap_k, tr, s_1_hat_mont, s_2_hat_mont, t_0_hat_mont } = esk;
+    // --> calculated in `expand_private()` near the bottom of this file; the elements are
+    //     extracted from the private key struct here and within `sign_mu()`
     //
     // 2: s_1_hat ← NTT(s_1)
     // --> the montgomery form is extracted from the private key struct above
@@ -178,25 +253,74 @@ pub(crate) fn sign_internal<
     // --> the montgomery form is extracted from the private key struct above
     //
     // 5: cap_a_hat ← ExpandA(ρ)    ▷ A is generated and stored in NTT representation as Â
-    let cap_a_hat: [[T; L]; K] = expand_a::<CTEST, K, L>(rho);
+    let cap_a_hat: [[T; L]; K] = expand_a::<CTEST, K, L>(&esk.rho);
 
     // 6: 𝜇 ← H(BytesToBits(𝑡𝑟)||𝑀 , 64)    ▷ Compute message representative µ
-    // Calculate mu based on which of the three different paths led us here
-    let mut h6 = if nist {
-        // 6a. NIST vectors are being applied to "internal" functions
-        h256_xof(&[tr, message])
-    } else if oid.is_empty() {
-        // 6b. From ML-DSA.Sign():  𝑀′ ← BytesToBits(IntegerToBytes(0,1) ∥ IntegerToBytes(|𝑐𝑡𝑥|,1) ∥ 𝑐𝑡𝑥) ∥ 𝑀
-        h256_xof(&[tr, &[0u8], &[ctx.len().to_le_bytes()[0]], ctx, message])
-    } else {
-        // 6c. From HashML-DSA.Sign(): 𝑀′ ← BytesToBits(IntegerToBytes(1,1) ∥ IntegerToBytes(|𝑐𝑡𝑥|,1) ∥ 𝑐𝑡𝑥 ∥ OID ∥ PH𝑀 )
-        h256_xof(&[tr, &[1u8], &[ctx.len().to_le_bytes()[0]], ctx, oid, phm])
-    };
-    let mut mu = [0u8; 64];
-    h6.read(&mut mu);
+    let mu = compute_mu(&esk.tr, message, ctx, oid, phm, nist);
+
+    // Steps 7 onward are performed within `sign_mu()`
+    sign_mu::<CTEST, K, L, LAMBDA_DIV4, SIG_LEN, W1_LEN>(
+        beta, gamma1, gamma2, omega, tau, esk, &cap_a_hat, &mu, rnd,
+    )
+}
+
+
+/// Remainder of Algorithm 7 (steps 7 through 34) operating on a precomputed `cap_a_hat` and
+/// message representative `mu`. This allows multiple messages to be signed against a single
+/// expansion of `A` (see `sign_many()`).
+///
+/// # Errors
+/// Returns an error only with the `fips-mode` feature, when the module is in the error state.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn sign_mu<
+    const CTEST: bool,
+    const K: usize,
+    const L: usize,
+    const LAMBDA_DIV4: usize,
+    const SIG_LEN: usize,
+    const W1_LEN: usize,
+>(
+    beta: i32, gamma1: i32, gamma2: i32, omega: i32, tau: i32, esk: &PrivateKey<K, L>,
+    cap_a_hat: &[[T; L]; K], mu: &[u8; 64], rnd: [u8; 32],
+) -> Result<[u8; SIG_LEN], &'static str> {
+    Ok(sign_mu_counted::<CTEST, K, L, LAMBDA_DIV4, SIG_LEN, W1_LEN>(
+        beta, gamma1, gamma2, omega, tau, esk, cap_a_hat, mu, rnd,
+    )?
+    .0)
+}
+
+
+/// As `sign_mu()`, additionally returning the number of iterations of the rejection sampling
+/// loop (for latency budgeting; the count depends only on public and per-signature values).
+///
+/// # Errors
+/// Returns an error only with the `fips-mode` feature, when the module is in the error state.
+#[allow(
+    clippy::similar_names,
+    clippy::many_single_char_names,
+    clippy::too_many_arguments,
+    clippy::too_many_lines,
+    clippy::unnecessary_wraps // Without `fips-mode`
+)]
+pub(crate) fn sign_mu_counted<
+    const CTEST: bool,
+    const K: usize,
+    const L: usize,
+    const LAMBDA_DIV4: usize,
+    const SIG_LEN: usize,
+    const W1_LEN: usize,
+>(
+    beta: i32, gamma1: i32, gamma2: i32, omega: i32, tau: i32, esk: &PrivateKey<K, L>,
+    cap_a_hat: &[[T; L]; K], mu: &[u8; 64], rnd: [u8; 32],
+) -> Result<([u8; SIG_LEN], u32), &'static str> {
+    #[cfg(feature = "fips-mode")]
+    crate::fips::check_operational()?;
+    //
+    // Extract elements from private key
+    let PrivateKey { rho: _, cap_k, tr: _, s_1_hat_mont, s_2_hat_mont, t_0_hat_mont } = esk;
 
     // 7: ρ′' ← H(K || rnd || µ, 64)    ▷ Compute private random seed
-    let mut h7 = h256_xof(&[cap_k, &rnd, &mu]);
+    let mut h7 = h256_xof(&[cap_k, &rnd, mu]);
     let mut rho_prime = [0u8; 64];
     h7.read(&mut rho_prime);
 
@@ -217,20 +341,19 @@ pub(crate) fn sign_internal<
         // 12: w ← NTT−1(cap_a_hat ◦ NTT(y))
         let w: [R; K] = {
             let y_hat: [T; L] = ntt(&y);
-            let ay_hat: [T; K] = mat_vec_mul(&cap_a_hat, &y_hat);
+            let ay_hat: [T; K] = mat_vec_mul(cap_a_hat, &y_hat);
             inv_ntt(&ay_hat)
         };
 
         // 13: w_1 ← HighBits(w)    ▷ Signer’s commitment
-        let w_1: [R; K] =
-            core::array::from_fn(|k| R(core::array::from_fn(|n| high_bits(gamma2, w[k].0[n]))));
+        let w_1: [R; K] = high_bits_poly(gamma2, &w);
 
         // There is effectively no step 14 due to formatting oddity in spec
 
         // 15: c_tildẽ ← H(mu||w1Encode(w_1), 𝜆/4)    ▷ commitment hash
         let mut w1_tilde = [0u8; W1_LEN];
         w1_encode::<K>(gamma2, &w_1, &mut w1_tilde);
-        let mut h15 = h256_xof(&[&mu, &w1_tilde]);
+        let mut h15 = h256_xof(&[mu, &w1_tilde]);
         h15.read(&mut c_tilde);
 
         // 16: c ∈ 𝑅𝑞 ← SampleInBall(c_tilde_1)    ▷ Verifier’s challenge
@@ -295,15 +418,16 @@ pub(crate) fn sign_internal<
         };
 
         // 26: h ← MakeHint(−⟨⟨c_t_0⟩⟩, w − ⟨⟨c_s_2⟩⟩ + ⟨⟨c_t_0⟩⟩)    ▷ Signer’s hint
-        h = core::array::from_fn(|k| {
-            R(core::array::from_fn(|n| {
-                i32::from(make_hint(
-                    gamma2,
-                    Q - c_t_0[k].0[n], // no reduce
-                    partial_reduce32(w[k].0[n] - c_s_2[k].0[n] + c_t_0[k].0[n]),
-                ))
-            }))
-        });
+        h = {
+            let minus_c_t_0: [R; K] =
+                core::array::from_fn(|k| R(core::array::from_fn(|n| Q - c_t_0[k].0[n]))); // no reduce
+            let r: [R; K] = core::array::from_fn(|k| {
+                R(core::array::from_fn(|n| {
+                    partial_reduce32(w[k].0[n] - c_s_2[k].0[n] + c_t_0[k].0[n])
+                }))
+            });
+            make_hint_poly(gamma2, &minus_c_t_0, &r)
+        };
 
         // There is effectively no step 22 due to formatting error in spec
 
@@ -333,7 +457,86 @@ pub(crate) fn sign_internal<
     // 34: return σ
     let zmodq: [R; L] =
         core::array::from_fn(|l| R(core::array::from_fn(|n| center_mod(z[l].0[n]))));
-    sig_encode::<CTEST, K, L, LAMBDA_DIV4, SIG_LEN>(gamma1, omega, &c_tilde, &zmodq, &h)
+    let iterations = u32::from(kappa_ctr) / u32::try_from(L).expect("cannot fail; L is static parameter") + 1;
+    Ok((sig_encode::<CTEST, K, L, LAMBDA_DIV4, SIG_LEN>(gamma1, omega, &c_tilde, &zmodq, &h), iterations))
+}
+
+
+/// Signs a batch of (pure ML-DSA) messages under a single context, expanding `A` and absorbing
+/// the `tr || 0 || |ctx| || ctx` prefix of `M′` only once. Each message consumes the next 32
+/// bytes of `rnds` and writes the corresponding entry of `sigs`.
+///
+/// # Errors
+/// Returns an error only with the `fips-mode` feature, when the module is in the error state.
+#[cfg(feature = "alloc")]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn sign_many<
+    const CTEST: bool,
+    const K: usize,
+    const L: usize,
+    const LAMBDA_DIV4: usize,
+    const SIG_LEN: usize,
+    const W1_LEN: usize,
+>(
+    beta: i32, gamma1: i32, gamma2: i32, omega: i32, tau: i32, esk: &PrivateKey<K, L>,
+    messages: &[&[u8]], ctx: &[u8], rnds: &[u8], sigs: &mut [[u8; SIG_LEN]],
+) -> Result<(), &'static str> {
+    debug_assert_eq!(messages.len(), sigs.len(), "sign_many: output length mismatch");
+    debug_assert_eq!(messages.len() * 32, rnds.len(), "sign_many: rnd length mismatch");
+
+    // 5: cap_a_hat ← ExpandA(ρ)    ▷ Shared across all messages
+    let cap_a_hat: [[T; L]; K] = expand_a::<CTEST, K, L>(&esk.rho);
+
+    // The prefix of step 6 is common to all messages, so absorb it just once
+    let prefix = mu_hasher(&esk.tr, ctx, &[], false);
+
+    for ((message, rnd), sig) in messages.iter().zip(rnds.chunks_exact(32)).zip(sigs.iter_mut()) {
+        let mut mu = [0u8; 64];
+        let mut hasher = prefix.clone();
+        hasher.update(message);
+        hasher.finalize_xof().read(&mut mu);
+        let rnd: [u8; 32] = rnd.try_into().expect("cannot fail; chunks are 32 bytes");
+        *sig = sign_mu::<CTEST, K, L, LAMBDA_DIV4, SIG_LEN, W1_LEN>(
+            beta, gamma1, gamma2, omega, tau, esk, &cap_a_hat, &mu, rnd,
+        )?;
+    }
+    Ok(())
+}
+
+
+/// Returns a hasher that has absorbed everything in `H(BytesToBits(tr)||M′, 64)` other than the
+/// trailing message (pure) or `PH𝑀` (pre-hash) element.
+pub(crate) fn mu_hasher(tr: &[u8; 64], ctx: &[u8], oid: &[u8], nist: bool) -> Shake256 {
+    let mut hasher = Shake256::default();
+    hasher.update(tr);
+    if nist {
+        // a. NIST vectors are being applied to "internal" functions
+        return hasher;
+    }
+    if oid.is_empty() {
+        // b. From ML-DSA: 𝑀′ ← BytesToBits(IntegerToBytes(0,1) ∥ IntegerToBytes(|𝑐𝑡𝑥|,1) ∥ 𝑐𝑡𝑥) ∥ 𝑀
+        hasher.update(&[0u8, ctx.len().to_le_bytes()[0]]);
+        hasher.update(ctx);
+    } else {
+        // c. From HashML-DSA: 𝑀′ ← BytesToBits(IntegerToBytes(1,1) ∥ IntegerToBytes(|𝑐𝑡𝑥|,1) ∥ 𝑐𝑡𝑥 ∥ OID ∥ PH𝑀 )
+        hasher.update(&[1u8, ctx.len().to_le_bytes()[0]]);
+        hasher.update(ctx);
+        hasher.update(oid);
+    }
+    hasher
+}
+
+
+/// Computes the message representative `µ ← H(BytesToBits(tr)||M′, 64)` based on which of the
+/// three different paths (NIST internal, pure or pre-hash) led us here.
+fn compute_mu(
+    tr: &[u8; 64], message: &[u8], ctx: &[u8], oid: &[u8], phm: &[u8], nist: bool,
+) -> [u8; 64] {
+    let mut hasher = mu_hasher(tr, ctx, oid, nist);
+    hasher.update(if oid.is_empty() || nist { message } else { phm });
+    let mut mu = [0u8; 64];
+    hasher.finalize_xof().read(&mut mu);
+    mu
 }
 
 
@@ -358,56 +561,253 @@ pub(crate) fn verify_internal<
     const W1_LEN: usize,
 >(
     beta: i32, gamma1: i32, gamma2: i32, omega: i32, tau: i32, epk: &PublicKey<K, L>, m: &[u8],
-    sig: &[u8; SIG_LEN], ctx: &[u8], oid: &[u8], phm: &[u8], nist: bool,
+    sig: &[u8; SIG_LEN], ctx: &[u8], oid: &[u8], phm: &[u8], nist: bool, mode: VerifyMode,
 ) -> bool {
+    try_verify_internal::<CTEST, K, L, LAMBDA_DIV4, PK_LEN, SIG_LEN, W1_LEN>(
+        beta, gamma1, gamma2, omega, tau, epk, m, sig, ctx, oid, phm, nist, mode,
+    )
+    .is_ok()
+}
+
+
+/// As [`verify_internal()`], but the reason for rejection is retained. This is surfaced only
+/// by the `diagnostics` feature; the production path reduces it to success/failure.
+///
+/// # Errors
+/// Returns an error describing the first failed check.
+#[allow(clippy::too_many_arguments, clippy::similar_names, clippy::type_complexity)]
+pub(crate) fn try_verify_internal<
+    const CTEST: bool,
+    const K: usize,
+    const L: usize,
+    const LAMBDA_DIV4: usize,
+    const PK_LEN: usize,
+    const SIG_LEN: usize,
+    const W1_LEN: usize,
+>(
+    beta: i32, gamma1: i32, gamma2: i32, omega: i32, tau: i32, epk: &PublicKey<K, L>, m: &[u8],
+    sig: &[u8; SIG_LEN], ctx: &[u8], oid: &[u8], phm: &[u8], nist: bool, mode: VerifyMode,
+) -> Result<(), &'static str> {
     //
     // 1: (ro, t_1) ← pkDecode(pk)  pull out pre-computed elements
-    let PublicKey { rho, tr, t1_d2_hat_mont } = epk;
+    let PublicKey { rho: _, tr, t1_d2_hat_mont: _ } = epk;
 
-    // 2: (c_tilde, z, h) ← sigDecode(σ)    ▷ Signer’s commitment hash c_tilde, response z and hint h
-    let Ok((c_tilde, z, h)): Result<([u8; LAMBDA_DIV4], [R; L], Option<[R; K]>), &'static str> =
-        sig_decode(gamma1, omega, sig)
-    else {
-        return false;
+    // 6: tr ← H(pk, 64)
+    // --> extracted from public key pre-computes in step 1 above
+
+    // 7: 𝜇 ← (H(BytesToBits(tr)||𝑀′, 64))    ▷ Compute message representative µ
+    let mu = compute_mu(tr, m, ctx, oid, phm, nist);
+
+    try_verify_mu::<K, L, LAMBDA_DIV4, SIG_LEN, W1_LEN>(beta, gamma1, gamma2, omega, tau, epk, &mu, sig, mode)
+}
+
+
+/// Remainder of Algorithm 8 (steps 2-5 and 8-13) operating on a precomputed message
+/// representative `mu`. This allows the message to be absorbed incrementally (see the
+/// streaming verifier).
+///
+/// # Errors
+/// Returns an error describing the first failed check.
+#[allow(clippy::too_many_arguments, clippy::similar_names)]
+pub(crate) fn try_verify_mu<
+    const K: usize,
+    const L: usize,
+    const LAMBDA_DIV4: usize,
+    const SIG_LEN: usize,
+    const W1_LEN: usize,
+>(
+    beta: i32, gamma1: i32, gamma2: i32, omega: i32, tau: i32, epk: &PublicKey<K, L>,
+    mu: &[u8; 64], sig: &[u8; SIG_LEN], mode: VerifyMode,
+) -> Result<(), &'static str> {
+    //
+    // With fast rejection, the norm check of step 13 is brought forward into `reconstruct_w1()`
+    let early_norm_bound = match mode {
+        VerifyMode::Uniform => None,
+        VerifyMode::FastReject => Some(gamma1 - beta),
     };
 
+    // 2-5 and 8-11 are performed within `reconstruct_w1()`, which leaves w1Encode(w′_1) in `tmp`
+    let mut tmp = [0u8; W1_LEN];
+    let (c_tilde, z) = reconstruct_w1::<K, L, LAMBDA_DIV4, SIG_LEN, W1_LEN>(
+        gamma1, gamma2, omega, tau, epk, sig, early_norm_bound, &mut tmp,
+    )?;
+
+    // 6-7: µ was computed by the caller
+
+    // 12: c_tilde_′ ← H(µ || w1Encode(w′_1), λ/4)     ▷ Hash it; this should match c_tilde
+    let mut h12 = h256_xof(&[mu, &tmp]);
+    let mut c_tilde_p = [0u8; LAMBDA_DIV4];
+    h12.read(&mut c_tilde_p);
+
+    // 13: return [[ ||z||∞ < γ1 −β]] and [[c_tilde = c_tilde_′]]
+    match mode {
+        VerifyMode::Uniform => {
+            let left = infinity_norm(&z) < (gamma1 - beta);
+            let right = c_tilde.iter().zip(c_tilde_p.iter()).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0;
+            // Only after all of the work is the reason for any rejection distinguished
+            if !(left & right) {
+                return Err(if left { "Alg 8: c_tilde mismatch" } else { "Alg 8: z out of range" });
+            }
+            Ok(())
+        }
+        // The norm was already checked within `reconstruct_w1()`
+        VerifyMode::FastReject => {
+            ensure!(c_tilde == c_tilde_p, "Alg 8: c_tilde mismatch");
+            Ok(())
+        }
+    }
+}
+
+
+/// Steps 2-5 and 8-12 of Algorithm 8 `ML-DSA.Verify_internal()` without the final comparison of
+/// step 13: returns `w1Encode(w′_1)`, the signature's `c_tilde`, the recomputed `c_tilde_′` and
+/// whether `||z||∞ < γ1 − β`, for experimental batch/aggregate verification.
+///
+/// # Errors
+/// Returns an error when the signature (or its hint) is malformed.
+#[cfg(all(feature = "hazmat", feature = "unstable", not(feature = "fips-mode")))]
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(crate) fn verify_residual<
+    const K: usize,
+    const L: usize,
+    const LAMBDA_DIV4: usize,
+    const SIG_LEN: usize,
+    const W1_LEN: usize,
+>(
+    beta: i32, gamma1: i32, gamma2: i32, omega: i32, tau: i32, epk: &PublicKey<K, L>,
+    mu: &[u8; 64], sig: &[u8; SIG_LEN],
+) -> Result<([u8; W1_LEN], [u8; LAMBDA_DIV4], [u8; LAMBDA_DIV4], bool), &'static str> {
+    let mut w1_tilde = [0u8; W1_LEN];
+    let (c_tilde, z) = reconstruct_w1::<K, L, LAMBDA_DIV4, SIG_LEN, W1_LEN>(
+        gamma1, gamma2, omega, tau, epk, sig, None, &mut w1_tilde,
+    )?;
+
+    // 12: c_tilde_′ ← H(µ || w1Encode(w′_1), λ/4)
+    let mut c_tilde_p = [0u8; LAMBDA_DIV4];
+    h256_xof(&[mu, &w1_tilde]).read(&mut c_tilde_p);
+    Ok((w1_tilde, c_tilde, c_tilde_p, infinity_norm(&z) < (gamma1 - beta)))
+}
+
+
+/// Steps 2-5 and 8-11 of Algorithm 8 `ML-DSA.Verify_internal()`: decodes the signature and
+/// reconstructs the signer's commitment `w′_1`, writing `w1Encode(w′_1)` into `w1_tilde`. The
+/// decoded `c_tilde` and `z` are returned for the final checks of step 13. When an
+/// `early_norm_bound` is supplied, a `z` whose norm is not below it is rejected before the
+/// matrix expansion and NTT work.
+///
+/// # Errors
+/// Returns an error when the signature (or its hint) is malformed, or `z` exceeds the bound.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(crate) fn reconstruct_w1<
+    const K: usize,
+    const L: usize,
+    const LAMBDA_DIV4: usize,
+    const SIG_LEN: usize,
+    const W1_LEN: usize,
+>(
+    gamma1: i32, gamma2: i32, omega: i32, tau: i32, epk: &PublicKey<K, L>, sig: &[u8; SIG_LEN],
+    early_norm_bound: Option<i32>, w1_tilde: &mut [u8; W1_LEN],
+) -> Result<([u8; LAMBDA_DIV4], [R; L]), &'static str> {
+    let (c_tilde, z, h, c) =
+        decode_challenge::<K, L, LAMBDA_DIV4, SIG_LEN>(gamma1, omega, tau, sig, early_norm_bound)?;
+    // The NTTs of step 9 are taken here rather than held in a `DecodedSig` alongside `z` and
+    // `h`, keeping the single-key path within its stack budget
+    reconstruct_w1_from::<K, L, W1_LEN>(gamma2, epk, &ntt(&z), &ntt(&[c])[0], &h, w1_tilde);
+    Ok((c_tilde, z))
+}
+
+
+/// The key-independent part of verification: a decoded signature, with `NTT(z)` and `NTT(c)`
+/// precomputed so that it can be checked against several public keys.
+pub(crate) struct DecodedSig<const K: usize, const L: usize, const LAMBDA_DIV4: usize> {
+    c_tilde: [u8; LAMBDA_DIV4],
+    z: [R; L],
+    z_hat: [T; L],
+    c_hat: T,
+    h: [R; K],
+}
+
+
+/// Steps 2-4 and 8 of Algorithm 8 `ML-DSA.Verify_internal()`: decodes the signature and
+/// samples the challenge `c`, which involve no key. When an `early_norm_bound` is supplied, a
+/// `z` whose norm is not below it is rejected before the NTT work.
+///
+/// # Errors
+/// Returns an error when the signature (or its hint) is malformed, or `z` exceeds the bound.
+pub(crate) fn decode_sig<const K: usize, const L: usize, const LAMBDA_DIV4: usize, const SIG_LEN: usize>(
+    gamma1: i32, omega: i32, tau: i32, sig: &[u8; SIG_LEN], early_norm_bound: Option<i32>,
+) -> Result<DecodedSig<K, L, LAMBDA_DIV4>, &'static str> {
+    let (c_tilde, z, h, c) =
+        decode_challenge::<K, L, LAMBDA_DIV4, SIG_LEN>(gamma1, omega, tau, sig, early_norm_bound)?;
+
+    // The NTTs of step 9
+    let z_hat: [T; L] = ntt(&z);
+    let [c_hat] = ntt(&[c]);
+    Ok(DecodedSig { c_tilde, z, z_hat, c_hat, h })
+}
+
+
+/// Steps 2-4 and 8 of Algorithm 8 `ML-DSA.Verify_internal()`, as [`decode_sig()`] but without
+/// the NTTs: returns `c_tilde`, `z`, `h` and the challenge `c`. Always inlined, as returning
+/// the tuple through a separate frame pushes verification past its stack budget.
+///
+/// # Errors
+/// Returns an error when the signature (or its hint) is malformed, or `z` exceeds the bound.
+#[allow(clippy::type_complexity, clippy::inline_always)]
+#[inline(always)]
+fn decode_challenge<const K: usize, const L: usize, const LAMBDA_DIV4: usize, const SIG_LEN: usize>(
+    gamma1: i32, omega: i32, tau: i32, sig: &[u8; SIG_LEN], early_norm_bound: Option<i32>,
+) -> Result<([u8; LAMBDA_DIV4], [R; L], [R; K], R), &'static str> {
+    #[cfg(feature = "fips-mode")]
+    crate::fips::check_operational()?;
+
+    // 2: (c_tilde, z, h) ← sigDecode(σ)    ▷ Signer’s commitment hash c_tilde, response z and hint h
+    let (c_tilde, z, h): ([u8; LAMBDA_DIV4], [R; L], Option<[R; K]>) =
+        sig_decode(gamma1, omega, sig)?;
+
     // 3: if h = ⊥ then return false     ▷ Hint was not properly encoded
     // 4: end if
-    let Some(h) = h else { return false };
+    let Some(h) = h else { return Err("Alg 8: hint not properly encoded") };
 
     debug_assert!(infinity_norm(&z) <= gamma1, "Alg 8: i_norm out of range"); // Fuzz target
 
-
-    // 6: tr ← H(pk, 64)
-    // --> extracted from public key pre-computes in step 1 above
-
-    // 7: 𝜇 ← (H(BytesToBits(tr)||𝑀′, 64))    ▷ Compute message representative µ
-    // Calculate mu based on which of the three different paths led us here
-    let mut h7 = if nist {
-        // 7a. NIST vectors are being applied to "internal" functions
-        h256_xof(&[tr, m])
-    } else if oid.is_empty() {
-        // 7b. From ML-DSA.Verify(): 5: 𝑀′ ← BytesToBits(IntegerToBytes(0,1) ∥ IntegerToBytes(|𝑐𝑡𝑥|,1) ∥ 𝑐𝑡𝑥) ∥ 𝑀
-        h256_xof(&[tr, &[0u8], &[ctx.len().to_le_bytes()[0]], ctx, m])
-    } else {
-        // 7c. From HashML-DSA.Verify(): 18: 𝑀′ ← BytesToBits(IntegerToBytes(1,1) ∥ IntegerToBytes(|𝑐𝑡𝑥|,1) ∥ 𝑐𝑡𝑥 ∥ OID ∥ PH𝑀 )
-        h256_xof(&[tr, &[1u8], &[ctx.len().to_le_bytes()[0]], ctx, oid, phm])
-    };
-    let mut mu = [0u8; 64];
-    h7.read(&mut mu);
+    // Part of step 13, brought forward when rejecting early
+    if let Some(bound) = early_norm_bound {
+        ensure!(infinity_norm(&z) < bound, "Alg 8: z out of range");
+    }
 
     // 8: c ∈ 𝑅𝑞 ← SampleInBall(c_tilde_1)    ▷ Compute verifier’s challenge from c_tilde
     let c: R = sample_in_ball::<false>(tau, &c_tilde); // CTEST is always false (as no CT guarantees)
+    Ok((c_tilde, z, h, c))
+}
+
+
+/// Steps 5 and 9-11 of Algorithm 8 `ML-DSA.Verify_internal()` for a decoded signature:
+/// reconstructs the signer's commitment `w′_1` under the public key `epk`, writing
+/// `w1Encode(w′_1)` into `w1_tilde`.
+pub(crate) fn reconstruct_w1_decoded<const K: usize, const L: usize, const LAMBDA_DIV4: usize, const W1_LEN: usize>(
+    gamma2: i32, epk: &PublicKey<K, L>, decoded: &DecodedSig<K, L, LAMBDA_DIV4>, w1_tilde: &mut [u8; W1_LEN],
+) {
+    reconstruct_w1_from::<K, L, W1_LEN>(gamma2, epk, &decoded.z_hat, &decoded.c_hat, &decoded.h, w1_tilde);
+}
+
+
+/// Steps 5 and 9-11 of Algorithm 8 `ML-DSA.Verify_internal()` given `NTT(z)`, `NTT(c)` and the
+/// hint `h`.
+fn reconstruct_w1_from<const K: usize, const L: usize, const W1_LEN: usize>(
+    gamma2: i32, epk: &PublicKey<K, L>, z_hat: &[T; L], c_hat: &T, h: &[R; K], w1_tilde: &mut [u8; W1_LEN],
+) {
+    //
+    // 1: (ro, t_1) ← pkDecode(pk)  pull out pre-computed elements
+    let PublicKey { rho, tr: _, t1_d2_hat_mont } = epk;
 
     // 5: cap_a_hat ← ExpandA(ρ)    ▷ A is generated and stored in NTT representation as cap_A_hat
     // 9: w′_Approx ← invNTT(cap_A_hat ◦ NTT(z) - NTT(c) ◦ NTT(t_1 · 2^d)    ▷ w′_Approx = Az − ct1·2^d
     let wp_approx: [R; K] = {
-        // CTEST is always false (as no CT guarantees); from step 5 above
-        let cap_a_hat: [[T; L]; K] = expand_a::<CTEST, K, L>(rho);
-        let z_hat: [T; L] = ntt(&z);
-        let az_hat: [T; K] = mat_vec_mul(&cap_a_hat, &z_hat);
+        // CTEST is always false (as no CT guarantees); from step 5 above, with each element
+        // expanded as it is consumed so that the matrix is never held on the stack
+        let az_hat: [T; K] = mat_vec_mul_with(|r, s| expand_a_element::<false>(rho, r, s), z_hat);
         // NTT(t_1 · 2^d) --> extracted from public key struct
-        let c_hat: &T = &ntt(&[c])[0];
         inv_ntt(&core::array::from_fn(|k| {
             T(core::array::from_fn(|n| {
                 az_hat[k].0[n]
@@ -417,23 +817,182 @@ pub(crate) fn verify_internal<
     };
 
     // 10: w′_1 ← UseHint(h, w′_Approx)    ▷ Reconstruction of signer’s commitment
-    let wp_1: [R; K] = core::array::from_fn(|k| {
-        R(core::array::from_fn(|n| use_hint(gamma2, h[k].0[n], wp_approx[k].0[n])))
-    });
+    let wp_1: [R; K] = use_hint_poly(gamma2, h, &wp_approx);
 
     // There is effectively no step 11 due to formatting oddity in spec
 
-    // 12: c_tilde_′ ← H(µ || w1Encode(w′_1), λ/4)     ▷ Hash it; this should match c_tilde
-    let mut tmp = [0u8; W1_LEN];
-    w1_encode::<K>(gamma2, &wp_1, &mut tmp);
-    let mut h12 = h256_xof(&[&mu, &tmp]);
-    let mut c_tilde_p = [0u8; LAMBDA_DIV4];
-    h12.read(&mut c_tilde_p);
+    // The w1Encode(w′_1) portion of step 12
+    w1_encode::<K>(gamma2, &wp_1, w1_tilde);
+}
 
-    // 13: return [[ ||z||∞ < γ1 −β]] and [[c_tilde = c_tilde_′]]
-    let left = infinity_norm(&z) < (gamma1 - beta);
-    let right = c_tilde == c_tilde_p; // verify() is not CT
-    left && right
+
+/// Algorithm 8 `ML-DSA.Verify_internal()` (of pure ML-DSA) against each of the public keys
+/// `epks` in turn, returning the index of the first under which the signature verifies. The
+/// signature is decoded, and `NTT(z)` and the challenge computed, just once; `µ` depends on
+/// the key's `tr`, so is only reused across consecutive keys sharing it.
+#[allow(clippy::too_many_arguments, clippy::similar_names)]
+pub(crate) fn verify_any<
+    const K: usize,
+    const L: usize,
+    const LAMBDA_DIV4: usize,
+    const SIG_LEN: usize,
+    const W1_LEN: usize,
+>(
+    beta: i32, gamma1: i32, gamma2: i32, omega: i32, tau: i32, epks: &[&PublicKey<K, L>], m: &[u8],
+    sig: &[u8; SIG_LEN], ctx: &[u8],
+) -> Option<usize> {
+    let decoded = decode_sig::<K, L, LAMBDA_DIV4, SIG_LEN>(gamma1, omega, tau, sig, None).ok()?;
+
+    // Part of step 13, which involves no key
+    let z_in_range = infinity_norm(&decoded.z) < (gamma1 - beta);
+
+    let mut mu = [0u8; 64];
+    let mut mu_tr: Option<&[u8; 64]> = None;
+    for (index, epk) in epks.iter().enumerate() {
+        // 7: 𝜇 ← (H(BytesToBits(tr)||𝑀′, 64))
+        if mu_tr != Some(&epk.tr) {
+            mu = compute_mu(&epk.tr, m, ctx, &[], &[], false);
+            mu_tr = Some(&epk.tr);
+        }
+
+        // 12: c_tilde_′ ← H(µ || w1Encode(w′_1), λ/4)
+        let mut w1_tilde = [0u8; W1_LEN];
+        reconstruct_w1_decoded::<K, L, LAMBDA_DIV4, W1_LEN>(gamma2, epk, &decoded, &mut w1_tilde);
+        let mut c_tilde_p = [0u8; LAMBDA_DIV4];
+        h256_xof(&[&mu, &w1_tilde]).read(&mut c_tilde_p);
+
+        // 13: return [[ ||z||∞ < γ1 −β]] and [[c_tilde = c_tilde_′]]
+        if z_in_range && c_tilde_p == decoded.c_tilde {
+            return Some(index);
+        }
+    }
+    None
+}
+
+
+/// Algorithm 8 `ML-DSA.Verify_internal()` over a signature supplied incrementally, so that it
+/// need never be resident in full. Each polynomial of `z` is decoded, transformed and multiplied
+/// into the accumulated `NTT(A·z)` as soon as its bytes are complete; thereafter only `c_tilde`,
+/// the partial `z` polynomial and the hint bytes are buffered. `Z_LEN` is the encoded length of
+/// one polynomial of `z`, and `HINT_LEN` is `ω + k`.
+#[derive(Clone)]
+pub(crate) struct IncrementalSig<
+    const K: usize,
+    const L: usize,
+    const LAMBDA_DIV4: usize,
+    const Z_LEN: usize,
+    const HINT_LEN: usize,
+> {
+    c_tilde: [u8; LAMBDA_DIV4],
+    z_bytes: [u8; Z_LEN],
+    hint: [u8; HINT_LEN],
+    received: usize,
+    az_hat: [T; K],
+    z_norm: i32,
+}
+
+
+impl<const K: usize, const L: usize, const LAMBDA_DIV4: usize, const Z_LEN: usize, const HINT_LEN: usize>
+    IncrementalSig<K, L, LAMBDA_DIV4, Z_LEN, HINT_LEN>
+{
+    const SIG_LEN: usize = LAMBDA_DIV4 + L * Z_LEN + HINT_LEN;
+
+
+    pub(crate) fn new() -> Self {
+        Self {
+            c_tilde: [0u8; LAMBDA_DIV4],
+            z_bytes: [0u8; Z_LEN],
+            hint: [0u8; HINT_LEN],
+            received: 0,
+            az_hat: core::array::from_fn(|_| T([0i32; 256])),
+            z_norm: 0,
+        }
+    }
+
+
+    /// Absorbs the next bytes of the signature.
+    ///
+    /// # Errors
+    /// Returns an error when more than the signature length is supplied, or a polynomial of
+    /// `z` is malformed.
+    pub(crate) fn update(&mut self, gamma1: i32, rho: &[u8; 32], mut bytes: &[u8]) -> Result<(), &'static str> {
+        ensure!(bytes.len() <= Self::SIG_LEN - self.received, "Alg 8: signature too long");
+        while !bytes.is_empty() {
+            let (region, offset): (&mut [u8], usize) = if self.received < LAMBDA_DIV4 {
+                (&mut self.c_tilde, self.received)
+            } else if self.received < LAMBDA_DIV4 + L * Z_LEN {
+                (&mut self.z_bytes, (self.received - LAMBDA_DIV4) % Z_LEN)
+            } else {
+                (&mut self.hint, self.received - LAMBDA_DIV4 - L * Z_LEN)
+            };
+            let take = bytes.len().min(region.len() - offset);
+            region[offset..offset + take].copy_from_slice(&bytes[..take]);
+            (self.received, bytes) = (self.received + take, &bytes[take..]);
+
+            // 9: (part of) w′_Approx ← invNTT(cap_A_hat ◦ NTT(z) ...), one column of A at a time
+            let z_end = self.received.checked_sub(LAMBDA_DIV4).filter(|end| *end <= L * Z_LEN);
+            if let Some(j) = z_end.filter(|end| *end > 0 && end % Z_LEN == 0).map(|end| end / Z_LEN - 1) {
+                let z_j = bit_unpack(&self.z_bytes, gamma1 - 1, gamma1)?;
+                self.z_norm = self.z_norm.max(infinity_norm(core::array::from_ref(&z_j)));
+                let z_hat_mont_j = &to_mont(&ntt(&[z_j]))[0];
+                for (i, az_hat_i) in self.az_hat.iter_mut().enumerate() {
+                    let a_hat_ij = expand_a_element::<false>(rho, i, j);
+                    az_hat_i.0.iter_mut().enumerate().for_each(|(n, e)| {
+                        *e += mont_reduce(i64::from(a_hat_ij.0[n]) * i64::from(z_hat_mont_j.0[n]));
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+
+    /// Completes steps 3-4, 8-10 and 12-13 of Algorithm 8 over the message representative `mu`.
+    ///
+    /// # Errors
+    /// Returns an error when the signature is incomplete or malformed, or does not verify.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn finish<const W1_LEN: usize>(
+        &self, beta: i32, gamma1: i32, gamma2: i32, omega: i32, tau: i32, epk: &PublicKey<K, L>,
+        mu: &[u8; 64],
+    ) -> Result<(), &'static str> {
+        ensure!(self.received == Self::SIG_LEN, "Alg 8: signature incomplete");
+        #[cfg(feature = "fips-mode")]
+        crate::fips::check_operational()?;
+
+        // 3: if h = ⊥ then return false     ▷ Hint was not properly encoded
+        let h: [R; K] = hint_bit_unpack::<K>(omega, &self.hint)?;
+
+        // 8: c ∈ 𝑅𝑞 ← SampleInBall(c_tilde_1)
+        let c: R = sample_in_ball::<false>(tau, &self.c_tilde);
+
+        // 9: (remainder of) w′_Approx ← invNTT(cap_A_hat ◦ NTT(z) - NTT(c) ◦ NTT(t_1 · 2^d)
+        let c_hat: &T = &ntt(&[c])[0];
+        let wp_approx: [R; K] = inv_ntt(&core::array::from_fn(|k| {
+            T(core::array::from_fn(|n| {
+                self.az_hat[k].0[n]
+                    - mont_reduce(i64::from(c_hat.0[n]) * i64::from(epk.t1_d2_hat_mont[k].0[n]))
+            }))
+        }));
+
+        // 10: w′_1 ← UseHint(h, w′_Approx)
+        let wp_1: [R; K] = use_hint_poly(gamma2, &h, &wp_approx);
+
+        // 12: c_tilde_′ ← H(µ || w1Encode(w′_1), λ/4)
+        let mut w1_tilde = [0u8; W1_LEN];
+        w1_encode::<K>(gamma2, &wp_1, &mut w1_tilde);
+        let mut h12 = h256_xof(&[mu, &w1_tilde]);
+        let mut c_tilde_p = [0u8; LAMBDA_DIV4];
+        h12.read(&mut c_tilde_p);
+
+        // 13: return [[ ||z||∞ < γ1 −β]] and [[c_tilde = c_tilde_′]]
+        let left = self.z_norm < (gamma1 - beta);
+        let right = self.c_tilde.iter().zip(c_tilde_p.iter()).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0;
+        if !(left & right) {
+            return Err(if left { "Alg 8: c_tilde mismatch" } else { "Alg 8: z out of range" });
+        }
+        Ok(())
+    }
 }
 
 
@@ -490,14 +1049,148 @@ pub(crate) fn expand_public<const K: usize, const L: usize, const PK_LEN: usize>
     // the last term of:
     // 9: 𝐰Approx ← NTT (𝐀 ∘ NTT(𝐳) − NTT(𝑐) ∘ NTT(𝐭1 ⋅ 2𝑑 ))    ▷ 𝐰Approx = 𝐀𝐳 − 𝑐𝐭1 ⋅ 2𝑑
     let t1_hat_mont: [T; K] = to_mont(&ntt(&t_1));
-    let t1_d2_hat_mont: [T; K] = to_mont(&core::array::from_fn(|k| {
-        T(core::array::from_fn(|n| mont_reduce(i64::from(t1_hat_mont[k].0[n]) << D)))
-    }));
+    let t1_d2_hat_mont: [T; K] = to_mont(&mont_mul_const(&t1_hat_mont, 1 << D));
 
     Ok(PublicKey { rho: *rho, tr, t1_d2_hat_mont })
 }
 
 
+/// As `expand_private()`, but writing the expanded key into caller-provided storage one
+/// polynomial at a time, so that no complete expanded key is held on the stack. On error, the
+/// storage is zeroized.
+///
+/// # Errors
+/// Returns an error on malformed private key.
+pub(crate) fn expand_private_into<const K: usize, const L: usize, const SK_LEN: usize>(
+    eta: i32, sk: &[u8; SK_LEN], out: &mut PrivateKey<K, L>,
+) -> Result<(), &'static str> {
+    //
+    // 1: (ρ, K, tr, s_1, s_2, t_0) ← skDecode(sk)
+    let (rho, cap_k, tr, s_1, s_2, t_0) = match sk_decode::<K, L, SK_LEN>(eta, sk) {
+        Ok(decoded) => decoded,
+        Err(e) => {
+            out.zeroize();
+            return Err(e);
+        }
+    };
+    out.rho = *rho;
+    out.cap_k = *cap_k;
+    out.tr = *tr;
+
+    // 2-4: s_hat_1 ← NTT(s_1), s_hat_2 ← NTT(s_2), t_hat_0 ← NTT(t_0)
+    for (s_1_hat_mont, s_1) in out.s_1_hat_mont.iter_mut().zip(s_1.iter()) {
+        let [s_1_hat] = to_mont(&ntt(core::array::from_ref(s_1)));
+        *s_1_hat_mont = s_1_hat;
+    }
+    for (s_2_hat_mont, s_2) in out.s_2_hat_mont.iter_mut().zip(s_2.iter()) {
+        let [s_2_hat] = to_mont(&ntt(core::array::from_ref(s_2)));
+        *s_2_hat_mont = s_2_hat;
+    }
+    for (t_0_hat_mont, t_0) in out.t_0_hat_mont.iter_mut().zip(t_0.iter()) {
+        let [t_0_hat] = to_mont(&ntt(core::array::from_ref(t_0)));
+        *t_0_hat_mont = t_0_hat;
+    }
+    Ok(())
+}
+
+
+/// As `expand_public()`, but writing the expanded key into caller-provided storage one
+/// polynomial at a time, so that no complete expanded key is held on the stack.
+///
+/// # Errors
+/// Returns an error on malformed public key.
+pub(crate) fn expand_public_into<const K: usize, const L: usize, const PK_LEN: usize>(
+    pk: &[u8; PK_LEN], out: &mut PublicKey<K, L>,
+) -> Result<(), &'static str> {
+    const BLQD: usize = 10; // bitlen(q−1) − d
+    debug_assert_eq!(PK_LEN, 32 + 32 * K * BLQD, "Alg 23: bad pk/config size");
+    out.rho.copy_from_slice(&pk[0..32]);
+
+    // 6: tr ← H(pk, 64)
+    h256_xof(&[pk]).read(&mut out.tr);
+
+    // As in `expand_public()`: t1 ← pkDecode(pk), then NTT(t1 ⋅ 2^d) in Montgomery form
+    for (k, t1_d2_hat_mont) in out.t1_d2_hat_mont.iter_mut().enumerate() {
+        *t1_d2_hat_mont = expand_public_row(pk, k)?;
+    }
+    Ok(())
+}
+
+
+/// Row `k` of `expand_public()`: decodes `t1[k]` from the encoded public key and returns
+/// `NTT(t1[k] ⋅ 2^d)` in Montgomery form, for expanding a public key one row at a time.
+///
+/// # Errors
+/// Returns an error on malformed public key.
+pub(crate) fn expand_public_row<const PK_LEN: usize>(pk: &[u8; PK_LEN], k: usize) -> Result<T, &'static str> {
+    const BLQD: usize = 10; // bitlen(q−1) − d
+    let t_1 = simple_bit_unpack(&pk[32 + 32 * k * BLQD..32 + 32 * (k + 1) * BLQD], (1 << BLQD) - 1)?;
+    Ok(t1_d2_hat_mont(&t_1))
+}
+
+
+/// Row `k` of `private_to_public_key()`: computes `t1[k]` while expanding only row `k` of `A`
+/// (one element at a time), and returns `NTT(t1[k] ⋅ 2^d)` in Montgomery form, for deriving a
+/// public key one row at a time.
+pub(crate) fn private_to_public_row<const K: usize, const L: usize>(sk: &PrivateKey<K, L>, k: usize) -> T {
+    // mont->norm elements to recover s_1_hat, and row k of s_2 (centered around 0)
+    let s_1_hat: [T; L] = from_mont(&sk.s_1_hat_mont);
+    let [s_2] = inv_ntt(&from_mont(core::array::from_ref(&sk.s_2_hat_mont[k])));
+    let s_2 = R(core::array::from_fn(|n| if s_2.0[n] > (Q / 2) { s_2.0[n] - Q } else { s_2.0[n] }));
+
+    // 5: t[k] ← NTT−1(cap_a_hat[k] ◦ NTT(s_1)) + s_2[k]
+    let as1_hat: [T; 1] = mat_vec_mul_with(|_, s| expand_a_element::<false>(&sk.rho, k, s), &s_1_hat);
+    let [t_not_reduced] = add_vector_ntt(&inv_ntt(&as1_hat), &[s_2]);
+    let t = R(core::array::from_fn(|n| full_reduce32(t_not_reduced.0[n])));
+
+    // 6: (t_1, t_0) ← Power2Round(t, d)
+    let ([t_1], _t_0) = power2round(&[t]);
+    t1_d2_hat_mont(&t_1)
+}
+
+
+/// Returns `NTT(t1 ⋅ 2^d)` in Montgomery form for one polynomial of `t1`, as in the last term of
+/// verification step 9.
+fn t1_d2_hat_mont(t_1: &R) -> T {
+    let [t1_hat_mont] = to_mont(&ntt(core::array::from_ref(t_1)));
+    let [t1_d2_hat] = to_mont(&mont_mul_const(&[t1_hat_mont], 1 << D));
+    t1_d2_hat
+}
+
+
+/// Checks that an encoded private key is internally consistent, i.e., that its `t0` and `tr`
+/// are those that key generation derives from its `rho`, `s_1` and `s_2`. Decoding alone only
+/// checks the coefficient ranges.
+///
+/// # Errors
+/// Returns an error on a malformed or inconsistent private key.
+pub(crate) fn check_private<const K: usize, const L: usize, const PK_LEN: usize, const SK_LEN: usize>(
+    eta: i32, sk: &[u8; SK_LEN],
+) -> Result<(), &'static str> {
+    let (rho, _cap_k, tr, s_1, s_2, t_0) = sk_decode::<K, L, SK_LEN>(eta, sk)?;
+
+    // As key generation steps 3-6: t ← NTT−1(cap_a_hat ◦ NTT(s_1)) + s_2, (t_1, t_0) ← Power2Round(t, d)
+    let cap_a_hat: [[T; L]; K] = expand_a::<false, K, L>(rho);
+    let as1_hat: [T; K] = mat_vec_mul(&cap_a_hat, &ntt(&s_1));
+    let t_not_reduced: [R; K] = add_vector_ntt(&inv_ntt(&as1_hat), &s_2);
+    let t: [R; K] =
+        core::array::from_fn(|k| R(core::array::from_fn(|n| full_reduce32(t_not_reduced[k].0[n]))));
+    let (t_1, t_0_expected) = power2round(&t);
+
+    // 8-9: tr ← H(pkEncode(ρ, t_1), 64)
+    let mut tr_expected = [0u8; 64];
+    h256_xof(&[&pk_encode::<K, PK_LEN>(rho, &t_1)]).read(&mut tr_expected);
+
+    // Compare all of t_0 (secret) without early exit
+    let t_0_diff = t_0.iter().zip(t_0_expected.iter()).fold(0, |acc, (a, b)| {
+        a.0.iter().zip(b.0.iter()).fold(acc, |acc, (x, y)| acc | (x ^ y))
+    });
+    ensure!(t_0_diff == 0, "PrivateKey: t0 does not match s1 and s2");
+    ensure!(*tr == tr_expected, "PrivateKey: tr does not match the public key");
+    Ok(())
+}
+
+
 /// Generate public key corresponding to a private key
 pub(crate) fn private_to_public_key<const K: usize, const L: usize>(
     sk: &PrivateKey<K, L>,
@@ -508,14 +1201,10 @@ pub(crate) fn private_to_public_key<const K: usize, const L: usize>(
     let cap_a_hat: [[T; L]; K] = expand_a::<false, K, L>(rho);
 
     // mont->norm elements to recover s_1_hat
-    let s_1_hat: [T; L] = core::array::from_fn(|l| {
-        T(core::array::from_fn(|n| mont_reduce(i64::from(s_1_hat_mont[l].0[n]))))
-    });
+    let s_1_hat: [T; L] = from_mont(s_1_hat_mont);
 
     // mont->norm each n coeff, of L entries of T, then inverse NTT into R
-    let s_2: [R; K] = inv_ntt(&core::array::from_fn(|k| {
-        T(core::array::from_fn(|n| mont_reduce(i64::from(s_2_hat_mont[k].0[n]))))
-    }));
+    let s_2: [R; K] = inv_ntt(&from_mont(s_2_hat_mont));
     // correct each coeff such that they are centered around 0
     let s_2: [R; K] = core::array::from_fn(|k| {
         R(core::array::from_fn(|n| {
@@ -527,9 +1216,7 @@ pub(crate) fn private_to_public_key<const K: usize, const L: usize>(
         }))
     });
 
-    let t_0: [R; K] = inv_ntt(&core::array::from_fn(|k| {
-        T(core::array::from_fn(|n| mont_reduce(i64::from(t_0_hat_mont[k].0[n]))))
-    }));
+    let t_0: [R; K] = inv_ntt(&from_mont(t_0_hat_mont));
     let sk_t_0: [R; K] = core::array::from_fn(|k| {
         R(core::array::from_fn(|n| {
             if t_0[k].0[n] > (Q / 2) {
@@ -554,9 +1241,7 @@ pub(crate) fn private_to_public_key<const K: usize, const L: usize>(
     // 7: pk ← pkEncode(ρ, t_1)
     // 9: 𝐰Approx ← NTT (𝐀 ∘ NTT(𝐳) − NTT(𝑐) ∘ NTT(𝐭1 ⋅ 2𝑑 ))    ▷ 𝐰Approx = 𝐀𝐳 − 𝑐𝐭1 ⋅ 2𝑑
     let t1_hat_mont: [T; K] = to_mont(&ntt(&t_1));
-    let t1_d2_hat_mont: [T; K] = to_mont(&core::array::from_fn(|k| {
-        T(core::array::from_fn(|n| mont_reduce(i64::from(t1_hat_mont[k].0[n]) << D)))
-    }));
+    let t1_d2_hat_mont: [T; K] = to_mont(&mont_mul_const(&t1_hat_mont, 1 << D));
 
     // 10: return pk
     PublicKey { rho: *rho, tr: *tr, t1_d2_hat_mont }
@@ -1,6 +1,6 @@
 // This file implements functionality from FIPS 204 section 7.5 `NTT` and `invNTT`
 
-use crate::helpers::{full_reduce32, mont_reduce, ZETA_TABLE_MONT};
+use crate::helpers::{checked, mont_mul_const_full, mont_reduce, ZETA_TABLE_MONT};
 use crate::types::{R, T};
 use crate::Q;
 
@@ -45,13 +45,13 @@ pub(crate) fn ntt<const KL: usize>(w: &[R; KL]) -> [T; KL] {
                 for j in start..(start + len) {
                     //
                     // 12: t ← zeta · w_hat[j + len]
-                    let t = mont_reduce(zeta * i64::from(w_poly.0[j + len]));
+                    let t = mont_reduce(checked!(mul, zeta, i64::from(w_poly.0[j + len]), "NTT (zeta·w)"));
 
                     // 13: w_hat[j + len] ← w_hat[j] − t
-                    w_poly.0[j + len] = w_poly.0[j] - t;
+                    w_poly.0[j + len] = checked!(sub, w_poly.0[j], t, "NTT butterfly (w - t)");
 
                     // 14: w_hat[j] ← w_hat[j] + t
-                    w_poly.0[j] += t;
+                    w_poly.0[j] = checked!(add, w_poly.0[j], t, "NTT butterfly (w + t)");
 
                     // 15: end for
                 }
@@ -85,7 +85,7 @@ pub(crate) fn ntt<const KL: usize>(w: &[R; KL]) -> [T; KL] {
 pub(crate) fn inv_ntt<const KL: usize>(w_hat: &[T; KL]) -> [R; KL] {
     //
     #[allow(clippy::cast_possible_truncation)]
-    const F_MONT: i64 = 8_347_681_i128.wrapping_mul(1 << 32).rem_euclid(Q as i128) as i64;
+    const F_MONT: i32 = 8_347_681_i128.wrapping_mul(1 << 32).rem_euclid(Q as i128) as i32;
     //
     // 1: for j from 0 to 255 do
     // 2: w_j ← w_hat[j]
@@ -123,13 +123,18 @@ pub(crate) fn inv_ntt<const KL: usize>(w_hat: &[T; KL]) -> [R; KL] {
                     let t = w_poly.0[j];
 
                     // 13: w_j ← t + w_{j+len}
-                    w_poly.0[j] = t + w_poly.0[j + len];
+                    w_poly.0[j] = checked!(add, t, w_poly.0[j + len], "invNTT butterfly (t + w)");
 
                     // 14: w_{j+len} ← t − w_{j+len}
-                    w_poly.0[j + len] = t - w_poly.0[j + len];
+                    w_poly.0[j + len] = checked!(sub, t, w_poly.0[j + len], "invNTT butterfly (t - w)");
 
                     // 15: w_{j+len} ← zeta · w_{j+len}
-                    w_poly.0[j + len] = mont_reduce(i64::from(zeta) * i64::from(w_poly.0[j + len]));
+                    w_poly.0[j + len] = mont_reduce(checked!(
+                        mul,
+                        i64::from(zeta),
+                        i64::from(w_poly.0[j + len]),
+                        "invNTT (zeta·w)"
+                    ));
 
                     // 16: end for
                 }
@@ -146,16 +151,42 @@ pub(crate) fn inv_ntt<const KL: usize>(w_hat: &[T; KL]) -> [R; KL] {
             // 20: end while
         }
 
-        // 21: f ← 8347681          ▷ f = 256^{−1} mod q
-        // 22: for j from 0 to 255 do
-        // 23: wj ← f · wj
-        for i in &mut w_poly.0 {
-            *i = full_reduce32(mont_reduce(F_MONT * i64::from(*i)));
-        }
-
-        // 24: end for
+        // end for each element of w_hat
     }
 
+    // 21: f ← 8347681          ▷ f = 256^{−1} mod q
+    // 22: for j from 0 to 255 do
+    // 23: wj ← f · wj
+    // 24: end for
+    // (applied across all elements in a single pass)
+    mont_mul_const_full(&mut w_out, F_MONT);
+
     // 25: return w
     w_out
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Coefficients at +/-(q-1) produce the largest intermediate values in both transforms;
+    // the inverse NTT accumulates up to 256·(q-1), just short of i32::MAX
+    #[test]
+    fn test_ntt_roundtrip_extremes() {
+        for fill in [Q - 1, -(Q - 1)] {
+            let w = [R([fill; 256])];
+            let w_hat = ntt(&w);
+            let w_back = inv_ntt(&w_hat);
+            assert!(w_back[0].0.iter().all(|&x| (x - fill).rem_euclid(Q) == 0));
+        }
+        let w_hat = [T([Q - 1; 256])];
+        let w = inv_ntt(&w_hat);
+        assert!(w[0].0.iter().all(|&x| (0..Q).contains(&x)));
+    }
+
+    #[cfg(feature = "checked-math")]
+    #[test]
+    #[should_panic(expected = "checked-math: integer overflow in invNTT butterfly")]
+    fn test_inv_ntt_overflow_panics() { drop(inv_ntt(&[T([1 << 29; 256])])); }
+}
@@ -0,0 +1,262 @@
+// This file implements a usage policy wrapper for private keys: a maximum signature count with a
+// persistence hook for the counter, a validity window, and a context string allow-list.
+
+//! Key usage policy enforcement, only exposed when the `policy` feature is enabled.
+//!
+//! A [`PolicyKey`] wraps a private key (any [`Signer`]) and checks a [`Policy`] before every
+//! signature: a maximum number of signatures, a validity window, and a list of permitted
+//! context strings. The signature counter is persisted through a [`CounterStore`] **before**
+//! each signature is produced, so that a crash can never lead to the limit being exceeded; a
+//! signing failure after the counter has been stored consumes that count.
+//!
+//! Time is supplied by the caller (as e.g. seconds since the Unix epoch), as `no_std` targets
+//! have no common clock; the policy only compares it against the window.
+//!
+//! # Examples
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+//! use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+//! use fips204::policy::{MemoryCounter, Policy, PolicyKey};
+//! use fips204::traits::Verifier;
+//!
+//! let (pk, sk) = ml_dsa_44::try_keygen()?;
+//! let policy = Policy::new()
+//!     .with_max_signatures(2)
+//!     .with_validity(1_700_000_000, 1_800_000_000)
+//!     .with_allowed_contexts(&[b"firmware"]);
+//! let mut key = PolicyKey::try_new(sk, policy, MemoryCounter::default())?;
+//!
+//! let now = 1_750_000_000;
+//! let sig = key.try_sign(b"image", b"firmware", now)?;
+//! assert!(pk.verify(b"image", &sig, b"firmware"));
+//! assert!(key.try_sign(b"image", b"other", now).is_err()); // Context not permitted
+//! assert!(key.try_sign(b"image", b"firmware", 1_900_000_000).is_err()); // Expired
+//! let _sig = key.try_sign(b"image", b"firmware", now)?;
+//! assert!(key.try_sign(b"image", b"firmware", now).is_err()); // Limit reached
+//! # }
+//! # Ok(())}
+//! ```
+
+use crate::helpers::ensure;
+use crate::traits::Signer;
+use rand_core::CryptoRngCore;
+#[cfg(feature = "default-rng")]
+use rand_core::OsRng;
+
+
+/// The usage limits enforced by a [`PolicyKey`]; by default nothing is limited.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Policy<'a> {
+    max_signatures: Option<u64>,
+    not_before: Option<u64>,
+    not_after: Option<u64>,
+    allowed_contexts: Option<&'a [&'a [u8]]>,
+}
+
+
+impl<'a> Policy<'a> {
+    /// Creates a policy that limits nothing.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { max_signatures: None, not_before: None, not_after: None, allowed_contexts: None }
+    }
+
+
+    /// Limits the total number of signatures, across restarts (see [`CounterStore`]).
+    #[must_use]
+    pub const fn with_max_signatures(self, max: u64) -> Self { Self { max_signatures: Some(max), ..self } }
+
+
+    /// Limits signing to times `t` with `not_before <= t <= not_after`.
+    #[must_use]
+    pub const fn with_validity(self, not_before: u64, not_after: u64) -> Self {
+        Self { not_before: Some(not_before), not_after: Some(not_after), ..self }
+    }
+
+
+    /// Limits signing to the listed context strings. The empty context is permitted only when
+    /// listed (as `b""`), and an empty list permits no signing at all.
+    #[must_use]
+    pub const fn with_allowed_contexts(self, contexts: &'a [&'a [u8]]) -> Self {
+        Self { allowed_contexts: Some(contexts), ..self }
+    }
+
+
+    /// Checks a signature at time `now` under `ctx`, given the number already issued.
+    ///
+    /// # Errors
+    /// Returns an error naming the first limit that would be violated.
+    pub fn check(&self, issued: u64, now: u64, ctx: &[u8]) -> Result<(), &'static str> {
+        ensure!(self.max_signatures.map_or(true, |max| issued < max), "Policy: signature limit reached");
+        ensure!(self.not_before.map_or(true, |nb| nb <= now), "Policy: key not yet valid");
+        ensure!(self.not_after.map_or(true, |na| now <= na), "Policy: key expired");
+        ensure!(
+            self.allowed_contexts.map_or(true, |allowed| allowed.contains(&ctx)),
+            "Policy: context not permitted"
+        );
+        Ok(())
+    }
+}
+
+
+/// Persistence hook for the signature counter of a [`PolicyKey`], e.g., backed by a file,
+/// flash page or monotonic hardware counter.
+pub trait CounterStore {
+    /// Returns the number of signatures issued so far.
+    ///
+    /// # Errors
+    /// Returns an error when the counter cannot be read.
+    fn load(&mut self) -> Result<u64, &'static str>;
+
+
+    /// Durably records that `count` signatures have been issued; it is called before the
+    /// signature is produced.
+    ///
+    /// # Errors
+    /// Returns an error when the counter cannot be written, in which case no signature is
+    /// produced.
+    fn store(&mut self, count: u64) -> Result<(), &'static str>;
+}
+
+
+/// A [`CounterStore`] held only in memory, and so reset whenever the process restarts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryCounter(pub u64);
+
+
+impl CounterStore for MemoryCounter {
+    fn load(&mut self) -> Result<u64, &'static str> { Ok(self.0) }
+
+    fn store(&mut self, count: u64) -> Result<(), &'static str> {
+        self.0 = count;
+        Ok(())
+    }
+}
+
+
+/// A private key that enforces a [`Policy`] before every signature; see the
+/// [module documentation](self).
+pub struct PolicyKey<'a, S: Signer, C: CounterStore> {
+    sk: S,
+    policy: Policy<'a>,
+    counter: C,
+    issued: u64,
+}
+
+
+impl<'a, S: Signer, C: CounterStore> PolicyKey<'a, S, C> {
+    /// Wraps the private key, loading the number of signatures already issued from `counter`.
+    ///
+    /// # Errors
+    /// Returns an error when the counter cannot be loaded.
+    pub fn try_new(sk: S, policy: Policy<'a>, mut counter: C) -> Result<Self, &'static str> {
+        let issued = counter.load()?;
+        Ok(Self { sk, policy, counter, issued })
+    }
+
+
+    /// Returns the number of signatures issued (including any consumed by failed signing).
+    #[must_use]
+    pub fn signatures_issued(&self) -> u64 { self.issued }
+
+
+    /// Returns the policy being enforced.
+    #[must_use]
+    pub fn policy(&self) -> &Policy<'a> { &self.policy }
+
+
+    /// Returns the public key corresponding to the wrapped private key.
+    #[must_use]
+    pub fn get_public_key(&self) -> S::PublicKey { self.sk.get_public_key() }
+
+
+    /// Checks the policy at time `now`, persists the incremented counter and then signs the
+    /// message with the context string. This function utilizes the **OS default** random
+    /// number generator.
+    ///
+    /// # Errors
+    /// Returns an error when the policy forbids the signature, the counter cannot be stored,
+    /// or signing fails.
+    #[cfg(feature = "default-rng")]
+    pub fn try_sign(&mut self, message: &[u8], ctx: &[u8], now: u64) -> Result<S::Signature, &'static str> {
+        self.try_sign_with_rng(&mut OsRng, message, ctx, now)
+    }
+
+
+    /// Checks the policy at time `now`, persists the incremented counter and then signs the
+    /// message with the context string. This function utilizes the **provided** random
+    /// number generator.
+    ///
+    /// # Errors
+    /// Returns an error when the policy forbids the signature, the counter cannot be stored,
+    /// or signing fails.
+    pub fn try_sign_with_rng(
+        &mut self, rng: &mut impl CryptoRngCore, message: &[u8], ctx: &[u8], now: u64,
+    ) -> Result<S::Signature, &'static str> {
+        self.policy.check(self.issued, now, ctx)?;
+        let next = self.issued.checked_add(1).ok_or("Policy: signature limit reached")?;
+        self.counter.store(next)?;
+        self.issued = next;
+        self.sk.try_sign_with_rng(rng, message, ctx)
+    }
+
+
+    /// Returns the wrapped private key and counter store, ending policy enforcement.
+    pub fn into_parts(self) -> (S, C) { (self.sk, self.counter) }
+}
+
+
+#[cfg(test)]
+#[cfg(feature = "ml-dsa-44")]
+mod tests {
+    use super::*;
+    use crate::ml_dsa_44;
+    use crate::traits::Verifier;
+    use rand_chacha::rand_core::SeedableRng;
+
+    struct FailingStore;
+
+    impl CounterStore for FailingStore {
+        fn load(&mut self) -> Result<u64, &'static str> { Ok(0) }
+
+        fn store(&mut self, _count: u64) -> Result<(), &'static str> { Err("store failed") }
+    }
+
+    #[test]
+    fn test_policy_key() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+        let (pk, sk) = ml_dsa_44::try_keygen_with_rng(&mut rng).unwrap();
+        let allowed: [&[u8]; 2] = [b"a", b""];
+        let policy = Policy::new().with_max_signatures(3).with_validity(10, 20).with_allowed_contexts(&allowed);
+
+        // One signature was issued before a restart
+        let mut key = PolicyKey::try_new(sk.clone(), policy, MemoryCounter(1)).unwrap();
+        let sig = key.try_sign_with_rng(&mut rng, b"msg", b"a", 10).unwrap();
+        assert!(pk.verify(b"msg", &sig, b"a"));
+        assert_eq!(key.try_sign_with_rng(&mut rng, b"msg", b"b", 15).err(), Some("Policy: context not permitted"));
+        assert_eq!(key.try_sign_with_rng(&mut rng, b"msg", b"", 9).err(), Some("Policy: key not yet valid"));
+        assert_eq!(key.try_sign_with_rng(&mut rng, b"msg", b"", 21).err(), Some("Policy: key expired"));
+        assert_eq!(key.signatures_issued(), 2); // Rejected requests consume nothing
+        let _sig = key.try_sign_with_rng(&mut rng, b"msg", b"", 20).unwrap();
+        assert_eq!(key.try_sign_with_rng(&mut rng, b"msg", b"", 15).err(), Some("Policy: signature limit reached"));
+        assert_eq!(key.into_parts().1, MemoryCounter(3));
+
+        // Nothing is signed unless the counter was stored
+        let mut key = PolicyKey::try_new(sk.clone(), Policy::new(), FailingStore).unwrap();
+        assert_eq!(key.try_sign_with_rng(&mut rng, b"msg", b"", 0).err(), Some("store failed"));
+        assert_eq!(key.signatures_issued(), 0);
+
+        // An unlimited policy still counts
+        let mut key = PolicyKey::try_new(sk, Policy::default(), MemoryCounter::default()).unwrap();
+        let _sig = key.try_sign_with_rng(&mut rng, b"msg", &[0u8; 200], u64::MAX).unwrap();
+        assert_eq!(key.signatures_issued(), 1);
+
+        // The empty context must be listed explicitly, and an empty list permits nothing
+        let listed: [&[u8]; 1] = [b"a"];
+        assert!(Policy::new().with_allowed_contexts(&listed).check(0, 0, b"").is_err());
+        assert!(Policy::new().with_allowed_contexts(&[]).check(0, 0, b"").is_err());
+        assert!(Policy::new().check(0, 0, b"").is_ok());
+    }
+}
@@ -0,0 +1,233 @@
+// This file implements a multi-tenant verification worker pool: a fixed set of threads sharing
+// a cache of expanded public keys, fed by a job channel and reporting over a results channel.
+
+//! Multi-tenant verification worker pool, only exposed when the `std` feature is enabled.
+//!
+//! A [`VerifyPool`] owns a fixed number of worker threads and a cache of expanded public keys
+//! indexed by a caller-chosen id. Public keys are expanded once, when inserted into the cache,
+//! rather than per signature. Jobs naming a key id, message, context and signature are
+//! submitted to the pool, and each is answered by an [`Outcome`] on the results channel
+//! returned at construction. Outcomes arrive in completion order, so each job carries a
+//! caller-chosen `tag` to correlate the two.
+//!
+//! # Examples
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+//! use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+//! use fips204::pool::{Job, VerifyPool};
+//! use fips204::traits::Signer;
+//!
+//! let (pk, sk) = ml_dsa_44::try_keygen()?;
+//! let (pool, results) = VerifyPool::<&str, ml_dsa_44::PublicKey>::new(4)?;
+//! pool.insert_key("tenant-a", pk);
+//!
+//! let sig = sk.try_sign(b"message", b"ctx")?;
+//! pool.submit(Job { tag: 1, pk_id: "tenant-a", message: b"message".to_vec(), ctx: b"ctx".to_vec(), sig })?;
+//! pool.submit(Job { tag: 2, pk_id: "tenant-b", message: b"message".to_vec(), ctx: b"ctx".to_vec(), sig })?;
+//!
+//! let mut outcomes = vec![results.recv()?, results.recv()?];
+//! outcomes.sort_by_key(|outcome| outcome.tag);
+//! assert_eq!(outcomes[0].result, Ok(true));
+//! assert!(outcomes[1].result.is_err()); // No key was inserted for "tenant-b"
+//! # }
+//! # Ok(())}
+//! ```
+
+use crate::helpers::ensure;
+use crate::traits::{SerDes, Verifier};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use std::thread::JoinHandle;
+use std::vec::Vec;
+
+
+/// A verification request submitted to a [`VerifyPool`].
+pub struct Job<I, S> {
+    /// Caller-chosen value returned in the corresponding [`Outcome`].
+    pub tag: u64,
+    /// Id of the (previously inserted) public key to verify against.
+    pub pk_id: I,
+    /// The signed message.
+    pub message: Vec<u8>,
+    /// The context string (of at most 255 bytes).
+    pub ctx: Vec<u8>,
+    /// The signature.
+    pub sig: S,
+}
+
+
+/// The result of a [`Job`], delivered on the results channel of a [`VerifyPool`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outcome<I> {
+    /// The `tag` of the job.
+    pub tag: u64,
+    /// The `pk_id` of the job.
+    pub pk_id: I,
+    /// Whether the signature verified, or an error when no public key has the job's id.
+    pub result: Result<bool, &'static str>,
+}
+
+
+type KeyCache<I, P> = Arc<RwLock<HashMap<I, Arc<P>>>>;
+
+
+/// A fixed set of verification worker threads sharing a cache of expanded public keys; see
+/// the [module documentation](self). Dropping the pool lets the workers finish the jobs
+/// already submitted, then joins them.
+pub struct VerifyPool<I, P: Verifier> {
+    jobs: Option<Sender<Job<I, P::Signature>>>,
+    keys: KeyCache<I, P>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+
+impl<I, P> VerifyPool<I, P>
+where
+    I: Eq + Hash + Clone + Send + Sync + 'static,
+    P: Verifier + Send + Sync + 'static,
+    P::Signature: Send + 'static,
+{
+    /// Starts `threads` worker threads, returning the pool along with the channel on which
+    /// an [`Outcome`] is delivered for every submitted job.
+    ///
+    /// # Errors
+    /// Returns an error when `threads` is zero or a thread cannot be spawned.
+    pub fn new(threads: usize) -> Result<(Self, Receiver<Outcome<I>>), &'static str> {
+        ensure!(threads > 0, "VerifyPool: zero threads");
+        let (jobs, job_rx) = channel::<Job<I, P::Signature>>();
+        let (outcome_tx, outcomes) = channel();
+        let job_rx = Arc::new(Mutex::new(job_rx));
+        let keys: KeyCache<I, P> = Arc::new(RwLock::new(HashMap::new()));
+        let mut workers = Vec::with_capacity(threads);
+        for _ in 0..threads {
+            let (job_rx, outcome_tx, keys) = (Arc::clone(&job_rx), outcome_tx.clone(), Arc::clone(&keys));
+            let worker = std::thread::Builder::new()
+                .name("fips204-verify".into())
+                .spawn(move || Self::work(&job_rx, &outcome_tx, &keys))
+                .map_err(|_| "VerifyPool: failed to spawn thread")?;
+            workers.push(worker);
+        }
+        Ok((Self { jobs: Some(jobs), keys, workers }, outcomes))
+    }
+
+
+    /// Inserts the (expanded) public key under `pk_id`, replacing any key already held under
+    /// it. Jobs already submitted but not yet started use the new key.
+    pub fn insert_key(&self, pk_id: I, pk: P) {
+        drop(self.keys.write().unwrap_or_else(PoisonError::into_inner).insert(pk_id, Arc::new(pk)));
+    }
+
+
+    /// Expands the encoded public key and inserts it under `pk_id`; see
+    /// [`VerifyPool::insert_key()`].
+    ///
+    /// # Errors
+    /// Returns an error when the public key is malformed.
+    pub fn try_insert_encoded_key(&self, pk_id: I, pk: P::ByteArray) -> Result<(), &'static str>
+    where
+        P: SerDes,
+    {
+        self.insert_key(pk_id, P::try_from_bytes(pk)?);
+        Ok(())
+    }
+
+
+    /// Removes the public key held under `pk_id`, returning whether there was one. Jobs
+    /// naming it that have not yet started will fail.
+    pub fn remove_key(&self, pk_id: &I) -> bool {
+        self.keys.write().unwrap_or_else(PoisonError::into_inner).remove(pk_id).is_some()
+    }
+
+
+    /// Queues a job for the next free worker; its [`Outcome`] is delivered on the results
+    /// channel.
+    ///
+    /// # Errors
+    /// Returns an error when no worker remains to run the job (i.e., all have panicked).
+    pub fn submit(&self, job: Job<I, P::Signature>) -> Result<(), &'static str> {
+        let jobs = self.jobs.as_ref().ok_or("VerifyPool: shut down")?;
+        jobs.send(job).map_err(|_| "VerifyPool: no workers remain")
+    }
+
+
+    fn work(job_rx: &Mutex<Receiver<Job<I, P::Signature>>>, outcome_tx: &Sender<Outcome<I>>, keys: &KeyCache<I, P>) {
+        loop {
+            // The lock is held only while waiting for the next job, not while verifying
+            let job = match job_rx.lock() {
+                Ok(job_rx) => job_rx.recv(),
+                Err(_) => return,
+            };
+            let Ok(Job { tag, pk_id, message, ctx, sig }) = job else { return }; // Pool dropped
+            let pk = keys.read().unwrap_or_else(PoisonError::into_inner).get(&pk_id).cloned();
+            let result = pk
+                .map(|pk| pk.verify(&message, &sig, &ctx))
+                .ok_or("VerifyPool: unknown public key id");
+            if outcome_tx.send(Outcome { tag, pk_id, result }).is_err() {
+                return; // Nobody is listening for outcomes
+            }
+        }
+    }
+}
+
+
+impl<I, P: Verifier> Drop for VerifyPool<I, P> {
+    fn drop(&mut self) {
+        drop(self.jobs.take()); // Workers exit once the queued jobs are drained
+        for worker in self.workers.drain(..) {
+            let _panicked = worker.join();
+        }
+    }
+}
+
+
+#[cfg(test)]
+#[cfg(feature = "ml-dsa-65")]
+mod tests {
+    use super::*;
+    use crate::ml_dsa_65;
+    use crate::traits::Signer;
+    use rand_chacha::rand_core::SeedableRng;
+
+    #[test]
+    fn test_verify_pool() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+        let (pool, results) = VerifyPool::<u32, ml_dsa_65::PublicKey>::new(3).unwrap();
+        let mut sigs = Vec::new();
+        for id in 0..4u32 {
+            let (pk, sk) = ml_dsa_65::try_keygen_with_rng(&mut rng).unwrap();
+            pool.try_insert_encoded_key(id, pk.into_bytes()).unwrap();
+            sigs.push(sk.try_sign_with_rng(&mut rng, &id.to_le_bytes(), b"ctx").unwrap());
+        }
+
+        // Every key against every signature, plus an unknown key id
+        for (tag, (id, sig_id)) in (0..5u32).flat_map(|id| (0..4u32).map(move |s| (id, s))).enumerate() {
+            let job = Job {
+                tag: tag as u64,
+                pk_id: id,
+                message: sig_id.to_le_bytes().to_vec(),
+                ctx: b"ctx".to_vec(),
+                sig: sigs[sig_id as usize],
+            };
+            pool.submit(job).unwrap();
+        }
+        let mut outcomes: Vec<Outcome<u32>> = results.iter().take(20).collect();
+        outcomes.sort_by_key(|outcome| outcome.tag);
+        for (tag, outcome) in outcomes.iter().enumerate() {
+            let (id, sig_id) = (tag / 4, tag % 4);
+            assert_eq!(outcome.tag, tag as u64);
+            let expected = if id == 4 { Err("VerifyPool: unknown public key id") } else { Ok(id == sig_id) };
+            assert_eq!(outcome.result, expected);
+        }
+
+        assert!(pool.remove_key(&0));
+        assert!(!pool.remove_key(&0));
+        let job = Job { tag: 99, pk_id: 0, message: Vec::new(), ctx: Vec::new(), sig: sigs[0] };
+        pool.submit(job).unwrap();
+        assert!(results.recv().unwrap().result.is_err());
+        assert!(VerifyPool::<u32, ml_dsa_65::PublicKey>::new(0).is_err());
+    }
+}
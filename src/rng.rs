@@ -0,0 +1,514 @@
+// This file implements random number generator adapters for embedded integrators.
+
+//! Random number generator adapters.
+//!
+//! Embedded targets frequently have a hardware true random number generator (TRNG) peripheral
+//! whose output quality cannot be fully assured. The [`CombinedRng`] adapter mixes such a TRNG
+//! with a software deterministic random bit generator (DRBG) using the XOR construction of
+//! SP 800-90C, so that the output is no weaker than the stronger of the two sources.
+//!
+//! A TRNG peripheral driver is fed to key generation and signing via [`TrngRng`] when its HAL
+//! implements `rand_core::RngCore` but not `CryptoRng`, or via `HalRng` (with the `embedded-hal`
+//! feature) when it implements the embedded-hal 0.2 `blocking::rng::Read` trait (e.g., the `Rng`
+//! of the `stm32f4xx-hal` and `nrf-hal` crates). Either adapter asserts that the
+//! peripheral is cryptographically secure; if in doubt, combine it with a DRBG via
+//! [`CombinedRng`]. See `ct_cm4/examples/trng_keygen.rs` for a micro:bit v2 (nRF52833) example:
+//! ```rust,ignore
+//! let board = microbit::Board::take().unwrap();
+//! let mut rng = fips204::rng::HalRng::new(microbit::hal::Rng::new(board.RNG));
+//! let (pk, sk) = fips204::ml_dsa_44::try_keygen_with_rng(&mut rng)?;
+//! ```
+//!
+//! The [`RepeatMonitor`] adapter instruments an RNG during soak and fuzz runs (or in
+//! production), refusing any 32-byte draw that repeats one of the recent draws. Hedged signing
+//! draws its per-signature randomness `rnd` with exactly one 32-byte `try_fill_bytes()` call,
+//! as does key generation for its seed `ξ`, so a repeat indicates broken RNG wiring (e.g., a
+//! DRBG re-seeded to the same state after every reset).
+//!
+//! For tests, [`FailingRng`] fails every request once a byte budget is spent, simulating an
+//! entropy source that dies mid-operation; `tests/rng_failure.rs` uses it to check that every
+//! RNG-consuming function returns an error rather than panicking or retrying.
+
+use rand_core::{impls, CryptoRng, Error, RngCore};
+use zeroize::Zeroize;
+
+
+/// Mixes a hardware TRNG with a software DRBG by XOR-ing their outputs, per the SP 800-90C
+/// style XOR construction. The result implements `CryptoRngCore` and so can be supplied to
+/// e.g. [`crate::traits::KeyGen::try_keygen_with_rng()`].
+///
+/// The DRBG must be a `CryptoRng` (and should be seeded independently of the TRNG, for example
+/// from a device-unique secret or an earlier boot), while the TRNG need only implement
+/// `RngCore`. A failure of either source is propagated as an error rather than silently
+/// falling back to the other.
+///
+/// # Examples
+/// ```rust
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # #[cfg(feature = "ml-dsa-44")] {
+/// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+/// use fips204::rng::CombinedRng;
+/// use fips204::traits::{Signer, Verifier};
+/// use fips204::{RngCore, RngError};
+/// use rand_chacha::rand_core::SeedableRng;
+///
+/// // Stand-in for a TRNG peripheral driver, e.g. reading a `DATA` register per word
+/// struct Trng(u32);
+/// impl RngCore for Trng {
+///     fn next_u32(&mut self) -> u32 { self.0 = self.0.wrapping_mul(1_664_525).wrapping_add(1); self.0 }
+///     fn next_u64(&mut self) -> u64 { u64::from(self.next_u32()) << 32 | u64::from(self.next_u32()) }
+///     fn fill_bytes(&mut self, out: &mut [u8]) { out.iter_mut().for_each(|b| *b = self.next_u32() as u8) }
+///     fn try_fill_bytes(&mut self, out: &mut [u8]) -> Result<(), RngError> { self.fill_bytes(out); Ok(()) }
+/// }
+///
+/// let drbg = rand_chacha::ChaCha20Rng::from_seed([7u8; 32]); // Seed from e.g. a device secret
+/// let mut rng = CombinedRng::new(Trng(1), drbg);
+///
+/// let (pk, sk) = ml_dsa_44::try_keygen_with_rng(&mut rng)?;
+/// let sig = sk.try_sign_with_rng(&mut rng, &[0u8, 1, 2, 3], &[])?;
+/// assert!(pk.verify(&[0u8, 1, 2, 3], &sig, &[]));
+/// # }
+/// # Ok(())}
+/// ```
+pub struct CombinedRng<H: RngCore, D: RngCore + CryptoRng> {
+    trng: H,
+    drbg: D,
+}
+
+
+impl<H: RngCore, D: RngCore + CryptoRng> CombinedRng<H, D> {
+    /// Combines a hardware TRNG with a (cryptographically secure) software DRBG.
+    pub fn new(trng: H, drbg: D) -> Self { Self { trng, drbg } }
+
+
+    /// Returns the underlying TRNG and DRBG.
+    pub fn into_inner(self) -> (H, D) { (self.trng, self.drbg) }
+}
+
+
+impl<H: RngCore, D: RngCore + CryptoRng> RngCore for CombinedRng<H, D> {
+    fn next_u32(&mut self) -> u32 { impls::next_u32_via_fill(self) }
+
+    fn next_u64(&mut self) -> u64 { impls::next_u64_via_fill(self) }
+
+    fn fill_bytes(&mut self, out: &mut [u8]) {
+        if let Err(e) = self.try_fill_bytes(out) {
+            panic!("CombinedRng: {e}");
+        }
+    }
+
+    fn try_fill_bytes(&mut self, out: &mut [u8]) -> Result<(), Error> {
+        self.drbg.try_fill_bytes(out)?;
+        let mut buf = [0u8; 64];
+        for chunk in out.chunks_mut(buf.len()) {
+            let mix = &mut buf[..chunk.len()];
+            self.trng.try_fill_bytes(mix)?;
+            for (o, m) in chunk.iter_mut().zip(mix.iter()) {
+                *o ^= m;
+            }
+        }
+        buf.zeroize();
+        Ok(())
+    }
+}
+
+
+impl<H: RngCore, D: RngCore + CryptoRng> CryptoRng for CombinedRng<H, D> {}
+
+
+/// Marks a HAL's TRNG peripheral driver implementing `rand_core::RngCore` (but not
+/// `CryptoRng`) as cryptographically secure, so that it can be supplied to e.g.
+/// [`crate::traits::KeyGen::try_keygen_with_rng()`]. Errors of the driver are propagated.
+///
+/// Only wrap a peripheral documented as a TRNG (with its health tests enabled), not a
+/// pseudo-random generator.
+pub struct TrngRng<R: RngCore>(R);
+
+
+impl<R: RngCore> TrngRng<R> {
+    /// Wraps the TRNG driver, asserting that it is cryptographically secure.
+    pub fn new(trng: R) -> Self { Self(trng) }
+
+
+    /// Returns the underlying TRNG driver.
+    pub fn into_inner(self) -> R { self.0 }
+}
+
+
+impl<R: RngCore> RngCore for TrngRng<R> {
+    fn next_u32(&mut self) -> u32 { self.0.next_u32() }
+
+    fn next_u64(&mut self) -> u64 { self.0.next_u64() }
+
+    fn fill_bytes(&mut self, out: &mut [u8]) { self.0.fill_bytes(out) }
+
+    fn try_fill_bytes(&mut self, out: &mut [u8]) -> Result<(), Error> { self.0.try_fill_bytes(out) }
+}
+
+
+impl<R: RngCore> CryptoRng for TrngRng<R> {}
+
+
+/// The `rand_core::Error` code returned by `HalRng` when the peripheral reports an error.
+#[cfg(feature = "embedded-hal")]
+pub const HAL_ERROR_CODE: u32 = Error::CUSTOM_START + 0x205;
+
+
+/// Adapts a TRNG peripheral driver implementing the embedded-hal 0.2 `blocking::rng::Read`
+/// trait (e.g., `stm32f4xx_hal::rng::Rng` or `nrf52833_hal::Rng`) to `CryptoRngCore`, so that it
+/// can be supplied to e.g. [`crate::traits::KeyGen::try_keygen_with_rng()`]. A peripheral error
+/// (e.g., a failed health test) is returned with code [`HAL_ERROR_CODE`], and so fails the key
+/// generation or signing operation.
+///
+/// As for [`TrngRng`], only wrap a peripheral documented as a TRNG.
+///
+/// # Examples
+/// ```rust
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # #[cfg(feature = "ml-dsa-44")] {
+/// use embedded_hal::blocking::rng::Read;
+/// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+/// use fips204::rng::HalRng;
+///
+/// // Stand-in for a HAL's TRNG peripheral driver
+/// struct Trng(u8);
+/// impl Read for Trng {
+///     type Error = ();
+///     fn read(&mut self, out: &mut [u8]) -> Result<(), ()> {
+///         out.iter_mut().for_each(|b| { self.0 = self.0.wrapping_mul(5).wrapping_add(1); *b = self.0 });
+///         Ok(())
+///     }
+/// }
+///
+/// let mut rng = HalRng::new(Trng(1));
+/// let (_pk, _sk) = ml_dsa_44::try_keygen_with_rng(&mut rng)?;
+/// # }
+/// # Ok(())}
+/// ```
+#[cfg(feature = "embedded-hal")]
+pub struct HalRng<T: embedded_hal::blocking::rng::Read>(T);
+
+
+#[cfg(feature = "embedded-hal")]
+impl<T: embedded_hal::blocking::rng::Read> HalRng<T> {
+    /// Wraps the TRNG peripheral driver, asserting that it is cryptographically secure.
+    pub fn new(trng: T) -> Self { Self(trng) }
+
+
+    /// Returns the underlying TRNG peripheral driver.
+    pub fn into_inner(self) -> T { self.0 }
+}
+
+
+#[cfg(feature = "embedded-hal")]
+impl<T: embedded_hal::blocking::rng::Read> RngCore for HalRng<T> {
+    fn next_u32(&mut self) -> u32 { impls::next_u32_via_fill(self) }
+
+    fn next_u64(&mut self) -> u64 { impls::next_u64_via_fill(self) }
+
+    fn fill_bytes(&mut self, out: &mut [u8]) {
+        if let Err(e) = self.try_fill_bytes(out) {
+            panic!("HalRng: {e}");
+        }
+    }
+
+    fn try_fill_bytes(&mut self, out: &mut [u8]) -> Result<(), Error> {
+        self.0
+            .read(out)
+            .map_err(|_| Error::from(core::num::NonZeroU32::new(HAL_ERROR_CODE).expect("non-zero")))
+    }
+}
+
+
+#[cfg(feature = "embedded-hal")]
+impl<T: embedded_hal::blocking::rng::Read> CryptoRng for HalRng<T> {}
+
+
+/// The `rand_core::Error` code returned by [`RepeatMonitor`] when a draw repeats.
+pub const REPEAT_ERROR_CODE: u32 = Error::CUSTOM_START + 0x204;
+
+
+/// Wraps an RNG and refuses (with an error of code [`REPEAT_ERROR_CODE`]) any 32-byte draw equal
+/// to one of the previous `WINDOW` 32-byte draws; draws of other lengths are passed through
+/// unchecked. As signing and key generation propagate RNG errors, a repeated `rnd` (or `ξ`)
+/// then fails the operation rather than producing a signature (or key).
+///
+/// Checking costs `WINDOW` comparisons per draw, and the window occupies `32·WINDOW` bytes.
+///
+/// # Examples
+/// ```rust
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # #[cfg(feature = "ml-dsa-44")] {
+/// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+/// use fips204::rng::RepeatMonitor;
+/// use fips204::traits::Signer;
+/// use rand_chacha::rand_core::SeedableRng;
+///
+/// let mut rng = RepeatMonitor::<_, 1024>::new(rand_chacha::ChaCha20Rng::from_seed([7u8; 32]));
+/// let (_pk, sk) = ml_dsa_44::try_keygen_with_rng(&mut rng)?;
+/// for i in 0..100u8 {
+///     sk.try_sign_with_rng(&mut rng, &[i], &[])?; // Errors on a repeated rnd
+/// }
+/// assert_eq!(rng.draws(), 101);
+/// # }
+/// # Ok(())}
+/// ```
+pub struct RepeatMonitor<R: RngCore, const WINDOW: usize> {
+    rng: R,
+    recent: [[u8; 32]; WINDOW],
+    draws: u64,
+}
+
+
+impl<R: RngCore, const WINDOW: usize> RepeatMonitor<R, WINDOW> {
+    /// Starts monitoring the RNG with an empty window.
+    pub fn new(rng: R) -> Self { Self { rng, recent: [[0u8; 32]; WINDOW], draws: 0 } }
+
+
+    /// Returns the number of (non-repeating) 32-byte draws so far.
+    pub fn draws(&self) -> u64 { self.draws }
+
+
+    /// Returns the underlying RNG.
+    pub fn into_inner(self) -> R { self.rng }
+}
+
+
+impl<R: RngCore, const WINDOW: usize> RngCore for RepeatMonitor<R, WINDOW> {
+    fn next_u32(&mut self) -> u32 { self.rng.next_u32() }
+
+    fn next_u64(&mut self) -> u64 { self.rng.next_u64() }
+
+    fn fill_bytes(&mut self, out: &mut [u8]) {
+        if let Err(e) = self.try_fill_bytes(out) {
+            panic!("RepeatMonitor: {e}");
+        }
+    }
+
+    fn try_fill_bytes(&mut self, out: &mut [u8]) -> Result<(), Error> {
+        self.rng.try_fill_bytes(out)?;
+        if out.len() != 32 || WINDOW == 0 {
+            return Ok(());
+        }
+        let filled = usize::try_from(self.draws).map_or(WINDOW, |draws| draws.min(WINDOW));
+        if self.recent[..filled].iter().any(|prior| prior[..] == out[..]) {
+            return Err(Error::from(core::num::NonZeroU32::new(REPEAT_ERROR_CODE).expect("non-zero")));
+        }
+        let slot = usize::try_from(self.draws % WINDOW as u64).expect("less than WINDOW");
+        self.recent[slot].copy_from_slice(out);
+        self.draws += 1;
+        Ok(())
+    }
+}
+
+
+impl<R: RngCore + CryptoRng, const WINDOW: usize> CryptoRng for RepeatMonitor<R, WINDOW> {}
+
+
+/// The `rand_core::Error` code returned by [`FailingRng`] once its budget is exhausted.
+pub const EXHAUSTED_ERROR_CODE: u32 = Error::CUSTOM_START + 0x206;
+
+
+/// Wraps an RNG and fails (with an error of code [`EXHAUSTED_ERROR_CODE`]) every request once
+/// `budget` bytes have been supplied, simulating an entropy source that dies mid-operation.
+/// A request that would exceed the budget fails as a whole, and exhausts it. This is intended
+/// for tests: key generation and signing must then return an error, neither panicking nor
+/// retrying. The infallible `RngCore` methods panic when the budget is exhausted.
+///
+/// # Examples
+/// ```rust
+/// # #[cfg(feature = "ml-dsa-44")] {
+/// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+/// use fips204::rng::FailingRng;
+/// use fips204::traits::Signer;
+/// use rand_chacha::rand_core::SeedableRng;
+///
+/// // Key generation draws 32 bytes, so signing then finds the RNG failed
+/// let mut rng = FailingRng::new(rand_chacha::ChaCha20Rng::from_seed([7u8; 32]), 32);
+/// let (_pk, sk) = ml_dsa_44::try_keygen_with_rng(&mut rng).unwrap();
+/// assert!(sk.try_sign_with_rng(&mut rng, b"message", b"ctx").is_err());
+/// assert_eq!(rng.remaining(), 0);
+/// # }
+/// ```
+pub struct FailingRng<R: RngCore> {
+    rng: R,
+    remaining: usize,
+}
+
+
+impl<R: RngCore> FailingRng<R> {
+    /// Wraps the RNG, allowing it to supply `budget` bytes in total.
+    pub fn new(rng: R, budget: usize) -> Self { Self { rng, remaining: budget } }
+
+
+    /// Returns the number of bytes that may still be supplied.
+    pub fn remaining(&self) -> usize { self.remaining }
+
+
+    /// Returns the underlying RNG.
+    pub fn into_inner(self) -> R { self.rng }
+}
+
+
+impl<R: RngCore> RngCore for FailingRng<R> {
+    fn next_u32(&mut self) -> u32 { impls::next_u32_via_fill(self) }
+
+    fn next_u64(&mut self) -> u64 { impls::next_u64_via_fill(self) }
+
+    fn fill_bytes(&mut self, out: &mut [u8]) {
+        if let Err(e) = self.try_fill_bytes(out) {
+            panic!("FailingRng: {e}");
+        }
+    }
+
+    fn try_fill_bytes(&mut self, out: &mut [u8]) -> Result<(), Error> {
+        if out.len() > self.remaining {
+            self.remaining = 0;
+            return Err(Error::from(core::num::NonZeroU32::new(EXHAUSTED_ERROR_CODE).expect("non-zero")));
+        }
+        self.rng.try_fill_bytes(out)?;
+        self.remaining -= out.len();
+        Ok(())
+    }
+}
+
+
+impl<R: RngCore + CryptoRng> CryptoRng for FailingRng<R> {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_chacha::rand_core::SeedableRng;
+
+    // Supplies a fixed byte, optionally failing
+    struct FixedRng(u8, bool);
+
+    impl RngCore for FixedRng {
+        fn next_u32(&mut self) -> u32 { impls::next_u32_via_fill(self) }
+
+        fn next_u64(&mut self) -> u64 { impls::next_u64_via_fill(self) }
+
+        fn fill_bytes(&mut self, out: &mut [u8]) { self.try_fill_bytes(out).unwrap() }
+
+        fn try_fill_bytes(&mut self, out: &mut [u8]) -> Result<(), Error> {
+            if self.1 {
+                return Err(Error::from(core::num::NonZeroU32::new(Error::CUSTOM_START).unwrap()));
+            }
+            out.fill(self.0);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_xor_mixing() {
+        let mut drbg = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+        let mut expected = [0u8; 200];
+        drbg.fill_bytes(&mut expected);
+        for e in &mut expected {
+            *e ^= 0x5A;
+        }
+
+        let mut rng =
+            CombinedRng::new(FixedRng(0x5A, false), rand_chacha::ChaCha8Rng::seed_from_u64(123));
+        let mut out = [0u8; 200];
+        rng.try_fill_bytes(&mut out).unwrap();
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_repeat_detection() {
+        // A DRBG "re-seeded" to the same state after 5 draws repeats its 32-byte outputs
+        struct ResetRng(rand_chacha::ChaCha8Rng, u32);
+        impl RngCore for ResetRng {
+            fn next_u32(&mut self) -> u32 { impls::next_u32_via_fill(self) }
+
+            fn next_u64(&mut self) -> u64 { impls::next_u64_via_fill(self) }
+
+            fn fill_bytes(&mut self, out: &mut [u8]) { self.try_fill_bytes(out).unwrap() }
+
+            fn try_fill_bytes(&mut self, out: &mut [u8]) -> Result<(), Error> {
+                self.1 += 1;
+                if self.1 % 5 == 1 {
+                    self.0 = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+                }
+                self.0.try_fill_bytes(out)
+            }
+        }
+
+        let mut rng = RepeatMonitor::<_, 8>::new(ResetRng(rand_chacha::ChaCha8Rng::seed_from_u64(123), 0));
+        let mut out = [0u8; 32];
+        let results: [bool; 6] = core::array::from_fn(|_| rng.try_fill_bytes(&mut out).is_ok());
+        assert_eq!(results, [true, true, true, true, true, false]);
+        assert_eq!(rng.draws(), 5);
+        assert!(rng.try_fill_bytes(&mut [0u8; 31]).is_ok()); // Other lengths are not checked
+
+        // Outside the window, a repeat goes unnoticed
+        let mut rng = RepeatMonitor::<_, 3>::new(ResetRng(rand_chacha::ChaCha8Rng::seed_from_u64(123), 0));
+        assert!((0..10).all(|_| rng.try_fill_bytes(&mut out).is_ok()));
+        let mut rng = RepeatMonitor::<_, 100>::new(FixedRng(1, false));
+        assert!(rng.try_fill_bytes(&mut out).is_ok());
+        let err = rng.try_fill_bytes(&mut out).unwrap_err();
+        assert_eq!(err.code().map(core::num::NonZeroU32::get), Some(REPEAT_ERROR_CODE));
+    }
+
+    #[test]
+    fn test_failing_rng() {
+        let mut rng = FailingRng::new(FixedRng(3, false), 10);
+        let mut out = [0u8; 4];
+        assert!(rng.try_fill_bytes(&mut out).is_ok());
+        assert_eq!((out, rng.remaining()), ([3u8; 4], 6));
+        assert!(rng.try_fill_bytes(&mut [0u8; 6]).is_ok());
+        assert!(rng.try_fill_bytes(&mut []).is_ok());
+        let err = rng.try_fill_bytes(&mut out[..1]).unwrap_err();
+        assert_eq!(err.code().map(core::num::NonZeroU32::get), Some(EXHAUSTED_ERROR_CODE));
+
+        // An oversized request fails as a whole, and exhausts the budget
+        let mut rng = FailingRng::new(FixedRng(3, false), 10);
+        assert!(rng.try_fill_bytes(&mut [0u8; 11]).is_err());
+        assert!(rng.try_fill_bytes(&mut out).is_err());
+        assert!(FailingRng::new(FixedRng(0, true), 10).try_fill_bytes(&mut out).is_err());
+    }
+
+    #[test]
+    fn test_failure_propagates() {
+        let mut rng =
+            CombinedRng::new(FixedRng(0, true), rand_chacha::ChaCha8Rng::seed_from_u64(123));
+        assert!(rng.try_fill_bytes(&mut [0u8; 32]).is_err());
+    }
+
+    #[test]
+    fn test_trng_adapters() {
+        let mut rng = TrngRng::new(FixedRng(7, false));
+        let mut out = [0u8; 4];
+        rng.fill_bytes(&mut out);
+        assert_eq!(out, [7u8; 4]);
+        assert!(TrngRng::new(FixedRng(0, true)).try_fill_bytes(&mut out).is_err());
+
+        #[cfg(feature = "embedded-hal")]
+        {
+            struct Trng(bool);
+            impl embedded_hal::blocking::rng::Read for Trng {
+                type Error = ();
+
+                fn read(&mut self, out: &mut [u8]) -> Result<(), ()> {
+                    out.fill(0xA5);
+                    if self.0 {
+                        Err(())
+                    } else {
+                        Ok(())
+                    }
+                }
+            }
+
+            let mut rng = HalRng::new(Trng(false));
+            assert!(rng.try_fill_bytes(&mut out).is_ok());
+            assert_eq!(out, [0xA5; 4]);
+            let err = HalRng::new(Trng(true)).try_fill_bytes(&mut out).unwrap_err();
+            assert_eq!(err.code().map(core::num::NonZeroU32::get), Some(HAL_ERROR_CODE));
+        }
+    }
+}
@@ -0,0 +1,222 @@
+// This file implements key rollover attestations: a new public key endorsed by the old private
+// key and, optionally, the old public key endorsed by the new private key.
+
+//! Key rollover attestations.
+//!
+//! A rollover attestation binds an old and a new public key under a structured, domain
+//! separated statement so that it cannot be confused with any other signed message. The old
+//! private key endorses the new public key, and the new private key may co-sign an endorsement
+//! of the old public key (which demonstrates possession of the new private key). The two keys
+//! may belong to different parameter sets, e.g., when upgrading from ML-DSA-44 to ML-DSA-87.
+//!
+//! The signed message is `"fips204-rollover-v1" || H(|pk_old| || pk_old || |pk_new| || pk_new, 64)`,
+//! where lengths are 2-byte little-endian, `H` is SHAKE256, and the direction of the endorsement
+//! is carried in the FIPS 204 context string.
+//!
+//! # Examples
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! # #[cfg(all(feature = "ml-dsa-44", feature = "ml-dsa-87", feature = "default-rng"))] {
+//! use fips204::rollover;
+//! use fips204::{ml_dsa_44, ml_dsa_87};
+//!
+//! let (old_pk, old_sk) = ml_dsa_44::try_keygen()?;
+//! let (new_pk, new_sk) = ml_dsa_87::try_keygen()?;
+//!
+//! // The old key endorses the new key, and the new key co-signs the old key
+//! let old_sig = rollover::try_endorse_new_key(&old_sk, &new_pk)?;
+//! let new_sig = rollover::try_endorse_old_key(&new_sk, &old_pk)?;
+//!
+//! assert!(rollover::verify_rollover(&old_pk, &new_pk, &old_sig, &new_sig));
+//! # }
+//! # Ok(())}
+//! ```
+
+use crate::hashing::h256_xof;
+use crate::traits::{SerDes, Signer, Verifier};
+use rand_core::CryptoRngCore;
+#[cfg(feature = "default-rng")]
+use rand_core::OsRng;
+use sha3::digest::XofReader;
+
+const DOMAIN: &[u8; 19] = b"fips204-rollover-v1";
+const CTX_NEW_BY_OLD: &[u8] = b"fips204-rollover: old key endorses new key";
+const CTX_OLD_BY_NEW: &[u8] = b"fips204-rollover: new key endorses old key";
+
+
+/// Signs a rollover statement endorsing `new_pk` with the old private key. This function
+/// utilizes the **OS default** random number generator.
+///
+/// # Errors
+/// Returns an error when the random number generator fails.
+#[cfg(feature = "default-rng")]
+pub fn try_endorse_new_key<S, P>(old_sk: &S, new_pk: &P) -> Result<S::Signature, &'static str>
+where
+    S: Signer,
+    S::PublicKey: SerDes,
+    <S::PublicKey as SerDes>::ByteArray: AsRef<[u8]>,
+    P: SerDes + Clone,
+    P::ByteArray: AsRef<[u8]>,
+{
+    try_endorse_new_key_with_rng(old_sk, &mut OsRng, new_pk)
+}
+
+
+/// Signs a rollover statement endorsing `new_pk` with the old private key. This function
+/// utilizes the **provided** random number generator.
+///
+/// # Errors
+/// Returns an error when the random number generator fails.
+pub fn try_endorse_new_key_with_rng<S, P>(
+    old_sk: &S, rng: &mut impl CryptoRngCore, new_pk: &P,
+) -> Result<S::Signature, &'static str>
+where
+    S: Signer,
+    S::PublicKey: SerDes,
+    <S::PublicKey as SerDes>::ByteArray: AsRef<[u8]>,
+    P: SerDes + Clone,
+    P::ByteArray: AsRef<[u8]>,
+{
+    let old_pk_bytes = old_sk.get_public_key().into_bytes();
+    let statement = statement(old_pk_bytes.as_ref(), new_pk.clone().into_bytes().as_ref());
+    old_sk.try_sign_with_rng(rng, &statement, CTX_NEW_BY_OLD)
+}
+
+
+/// Signs a rollover statement endorsing `old_pk` with the new private key, demonstrating
+/// possession of the latter. This function utilizes the **OS default** random number generator.
+///
+/// # Errors
+/// Returns an error when the random number generator fails.
+#[cfg(feature = "default-rng")]
+pub fn try_endorse_old_key<S, P>(new_sk: &S, old_pk: &P) -> Result<S::Signature, &'static str>
+where
+    S: Signer,
+    S::PublicKey: SerDes,
+    <S::PublicKey as SerDes>::ByteArray: AsRef<[u8]>,
+    P: SerDes + Clone,
+    P::ByteArray: AsRef<[u8]>,
+{
+    try_endorse_old_key_with_rng(new_sk, &mut OsRng, old_pk)
+}
+
+
+/// Signs a rollover statement endorsing `old_pk` with the new private key, demonstrating
+/// possession of the latter. This function utilizes the **provided** random number generator.
+///
+/// # Errors
+/// Returns an error when the random number generator fails.
+pub fn try_endorse_old_key_with_rng<S, P>(
+    new_sk: &S, rng: &mut impl CryptoRngCore, old_pk: &P,
+) -> Result<S::Signature, &'static str>
+where
+    S: Signer,
+    S::PublicKey: SerDes,
+    <S::PublicKey as SerDes>::ByteArray: AsRef<[u8]>,
+    P: SerDes + Clone,
+    P::ByteArray: AsRef<[u8]>,
+{
+    let new_pk_bytes = new_sk.get_public_key().into_bytes();
+    let statement = statement(old_pk.clone().into_bytes().as_ref(), new_pk_bytes.as_ref());
+    new_sk.try_sign_with_rng(rng, &statement, CTX_OLD_BY_NEW)
+}
+
+
+/// Verifies that `old_sig` is an endorsement of `new_pk` by `old_pk`. As this function
+/// operates on purely public data, it need/does not provide constant-time assurances.
+pub fn verify_new_key_endorsement<V, P>(old_pk: &V, new_pk: &P, old_sig: &V::Signature) -> bool
+where
+    V: Verifier + SerDes + Clone,
+    V::ByteArray: AsRef<[u8]>,
+    P: SerDes + Clone,
+    P::ByteArray: AsRef<[u8]>,
+{
+    let statement =
+        statement(old_pk.clone().into_bytes().as_ref(), new_pk.clone().into_bytes().as_ref());
+    old_pk.verify(&statement, old_sig, CTX_NEW_BY_OLD)
+}
+
+
+/// Verifies that `new_sig` is an endorsement of `old_pk` by `new_pk`. As this function
+/// operates on purely public data, it need/does not provide constant-time assurances.
+pub fn verify_old_key_endorsement<V, P>(new_pk: &V, old_pk: &P, new_sig: &V::Signature) -> bool
+where
+    V: Verifier + SerDes + Clone,
+    V::ByteArray: AsRef<[u8]>,
+    P: SerDes + Clone,
+    P::ByteArray: AsRef<[u8]>,
+{
+    let statement =
+        statement(old_pk.clone().into_bytes().as_ref(), new_pk.clone().into_bytes().as_ref());
+    new_pk.verify(&statement, new_sig, CTX_OLD_BY_NEW)
+}
+
+
+/// Verifies a complete (co-signed) rollover: `old_pk` endorses `new_pk` and vice versa.
+pub fn verify_rollover<O, N>(
+    old_pk: &O, new_pk: &N, old_sig: &O::Signature, new_sig: &N::Signature,
+) -> bool
+where
+    O: Verifier + SerDes + Clone,
+    O::ByteArray: AsRef<[u8]>,
+    N: Verifier + SerDes + Clone,
+    N::ByteArray: AsRef<[u8]>,
+{
+    verify_new_key_endorsement(old_pk, new_pk, old_sig)
+        && verify_old_key_endorsement(new_pk, old_pk, new_sig)
+}
+
+
+/// Assembles the domain-separated rollover statement binding both public keys.
+fn statement(old_pk: &[u8], new_pk: &[u8]) -> [u8; 19 + 64] {
+    let old_len = u16::try_from(old_pk.len()).expect("cannot fail; public keys are < 64KiB");
+    let new_len = u16::try_from(new_pk.len()).expect("cannot fail; public keys are < 64KiB");
+    let mut out = [0u8; 19 + 64];
+    out[..19].copy_from_slice(DOMAIN);
+    h256_xof(&[&old_len.to_le_bytes(), old_pk, &new_len.to_le_bytes(), new_pk])
+        .read(&mut out[19..]);
+    out
+}
+
+
+#[cfg(all(test, feature = "ml-dsa-44", feature = "ml-dsa-65"))]
+mod tests {
+    use super::*;
+    use crate::{ml_dsa_44, ml_dsa_65};
+    use rand_chacha::rand_core::SeedableRng;
+
+    #[test]
+    #[allow(clippy::similar_names)]
+    fn test_rollover() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+        let (old_pk, old_sk) = ml_dsa_44::try_keygen_with_rng(&mut rng).unwrap();
+        let (new_pk, new_sk) = ml_dsa_65::try_keygen_with_rng(&mut rng).unwrap();
+        let (other_pk, _) = ml_dsa_65::try_keygen_with_rng(&mut rng).unwrap();
+
+        let old_sig = try_endorse_new_key_with_rng(&old_sk, &mut rng, &new_pk).unwrap();
+        let new_sig = try_endorse_old_key_with_rng(&new_sk, &mut rng, &old_pk).unwrap();
+
+        assert!(verify_new_key_endorsement(&old_pk, &new_pk, &old_sig));
+        assert!(verify_old_key_endorsement(&new_pk, &old_pk, &new_sig));
+        assert!(verify_rollover(&old_pk, &new_pk, &old_sig, &new_sig));
+
+        // Wrong new key, and no confusion with a plain signature over the statement
+        assert!(!verify_new_key_endorsement(&old_pk, &other_pk, &old_sig));
+        let statement = statement(&old_pk.clone().into_bytes(), &new_pk.clone().into_bytes());
+        assert!(!old_pk.verify(&statement, &old_sig, &[]));
+    }
+
+    #[test]
+    #[allow(clippy::similar_names)]
+    fn test_direction_is_bound() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(456);
+        let (pk_a, sk_a) = ml_dsa_44::try_keygen_with_rng(&mut rng).unwrap();
+        let (pk_b, _sk_b) = ml_dsa_44::try_keygen_with_rng(&mut rng).unwrap();
+
+        // A endorsing B as its successor is not A endorsing B as its predecessor
+        let sig = try_endorse_new_key_with_rng(&sk_a, &mut rng, &pk_b).unwrap();
+        assert!(verify_new_key_endorsement(&pk_a, &pk_b, &sig));
+        assert!(!verify_old_key_endorsement(&pk_a, &pk_b, &sig));
+    }
+}
@@ -0,0 +1,226 @@
+// This file implements a power-on style self-test: known-answer keygen/sign/verify checks for
+// each compiled-in security parameter set.
+
+use crate::hashing::h256_xof;
+use crate::types::ParamSet;
+use sha3::digest::XofReader;
+
+
+// The known-answer tests of each parameter set, taken from the NIST ACVP vectors by
+// `tests/nist_vectors` (which also checks that these files are current). Each file holds the
+// little-endian `u32` ACVP tcId of its signature generation vector, followed by five fields,
+// each preceded by its little-endian `u32` length: a key generation seed, the SHAKE256 digest of
+// the expected `pk || sk`, the private key and (formatted) message of a deterministic signature
+// generation vector, and the SHAKE256 digest of the expected signature.
+#[cfg(feature = "ml-dsa-44")]
+static KAT_44: &[u8] = include_bytes!("kats/ml_dsa_44.bin");
+#[cfg(feature = "ml-dsa-65")]
+static KAT_65: &[u8] = include_bytes!("kats/ml_dsa_65.bin");
+#[cfg(feature = "ml-dsa-87")]
+static KAT_87: &[u8] = include_bytes!("kats/ml_dsa_87.bin");
+
+
+// The known-answer test inputs and expected digests of one parameter set
+#[derive(Clone, Copy)]
+struct Kat {
+    xi: &'static [u8; 32],
+    keys: &'static [u8; 32],
+    sk: &'static [u8],
+    message: &'static [u8],
+    sig: &'static [u8; 32],
+}
+
+
+/// Outcome of the known-answer tests for a single security parameter set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SelfTestResult {
+    /// The security parameter set that was tested.
+    pub param_set: ParamSet,
+    /// Key generation from a fixed seed produced the expected key pair.
+    pub keygen: bool,
+    /// Deterministic signing produced the expected signature.
+    pub sign: bool,
+    /// The expected signature verified, and a corrupted copy was rejected.
+    pub verify: bool,
+}
+
+impl SelfTestResult {
+    /// Returns `true` when every known-answer test for this parameter set passed.
+    #[must_use]
+    pub fn passed(&self) -> bool { self.keygen && self.sign && self.verify }
+}
+
+
+/// Outcome of [`self_test()`] across all security parameter sets; entries for parameter sets
+/// that are not compiled in (per the `ml-dsa-44`, `ml-dsa-65` and `ml-dsa-87` features) are `None`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SelfTestReport {
+    /// Result for ML-DSA-44, if compiled in.
+    pub ml_dsa_44: Option<SelfTestResult>,
+    /// Result for ML-DSA-65, if compiled in.
+    pub ml_dsa_65: Option<SelfTestResult>,
+    /// Result for ML-DSA-87, if compiled in.
+    pub ml_dsa_87: Option<SelfTestResult>,
+}
+
+impl SelfTestReport {
+    /// Returns `true` when every compiled-in parameter set passed all of its tests.
+    #[must_use]
+    pub fn passed(&self) -> bool { self.results().all(SelfTestResult::passed) }
+
+
+    /// Iterates over the results of the compiled-in parameter sets.
+    pub fn results(&self) -> impl Iterator<Item = &SelfTestResult> {
+        [&self.ml_dsa_44, &self.ml_dsa_65, &self.ml_dsa_87].into_iter().flatten()
+    }
+}
+
+
+/// Runs a known-answer key generation, signature and verification test for each compiled-in
+/// security parameter set, as is required of a power-on self-test by FIPS 140-3 modules. Key
+/// generation and deterministic signing are applied to inputs of the NIST ACVP vectors, and
+/// the key pair and signature are compared against digests of the vectors' expected outputs.
+///
+/// With the `fips-mode` feature, the outcome also sets the module state (see `fips::state()`).
+///
+/// # Examples
+/// ```rust
+/// let report = fips204::self_test();
+/// assert!(report.passed());
+/// ```
+#[must_use]
+pub fn self_test() -> SelfTestReport {
+    #[cfg(feature = "fips-mode")]
+    let report = crate::fips::run_self_test(run_kats, SelfTestReport::passed);
+    #[cfg(not(feature = "fips-mode"))]
+    let report = run_kats();
+    report
+}
+
+
+/// Runs the known-answer tests of [`self_test()`] without affecting the module state.
+pub(crate) fn run_kats() -> SelfTestReport {
+    SelfTestReport {
+        #[cfg(feature = "ml-dsa-44")]
+        ml_dsa_44: Some(kat_44(kat(KAT_44))),
+        #[cfg(not(feature = "ml-dsa-44"))]
+        ml_dsa_44: None,
+        #[cfg(feature = "ml-dsa-65")]
+        ml_dsa_65: Some(kat_65(kat(KAT_65))),
+        #[cfg(not(feature = "ml-dsa-65"))]
+        ml_dsa_65: None,
+        #[cfg(feature = "ml-dsa-87")]
+        ml_dsa_87: Some(kat_87(kat(KAT_87))),
+        #[cfg(not(feature = "ml-dsa-87"))]
+        ml_dsa_87: None,
+    }
+}
+
+
+/// Parses the known-answer test of one parameter set, or returns `None` if it is malformed
+/// (which the tests rule out).
+#[allow(dead_code)] // Unused when no parameter sets are compiled in
+fn kat(bytes: &'static [u8]) -> Option<Kat> {
+    let mut rest = bytes.get(4..)?;
+    let mut fields = [&[][..]; 5];
+    for field in &mut fields {
+        let len = usize::try_from(u32::from_le_bytes(rest.get(..4)?.try_into().ok()?)).ok()?;
+        *field = rest.get(4..4 + len)?;
+        rest = &rest[4 + len..];
+    }
+    if !rest.is_empty() {
+        return None;
+    }
+    let [xi, keys, sk, message, sig] = fields;
+    let (xi, keys, sig) = (xi.try_into().ok()?, keys.try_into().ok()?, sig.try_into().ok()?);
+    Some(Kat { xi, keys, sk, message, sig })
+}
+
+
+// Defines the function running the known-answer tests for one parameter set, which compares
+// SHAKE256 digests of the key pair and signature against those of the ACVP vectors. As the
+// signature generation vectors apply to `ML-DSA.Sign_internal()` and `ML-DSA.Verify_internal()`,
+// so do the signing and verification tests.
+macro_rules! kat_fn {
+    ($name:ident, $feature:literal, $module:ident) => {
+        #[cfg(feature = $feature)]
+        #[allow(deprecated)] // The internal signing and verification functions
+        fn $name(kat: Option<Kat>) -> SelfTestResult {
+            use crate::traits::{KeyGen, SerDes, Signer};
+            use crate::$module;
+
+            let param_set = $module::PARAM_SET;
+            let failed = SelfTestResult { param_set, keygen: false, sign: false, verify: false };
+            let Some(kat) = kat else { return failed };
+
+            let (pk, sk) = $module::KG::keygen_from_seed(kat.xi);
+            let keygen = digest(&[&pk.into_bytes(), &sk.into_bytes()]) == *kat.keys;
+
+            let sk = kat.sk.try_into().ok().and_then(|sk| $module::PrivateKey::try_from_bytes(sk).ok());
+            let Some(sk) = sk else { return SelfTestResult { keygen, ..failed } };
+            let Ok(mut sig) = $module::_internal_sign(&sk, kat.message, &[], [0u8; 32]) else {
+                return SelfTestResult { keygen, ..failed };
+            };
+            let sign = digest(&[&sig]) == *kat.sig;
+
+            let pk = sk.get_public_key();
+            let good = $module::_internal_verify(&pk, kat.message, &sig, &[]);
+            sig[0] ^= 0x01;
+            let bad = $module::_internal_verify(&pk, kat.message, &sig, &[]);
+
+            SelfTestResult { param_set, keygen, sign, verify: good && !bad }
+        }
+    };
+}
+
+kat_fn!(kat_44, "ml-dsa-44", ml_dsa_44);
+kat_fn!(kat_65, "ml-dsa-65", ml_dsa_65);
+kat_fn!(kat_87, "ml-dsa-87", ml_dsa_87);
+
+
+#[allow(dead_code)] // Unused when no parameter sets are compiled in
+fn digest(v: &[&[u8]]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    h256_xof(v).read(&mut out);
+    out
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_test_passes() {
+        let report = self_test();
+        assert!(report.passed());
+        assert_eq!(report.ml_dsa_44.is_some(), cfg!(feature = "ml-dsa-44"));
+        assert_eq!(report.ml_dsa_65.is_some(), cfg!(feature = "ml-dsa-65"));
+        assert_eq!(report.ml_dsa_87.is_some(), cfg!(feature = "ml-dsa-87"));
+    }
+
+    #[test]
+    fn test_kats_present() {
+        #[cfg(feature = "ml-dsa-44")]
+        assert!(kat(KAT_44).is_some());
+        #[cfg(feature = "ml-dsa-65")]
+        assert!(kat(KAT_65).is_some());
+        #[cfg(feature = "ml-dsa-87")]
+        assert!(kat(KAT_87).is_some());
+        assert!(kat(&[0u8; 4]).is_none());
+    }
+
+    #[cfg(feature = "ml-dsa-44")]
+    #[test]
+    fn test_self_test_detects_mismatch() {
+        let kat = kat(KAT_44).unwrap();
+        let result = kat_44(Some(Kat { sig: &[0u8; 32], ..kat }));
+        assert!(result.keygen && !result.sign && result.verify);
+        assert!(!result.passed());
+        let result = kat_44(Some(Kat { keys: &[0u8; 32], ..kat }));
+        assert!(!result.keygen && result.sign);
+        let result = kat_44(Some(Kat { message: b"not the ACVP message", ..kat }));
+        assert!(result.keygen && !result.sign && result.verify);
+        assert!(!kat_44(None).passed());
+    }
+}
@@ -0,0 +1,46 @@
+// This file implements the DER `SubjectPublicKeyInfo` (SPKI) encoding of ML-DSA public keys, per
+// the IETF LAMPS ML-DSA X.509 profile: the algorithm identifier is one of the NIST OIDs
+// `2.16.840.1.101.3.4.3.{17,18,19}` with absent parameters, followed by the raw public key as a
+// BIT STRING. As the public key length is fixed per parameter set, the encoding is a fixed
+// 22-byte prefix followed by the public key.
+
+use crate::types::ParamSet;
+
+/// Length of the DER header preceding the public key bytes, for all parameter sets.
+pub(crate) const PREFIX_LEN: usize = 22;
+
+
+/// Returns the DER header preceding the public key bytes for the given parameter set.
+#[allow(clippy::cast_possible_truncation)] // lengths are all < 64KiB
+pub(crate) const fn prefix(param_set: ParamSet) -> [u8; PREFIX_LEN] {
+    let bits_len = param_set.pk_len() + 1; // leading 'unused bits' byte
+    let seq_len = 13 + 4 + bits_len; // AlgorithmIdentifier, BIT STRING header, BIT STRING
+    let arc = match param_set {
+        ParamSet::MlDsa44 => 0x11,
+        ParamSet::MlDsa65 => 0x12,
+        ParamSet::MlDsa87 => 0x13,
+    };
+    [
+        0x30, 0x82, (seq_len >> 8) as u8, seq_len as u8, // SEQUENCE
+        0x30, 0x0b, // SEQUENCE (AlgorithmIdentifier)
+        0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x03, arc, // OBJECT IDENTIFIER
+        0x03, 0x82, (bits_len >> 8) as u8, bits_len as u8, 0x00, // BIT STRING, 0 unused bits
+    ]
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix() {
+        // ML-DSA-44 SPKI header, as per the LAMPS example certificates
+        let expected = [
+            0x30, 0x82, 0x05, 0x32, 0x30, 0x0b, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03,
+            0x04, 0x03, 0x11, 0x03, 0x82, 0x05, 0x21, 0x00,
+        ];
+        assert_eq!(prefix(ParamSet::MlDsa44), expected);
+        assert_eq!(prefix(ParamSet::MlDsa87)[16], 0x13);
+    }
+}
@@ -0,0 +1,329 @@
+// This file implements Shamir secret sharing of the 32-byte key generation seed `ξ` for
+// M-of-N backup of ML-DSA private keys.
+
+//! Shamir secret sharing of the key generation seed, only exposed when the `sss` feature is
+//! enabled.
+//!
+//! The 32-byte seed `ξ` that deterministically generates an ML-DSA key pair (see
+//! [`crate::traits::KeyGen::keygen_from_seed()`]) is split byte-wise over GF(2^8) into `N`
+//! shares, any `threshold` of which reconstruct it. Fewer than `threshold` shares reveal
+//! nothing about the seed. The field arithmetic (reduction polynomial `x^8 + x^4 + x^3 + x + 1`)
+//! is table-free and constant-time relative to the seed and share values.
+//!
+//! Note that shares carry no integrity protection: combining a corrupted (or too few) shares
+//! yields a different seed, and so a different key pair. Operators should check the recovered
+//! public key against a known copy.
+//!
+//! # Examples
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! # #[cfg(all(feature = "ml-dsa-65", feature = "default-rng"))] {
+//! use fips204::ml_dsa_65; // Could also be ml_dsa_44 or ml_dsa_87.
+//! use fips204::sss;
+//! use fips204::traits::SerDes;
+//!
+//! // Generate a key pair along with five shares of its seed, any three of which suffice
+//! let (pk, _sk, shares) = sss::try_keygen_and_split::<ml_dsa_65::KG, 5>(3)?;
+//!
+//! // Later, recover the key pair from shares 1, 3 and 4
+//! let (pk2, _sk2) = sss::try_keygen_from_shares::<ml_dsa_65::KG>(&[
+//!     shares[1].clone(),
+//!     shares[3].clone(),
+//!     shares[4].clone(),
+//! ])?;
+//! assert_eq!(pk.into_bytes(), pk2.into_bytes());
+//! # }
+//! # Ok(())}
+//! ```
+
+use crate::helpers::ensure;
+use crate::traits::KeyGen;
+use rand_core::CryptoRngCore;
+#[cfg(feature = "default-rng")]
+use rand_core::OsRng;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+
+/// One share of a key generation seed.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct Share {
+    index: u8,
+    value: [u8; 32],
+}
+
+
+impl Share {
+    /// Returns the (non-zero) x-coordinate of this share.
+    #[must_use]
+    pub fn index(&self) -> u8 { self.index }
+
+
+    /// Serializes the share as its index followed by its 32-byte value.
+    #[must_use]
+    pub fn to_bytes(&self) -> [u8; 33] {
+        let mut out = [0u8; 33];
+        out[0] = self.index;
+        out[1..].copy_from_slice(&self.value);
+        out
+    }
+
+
+    /// Deserializes a share produced by [`Share::to_bytes()`].
+    ///
+    /// # Errors
+    /// Returns an error when the index is zero.
+    pub fn try_from_bytes(bytes: &[u8; 33]) -> Result<Self, &'static str> {
+        ensure!(bytes[0] != 0, "SSS: share index must be non-zero");
+        let mut value = [0u8; 32];
+        value.copy_from_slice(&bytes[1..]);
+        Ok(Share { index: bytes[0], value })
+    }
+}
+
+
+impl TryFrom<&[u8]> for Share {
+    type Error = &'static str;
+
+    /// Deserializes a share from a slice of exactly 33 bytes; see [`Share::try_from_bytes()`].
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::try_from_bytes(bytes.try_into().map_err(|_| "SSS: share must be 33 bytes")?)
+    }
+}
+
+
+/// Splits `seed` into `N` shares, any `threshold` of which reconstruct it. This function
+/// utilizes the **OS default** random number generator.
+///
+/// # Errors
+/// Returns an error when `threshold` is zero or exceeds `N`, when `N > 255`, or when the
+/// random number generator fails.
+#[cfg(feature = "default-rng")]
+pub fn try_split_seed<const N: usize>(
+    seed: &[u8; 32], threshold: u8,
+) -> Result<[Share; N], &'static str> {
+    try_split_seed_with_rng(&mut OsRng, seed, threshold)
+}
+
+
+/// Splits `seed` into `N` shares, any `threshold` of which reconstruct it. This function
+/// utilizes the **provided** random number generator.
+///
+/// # Errors
+/// Returns an error when `threshold` is zero or exceeds `N`, when `N > 255`, or when the
+/// random number generator fails.
+pub fn try_split_seed_with_rng<const N: usize>(
+    rng: &mut impl CryptoRngCore, seed: &[u8; 32], threshold: u8,
+) -> Result<[Share; N], &'static str> {
+    ensure!(N < 256, "SSS: too many shares");
+    ensure!(threshold > 0 && usize::from(threshold) <= N, "SSS: bad threshold");
+
+    // One random polynomial of degree threshold - 1 per seed byte, with the seed byte at x = 0
+    let mut coeffs = [[0u8; 32]; 255];
+    for c in &mut coeffs[..usize::from(threshold) - 1] {
+        rng.try_fill_bytes(c).map_err(|_| "SSS: random number generator failed")?;
+    }
+
+    #[allow(clippy::cast_possible_truncation)] // N < 256 checked above
+    let shares = core::array::from_fn(|i| {
+        let x = (i + 1) as u8;
+        let mut value = [0u8; 32];
+        for (b, v) in value.iter_mut().enumerate() {
+            // Horner's rule, highest degree first
+            let mut acc = 0u8;
+            for c in coeffs[..usize::from(threshold) - 1].iter().rev() {
+                acc = gf_mul(acc, x) ^ c[b];
+            }
+            *v = gf_mul(acc, x) ^ seed[b];
+        }
+        Share { index: x, value }
+    });
+    coeffs.zeroize();
+    Ok(shares)
+}
+
+
+/// Reconstructs a seed from shares. All supplied shares are used, so exactly (or at least)
+/// `threshold` shares of the same split must be supplied.
+///
+/// # Errors
+/// Returns an error when no shares are supplied, or when share indices are zero or repeated.
+pub fn try_combine_seed(shares: &[Share]) -> Result<[u8; 32], &'static str> {
+    ensure!(!shares.is_empty(), "SSS: no shares");
+    for (i, share) in shares.iter().enumerate() {
+        ensure!(share.index != 0, "SSS: share index must be non-zero");
+        ensure!(shares[..i].iter().all(|s| s.index != share.index), "SSS: repeated share index");
+    }
+
+    // Lagrange interpolation at x = 0; in GF(2^8) subtraction is addition (XOR)
+    let mut seed = [0u8; 32];
+    for (i, share) in shares.iter().enumerate() {
+        let mut basis = 1u8;
+        for (j, other) in shares.iter().enumerate() {
+            if i != j {
+                basis = gf_mul(basis, gf_mul(other.index, gf_inv(other.index ^ share.index)));
+            }
+        }
+        for (s, v) in seed.iter_mut().zip(share.value.iter()) {
+            *s ^= gf_mul(basis, *v);
+        }
+    }
+    Ok(seed)
+}
+
+
+/// Generates a key pair for parameter set `G` from a fresh seed, and splits the seed into `N`
+/// shares, any `threshold` of which reconstruct the key pair via [`try_keygen_from_shares()`].
+/// This function utilizes the **OS default** random number generator.
+///
+/// # Errors
+/// Returns an error when `threshold` is zero or exceeds `N`, when `N > 255`, or when the
+/// random number generator fails.
+#[cfg(feature = "default-rng")]
+#[allow(clippy::type_complexity)]
+pub fn try_keygen_and_split<G: KeyGen, const N: usize>(
+    threshold: u8,
+) -> Result<(G::PublicKey, G::PrivateKey, [Share; N]), &'static str> {
+    try_keygen_and_split_with_rng::<G, N>(&mut OsRng, threshold)
+}
+
+
+/// Generates a key pair for parameter set `G` from a fresh seed, and splits the seed into `N`
+/// shares; see [`try_keygen_and_split()`]. This function utilizes the **provided** random
+/// number generator.
+///
+/// # Errors
+/// Returns an error when `threshold` is zero or exceeds `N`, when `N > 255`, or when the
+/// random number generator fails.
+#[allow(clippy::type_complexity)]
+pub fn try_keygen_and_split_with_rng<G: KeyGen, const N: usize>(
+    rng: &mut impl CryptoRngCore, threshold: u8,
+) -> Result<(G::PublicKey, G::PrivateKey, [Share; N]), &'static str> {
+    #[cfg(feature = "fips-mode")]
+    crate::fips::check_operational()?;
+    let mut xi = [0u8; 32];
+    rng.try_fill_bytes(&mut xi).map_err(|_| "SSS: random number generator failed")?;
+    let shares = match try_split_seed_with_rng::<N>(rng, &xi, threshold) {
+        Ok(shares) => shares,
+        Err(e) => {
+            xi.zeroize();
+            return Err(e);
+        }
+    };
+    let (pk, sk) = G::keygen_from_seed(&xi);
+    xi.zeroize();
+    Ok((pk, sk, shares))
+}
+
+
+/// Reconstructs the key pair of parameter set `G` from seed shares.
+///
+/// # Errors
+/// Returns an error when no shares are supplied, or when share indices are zero or repeated.
+pub fn try_keygen_from_shares<G: KeyGen>(
+    shares: &[Share],
+) -> Result<(G::PublicKey, G::PrivateKey), &'static str> {
+    #[cfg(feature = "fips-mode")]
+    crate::fips::check_operational()?;
+    let mut xi = try_combine_seed(shares)?;
+    let keys = G::keygen_from_seed(&xi);
+    xi.zeroize();
+    Ok(keys)
+}
+
+
+// Constant-time multiplication in GF(2^8) modulo x^8 + x^4 + x^3 + x + 1
+fn gf_mul(a: u8, b: u8) -> u8 {
+    let (mut a, mut b, mut res) = (a, b, 0u8);
+    for _ in 0..8 {
+        res ^= a & 0u8.wrapping_sub(b & 1);
+        let carry = 0u8.wrapping_sub(a >> 7);
+        a = (a << 1) ^ (carry & 0x1b);
+        b >>= 1;
+    }
+    res
+}
+
+
+// Constant-time inversion in GF(2^8) as a^254; maps 0 to 0
+fn gf_inv(a: u8) -> u8 {
+    let mut res = a;
+    for _ in 0..6 {
+        res = gf_mul(gf_mul(res, res), a);
+    }
+    gf_mul(res, res)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_chacha::rand_core::SeedableRng;
+
+    #[test]
+    fn test_gf_arithmetic() {
+        assert_eq!(gf_mul(0x53, 0xca), 0x01); // FIPS 197 section 4.2 example
+        assert_eq!(gf_mul(0x57, 0x83), 0xc1);
+        for a in 1..=255u8 {
+            assert_eq!(gf_mul(a, gf_inv(a)), 1);
+        }
+        assert_eq!(gf_inv(0), 0);
+    }
+
+    #[test]
+    fn test_split_combine() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+        let seed: [u8; 32] = core::array::from_fn(|i| u8::try_from(i).unwrap());
+
+        let shares = try_split_seed_with_rng::<5>(&mut rng, &seed, 3).unwrap();
+        for a in 0..5 {
+            for b in (a + 1)..5 {
+                for c in (b + 1)..5 {
+                    let subset = [shares[a].clone(), shares[b].clone(), shares[c].clone()];
+                    assert_eq!(try_combine_seed(&subset).unwrap(), seed);
+                }
+                // Too few shares do not reconstruct the seed
+                let subset = [shares[a].clone(), shares[b].clone()];
+                assert_ne!(try_combine_seed(&subset).unwrap(), seed);
+            }
+        }
+        assert_eq!(try_combine_seed(&shares).unwrap(), seed);
+
+        let single = try_split_seed_with_rng::<2>(&mut rng, &seed, 1).unwrap();
+        assert_eq!(single[1].value, seed);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+        assert!(try_split_seed_with_rng::<3>(&mut rng, &[0u8; 32], 0).is_err());
+        assert!(try_split_seed_with_rng::<3>(&mut rng, &[0u8; 32], 4).is_err());
+        assert!(try_split_seed_with_rng::<256>(&mut rng, &[0u8; 32], 2).is_err());
+
+        let shares = try_split_seed_with_rng::<3>(&mut rng, &[7u8; 32], 2).unwrap();
+        assert!(try_combine_seed(&[]).is_err());
+        assert!(try_combine_seed(&[shares[0].clone(), shares[0].clone()]).is_err());
+
+        let bytes = shares[2].to_bytes();
+        assert_eq!(Share::try_from_bytes(&bytes).unwrap().index(), 3);
+        let mut bad = bytes;
+        bad[0] = 0;
+        assert!(Share::try_from_bytes(&bad).is_err());
+        assert_eq!(Share::try_from(&bytes[..]).unwrap().to_bytes(), bytes);
+        assert!(Share::try_from(&bytes[1..]).is_err());
+    }
+
+    #[cfg(feature = "ml-dsa-44")]
+    #[test]
+    fn test_keygen_integration() {
+        use crate::ml_dsa_44;
+        use crate::traits::SerDes;
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+        let (pk, sk, shares) =
+            try_keygen_and_split_with_rng::<ml_dsa_44::KG, 3>(&mut rng, 2).unwrap();
+        let (pk2, sk2) =
+            try_keygen_from_shares::<ml_dsa_44::KG>(&[shares[2].clone(), shares[0].clone()]).unwrap();
+        assert_eq!(pk.into_bytes(), pk2.into_bytes());
+        assert_eq!(sk.into_bytes(), sk2.into_bytes());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::types::Ph;
+use crate::types::{Context, Ctx, Msg, Ph};
 use rand_core::CryptoRngCore;
 #[cfg(feature = "default-rng")]
 use rand_core::OsRng;
@@ -14,6 +14,15 @@ pub trait KeyGen {
     /// signing performance. Derived from the private key.
     type PrivateKey;
 
+    /// Serialized public key length in bytes for this security parameter set.
+    const PK_LEN: usize;
+
+    /// Serialized private key length in bytes for this security parameter set.
+    const SK_LEN: usize;
+
+    /// Signature length in bytes for this security parameter set.
+    const SIG_LEN: usize;
+
 
     /// Generates a public and private key pair specific to this security parameter set.
     /// This function utilizes the **OS default** random number generator. This function operates
@@ -52,6 +61,10 @@ pub trait KeyGen {
     /// number generator internals, the `rho` value stored in the public key, and the hash-derived
     /// `rho_prime` values that are rejection-sampled/expanded into the internal `s_1` and `s_2` values).
     ///
+    /// The random number generator is consumed by exactly one `try_fill_bytes()` call for the
+    /// 32-byte seed `ξ`, so the keys are those of [`KeyGen::keygen_from_seed()`] on the first
+    /// 32 bytes of the generator's output. This is a compatibility guarantee across releases.
+    ///
     /// # Errors
     /// Returns an error when the random number generator fails.
     ///
@@ -88,7 +101,7 @@ pub trait KeyGen {
     /// ```rust
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// # #[cfg(feature = "ml-dsa-44")] {
+    /// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
     /// use crate::fips204::RngCore;
     /// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
     /// use fips204::traits::{KeyGen, Signer, Verifier};
@@ -114,7 +127,10 @@ pub trait KeyGen {
 }
 
 
-/// The Signer trait is implemented for the `PrivateKey` struct on each of the security parameter sets.
+/// The Signer trait is implemented for the `PrivateKey` struct on each of the security parameter sets,
+/// and for the corresponding validated `EncodedPrivateKey` (which is expanded on each call). Generic
+/// code bounded on `Signer` thereby accepts either form, in all of the pure, pre-hash and
+/// [`Context`] modes.
 pub trait Signer {
     /// The signature is specific to the chosen security parameter set, e.g., ml-dsa-44, ml-dsa-65 or ml-dsa-87
     type Signature;
@@ -122,6 +138,12 @@ pub trait Signer {
     /// The public key that corresponds to the private/secret key
     type PublicKey;
 
+    /// Serialized private key length in bytes, e.g., for sizing buffers in generic code.
+    const SK_LEN: usize;
+
+    /// Signature length in bytes, e.g., for sizing buffers in generic code.
+    const SIG_LEN: usize;
+
 
     /// Attempt to sign the given message, returning a digital signature on success, or an error if
     /// something went wrong. This function utilizes the **OS default** random number generator.
@@ -166,6 +188,10 @@ pub trait Signer {
     /// `s_1` and `s_2` values, and the main signing rejection loop as noted in section 5.5 of
     /// <https://pq-crystals.org/dilithium/data/dilithium-specification-round3-20210208.pdf>.
     ///
+    /// The random number generator is consumed by exactly one `try_fill_bytes()` call for the
+    /// 32-byte `rnd`, so the signature is that of the internal signing algorithm on the first 32
+    /// bytes of the generator's output. This is a compatibility guarantee across releases.
+    ///
     /// # Errors
     /// Returns an error when the random number generator fails; propagates internal errors.
     ///
@@ -222,6 +248,9 @@ pub trait Signer {
     /// main signing rejection loop as noted in section 5.5 of
     /// <https://pq-crystals.org/dilithium/data/dilithium-specification-round3-20210208.pdf>.
     ///
+    /// As with [`Signer::try_sign_with_rng()`], the random number generator is consumed by
+    /// exactly one 32-byte `try_fill_bytes()` call for `rnd`.
+    ///
     /// # Errors
     /// Will return an error on rng failure
     fn try_hash_sign_with_rng(
@@ -229,12 +258,87 @@ pub trait Signer {
     ) -> Result<Self::Signature, &'static str>;
 
 
+    /// Attempt to sign the given message under a [`Context`], which selects the context string
+    /// and either ML-DSA or HashML-DSA (when a pre-hash function was supplied). This function
+    /// utilizes the **OS default** random number generator.
+    ///
+    /// # Errors
+    /// Returns an error when the random number generator fails or the context is too long.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # #[cfg(all(feature = "ml-dsa-65", feature = "default-rng"))] {
+    /// use fips204::ml_dsa_65; // Could also be ml_dsa_44 or ml_dsa_87.
+    /// use fips204::traits::{Signer, Verifier};
+    /// use fips204::{Context, Ph};
+    ///
+    /// let message = [0u8, 1, 2, 3, 4, 5, 6, 7];
+    /// let context = Context::new(b"my-proto").with_hash(Ph::SHA512);
+    ///
+    /// let (pk, sk) = ml_dsa_65::try_keygen()?;
+    /// let sig = sk.try_sign_with_context(&message, &context)?;
+    /// assert!(pk.verify_with_context(&message, &sig, &context));
+    /// assert!(pk.hash_verify(&message, &sig, b"my-proto", &Ph::SHA512));
+    /// # }
+    /// # Ok(())}
+    /// ```
+    #[cfg(feature = "default-rng")]
+    fn try_sign_with_context(
+        &self, message: &[u8], context: &Context<'_>,
+    ) -> Result<Self::Signature, &'static str> {
+        self.try_sign_with_context_and_rng(&mut OsRng, message, context)
+    }
+
+
+    /// Attempt to sign the given message under a [`Context`]; see
+    /// [`Signer::try_sign_with_context()`]. This function utilizes the **provided** random
+    /// number generator.
+    ///
+    /// # Errors
+    /// Returns an error when the random number generator fails or the context is too long.
+    fn try_sign_with_context_and_rng(
+        &self, rng: &mut impl CryptoRngCore, message: &[u8], context: &Context<'_>,
+    ) -> Result<Self::Signature, &'static str> {
+        match context.hash() {
+            None => self.try_sign_with_rng(rng, message, context.ctx()),
+            Some(ph) => self.try_hash_sign_with_rng(rng, message, context.ctx(), ph),
+        }
+    }
+
+
+    /// Attempt to sign the message under the context string, given as distinct types so that
+    /// swapped arguments do not compile; see [`Ctx`]. This function utilizes the **OS default**
+    /// random number generator.
+    ///
+    /// # Errors
+    /// Returns an error when the random number generator fails or the context is too long.
+    #[cfg(feature = "default-rng")]
+    fn try_sign_msg<'a>(&self, msg: impl Into<Msg<'a>>, ctx: Ctx<'a>) -> Result<Self::Signature, &'static str> {
+        self.try_sign_with_rng(&mut OsRng, msg.into().as_bytes(), ctx.as_bytes())
+    }
+
+
+    /// Attempt to sign the message under the context string; see [`Signer::try_sign_msg()`].
+    /// This function utilizes the **provided** random number generator.
+    ///
+    /// # Errors
+    /// Returns an error when the random number generator fails or the context is too long.
+    fn try_sign_msg_with_rng<'a>(
+        &self, rng: &mut impl CryptoRngCore, msg: impl Into<Msg<'a>>, ctx: Ctx<'a>,
+    ) -> Result<Self::Signature, &'static str> {
+        self.try_sign_with_rng(rng, msg.into().as_bytes(), ctx.as_bytes())
+    }
+
+
     /// Retrieves the public key associated with this private/secret key
     ///
     /// # Examples
     /// ```rust
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # #[cfg(all(feature = "ml-dsa-65", feature = "default-rng"))] {
     /// use fips204::ml_dsa_65; // Could also be ml_dsa_44 or ml_dsa_87.
     /// use fips204::traits::{KeyGen, SerDes, Signer, Verifier};
     ///
@@ -246,6 +350,7 @@ pub trait Signer {
     /// // The public key can be derived from the secret key
     /// let pk2 = sk.get_public_key();
     /// assert_eq!(pk1.into_bytes(), pk2.into_bytes());
+    /// # }
     /// # Ok(())
     /// # }
     /// ```
@@ -253,12 +358,21 @@ pub trait Signer {
 }
 
 
-/// The Verifier trait is implemented for `PublicKey` on each of the security parameter sets.
+/// The Verifier trait is implemented for `PublicKey` on each of the security parameter sets, and
+/// for the corresponding encoded public key byte arrays (which are expanded on each call). Generic
+/// code bounded on `Verifier` thereby accepts either form, in all of the pure, pre-hash and
+/// [`Context`] modes.
 pub trait Verifier {
     /// The signature is specific to the chosen security parameter set, e.g., ml-dsa-44, ml-dsa-65
     /// or ml-dsa-87
     type Signature;
 
+    /// Serialized public key length in bytes, e.g., for sizing buffers in generic code.
+    const PK_LEN: usize;
+
+    /// Signature length in bytes, e.g., for sizing buffers in generic code.
+    const SIG_LEN: usize;
+
     /// Verifies a digital signature on a message with respect to a `PublicKey`. As this function
     /// operates on purely public data, it need/does not provide constant-time assurances.
     ///
@@ -286,6 +400,25 @@ pub trait Verifier {
     /// Verifies a digital signature on the hash of a message with respect to a `PublicKey`. As this
     /// function operates on purely public data, it need/does not provide constant-time assurances.
     fn hash_verify(&self, message: &[u8], sig: &Self::Signature, ctx: &[u8], ph: &Ph) -> bool;
+
+
+    /// Verifies a digital signature on the message under the context string, given as distinct
+    /// types so that swapped arguments do not compile; see [`Ctx`]. As this function operates on
+    /// purely public data, it need/does not provide constant-time assurances.
+    fn verify_msg<'a>(&self, msg: impl Into<Msg<'a>>, sig: &Self::Signature, ctx: Ctx<'a>) -> bool {
+        self.verify(msg.into().as_bytes(), sig, ctx.as_bytes())
+    }
+
+
+    /// Verifies a digital signature on a message under a [`Context`], which selects the context
+    /// string and either ML-DSA or HashML-DSA (when a pre-hash function was supplied). As this
+    /// function operates on purely public data, it need/does not provide constant-time assurances.
+    fn verify_with_context(&self, message: &[u8], sig: &Self::Signature, context: &Context<'_>) -> bool {
+        match context.hash() {
+            None => self.verify(message, sig, context.ctx()),
+            Some(ph) => self.hash_verify(message, sig, context.ctx(), ph),
+        }
+    }
 }
 
 
@@ -349,3 +482,263 @@ pub trait SerDes {
     where
         Self: Sized;
 }
+
+
+/// An object-safe counterpart to [`Signer`] operating on byte slices, so that plugin
+/// architectures can hold e.g. `Box<dyn DynSigner>` mixing the ML-DSA parameter sets and
+/// (via adapters implementing this trait directly) classical algorithms. Blanket implemented
+/// for every [`Signer`].
+///
+/// # Examples
+/// ```rust
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # #[cfg(all(feature = "ml-dsa-44", feature = "ml-dsa-87", feature = "default-rng", feature = "alloc"))] {
+/// use fips204::traits::{DynSigner, DynVerifier};
+/// use fips204::{ml_dsa_44, ml_dsa_87};
+///
+/// let (pk44, sk44) = ml_dsa_44::try_keygen()?;
+/// let (pk87, sk87) = ml_dsa_87::try_keygen()?;
+/// let signers: [Box<dyn DynSigner>; 2] = [Box::new(sk44), Box::new(sk87)];
+/// let verifiers: [Box<dyn DynVerifier>; 2] = [Box::new(pk44), Box::new(pk87)];
+///
+/// for (signer, verifier) in signers.iter().zip(verifiers.iter()) {
+///     let sig = signer.try_sign_to_vec(b"msg", b"ctx")?;
+///     assert!(verifier.verify_bytes(b"msg", &sig, b"ctx"));
+/// }
+/// # }
+/// # Ok(())}
+/// ```
+pub trait DynSigner {
+    /// Returns the length in bytes of the signatures produced.
+    fn signature_len(&self) -> usize;
+
+
+    /// Signs the message, writing the signature into the start of `out` and returning its
+    /// length. This function utilizes the **provided** random number generator.
+    ///
+    /// # Errors
+    /// Returns an error when `out` is shorter than [`DynSigner::signature_len()`], the random
+    /// number generator fails, or the context is too long.
+    fn try_sign_into_with_rng(
+        &self, rng: &mut dyn CryptoRngCore, message: &[u8], ctx: &[u8], out: &mut [u8],
+    ) -> Result<usize, &'static str>;
+
+
+    /// Signs the message, writing the signature into the start of `out` and returning its
+    /// length. This function utilizes the **OS default** random number generator.
+    ///
+    /// # Errors
+    /// Returns an error when `out` is shorter than [`DynSigner::signature_len()`], the random
+    /// number generator fails, or the context is too long.
+    #[cfg(feature = "default-rng")]
+    fn try_sign_into(&self, message: &[u8], ctx: &[u8], out: &mut [u8]) -> Result<usize, &'static str> {
+        self.try_sign_into_with_rng(&mut OsRng, message, ctx, out)
+    }
+
+
+    /// Signs the message, returning the signature as a vector. This function utilizes the
+    /// **provided** random number generator. Only exposed when the `alloc` feature is enabled.
+    ///
+    /// # Errors
+    /// Returns an error when the random number generator fails or the context is too long.
+    #[cfg(feature = "alloc")]
+    fn try_sign_to_vec_with_rng(
+        &self, rng: &mut dyn CryptoRngCore, message: &[u8], ctx: &[u8],
+    ) -> Result<alloc::vec::Vec<u8>, &'static str> {
+        let mut sig = alloc::vec![0u8; self.signature_len()];
+        let len = self.try_sign_into_with_rng(rng, message, ctx, &mut sig)?;
+        sig.truncate(len);
+        Ok(sig)
+    }
+
+
+    /// Signs the message, returning the signature as a vector. This function utilizes the
+    /// **OS default** random number generator. Only exposed when the `alloc` feature is enabled.
+    ///
+    /// # Errors
+    /// Returns an error when the random number generator fails or the context is too long.
+    #[cfg(all(feature = "alloc", feature = "default-rng"))]
+    fn try_sign_to_vec(&self, message: &[u8], ctx: &[u8]) -> Result<alloc::vec::Vec<u8>, &'static str> {
+        self.try_sign_to_vec_with_rng(&mut OsRng, message, ctx)
+    }
+}
+
+
+/// An object-safe counterpart to [`Verifier`] operating on byte slices; see [`DynSigner`].
+/// Blanket implemented for every [`Verifier`].
+pub trait DynVerifier {
+    /// Returns the length in bytes of the signatures accepted.
+    fn signature_len(&self) -> usize;
+
+
+    /// Verifies a signature supplied as a byte slice, returning `false` when it has the wrong
+    /// length. As this function operates on purely public data, it need/does not provide
+    /// constant-time assurances.
+    fn verify_bytes(&self, message: &[u8], sig: &[u8], ctx: &[u8]) -> bool;
+}
+
+
+impl<T: Signer> DynSigner for T
+where
+    T::Signature: AsRef<[u8]>,
+{
+    fn signature_len(&self) -> usize { T::SIG_LEN }
+
+    fn try_sign_into_with_rng(
+        &self, mut rng: &mut dyn CryptoRngCore, message: &[u8], ctx: &[u8], out: &mut [u8],
+    ) -> Result<usize, &'static str> {
+        let out = out.get_mut(..T::SIG_LEN).ok_or("DynSigner: output buffer too short")?;
+        let sig = self.try_sign_with_rng(&mut rng, message, ctx)?;
+        out.copy_from_slice(sig.as_ref());
+        Ok(T::SIG_LEN)
+    }
+}
+
+
+impl<T: Verifier> DynVerifier for T
+where
+    T::Signature: for<'a> TryFrom<&'a [u8]>,
+{
+    fn signature_len(&self) -> usize { T::SIG_LEN }
+
+    fn verify_bytes(&self, message: &[u8], sig: &[u8], ctx: &[u8]) -> bool {
+        T::Signature::try_from(sig).is_ok_and(|sig| self.verify(message, &sig, ctx))
+    }
+}
+
+
+/// A post-quantum signature scheme as a whole, implemented by a marker type per parameter set
+/// (`Scheme` in each of `ml_dsa_44`, `ml_dsa_65` and `ml_dsa_87`), so that applications can swap
+/// schemes (e.g., ML-DSA and SLH-DSA) behind a single generic parameter.
+///
+/// The trait is shared across the FIPS 203/204/205 crate family. It therefore depends only on
+/// `rand_core`, with keys and signatures decoded from byte slices and encoded to fixed-size
+/// arrays whose lengths are the associated constants, rather than on this crate's other
+/// traits; the same shape is to be implemented by `fips205`.
+///
+/// # Examples
+/// ```rust
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # #[cfg(all(feature = "ml-dsa-44", feature = "ml-dsa-87", feature = "default-rng"))] {
+/// use fips204::traits::PqSignature;
+/// use fips204::{ml_dsa_44, ml_dsa_87};
+///
+/// // Generic over the scheme, e.g. selected by configuration at compile time
+/// fn sign_and_check<S: PqSignature>(message: &[u8]) -> Result<bool, &'static str> {
+///     let (pk, sk) = S::try_keygen()?;
+///     let sig = S::try_sign(&sk, message, b"ctx")?;
+///     let pk = S::try_public_key_from_bytes(S::public_key_to_bytes(&pk).as_ref())?;
+///     Ok(S::verify(&pk, message, &sig, b"ctx"))
+/// }
+///
+/// assert!(sign_and_check::<ml_dsa_44::Scheme>(b"message")?);
+/// assert!(sign_and_check::<ml_dsa_87::Scheme>(b"message")?);
+/// assert_eq!(ml_dsa_87::Scheme::ALGORITHM, "ML-DSA-87");
+/// # }
+/// # Ok(())}
+/// ```
+pub trait PqSignature {
+    /// The name of the algorithm and parameter set, e.g. `ML-DSA-65` or `SLH-DSA-SHA2-128s`.
+    const ALGORITHM: &'static str;
+
+    /// Encoded public key length in bytes.
+    const PK_LEN: usize;
+
+    /// Encoded private key length in bytes.
+    const SK_LEN: usize;
+
+    /// Signature length in bytes.
+    const SIG_LEN: usize;
+
+    /// The public key type.
+    type PublicKey;
+
+    /// The private key type.
+    type PrivateKey;
+
+    /// The signature type, a byte array of `SIG_LEN` bytes.
+    type Signature: AsRef<[u8]>;
+
+    /// The encoded public key type, a byte array of `PK_LEN` bytes.
+    type PublicKeyBytes: AsRef<[u8]>;
+
+    /// The encoded private key type, a byte array of `SK_LEN` bytes.
+    type PrivateKeyBytes: AsRef<[u8]>;
+
+
+    /// Generates a key pair. This function utilizes the **OS default** random number generator.
+    ///
+    /// # Errors
+    /// Returns an error when the random number generator fails.
+    #[cfg(feature = "default-rng")]
+    fn try_keygen() -> Result<(Self::PublicKey, Self::PrivateKey), &'static str> {
+        Self::try_keygen_with_rng(&mut OsRng)
+    }
+
+
+    /// Generates a key pair. This function utilizes the **provided** random number generator.
+    ///
+    /// # Errors
+    /// Returns an error when the random number generator fails.
+    fn try_keygen_with_rng(
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<(Self::PublicKey, Self::PrivateKey), &'static str>;
+
+
+    /// Signs the message with the context string. This function utilizes the **OS default**
+    /// random number generator.
+    ///
+    /// # Errors
+    /// Returns an error when the random number generator fails or the context string is too
+    /// long.
+    #[cfg(feature = "default-rng")]
+    fn try_sign(sk: &Self::PrivateKey, message: &[u8], ctx: &[u8]) -> Result<Self::Signature, &'static str> {
+        Self::try_sign_with_rng(sk, &mut OsRng, message, ctx)
+    }
+
+
+    /// Signs the message with the context string. This function utilizes the **provided**
+    /// random number generator.
+    ///
+    /// # Errors
+    /// Returns an error when the random number generator fails or the context string is too
+    /// long.
+    fn try_sign_with_rng(
+        sk: &Self::PrivateKey, rng: &mut impl CryptoRngCore, message: &[u8], ctx: &[u8],
+    ) -> Result<Self::Signature, &'static str>;
+
+
+    /// Verifies the signature of the message with the context string.
+    fn verify(pk: &Self::PublicKey, message: &[u8], sig: &Self::Signature, ctx: &[u8]) -> bool;
+
+
+    /// Encodes the public key.
+    fn public_key_to_bytes(pk: &Self::PublicKey) -> Self::PublicKeyBytes;
+
+
+    /// Encodes the private key.
+    fn private_key_to_bytes(sk: &Self::PrivateKey) -> Self::PrivateKeyBytes;
+
+
+    /// Decodes a public key.
+    ///
+    /// # Errors
+    /// Returns an error when `bytes` has the wrong length or is malformed.
+    fn try_public_key_from_bytes(bytes: &[u8]) -> Result<Self::PublicKey, &'static str>;
+
+
+    /// Decodes a private key.
+    ///
+    /// # Errors
+    /// Returns an error when `bytes` has the wrong length or is malformed.
+    fn try_private_key_from_bytes(bytes: &[u8]) -> Result<Self::PrivateKey, &'static str>;
+
+
+    /// Decodes a signature; a well-formed signature may still fail to verify.
+    ///
+    /// # Errors
+    /// Returns an error when `bytes` has the wrong length.
+    fn try_signature_from_bytes(bytes: &[u8]) -> Result<Self::Signature, &'static str>;
+}
@@ -1,7 +1,11 @@
+use crate::helpers::ensure;
+use core::marker::PhantomData;
+use sha2::Digest;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 
 /// Supported hash functions for `hash_sign()` and `hash_verify()` functions
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Ph {
     /// Use SHA256 as the pre-hash function
     SHA256,
@@ -12,11 +16,462 @@ pub enum Ph {
 }
 
 
+impl Ph {
+    /// Returns the DER encoding (tag, length and value) of the pre-hash function's object
+    /// identifier, as included in the HashML-DSA formatted message `M′`.
+    #[must_use]
+    pub const fn oid(&self) -> [u8; 11] {
+        match self {
+            Ph::SHA256 => nist_hash_oid(0x01),
+            Ph::SHA512 => nist_hash_oid(0x03),
+            Ph::SHAKE128 => nist_hash_oid(0x0B),
+        }
+    }
+
+
+    /// Identifies the pre-hash function from the DER encoding of its object identifier, as
+    /// returned by [`Ph::oid()`]. Returns `None` for any other encoding.
+    #[must_use]
+    pub fn from_oid(oid: &[u8]) -> Option<Ph> {
+        [Ph::SHA256, Ph::SHA512, Ph::SHAKE128].into_iter().find(|ph| ph.oid() == oid)
+    }
+}
+
+
+/// The DER encoding of the NIST hash algorithm object identifier `2.16.840.1.101.3.4.2.arc`.
+const fn nist_hash_oid(arc: u8) -> [u8; 11] {
+    [0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, arc]
+}
+
+const SHA224_OID: [u8; 11] = nist_hash_oid(0x04);
+const SHA256_OID: [u8; 11] = nist_hash_oid(0x01);
+const SHA384_OID: [u8; 11] = nist_hash_oid(0x02);
+const SHA512_OID: [u8; 11] = nist_hash_oid(0x03);
+const SHA512_224_OID: [u8; 11] = nist_hash_oid(0x05);
+const SHA512_256_OID: [u8; 11] = nist_hash_oid(0x06);
+const SHA3_224_OID: [u8; 11] = nist_hash_oid(0x07);
+const SHA3_256_OID: [u8; 11] = nist_hash_oid(0x08);
+const SHA3_384_OID: [u8; 11] = nist_hash_oid(0x09);
+const SHA3_512_OID: [u8; 11] = nist_hash_oid(0x0A);
+
+
+/// A HashML-DSA pre-hash function given by any [`Digest`] implementation `D` together with the
+/// DER encoding of its object identifier, for hash functions beyond those of [`Ph`]. Accepted
+/// by e.g. `ml_dsa_65::PrivateKey::try_hash_sign_digest()` and
+/// `ml_dsa_65::PublicKey::hash_verify_digest()`.
+///
+/// Constructors are provided for the fixed-length SHA-2 and SHA-3 functions; FIPS 204 requires
+/// the pre-hash function to offer at least the collision strength of the parameter set (e.g.,
+/// 256 bits for ML-DSA-87, ruling out SHA-224 and SHA-384 there). Signatures made with
+/// [`DigestPh::sha256()`] and [`DigestPh::sha512()`] are identical to those made with
+/// [`Ph::SHA256`] and [`Ph::SHA512`].
+///
+/// # Examples
+/// ```rust
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # #[cfg(all(feature = "ml-dsa-65", feature = "default-rng"))] {
+/// use fips204::ml_dsa_65; // Could also be ml_dsa_44 or ml_dsa_87.
+/// use fips204::DigestPh;
+///
+/// let (pk, sk) = ml_dsa_65::try_keygen()?;
+/// let ph = DigestPh::sha3_384(); // E.g., as mandated by an organization's policy
+/// let sig = sk.try_hash_sign_digest(b"message", b"ctx", &ph)?;
+/// assert!(pk.hash_verify_digest(b"message", &sig, b"ctx", &ph));
+///
+/// // Any other `Digest` implementation, with its OID
+/// let ph = DigestPh::<sha3::Sha3_384>::try_new(&[0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x09])?;
+/// assert!(pk.hash_verify_digest(b"message", &sig, b"ctx", &ph));
+/// # }
+/// # Ok(())}
+/// ```
+#[derive(Debug)]
+pub struct DigestPh<'a, D: Digest> {
+    oid: &'a [u8],
+    digest: PhantomData<D>,
+}
+
+
+impl<'a, D: Digest> DigestPh<'a, D> {
+    /// Pairs the digest `D` with the DER encoding (tag, length and value) of its object
+    /// identifier.
+    ///
+    /// # Errors
+    /// Returns an error when `oid` is not a DER-encoded object identifier (with a length below
+    /// 128 bytes).
+    pub fn try_new(oid: &'a [u8]) -> Result<Self, &'static str> {
+        ensure!(oid.len() > 2 && oid[0] == 0x06, "DigestPh: malformed OID");
+        ensure!(usize::from(oid[1]) == oid.len() - 2 && oid[1] < 0x80, "DigestPh: malformed OID");
+        ensure!(oid[oid.len() - 1] & 0x80 == 0, "DigestPh: malformed OID");
+        Ok(Self { oid, digest: PhantomData })
+    }
+
+
+    /// Returns the DER encoding of the pre-hash function's object identifier.
+    #[must_use]
+    pub const fn oid(&self) -> &'a [u8] { self.oid }
+}
+
+
+impl<D: Digest> Clone for DigestPh<'_, D> {
+    fn clone(&self) -> Self { Self { oid: self.oid, digest: PhantomData } }
+}
+
+
+macro_rules! nist_digest_ph {
+    ($(($name:ident, $hash:ty, $oid:ident, $doc:literal)),* $(,)?) => {
+        $(
+            impl DigestPh<'static, $hash> {
+                #[doc = concat!("The ", $doc, " pre-hash function.")]
+                #[must_use]
+                pub const fn $name() -> Self { Self { oid: &$oid, digest: PhantomData } }
+            }
+        )*
+    };
+}
+
+nist_digest_ph!(
+    (sha224, sha2::Sha224, SHA224_OID, "SHA-224"),
+    (sha256, sha2::Sha256, SHA256_OID, "SHA-256"),
+    (sha384, sha2::Sha384, SHA384_OID, "SHA-384"),
+    (sha512, sha2::Sha512, SHA512_OID, "SHA-512"),
+    (sha512_224, sha2::Sha512_224, SHA512_224_OID, "SHA-512/224"),
+    (sha512_256, sha2::Sha512_256, SHA512_256_OID, "SHA-512/256"),
+    (sha3_224, sha3::Sha3_224, SHA3_224_OID, "SHA3-224"),
+    (sha3_256, sha3::Sha3_256, SHA3_256_OID, "SHA3-256"),
+    (sha3_384, sha3::Sha3_384, SHA3_384_OID, "SHA3-384"),
+    (sha3_512, sha3::Sha3_512, SHA3_512_OID, "SHA3-512"),
+);
+
+
+/// The context string along with the (optional) pre-hash function under which a message is
+/// signed and verified. Accepted by [`crate::traits::Signer::try_sign_with_context()`] and
+/// [`crate::traits::Verifier::verify_with_context()`], which dispatch to ML-DSA or HashML-DSA.
+///
+/// # Examples
+/// ```rust
+/// use fips204::{Context, Ph};
+///
+/// let pure = Context::new(b"my-proto"); // ML-DSA
+/// let prehashed = Context::new(b"my-proto").with_hash(Ph::SHA512); // HashML-DSA
+/// assert_eq!(prehashed.ctx(), pure.ctx());
+/// assert!(pure.hash().is_none());
+/// ```
+pub struct Context<'a> {
+    ctx: &'a [u8],
+    ph: Option<Ph>,
+}
+
+
+impl<'a> Context<'a> {
+    /// Creates a (pure ML-DSA) context from the context string; note that the 255 byte limit is
+    /// checked when signing or verifying.
+    #[must_use]
+    pub const fn new(ctx: &'a [u8]) -> Self { Self { ctx, ph: None } }
+
+
+    /// Selects HashML-DSA with the given pre-hash function.
+    #[must_use]
+    pub const fn with_hash(self, ph: Ph) -> Self { Self { ctx: self.ctx, ph: Some(ph) } }
+
+
+    /// Returns the context string.
+    #[must_use]
+    pub const fn ctx(&self) -> &'a [u8] { self.ctx }
+
+
+    /// Returns the pre-hash function, if HashML-DSA was selected.
+    #[must_use]
+    pub const fn hash(&self) -> Option<&Ph> { self.ph.as_ref() }
+}
+
+
+impl Default for Context<'_> {
+    /// The empty context string with pure ML-DSA.
+    fn default() -> Self { Self::new(&[]) }
+}
+
+
+/// A message to be signed or verified, as distinct from its context string [`Ctx`]. Accepted
+/// by [`crate::traits::Signer::try_sign_msg()`] and [`crate::traits::Verifier::verify_msg()`],
+/// and converted from byte slices, byte arrays and strings, so that only the context string
+/// needs naming at the call site.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Msg<'a>(&'a [u8]);
+
+
+impl<'a> Msg<'a> {
+    /// Wraps the message bytes.
+    #[must_use]
+    pub const fn new(message: &'a [u8]) -> Self { Self(message) }
+
+
+    /// Returns the message bytes.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &'a [u8] { self.0 }
+}
+
+
+impl<'a> From<&'a [u8]> for Msg<'a> {
+    fn from(message: &'a [u8]) -> Self { Self(message) }
+}
+
+
+impl<'a, const N: usize> From<&'a [u8; N]> for Msg<'a> {
+    fn from(message: &'a [u8; N]) -> Self { Self(message) }
+}
+
+
+impl<'a> From<&'a str> for Msg<'a> {
+    fn from(message: &'a str) -> Self { Self(message.as_bytes()) }
+}
+
+
+/// A (pure ML-DSA) context string, as distinct from the message [`Msg`]. Unlike `Msg`, it has
+/// no conversion from byte slices and must be created with [`Ctx::new()`], so that swapping the
+/// message and context arguments fails to compile rather than producing signatures that verify
+/// nothing. Converts into a [`Context`], e.g., to select HashML-DSA.
+///
+/// # Examples
+/// ```rust
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # #[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))] {
+/// use fips204::ml_dsa_44; // Could also be ml_dsa_65 or ml_dsa_87.
+/// use fips204::traits::{Signer, Verifier};
+/// use fips204::Ctx;
+///
+/// const CTX: Ctx = Ctx::new(b"my-proto");
+/// let (pk, sk) = ml_dsa_44::try_keygen()?;
+/// let sig = sk.try_sign_msg(b"message", CTX)?;
+/// assert!(pk.verify_msg(b"message", &sig, CTX));
+/// assert!(pk.verify(b"message", &sig, b"my-proto"));
+/// # }
+/// # Ok(())}
+/// ```
+///
+/// Swapped arguments are rejected by the compiler:
+/// ```rust,compile_fail
+/// # #[cfg(feature = "ml-dsa-44")] {
+/// # use fips204::ml_dsa_44;
+/// # use fips204::traits::Signer;
+/// # use fips204::Ctx;
+/// # fn sign(sk: &ml_dsa_44::PrivateKey, rng: &mut impl rand_core::CryptoRngCore) {
+/// let sig = sk.try_sign_msg_with_rng(rng, Ctx::new(b"my-proto"), b"message");
+/// # }
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Ctx<'a>(&'a [u8]);
+
+
+impl<'a> Ctx<'a> {
+    /// The empty context string.
+    pub const EMPTY: Ctx<'static> = Ctx(&[]);
+
+
+    /// Wraps the context string; note that the 255 byte limit is checked when signing or
+    /// verifying.
+    #[must_use]
+    pub const fn new(ctx: &'a [u8]) -> Self { Self(ctx) }
+
+
+    /// Returns the context string.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &'a [u8] { self.0 }
+}
+
+
+impl<'a> From<Ctx<'a>> for Context<'a> {
+    fn from(ctx: Ctx<'a>) -> Self { Context::new(ctx.0) }
+}
+
+
+/// Controls how signature verification rejects an invalid signature.
+///
+/// A signature is rejected when the norm of its response `z` is too large or when the challenge
+/// hash `c_tilde` recomputed from the reconstructed commitment does not match. Malformed
+/// encodings (e.g., an improperly encoded hint) are rejected immediately in either mode.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum VerifyMode {
+    /// Perform all of the verification work and compare all of `c_tilde` before rejecting, so
+    /// that the time taken does not reveal which check failed. This is the **default**, and is
+    /// what [`crate::traits::Verifier::verify()`] uses.
+    #[default]
+    Uniform,
+    /// Reject as early as possible: the norm of `z` is checked before the (expensive) matrix
+    /// expansion and NTT work, and the `c_tilde` comparison stops at the first mismatch. Suited
+    /// to servers filtering large volumes of junk signatures.
+    FastReject,
+}
+
+
+/// How the per-signature randomness `rnd` was chosen, per FIPS 204 section 3.4.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SigningMode {
+    /// `rnd` was drawn from a random number generator (the default, and recommended, variant).
+    Hedged,
+    /// `rnd` was all zeros, so the signature is a function of the key and message alone.
+    Deterministic,
+}
+
+
+/// A signature together with the [`SigningMode`] that produced it, as returned by e.g.
+/// `ml_dsa_44::PrivateKey::try_sign_tagged()`.
+///
+/// Hedged and deterministic signatures are indistinguishable on the wire, so the tag is known
+/// only to the signer and is not part of any encoding. It cannot be constructed outside this
+/// crate, so application code and audit hooks can rely on it to enforce a policy such as
+/// "deterministic signing is forbidden in production", e.g., via
+/// [`TaggedSignature::require_hedged()`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaggedSignature<S> {
+    signature: S,
+    mode: SigningMode,
+}
+
+
+impl<S> TaggedSignature<S> {
+    pub(crate) const fn new(signature: S, mode: SigningMode) -> Self { Self { signature, mode } }
+
+
+    /// Returns the mode in which the signature was produced.
+    #[must_use]
+    pub const fn mode(&self) -> SigningMode { self.mode }
+
+
+    /// Returns the signature.
+    #[must_use]
+    pub const fn signature(&self) -> &S { &self.signature }
+
+
+    /// Returns the signature, discarding the tag.
+    pub fn into_signature(self) -> S { self.signature }
+
+
+    /// Returns the signature if it was hedged.
+    ///
+    /// # Errors
+    /// Returns an error when the signature was produced deterministically.
+    pub fn require_hedged(self) -> Result<S, &'static str> {
+        ensure!(self.mode == SigningMode::Hedged, "TaggedSignature: deterministic signature not permitted");
+        Ok(self.signature)
+    }
+}
+
+
+/// Identifies one of the ML-DSA security parameter sets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParamSet {
+    /// ML-DSA-44, claimed to be in security strength category 2
+    MlDsa44,
+    /// ML-DSA-65, claimed to be in security strength category 3
+    MlDsa65,
+    /// ML-DSA-87, claimed to be in security strength category 5
+    MlDsa87,
+}
+
+
+impl ParamSet {
+    /// The name of the parameter set, e.g. `ML-DSA-65`, as also rendered by `Display`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            ParamSet::MlDsa44 => "ML-DSA-44",
+            ParamSet::MlDsa65 => "ML-DSA-65",
+            ParamSet::MlDsa87 => "ML-DSA-87",
+        }
+    }
+
+
+    /// Public key length in bytes for this parameter set.
+    #[must_use]
+    pub const fn pk_len(self) -> usize {
+        match self {
+            ParamSet::MlDsa44 => 1312,
+            ParamSet::MlDsa65 => 1952,
+            ParamSet::MlDsa87 => 2592,
+        }
+    }
+
+
+    /// Private (secret) key length in bytes for this parameter set.
+    #[must_use]
+    pub const fn sk_len(self) -> usize {
+        match self {
+            ParamSet::MlDsa44 => 2560,
+            ParamSet::MlDsa65 => 4032,
+            ParamSet::MlDsa87 => 4896,
+        }
+    }
+
+
+    /// Signature length in bytes for this parameter set.
+    #[must_use]
+    pub const fn sig_len(self) -> usize {
+        match self {
+            ParamSet::MlDsa44 => 2420,
+            ParamSet::MlDsa65 => 3309,
+            ParamSet::MlDsa87 => 4627,
+        }
+    }
+
+
+    /// Identifies the parameter set from a public key length; the lengths are unique across
+    /// the parameter sets. Returns `None` when no parameter set has this public key length.
+    #[must_use]
+    pub const fn from_pk_len(len: usize) -> Option<ParamSet> {
+        match len {
+            1312 => Some(ParamSet::MlDsa44),
+            1952 => Some(ParamSet::MlDsa65),
+            2592 => Some(ParamSet::MlDsa87),
+            _ => None,
+        }
+    }
+
+
+    /// Identifies the parameter set from a private key length; the lengths are unique across
+    /// the parameter sets. Returns `None` when no parameter set has this private key length.
+    #[must_use]
+    pub const fn from_sk_len(len: usize) -> Option<ParamSet> {
+        match len {
+            2560 => Some(ParamSet::MlDsa44),
+            4032 => Some(ParamSet::MlDsa65),
+            4896 => Some(ParamSet::MlDsa87),
+            _ => None,
+        }
+    }
+
+
+    /// Identifies the parameter set from a signature length; the lengths are unique across
+    /// the parameter sets. Returns `None` when no parameter set has this signature length.
+    #[must_use]
+    pub const fn from_sig_len(len: usize) -> Option<ParamSet> {
+        match len {
+            2420 => Some(ParamSet::MlDsa44),
+            3309 => Some(ParamSet::MlDsa65),
+            4627 => Some(ParamSet::MlDsa87),
+            _ => None,
+        }
+    }
+}
+
+
+impl core::fmt::Display for ParamSet {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+
 /// Private key specific to the target security parameter set that contains
 /// precomputed elements which improve signature performance.
 ///
-/// Implements the [`crate::traits::Signer`] and [`crate::traits::SerDes`] traits.
-#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+/// Implements the [`crate::traits::Signer`] and [`crate::traits::SerDes`] traits (plus
+/// `TryFrom<&[u8]>`), and `Clone` only with the `clone-private-key` feature; `duplicate()`
+/// copies the key explicitly.
+#[cfg_attr(feature = "clone-private-key", derive(Clone))]
+#[derive(Zeroize, ZeroizeOnDrop)]
 #[repr(align(8))]
 pub struct PrivateKey<const K: usize, const L: usize> {
     pub(crate) rho: [u8; 32],
@@ -31,7 +486,9 @@ pub struct PrivateKey<const K: usize, const L: usize> {
 /// Public key specific to the target security parameter set that contains
 /// precomputed elements which improve verification performance.
 ///
-/// Implements the [`crate::traits::Verifier`] and [`crate::traits::SerDes`] traits.
+/// Implements the [`crate::traits::Verifier`] and [`crate::traits::SerDes`] traits, and
+/// `TryFrom<&[u8]>`; `&PublicKey` converts into its byte encoding. Also implements `Eq` and
+/// `Hash` (e.g., to key a map of trusted keys) and a `Debug` showing the key's fingerprint.
 #[derive(Clone, Zeroize, ZeroizeOnDrop)]
 #[repr(align(8))]
 pub struct PublicKey<const K: usize, const L: usize> {
@@ -41,6 +498,22 @@ pub struct PublicKey<const K: usize, const L: usize> {
 }
 
 
+/// An encoded private key specific to the target security parameter set, validated on
+/// construction. It holds only the encoding, expanding it on each signature, and zeroizes
+/// itself on drop.
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct EncodedPrivateKey<const SK_LEN: usize>(pub(crate) [u8; SK_LEN]);
+
+
+/// A public key together with its private key, for the target security parameter set. Each
+/// key zeroizes itself on drop. Implements `Clone` only with the `clone-private-key` feature.
+#[cfg_attr(feature = "clone-private-key", derive(Clone))]
+pub struct Keypair<const K: usize, const L: usize> {
+    pub(crate) pk: PublicKey<K, L>,
+    pub(crate) sk: PrivateKey<K, L>,
+}
+
+
 /// Polynomial coefficients in R, with default R0
 #[derive(Clone, Debug, PartialEq, Zeroize, ZeroizeOnDrop)]
 #[repr(align(8))]
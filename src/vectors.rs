@@ -0,0 +1,159 @@
+// This file exposes the NIST ACVP test vectors applied by `tests/nist_vectors`, embedded in the
+// crate, so that downstream wrappers can run the same conformance corpus.
+
+//! The NIST ACVP conformance vectors, only exposed when the `vectors` feature is enabled.
+//!
+//! These are the key generation, signature generation and signature verification vectors of
+//! the ACVP server's `internalProjection.json` files for FIPS 204 (as applied by this crate's
+//! own `tests/nist_vectors`), for all three parameter sets. They are embedded as a compact
+//! binary corpus (`tests/nist_vectors/acvp.bin`, regenerated from the JSON files by an ignored
+//! test) of roughly 1.3 MB, and iterated without allocation.
+//!
+//! The signature vectors exercise the internal interface: their `message` is the formatted
+//! message `M′` of `ML-DSA.Sign_internal()` and `ML-DSA.Verify_internal()`, with no domain
+//! separator or context string prepended (see [`crate::message`]). A wrapper exposing only the
+//! external interface applies them via e.g. [`crate::message::mu_internal()`] and the `hazmat`
+//! functions.
+//!
+//! # Examples
+//! ```rust
+//! # #[cfg(feature = "ml-dsa-44")] {
+//! use fips204::traits::{KeyGen, SerDes};
+//! use fips204::{ml_dsa_44, vectors, ParamSet};
+//!
+//! for v in vectors::keygen().filter(|v| v.param_set == ParamSet::MlDsa44) {
+//!     let (pk, sk) = ml_dsa_44::KG::keygen_from_seed(v.seed);
+//!     assert_eq!(pk.into_bytes()[..], v.pk[..]);
+//!     assert_eq!(sk.into_bytes()[..], v.sk[..]);
+//! }
+//! # }
+//! ```
+
+use crate::types::ParamSet;
+
+
+/// The embedded corpus: a sequence of records, each a kind byte, a parameter set byte, a
+/// little-endian `u32` test case identifier, and the little-endian `u32` length of the
+/// record's fields, each of which is in turn preceded by its little-endian `u32` length.
+static CORPUS: &[u8] = include_bytes!("../tests/nist_vectors/acvp.bin");
+
+const KIND_KEYGEN: u8 = 0;
+const KIND_SIGGEN: u8 = 1;
+const KIND_SIGVER: u8 = 2;
+
+
+/// A key generation vector: `ML-DSA.KeyGen_internal(seed)` yields `(pk, sk)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyGenVector {
+    /// The parameter set.
+    pub param_set: ParamSet,
+    /// The ACVP test case identifier, unique within the parameter set.
+    pub tc_id: u32,
+    /// The seed `ξ`.
+    pub seed: &'static [u8; 32],
+    /// The expected encoded public key.
+    pub pk: &'static [u8],
+    /// The expected encoded private key.
+    pub sk: &'static [u8],
+}
+
+
+/// A signature generation vector: `ML-DSA.Sign_internal(sk, message, rnd)` yields `signature`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SigGenVector {
+    /// The parameter set.
+    pub param_set: ParamSet,
+    /// The ACVP test case identifier, unique within the parameter set.
+    pub tc_id: u32,
+    /// Whether this is the deterministic variant, for which `rnd` is all zeros.
+    pub deterministic: bool,
+    /// The encoded private key.
+    pub sk: &'static [u8],
+    /// The formatted message `M′`.
+    pub message: &'static [u8],
+    /// The per-signature randomness `rnd`.
+    pub rnd: &'static [u8; 32],
+    /// The expected signature.
+    pub signature: &'static [u8],
+}
+
+
+/// A signature verification vector: `ML-DSA.Verify_internal(pk, message, signature)` returns
+/// `test_passed`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SigVerVector {
+    /// The parameter set.
+    pub param_set: ParamSet,
+    /// The ACVP test case identifier, unique within the parameter set.
+    pub tc_id: u32,
+    /// The encoded public key.
+    pub pk: &'static [u8],
+    /// The formatted message `M′`.
+    pub message: &'static [u8],
+    /// The (possibly invalid) signature.
+    pub signature: &'static [u8],
+    /// Whether the signature is valid.
+    pub test_passed: bool,
+}
+
+
+/// Returns the key generation vectors, for all parameter sets.
+pub fn keygen() -> impl Iterator<Item = KeyGenVector> {
+    records(KIND_KEYGEN).filter_map(|(param_set, tc_id, [seed, pk, sk])| {
+        Some(KeyGenVector { param_set, tc_id, seed: seed.try_into().ok()?, pk, sk })
+    })
+}
+
+
+/// Returns the signature generation vectors, for all parameter sets.
+pub fn siggen() -> impl Iterator<Item = SigGenVector> {
+    // The deterministic vectors carry no `rnd`
+    records(KIND_SIGGEN).filter_map(|(param_set, tc_id, [sk, message, rnd, signature])| {
+        let deterministic = rnd.is_empty();
+        let rnd = if deterministic { &[0u8; 32] } else { rnd.try_into().ok()? };
+        Some(SigGenVector { param_set, tc_id, deterministic, sk, message, rnd, signature })
+    })
+}
+
+
+/// Returns the signature verification vectors, for all parameter sets.
+pub fn sigver() -> impl Iterator<Item = SigVerVector> {
+    records(KIND_SIGVER).map(|(param_set, tc_id, [pk, message, signature, passed])| SigVerVector {
+        param_set,
+        tc_id,
+        pk,
+        message,
+        signature,
+        test_passed: passed == [1],
+    })
+}
+
+
+/// Iterates over the corpus records of the given kind, which have `N` fields each. Iteration
+/// stops at the end of the corpus, or at a malformed record (which the tests rule out).
+fn records<const N: usize>(kind: u8) -> impl Iterator<Item = (ParamSet, u32, [&'static [u8]; N])> {
+    let mut rest = CORPUS;
+    core::iter::from_fn(move || loop {
+        let header = rest.get(..10)?;
+        let param_set = match header[1] {
+            0 => ParamSet::MlDsa44,
+            1 => ParamSet::MlDsa65,
+            2 => ParamSet::MlDsa87,
+            _ => return None,
+        };
+        let tc_id = u32::from_le_bytes(header[2..6].try_into().ok()?);
+        let body_len = usize::try_from(u32::from_le_bytes(header[6..10].try_into().ok()?)).ok()?;
+        let mut body = rest.get(10..10 + body_len)?;
+        rest = &rest[10 + body_len..];
+        if header[0] != kind {
+            continue;
+        }
+        let mut fields = [&[][..]; N];
+        for field in &mut fields {
+            let len = usize::try_from(u32::from_le_bytes(body.get(..4)?.try_into().ok()?)).ok()?;
+            *field = body.get(4..4 + len)?;
+            body = &body[4 + len..];
+        }
+        return body.is_empty().then_some((param_set, tc_id, fields));
+    })
+}
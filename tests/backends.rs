@@ -0,0 +1,117 @@
+// Differential tests across arithmetic backends, and across the internal code paths that reach
+// the same computation. Seeded random inputs are run through every backend compiled in (see
+// `fips204::algorithm_id().backends`) and the keys and signatures must be bit-identical to the
+// scalar reference. Within the scalar backend, every alternative key generation, signing and
+// verification path must agree with the primary trait API, so that an optimization landing in
+// one path cannot silently diverge from the others.
+//
+// A new backend must be added to `run()` below; the test fails until it is.
+
+use fips204::traits::{KeyGen, SerDes, Signer, Verifier};
+use fips204::Backend;
+use rand_chacha::rand_core::SeedableRng;
+use rand_core::RngCore;
+
+
+#[derive(Debug, PartialEq)]
+struct Outputs {
+    pk: Vec<u8>,
+    sk: Vec<u8>,
+    sig: Vec<u8>,
+}
+
+
+macro_rules! backend_test {
+    ($name:ident, $feature:literal, $module:ident) => {
+        #[cfg(feature = $feature)]
+        mod $name {
+            use super::*;
+            use fips204::$module;
+
+            /// The scalar backend, cross-checking its alternative code paths along the way.
+            fn scalar(seed: &[u8; 32], message: &[u8], ctx: &[u8]) -> Outputs {
+                let (pk, sk) = $module::KG::keygen_from_seed(seed);
+                let (pk_bytes, sk_bytes) = (pk.clone().into_bytes(), sk.duplicate().into_bytes());
+                let sig = sk.try_sign_deterministic(message, ctx).unwrap().into_signature();
+
+                // Key generation into caller-provided buffers
+                let (mut pk_into, mut sk_into) = ([0u8; $module::PK_LEN], [0u8; $module::SK_LEN]);
+                $module::keygen_from_seed_into(seed, &mut pk_into, &mut sk_into);
+                assert_eq!((pk_into, sk_into), (pk_bytes, sk_bytes), "keygen_from_seed_into()");
+
+                // Signing from the encoded private key, and in place, each drawing the same `rnd`
+                let rng = || rand_chacha::ChaCha8Rng::seed_from_u64(1190);
+                let hedged = sk.try_sign_with_rng(&mut rng(), message, ctx).unwrap();
+                let from_bytes = $module::try_sign_from_bytes_with_rng(&mut rng(), &sk_bytes, message, ctx);
+                assert_eq!(from_bytes.unwrap(), hedged, "try_sign_from_bytes_with_rng()");
+                let mut buf = message.to_vec();
+                buf.resize(message.len() + $module::SIG_LEN, 0);
+                sk.try_sign_in_place_with_rng(&mut rng(), &mut buf, message.len(), ctx).unwrap();
+                assert_eq!(buf[message.len()..], hedged[..], "try_sign_in_place_with_rng()");
+
+                // Signing via the start/finish split
+                #[cfg(all(feature = "hazmat", not(feature = "fips-mode")))]
+                {
+                    let esk = $module::hazmat::sign_start(&sk_bytes).unwrap();
+                    let tr = $module::hazmat::private_key_tr(&esk);
+                    let mu = fips204::message::mu(&tr, message, ctx, None).unwrap();
+                    assert_eq!($module::hazmat::sign_finish(&esk, &mu, [0u8; 32]), sig, "hazmat::sign_finish()");
+                }
+
+                // Verification paths agree on both the valid and a corrupted signature
+                let mut bad_sig = sig;
+                bad_sig[$module::SIG_LEN / 2] ^= 0x10;
+                for (sig, expected) in [(&sig, true), (&bad_sig, false)] {
+                    assert_eq!(pk.verify(message, sig, ctx), expected, "verify()");
+                    let mut chunked = pk.try_verify_chunked(ctx).unwrap();
+                    message.chunks(100).for_each(|chunk| chunked.update_message(chunk));
+                    sig.chunks(333).for_each(|chunk| {
+                        let _ = chunked.update_signature(chunk);
+                    });
+                    assert_eq!(chunked.verify(), expected, "try_verify_chunked()");
+                    let mut stepwise = pk.try_verify_stepwise(message, sig, ctx).unwrap();
+                    let result = loop {
+                        if let Some(result) = stepwise.step() {
+                            break result;
+                        }
+                    };
+                    assert_eq!(result, expected, "try_verify_stepwise()");
+                }
+
+                Outputs { pk: pk_bytes.to_vec(), sk: sk_bytes.to_vec(), sig: sig.to_vec() }
+            }
+
+            fn run(backend: Backend, seed: &[u8; 32], message: &[u8], ctx: &[u8]) -> Outputs {
+                match backend {
+                    Backend::Scalar => scalar(seed, message, ctx),
+                    _ => panic!("no differential test harness for the {backend} backend"),
+                }
+            }
+
+            #[test]
+            fn backends_agree() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1190);
+                for _ in 0..8 {
+                    let mut seed = [0u8; 32];
+                    rng.fill_bytes(&mut seed);
+                    let mut message = vec![0u8; rng.next_u32() as usize % 5000];
+                    rng.fill_bytes(&mut message);
+                    let ctx = [0x5Au8; 7];
+                    let reference = scalar(&seed, &message, &ctx);
+                    for backend in fips204::algorithm_id().backends {
+                        assert_eq!(
+                            run(*backend, &seed, &message, &ctx),
+                            reference,
+                            "the {backend} backend diverges from the scalar backend"
+                        );
+                    }
+                }
+            }
+        }
+    };
+}
+
+
+backend_test!(ml_dsa_44_backends, "ml-dsa-44", ml_dsa_44);
+backend_test!(ml_dsa_65_backends, "ml-dsa-65", ml_dsa_65);
+backend_test!(ml_dsa_87_backends, "ml-dsa-87", ml_dsa_87);
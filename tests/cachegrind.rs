@@ -0,0 +1,120 @@
+//! Empirical check of secret-independent control flow and memory access, using valgrind.
+//!
+//! Each secret seed is run through ML-DSA-44 key generation and signing in a separate child
+//! process under `valgrind --tool=callgrind --cache-sim=yes`, with collection restricted to
+//! `traced_keygen_sign()`. The `dudect` entry point is used so that the (permitted) variability
+//! from rejection sampling of public data and from the signing rejection loop is removed,
+//! leaving a single pass whose traces should not depend upon the secret at all.
+//!
+//! Run with (requires `valgrind` on the path):
+//! ~~~
+//! $ cargo test --release --features dudect --test cachegrind -- --ignored --nocapture
+//! ~~~
+//!
+//! Interpreting the results:
+//! * `Ir` (instructions executed) differing across seeds indicates secret-dependent control
+//!   flow, e.g., a branch or a loop count on secret data. The test fails.
+//! * `Dr`/`Dw` (data reads/writes) differing indicates secret-dependent memory access counts.
+//!   The test fails.
+//! * The simulated cache miss counts (`D1mr`, `DLmr`, ...) are printed for comparison only. A
+//!   difference with identical `Ir`/`Dr`/`Dw` points to secret-dependent addresses (e.g., a
+//!   table lookup indexed by a secret) and deserves investigation with `callgrind_annotate`
+//!   on the retained output files; the simulated cache is only a model of real hardware.
+//!
+//! Identical counts are evidence (not proof) of constant-time behavior for these inputs, on
+//! this compiler, target and optimization level.
+#![cfg(all(feature = "dudect", feature = "ml-dsa-44", not(feature = "fips-mode")))]
+
+use fips204::ml_dsa_44;
+use rand_core::{CryptoRng, RngCore};
+use std::process::Command;
+
+const SEED_ENV: &str = "FIPS204_CACHEGRIND_SEED";
+const SEEDS: [u8; 3] = [0x00, 0x55, 0xAA];
+
+
+// Simplistic RNG to regurgitate set value
+struct TestRng([u8; 32]);
+
+impl RngCore for TestRng {
+    fn next_u32(&mut self) -> u32 { unimplemented!() }
+
+    fn next_u64(&mut self) -> u64 { unimplemented!() }
+
+    fn fill_bytes(&mut self, _out: &mut [u8]) { unimplemented!() }
+
+    fn try_fill_bytes(&mut self, out: &mut [u8]) -> Result<(), rand_core::Error> {
+        out.copy_from_slice(&self.0);
+        Ok(())
+    }
+}
+
+impl CryptoRng for TestRng {}
+
+
+// The only function whose execution is collected by callgrind
+#[inline(never)]
+#[allow(deprecated)]
+fn traced_keygen_sign(seed: u8) -> [u8; ml_dsa_44::SIG_LEN] {
+    let mut rng = TestRng([seed; 32]);
+    ml_dsa_44::dudect_keygen_sign_with_rng(&mut rng, &[0u8, 1, 2, 3, 4, 5, 6, 7]).unwrap()
+}
+
+
+// Returns the event names and totals from a callgrind output file
+fn totals(path: &std::path::Path) -> Vec<(String, u64)> {
+    let contents = std::fs::read_to_string(path).expect("cannot read callgrind output");
+    let line = |prefix: &str| {
+        contents
+            .lines()
+            .find_map(|l| l.strip_prefix(prefix))
+            .unwrap_or_else(|| panic!("no '{prefix}' line in {}", path.display()))
+            .split_whitespace()
+    };
+    let values = line("totals:").map(|v| v.parse::<u64>().unwrap());
+    line("events:").map(String::from).zip(values).collect()
+}
+
+
+#[test]
+#[ignore = "requires valgrind; see the documentation at the top of this file"]
+fn secret_independent_traces() {
+    // Child: run just the traced function for the given seed
+    if let Ok(seed) = std::env::var(SEED_ENV) {
+        let _sig = traced_keygen_sign(u8::from_str_radix(&seed, 16).unwrap());
+        return;
+    }
+
+    // Parent: trace each seed in a separate process
+    let exe = std::env::current_exe().unwrap();
+    let dir = std::env::temp_dir();
+    let mut results = Vec::new();
+    for seed in SEEDS {
+        let out = dir.join(format!("fips204-callgrind.{seed:02x}.out"));
+        let status = Command::new("valgrind")
+            .arg("--tool=callgrind")
+            .arg("--cache-sim=yes")
+            .arg("--toggle-collect=*traced_keygen_sign*")
+            .arg(format!("--callgrind-out-file={}", out.display()))
+            .arg(&exe)
+            .args(["secret_independent_traces", "--exact", "--ignored", "--test-threads=1"])
+            .env(SEED_ENV, format!("{seed:02x}"))
+            .status()
+            .expect("failed to run valgrind; is it installed?");
+        assert!(status.success(), "valgrind run failed for seed {seed:02x}");
+        results.push((seed, totals(&out)));
+        println!("seed {seed:02x}: {:?} ({})", results[results.len() - 1].1, out.display());
+    }
+
+    let (first_seed, first) = &results[0];
+    assert!(first.iter().any(|(_, count)| *count > 0), "nothing collected; check --toggle-collect");
+    for (seed, other) in &results[1..] {
+        for ((event, a), (_, b)) in first.iter().zip(other) {
+            if matches!(event.as_str(), "Ir" | "Dr" | "Dw") {
+                assert_eq!(a, b, "{event} differs between seeds {first_seed:02x} and {seed:02x}");
+            } else if a != b {
+                println!("note: {event} differs between seeds {first_seed:02x} ({a}) and {seed:02x} ({b})");
+            }
+        }
+    }
+}
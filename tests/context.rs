@@ -0,0 +1,65 @@
+// Checks that every context-bearing API treats an absent context as the empty context string,
+// encoded as a zero length byte per FIPS 204, and that this is distinct from omitting the `M′`
+// prefix altogether (the internal interface). See the `fips204::message` documentation.
+#![cfg(all(feature = "ml-dsa-65", feature = "hazmat", not(feature = "fips-mode")))]
+
+use fips204::message::{mu, mu_internal};
+use fips204::ml_dsa_65;
+use fips204::traits::{SerDes, Signer, Verifier};
+use fips204::{Context, Ph};
+use rand_chacha::rand_core::SeedableRng;
+
+
+/// Whether each verification API accepts the signature under the empty context.
+fn verifies_empty(pk: &ml_dsa_65::PublicKey, message: &[u8], sig: &[u8; ml_dsa_65::SIG_LEN]) -> [bool; 5] {
+    let mut chunked = pk.try_verify_chunked(&[]).unwrap();
+    chunked.update_message(message);
+    let _ = chunked.update_signature(sig);
+    let mut stepwise = pk.try_verify_stepwise(message, sig, &[]).unwrap();
+    let stepwise = loop {
+        if let Some(result) = stepwise.step() {
+            break result;
+        }
+    };
+    [
+        pk.verify(message, sig, &[]),
+        pk.verify_with_context(message, sig, &Context::default()),
+        pk.verify_fixed_ctx(message, sig, &[]),
+        chunked.verify(),
+        stepwise,
+    ]
+}
+
+
+#[test]
+fn empty_context_is_encoded() {
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1191);
+    let (pk, sk) = ml_dsa_65::try_keygen_with_rng(&mut rng).unwrap();
+    let message = b"message";
+
+    // Signatures under the empty context from every signing API verify under every verifier
+    let sigs = [
+        sk.try_sign_with_rng(&mut rng, message, &[]).unwrap(),
+        sk.try_sign_with_context_and_rng(&mut rng, message, &Context::default()).unwrap(),
+        sk.try_sign_fixed_ctx_with_rng(&mut rng, message, &[]).unwrap(),
+        ml_dsa_65::try_sign_from_bytes_with_rng(&mut rng, &sk.duplicate().into_bytes(), message, &[]).unwrap(),
+    ];
+    for sig in &sigs {
+        assert_eq!(verifies_empty(&pk, message, sig), [true; 5]);
+        assert!(!pk.verify(message, sig, &[0])); // A single zero byte is a different context
+        assert!(!pk.hash_verify(message, sig, &[], &Ph::SHA512)); // As is HashML-DSA
+    }
+
+    // The external-µ equivalent formats M′ = 0 ∥ 0 ∥ M
+    let esk = ml_dsa_65::hazmat::sign_start(&sk.duplicate().into_bytes()).unwrap();
+    let tr = pk.tr();
+    let empty = mu(&tr, message, &[], None).unwrap();
+    assert_eq!(empty, mu_internal(&tr, b"\x00\x00message"));
+    let sig = ml_dsa_65::hazmat::sign_finish(&esk, &empty, [0u8; 32]);
+    assert_eq!(verifies_empty(&pk, message, &sig), [true; 5]);
+
+    // Whereas a signature over the bare message (no prefix) verifies under no context
+    let sig = ml_dsa_65::hazmat::sign_finish(&esk, &mu_internal(&tr, message), [0u8; 32]);
+    assert_eq!(verifies_empty(&pk, message, &sig), [false; 5]);
+    assert!(ml_dsa_65::hazmat::verify_finish(&pk, &mu_internal(&tr, message), &sig));
+}
@@ -52,6 +52,28 @@ fn forever() {
     }
 }
 
+// Soak test of the RNG wiring: every rnd drawn while signing with the OS RNG is checked against
+// the previous 2^16 draws, and a repeat fails the signature (and the test).
+// $ cargo test --package fips204 --test integration forever_unique_rnd -- --ignored --nocapture
+#[cfg(all(feature = "ml-dsa-44", feature = "default-rng"))]
+#[ignore]
+#[test]
+fn forever_unique_rnd() {
+    let mut rng = Box::new(fips204::rng::RepeatMonitor::<_, 65536>::new(rand_core::OsRng));
+    let (pk, sk) = ml_dsa_44::KG::try_keygen_with_rng(&mut *rng).unwrap();
+    let mut i = 0u64;
+    loop {
+        let msg = i.to_le_bytes();
+        let sig = sk.try_sign_with_rng(&mut *rng, &msg, &[]).expect("rnd repeated (or RNG failed)");
+        assert!(pk.verify(&msg, &sig, &[]));
+        if i % 10000 == 0 {
+            println!("So far i: {}", i)
+        };
+        i += 1;
+    }
+}
+
+
 // This test originally demonstrated in **draft** FIPS 204 that two different signatures can be
 // verified for the same pk/sig. However, it now demonstrates for **released** FIPS 204 that this
 // problem is now fixed.
@@ -90,30 +112,127 @@ fn test_44_no_verif() {
         assert!(!ver)
     }
 
-    // Bad secret key  (intriguing, byte 40 is 'k' which is allowed variance)
-    for i in 0..8 {
-        let mut sk_bad = sk.clone().into_bytes();
-        sk_bad[70 + i * 10] ^= 0x08;
-        let sk_bad = ml_dsa_44::PrivateKey::try_from_bytes(sk_bad).unwrap();
-        let sig = sk_bad.try_sign_with_rng(&mut rng, &msg, &[0]).unwrap();
-        let ver = pk.verify(&msg, &sig, &[0]);
-        assert!(!ver)
+    // Mutated keys and signatures are covered region by region in tests/mutation.rs
+}
+
+
+#[cfg(all(feature = "ml-dsa-44", feature = "ml-dsa-65", feature = "ml-dsa-87"))]
+#[test]
+fn test_algorithm_id() {
+    let id = fips204::algorithm_id();
+    assert_eq!(id.crate_version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(id.backends, &[fips204::Backend::Scalar]);
+    assert_eq!(id.param_sets.len(), 3);
+    assert_eq!(
+        id.to_string(),
+        format!(
+            "fips204 {}; FIPS 204 (August 13, 2024); backends: scalar; parameter sets: ML-DSA-44 \
+             ML-DSA-65 ML-DSA-87",
+            env!("CARGO_PKG_VERSION")
+        )
+    );
+}
+
+
+#[cfg(feature = "ml-dsa-44")]
+#[test]
+fn test_context_dispatch() {
+    use fips204::{Context, Ph};
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+    let msg = [0u8, 1, 2, 3, 4, 5, 6, 7];
+    let (pk, sk) = ml_dsa_44::KG::try_keygen_with_rng(&mut rng).unwrap();
+
+    let pure = Context::new(b"proto");
+    let sig = sk.try_sign_with_context_and_rng(&mut rng, &msg, &pure).unwrap();
+    assert!(pk.verify(&msg, &sig, b"proto"));
+    assert!(pk.verify_with_context(&msg, &sig, &pure));
+    assert!(!pk.verify_with_context(&msg, &sig, &Context::new(b"proto").with_hash(Ph::SHA256)));
+
+    let hashed = Context::new(b"proto").with_hash(Ph::SHAKE128);
+    let sig = sk.try_sign_with_context_and_rng(&mut rng, &msg, &hashed).unwrap();
+    assert!(pk.hash_verify(&msg, &sig, b"proto", &Ph::SHAKE128));
+    assert!(pk.verify_with_context(&msg, &sig, &hashed));
+    assert!(!pk.verify_with_context(&msg, &sig, &pure));
+
+    let too_long = [0u8; 256];
+    assert!(sk.try_sign_with_context_and_rng(&mut rng, &msg, &Context::new(&too_long)).is_err());
+    assert!(!pk.verify_with_context(&msg, &sig, &Context::default()));
+}
+
+
+#[cfg(all(feature = "ml-dsa-44", feature = "ml-dsa-65", feature = "ml-dsa-87"))]
+#[test]
+fn test_dyn_signer_verifier() {
+    use fips204::traits::{DynSigner, DynVerifier};
+    use fips204::{ml_dsa_65, ml_dsa_87};
+
+    // Stand-in for an adapter wrapping a classical algorithm
+    struct Xor(u8);
+    impl DynSigner for Xor {
+        fn signature_len(&self) -> usize { 1 }
+
+        fn try_sign_into_with_rng(
+            &self, _rng: &mut dyn rand_core::CryptoRngCore, message: &[u8], _ctx: &[u8], out: &mut [u8],
+        ) -> Result<usize, &'static str> {
+            *out.first_mut().ok_or("short")? = message.iter().fold(self.0, |acc, b| acc ^ b);
+            Ok(1)
+        }
     }
 
-    // Bad public key
-    for i in 0..8 {
-        let mut pk_bad = pk.clone().into_bytes();
-        pk_bad[i * 10] ^= 0x08;
-        let pk_bad = ml_dsa_44::PublicKey::try_from_bytes(pk_bad).unwrap();
-        let ver = pk_bad.verify(&msg, &sig, &[0]);
-        assert!(!ver)
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+    let (pk44, sk44) = ml_dsa_44::KG::try_keygen_with_rng(&mut rng).unwrap();
+    let (pk65, sk65) = ml_dsa_65::KG::try_keygen_with_rng(&mut rng).unwrap();
+    let (pk87, sk87) = ml_dsa_87::KG::try_keygen_with_rng(&mut rng).unwrap();
+    let signers: Vec<Box<dyn DynSigner>> =
+        vec![Box::new(sk44), Box::new(sk65), Box::new(sk87), Box::new(Xor(7))];
+    let verifiers: Vec<Box<dyn DynVerifier>> = vec![Box::new(pk44), Box::new(pk65), Box::new(pk87)];
+
+    let mut buf = [0u8; ml_dsa_87::SIG_LEN];
+    let mut sigs = Vec::new();
+    for signer in &signers {
+        let len = signer.try_sign_into_with_rng(&mut rng, b"msg", b"ctx", &mut buf).unwrap();
+        assert_eq!(len, signer.signature_len());
+        sigs.push(buf[..len].to_vec());
+        assert!(signer.try_sign_into_with_rng(&mut rng, b"msg", b"ctx", &mut buf[..len - 1]).is_err());
     }
+    assert_eq!(sigs[3], [7 ^ b'm' ^ b's' ^ b'g']);
 
-    // Bad signature
-    for i in 0..8 {
-        let mut sig_bad = sig;
-        sig_bad[i * 10] ^= 0x08;
-        let ver = pk.verify(&msg, &sig_bad, &[0]);
-        assert!(!ver)
+    for (i, verifier) in verifiers.iter().enumerate() {
+        assert_eq!(verifier.signature_len(), sigs[i].len());
+        for (j, sig) in sigs.iter().enumerate() {
+            assert_eq!(verifier.verify_bytes(b"msg", sig, b"ctx"), i == j);
+        }
+        assert!(!verifier.verify_bytes(b"msh", &sigs[i], b"ctx"));
     }
 }
+
+
+#[cfg(all(feature = "ml-dsa-65", feature = "default-rng"))]
+#[test]
+fn test_verify_expanded_or_encoded() {
+    use fips204::ml_dsa_65;
+    use fips204::traits::{SerDes, Signer, Verifier};
+    use fips204::{Context, Ph};
+
+    // Library code written once against the trait
+    fn check<V: Verifier<Signature = [u8; ml_dsa_65::SIG_LEN]>>(
+        pk: &V, sig: &[u8; ml_dsa_65::SIG_LEN], context: &Context<'_>,
+    ) -> bool {
+        pk.verify_with_context(b"msg", sig, context)
+    }
+
+    let (pk, sk) = ml_dsa_65::try_keygen().unwrap();
+    let pk_bytes = pk.clone().into_bytes();
+    for context in [Context::new(b"ctx"), Context::new(b"ctx").with_hash(Ph::SHA256)] {
+        let sig = sk.try_sign_with_context(b"msg", &context).unwrap();
+        assert!(check(&pk, &sig, &context));
+        assert!(check(&pk_bytes, &sig, &context));
+        assert!(!check(&pk_bytes, &sig, &Context::new(b"other")));
+    }
+
+    // The encoding of some other key rejects
+    let (other_pk, _) = ml_dsa_65::try_keygen().unwrap();
+    let sig = sk.try_sign(b"msg", b"ctx").unwrap();
+    assert!(!Verifier::verify(&other_pk.into_bytes(), b"msg", &sig, b"ctx"));
+}
+
@@ -0,0 +1,56 @@
+// This file checks interoperability against keys and signatures produced by OpenSSL 3.5; see
+// `tests/interop/generate.sh` for how `tests/interop/openssl-3.5.json` was produced.
+//
+// For every parameter set, with and without a context string:
+//   * key generation from the same seed yields the same public key (as a DER SPKI),
+//   * the OpenSSL signatures (deterministic and hedged) verify, and
+//   * deterministic signing here reproduces the OpenSSL deterministic signature byte for byte,
+//     so signatures produced by this crate verify under OpenSSL too.
+//
+// Only pure ML-DSA is covered: OpenSSL 3.5 does not implement HashML-DSA, and there are no
+// fixtures from other implementations (e.g., Bouncy Castle).
+#![cfg(all(feature = "ml-dsa-44", feature = "ml-dsa-65", feature = "ml-dsa-87"))]
+
+use fips204::traits::{KeyGen, Verifier};
+use fips204::{ml_dsa_44, ml_dsa_65, ml_dsa_87};
+use hex::decode;
+use serde_json::Value;
+
+
+macro_rules! check_case {
+    ($ml_dsa:ident, $case:expr, $message:expr) => {{
+        let field = |name: &str| decode($case[name].as_str().unwrap()).unwrap();
+        let (ctx, spki) = (field("ctx"), field("spki"));
+        let det_sig: [u8; $ml_dsa::SIG_LEN] = field("deterministic_sig").try_into().unwrap();
+        let hedged_sig: [u8; $ml_dsa::SIG_LEN] = field("hedged_sig").try_into().unwrap();
+
+        let (pk, sk) = $ml_dsa::KG::keygen_from_seed(&field("seed").try_into().unwrap());
+        assert_eq!(pk.to_spki_der()[..], spki[..]);
+
+        let pk = $ml_dsa::PublicKey::try_from_spki_der(&spki).unwrap();
+        assert!(pk.verify($message, &det_sig, &ctx));
+        assert!(pk.verify($message, &hedged_sig, &ctx));
+        assert!(!pk.verify($message, &hedged_sig, b"other ctx"));
+
+        let sig = sk.try_sign_deterministic($message, &ctx).unwrap();
+        assert_eq!(*sig.signature(), det_sig);
+    }};
+}
+
+
+#[test]
+fn openssl_interop() {
+    let json = std::fs::read_to_string("./tests/interop/openssl-3.5.json").unwrap();
+    let vectors: Value = serde_json::from_str(&json).unwrap();
+    let message = decode(vectors["message"].as_str().unwrap()).unwrap();
+    let cases = vectors["cases"].as_array().unwrap();
+    assert_eq!(cases.len(), 6);
+    for case in cases {
+        match case["param_set"].as_str().unwrap() {
+            "ML-DSA-44" => check_case!(ml_dsa_44, case, &message),
+            "ML-DSA-65" => check_case!(ml_dsa_65, case, &message),
+            "ML-DSA-87" => check_case!(ml_dsa_87, case, &message),
+            other => panic!("unexpected parameter set {other}"),
+        }
+    }
+}
@@ -0,0 +1,38 @@
+#!/bin/sh
+# Regenerates openssl-3.5.json: keys and signatures produced by OpenSSL 3.5 for each parameter
+# set, with and without a context string. Run from this directory; requires `openssl` >= 3.5.
+#
+# OpenSSL 3.5 implements pure ML-DSA only (HashML-DSA is not supported), so there are no
+# pre-hash fixtures. Each case carries both a deterministic signature (which this crate must
+# reproduce byte for byte) and a hedged signature (which this crate must verify).
+set -eu
+tmp=$(mktemp -d)
+trap 'rm -rf "$tmp"' EXIT
+printf 'OpenSSL interop message\n' > "$tmp/msg"
+
+hex() { od -An -v -tx1 "$1" | tr -d ' \n'; }
+
+{
+  printf '{\n  "openssl": "%s",\n  "message": "%s",\n  "cases": [\n' "$(openssl version)" "$(hex "$tmp/msg")"
+  first=1
+  for ps in 44 65 87; do
+    for ctx in "" "interop-ctx"; do
+      seed=$(printf 'fips204 interop ML-DSA-%s %s' "$ps" "$ctx" | openssl dgst -sha256 -r | cut -c1-64)
+      openssl genpkey -algorithm "ML-DSA-$ps" -pkeyopt "hexseed:$seed" -out "$tmp/sk.pem"
+      openssl pkey -in "$tmp/sk.pem" -pubout -outform DER -out "$tmp/pk.der"
+      opts="-pkeyopt context-string:$ctx"
+      [ -z "$ctx" ] && opts=""
+      # shellcheck disable=SC2086
+      openssl pkeyutl -sign -rawin -inkey "$tmp/sk.pem" -in "$tmp/msg" $opts -pkeyopt deterministic:1 -out "$tmp/det.sig"
+      # shellcheck disable=SC2086
+      openssl pkeyutl -sign -rawin -inkey "$tmp/sk.pem" -in "$tmp/msg" $opts -out "$tmp/hedged.sig"
+      [ $first -eq 1 ] || printf ',\n'
+      first=0
+      printf '    {"param_set": "ML-DSA-%s", "ctx": "%s", "seed": "%s",\n' "$ps" "$(printf '%s' "$ctx" | od -An -v -tx1 | tr -d ' \n')" "$seed"
+      printf '     "spki": "%s",\n' "$(hex "$tmp/pk.der")"
+      printf '     "deterministic_sig": "%s",\n' "$(hex "$tmp/det.sig")"
+      printf '     "hedged_sig": "%s"}' "$(hex "$tmp/hedged.sig")"
+    done
+  done
+  printf '\n  ]\n}\n'
+} > openssl-3.5.json
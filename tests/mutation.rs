@@ -0,0 +1,162 @@
+// Mutation testing of the key and signature decoders. Every byte region of valid public key,
+// private key and signature encodings is perturbed (length-preserving: single bit flips and
+// all-zero/all-one bytes), and the decode and verify behavior is asserted per region:
+//
+// - public key: every encoding decodes (`t1` coefficients use all 10 bits), and never verifies;
+// - private key: `rho`, `K`, `tr` and `t0` always decode, while `s1`/`s2` coefficients may fall
+//   outside `[-η, η]` and be rejected. A decoded key with a mutated `rho`, `tr` or `s1` produces
+//   signatures that never verify, one with a mutated `K` (only used to derive the signing
+//   randomness) always does, and mutations of `s2` and `t0` (only used to compute hints) may
+//   or may not be caught;
+// - signature: no mutated signature verifies, whether in `c_tilde`, `z` or the hint.
+//
+// The normal run samples positions in each region; run the exhaustive version with
+// $ cargo test --release --test mutation -- --ignored
+
+
+use fips204::traits::{SerDes, Signer, Verifier};
+use fips204::VerifyMode;
+use rand_chacha::rand_core::SeedableRng;
+use std::ops::Range;
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Decode {
+    Always,
+    Maybe,
+}
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Verify {
+    Always,
+    Never,
+    Either,
+}
+
+
+struct Region {
+    name: &'static str,
+    range: Range<usize>,
+    decode: Decode,
+    verify: Verify,
+}
+
+
+/// The byte positions perturbed within a region: all of them, or a sample including both ends.
+fn positions(range: &Range<usize>, exhaustive: bool) -> Vec<usize> {
+    let stride = if exhaustive { 1 } else { 97 };
+    let mut positions: Vec<usize> = range.clone().step_by(stride).collect();
+    positions.push(range.end - 1);
+    positions.dedup();
+    positions
+}
+
+
+/// The length-preserving perturbations of a byte.
+fn mutations(byte: u8) -> Vec<u8> {
+    let mut values = vec![byte ^ 0x01, byte ^ 0x80, 0x00, 0xFF];
+    values.retain(|&v| v != byte);
+    values.dedup();
+    values
+}
+
+
+/// Applies every perturbation in every region, calling `check` with the region and the mutated
+/// encoding.
+fn mutate<const N: usize>(encoding: &[u8; N], regions: &[Region], exhaustive: bool, mut check: impl FnMut(&Region, &[u8; N])) {
+    assert_eq!(regions.last().unwrap().range.end, N, "regions must cover the encoding");
+    for region in regions {
+        for pos in positions(&region.range, exhaustive) {
+            for value in mutations(encoding[pos]) {
+                let mut mutated = *encoding;
+                mutated[pos] = value;
+                check(region, &mutated);
+            }
+        }
+    }
+}
+
+
+macro_rules! mutation_test {
+    ($name:ident, $exhaustive_name:ident, $feature:literal, $module:ident) => {
+        #[cfg(feature = $feature)]
+        mod $name {
+            use super::*;
+            use fips204::$module;
+
+            fn run(exhaustive: bool) {
+                let params = $module::params();
+                let bitlen = |x: u32| (32 - x.leading_zeros()) as usize;
+                let (k, l, eta_len) = (params.k, params.l, bitlen(2 * params.eta));
+                let msg = b"mutation";
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1204);
+                let (pk, sk) = $module::try_keygen_with_rng(&mut rng).unwrap();
+                let sig = sk.try_sign_with_rng(&mut rng, msg, b"ctx").unwrap();
+                let (pk_bytes, sk_bytes) = (pk.clone().into_bytes(), sk.into_bytes());
+
+                // Public key: (rho, t1)
+                let regions = [
+                    Region { name: "pk rho", range: 0..32, decode: Decode::Always, verify: Verify::Never },
+                    Region { name: "pk t1", range: 32..$module::PK_LEN, decode: Decode::Always, verify: Verify::Never },
+                ];
+                mutate(&pk_bytes, &regions, exhaustive, |region, mutated| {
+                    let decoded = $module::PublicKey::try_from_bytes(*mutated);
+                    assert!(decoded.is_ok() || region.decode == Decode::Maybe, "{}: decode failed", region.name);
+                    let Ok(pk_bad) = decoded else { return };
+                    assert!(!pk_bad.verify(msg, &sig, b"ctx"), "{}: verified", region.name);
+                });
+
+                // Private key: (rho, K, tr, s1, s2, t0)
+                let s1 = 128..128 + 32 * l * eta_len;
+                let s2 = s1.end..s1.end + 32 * k * eta_len;
+                let regions = [
+                    Region { name: "sk rho", range: 0..32, decode: Decode::Always, verify: Verify::Never },
+                    Region { name: "sk K", range: 32..64, decode: Decode::Always, verify: Verify::Always },
+                    Region { name: "sk tr", range: 64..128, decode: Decode::Always, verify: Verify::Never },
+                    Region { name: "sk s1", range: s1, decode: Decode::Maybe, verify: Verify::Never },
+                    Region { name: "sk s2", range: s2.clone(), decode: Decode::Maybe, verify: Verify::Either },
+                    Region { name: "sk t0", range: s2.end..$module::SK_LEN, decode: Decode::Always, verify: Verify::Either },
+                ];
+                mutate(&sk_bytes, &regions, exhaustive, |region, mutated| {
+                    let decoded = $module::PrivateKey::try_from_bytes(*mutated);
+                    assert!(decoded.is_ok() || region.decode == Decode::Maybe, "{}: decode failed", region.name);
+                    let Ok(sk_bad) = decoded else { return };
+                    let sig = sk_bad.try_sign_with_rng(&mut rng, msg, b"ctx").unwrap();
+                    let verified = pk.verify(msg, &sig, b"ctx");
+                    match region.verify {
+                        Verify::Always => assert!(verified, "{}: did not verify", region.name),
+                        Verify::Never => assert!(!verified, "{}: verified", region.name),
+                        Verify::Either => (),
+                    }
+                });
+
+                // Signature: (c_tilde, z, h)
+                let z = params.lambda / 4..params.lambda / 4 + 32 * l * (1 + bitlen(params.gamma1 - 1));
+                let regions = [
+                    Region { name: "sig c_tilde", range: 0..z.start, decode: Decode::Maybe, verify: Verify::Never },
+                    Region { name: "sig z", range: z.clone(), decode: Decode::Maybe, verify: Verify::Never },
+                    Region { name: "sig h", range: z.end..$module::SIG_LEN, decode: Decode::Maybe, verify: Verify::Never },
+                ];
+                assert_eq!($module::SIG_LEN - z.end, params.omega as usize + k);
+                mutate(&sig, &regions, exhaustive, |region, mutated| {
+                    assert!(!pk.verify(msg, mutated, b"ctx"), "{}: verified", region.name);
+                    assert!(!pk.verify_with_mode(msg, mutated, b"ctx", VerifyMode::FastReject), "{}: verified", region.name);
+                    assert!(!pk_bytes.verify(msg, mutated, b"ctx"), "{}: verified", region.name);
+                });
+            }
+
+            #[test]
+            fn $name() { run(false) }
+
+            #[ignore]
+            #[test]
+            fn $exhaustive_name() { run(true) }
+        }
+    };
+}
+
+
+mutation_test!(mutation_44, mutation_44_exhaustive, "ml-dsa-44", ml_dsa_44);
+mutation_test!(mutation_65, mutation_65_exhaustive, "ml-dsa-65", ml_dsa_65);
+mutation_test!(mutation_87, mutation_87_exhaustive, "ml-dsa-87", ml_dsa_87);
@@ -201,3 +201,175 @@ fn test_sigver() {
         }
     }
 }
+
+
+// ----- EMBEDDED CORPUS (`vectors` FEATURE) -----
+
+// Builds the binary corpus embedded by `fips204::vectors` from the JSON files above
+fn embedded_corpus() -> Vec<u8> {
+    fn record(out: &mut Vec<u8>, kind: u8, param_set: &Value, tc_id: &Value, fields: &[Vec<u8>]) {
+        let param = ["ML-DSA-44", "ML-DSA-65", "ML-DSA-87"].iter().position(|ps| param_set == ps).unwrap();
+        let body_len: usize = fields.iter().map(|f| 4 + f.len()).sum();
+        out.extend_from_slice(&[kind, u8::try_from(param).unwrap()]);
+        out.extend_from_slice(&u32::try_from(tc_id.as_u64().unwrap()).unwrap().to_le_bytes());
+        out.extend_from_slice(&u32::try_from(body_len).unwrap().to_le_bytes());
+        for field in fields {
+            out.extend_from_slice(&u32::try_from(field.len()).unwrap().to_le_bytes());
+            out.extend_from_slice(field);
+        }
+    }
+    let read = |name: &str| -> Value {
+        let path = format!("./tests/nist_vectors/{name}/internalProjection.json");
+        serde_json::from_str(&fs::read_to_string(path).expect("Unable to read file")).unwrap()
+    };
+    let hex = |v: &Value| decode(v.as_str().unwrap()).unwrap();
+
+    let mut out = Vec::new();
+    for group in read("ML-DSA-keyGen-FIPS204")["testGroups"].as_array().unwrap() {
+        for test in group["tests"].as_array().unwrap() {
+            let fields = [hex(&test["seed"]), hex(&test["pk"]), hex(&test["sk"])];
+            record(&mut out, 0, &group["parameterSet"], &test["tcId"], &fields);
+        }
+    }
+    for group in read("ML-DSA-sigGen-FIPS204")["testGroups"].as_array().unwrap() {
+        for test in group["tests"].as_array().unwrap() {
+            let rnd = if test["rnd"].is_null() { Vec::new() } else { hex(&test["rnd"]) };
+            let fields = [hex(&test["sk"]), hex(&test["message"]), rnd, hex(&test["signature"])];
+            record(&mut out, 1, &group["parameterSet"], &test["tcId"], &fields);
+        }
+    }
+    for group in read("ML-DSA-sigVer-FIPS204")["testGroups"].as_array().unwrap() {
+        for test in group["tests"].as_array().unwrap() {
+            let passed = vec![u8::from(test["testPassed"].as_bool().unwrap())];
+            let fields = [hex(&group["pk"]), hex(&test["message"]), hex(&test["signature"]), passed];
+            record(&mut out, 2, &group["parameterSet"], &test["tcId"], &fields);
+        }
+    }
+    out
+}
+
+
+// Regenerates the embedded corpus after the JSON files are updated
+#[test]
+#[ignore]
+fn regenerate_embedded_corpus() {
+    fs::write("./tests/nist_vectors/acvp.bin", embedded_corpus()).unwrap();
+}
+
+
+#[test]
+fn test_embedded_corpus_current() {
+    let embedded = fs::read("./tests/nist_vectors/acvp.bin").expect("Unable to read file");
+    assert!(embedded == embedded_corpus(), "acvp.bin is stale; run the ignored regenerate_embedded_corpus test");
+}
+
+
+// Applies the embedded vectors through the public iteration API, as a downstream crate would
+#[cfg(feature = "vectors")]
+#[test]
+#[allow(deprecated)]
+fn test_embedded_vectors() {
+    use fips204::traits::KeyGen;
+    use fips204::{vectors, ParamSet};
+
+    assert_eq!((vectors::keygen().count(), vectors::siggen().count(), vectors::sigver().count()), (75, 60, 45));
+    assert!(vectors::siggen().any(|v| v.deterministic) && vectors::siggen().any(|v| !v.deterministic));
+    assert!(vectors::sigver().any(|v| v.test_passed) && vectors::sigver().any(|v| !v.test_passed));
+
+    macro_rules! apply {
+        ($feature:literal, $module:ident, $param_set:expr) => {
+            #[cfg(feature = $feature)]
+            {
+                use fips204::$module;
+                for v in vectors::keygen().filter(|v| v.param_set == $param_set) {
+                    let (pk, sk) = $module::KG::keygen_from_seed(v.seed);
+                    assert_eq!((&pk.into_bytes()[..], &sk.into_bytes()[..]), (v.pk, v.sk), "tcId {}", v.tc_id);
+                }
+                for v in vectors::siggen().filter(|v| v.param_set == $param_set) {
+                    let sk = $module::PrivateKey::try_from_bytes(v.sk.try_into().unwrap()).unwrap();
+                    let sig = $module::_internal_sign(&sk, v.message, &[], *v.rnd).unwrap();
+                    assert_eq!(&sig[..], v.signature, "tcId {}", v.tc_id);
+                }
+                for v in vectors::sigver().filter(|v| v.param_set == $param_set) {
+                    let pk = $module::PublicKey::try_from_bytes(v.pk.try_into().unwrap()).unwrap();
+                    let res = $module::_internal_verify(&pk, v.message, &v.signature.try_into().unwrap(), &[]);
+                    assert_eq!(res, v.test_passed, "tcId {}", v.tc_id);
+                }
+            }
+        };
+    }
+    apply!("ml-dsa-44", ml_dsa_44, ParamSet::MlDsa44);
+    apply!("ml-dsa-65", ml_dsa_65, ParamSet::MlDsa65);
+    apply!("ml-dsa-87", ml_dsa_87, ParamSet::MlDsa87);
+}
+
+
+// ----- SELF-TEST KNOWN ANSWERS -----
+
+// Builds the known-answer tests embedded by `fips204::self_test()` from the JSON files above, as
+// `src/kats/` file name and contents for each parameter set. A file holds the little-endian `u32`
+// tcId of the set's shortest deterministic signature generation vector, followed by five fields,
+// each preceded by its little-endian `u32` length: the seed of its first key generation vector,
+// the SHAKE256 digest of that vector's `pk || sk`, the private key and message of the signature
+// generation vector, and the SHAKE256 digest of its signature.
+fn self_test_kats() -> Vec<(&'static str, Vec<u8>)> {
+    use sha3::digest::{ExtendableOutput, Update, XofReader};
+
+    fn digest(parts: &[&[u8]]) -> Vec<u8> {
+        let mut hasher = sha3::Shake256::default();
+        parts.iter().for_each(|part| hasher.update(part));
+        let mut out = vec![0u8; 32];
+        hasher.finalize_xof().read(&mut out);
+        out
+    }
+    let read = |name: &str| -> Value {
+        let path = format!("./tests/nist_vectors/{name}/internalProjection.json");
+        serde_json::from_str(&fs::read_to_string(path).expect("Unable to read file")).unwrap()
+    };
+    let hex = |v: &Value| decode(v.as_str().unwrap()).unwrap();
+    let (keygen, siggen) = (read("ML-DSA-keyGen-FIPS204"), read("ML-DSA-sigGen-FIPS204"));
+
+    let mut kats = Vec::new();
+    for (file, param_set) in [("ml_dsa_44.bin", "ML-DSA-44"), ("ml_dsa_65.bin", "ML-DSA-65"), ("ml_dsa_87.bin", "ML-DSA-87")] {
+        let groups = |file: &Value| file["testGroups"].as_array().unwrap().clone().into_iter();
+        let key = groups(&keygen).find(|g| g["parameterSet"] == *param_set).unwrap()["tests"][0].clone();
+        let sig = groups(&siggen)
+            .filter(|g| g["parameterSet"] == *param_set && g["deterministic"] == true)
+            .flat_map(|g| g["tests"].as_array().unwrap().clone())
+            .min_by_key(|t| t["message"].as_str().unwrap().len())
+            .unwrap();
+        let mut out = u32::try_from(sig["tcId"].as_u64().unwrap()).unwrap().to_le_bytes().to_vec();
+        let fields = [
+            hex(&key["seed"]),
+            digest(&[&hex(&key["pk"]), &hex(&key["sk"])]),
+            hex(&sig["sk"]),
+            hex(&sig["message"]),
+            digest(&[&hex(&sig["signature"])]),
+        ];
+        for field in fields {
+            out.extend_from_slice(&u32::try_from(field.len()).unwrap().to_le_bytes());
+            out.extend_from_slice(&field);
+        }
+        kats.push((file, out));
+    }
+    kats
+}
+
+
+// Regenerates the self-test known answers after the JSON files are updated
+#[test]
+#[ignore]
+fn regenerate_self_test_kats() {
+    for (file, kat) in self_test_kats() {
+        fs::write(format!("./src/kats/{file}"), kat).unwrap();
+    }
+}
+
+
+#[test]
+fn test_self_test_kats_current() {
+    for (file, kat) in self_test_kats() {
+        let embedded = fs::read(format!("./src/kats/{file}")).expect("Unable to read file");
+        assert!(embedded == kat, "src/kats/{file} is stale; run the ignored regenerate_self_test_kats test");
+    }
+}
@@ -0,0 +1,191 @@
+// HashML-DSA pre-hash known-answer tests. The DER encodings of the pre-hash function object
+// identifiers are checked against an independent encoding of their dotted form, the formatted
+// message `M′ = 1 ∥ |ctx| ∥ ctx ∥ OID ∥ PH(M)` against the FIPS 180/202 digests of "abc", and
+// signatures against every mismatched pre-hash function, which must be rejected. The OID
+// embedded in `M′` is where other stacks have failed to interoperate.
+
+#![cfg(feature = "ml-dsa-44")]
+
+use fips204::message::{encode_prefix, mu, mu_internal, prehash, DOMAIN_PREHASH, MAX_PREFIX_LEN, OID_LEN};
+use fips204::ml_dsa_44;
+use fips204::traits::{Signer, Verifier};
+use fips204::{DigestPh, Ph};
+use rand_chacha::rand_core::SeedableRng;
+
+
+// The NIST hash algorithm arc 2.16.840.1.101.3.4.2, under which every pre-hash function lies
+const NIST_HASH_ARCS: [u32; 9] = [2, 16, 840, 1, 101, 3, 4, 2, 0];
+
+
+/// DER-encodes an object identifier from its dotted form (short-form length only).
+fn der_oid(arcs: &[u32]) -> Vec<u8> {
+    let mut value = vec![u8::try_from(40 * arcs[0] + arcs[1]).unwrap()];
+    for &arc in &arcs[2..] {
+        let mut groups = vec![u8::try_from(arc & 0x7F).unwrap()];
+        let mut rest = arc >> 7;
+        while rest > 0 {
+            groups.push(u8::try_from(rest & 0x7F).unwrap() | 0x80);
+            rest >>= 7;
+        }
+        value.extend(groups.iter().rev());
+    }
+    let mut der = vec![0x06, u8::try_from(value.len()).unwrap()];
+    der.extend(value);
+    der
+}
+
+
+/// The DER encoding of `2.16.840.1.101.3.4.2.arc`.
+fn nist_hash_oid(arc: u32) -> Vec<u8> {
+    let mut arcs = NIST_HASH_ARCS;
+    arcs[8] = arc;
+    der_oid(&arcs)
+}
+
+
+#[test]
+fn test_oid_encodings() {
+    assert_eq!(nist_hash_oid(1), hex::decode("0609608648016503040201").unwrap());
+
+    // FIPS 204 section 5.4 lists SHA-256, SHA-512 and SHAKE128 by their NIST arcs
+    for (ph, arc) in [(Ph::SHA256, 1), (Ph::SHA512, 3), (Ph::SHAKE128, 11)] {
+        assert_eq!(ph.oid().len(), OID_LEN);
+        assert_eq!(ph.oid()[..], nist_hash_oid(arc)[..], "{ph:?}");
+        assert_eq!(Ph::from_oid(&ph.oid()), Some(ph));
+    }
+
+    let digest_oids = [
+        (DigestPh::sha256().oid(), 1),
+        (DigestPh::sha384().oid(), 2),
+        (DigestPh::sha512().oid(), 3),
+        (DigestPh::sha224().oid(), 4),
+        (DigestPh::sha512_224().oid(), 5),
+        (DigestPh::sha512_256().oid(), 6),
+        (DigestPh::sha3_224().oid(), 7),
+        (DigestPh::sha3_256().oid(), 8),
+        (DigestPh::sha3_384().oid(), 9),
+        (DigestPh::sha3_512().oid(), 10),
+    ];
+    for (oid, arc) in digest_oids {
+        assert_eq!(oid, &nist_hash_oid(arc)[..], "arc {arc}");
+    }
+
+    // Near misses: other arcs, truncations, and the SHAKE256 arc that `Ph` does not offer
+    let shake128 = Ph::SHAKE128.oid();
+    assert_eq!(Ph::from_oid(&nist_hash_oid(12)), None);
+    assert_eq!(Ph::from_oid(&shake128[..10]), None);
+    assert_eq!(Ph::from_oid(&[&shake128[..], &[0]].concat()), None);
+    assert_eq!(Ph::from_oid(&shake128[2..]), None); // Without tag and length
+}
+
+
+#[test]
+fn test_digest_ph_rejects_malformed_oids() {
+    let good = nist_hash_oid(8);
+    assert!(DigestPh::<sha3::Sha3_256>::try_new(&good).is_ok());
+    let malformed: [&[u8]; 6] = [
+        &[],
+        &[0x06, 0x00],
+        &good[1..],                        // No tag
+        &good[..10],                       // Length exceeds the value
+        &[&good[..], &[0x01]].concat(),    // Trailing byte
+        &[0x06, 0x02, 0x60, 0x86],         // Unterminated arc
+    ];
+    for oid in malformed {
+        assert!(DigestPh::<sha3::Sha3_256>::try_new(oid).is_err(), "{oid:02x?}");
+    }
+    let mut wrong_tag = good.clone();
+    wrong_tag[0] = 0x04;
+    assert!(DigestPh::<sha3::Sha3_256>::try_new(&wrong_tag).is_err());
+}
+
+
+// `M′` for "abc" under the context "ctx", with the FIPS 180-4 and FIPS 202 digests of "abc"
+#[test]
+fn test_formatted_message_kat() {
+    let cases = [
+        (Ph::SHA256, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+        (Ph::SHA512, "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"),
+        (Ph::SHAKE128, "5881092dd818bf5cf8a3ddb793fbcba74097d5c526a6d35f97b83351940f2cc8"),
+    ];
+    let tr = [0x42u8; 64];
+    for (ph, digest) in cases {
+        let digest = hex::decode(digest).unwrap();
+        let mut phm = [0u8; 64];
+        let phm_len = prehash(b"abc", &ph, &mut phm);
+        assert_eq!(phm[..phm_len], digest[..], "{ph:?}");
+
+        let m_prime = [&[DOMAIN_PREHASH, 3][..], b"ctx", &ph.oid(), &digest].concat();
+        let mut prefix = [0u8; MAX_PREFIX_LEN];
+        let len = encode_prefix(b"ctx", Some(&ph), &mut prefix).unwrap();
+        assert_eq!(prefix[..len], m_prime[..m_prime.len() - digest.len()]);
+        assert_eq!(mu(&tr, b"abc", b"ctx", Some(&ph)).unwrap(), mu_internal(&tr, &m_prime), "{ph:?}");
+    }
+}
+
+
+// Signatures from every pre-hash function verify under that function only
+#[test]
+fn test_mismatched_prehash_rejected() {
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1216);
+    let (pk, sk) = ml_dsa_44::try_keygen_with_rng(&mut rng).unwrap();
+    let all = [Ph::SHA256, Ph::SHA512, Ph::SHAKE128];
+    for signer in &all {
+        let sig = sk.try_hash_sign_with_rng(&mut rng, b"abc", b"ctx", signer).unwrap();
+        for verifier in &all {
+            let expected = signer == verifier;
+            assert_eq!(pk.hash_verify(b"abc", &sig, b"ctx", verifier), expected, "{signer:?}/{verifier:?}");
+        }
+        assert!(!pk.verify(b"abc", &sig, b"ctx"));
+    }
+    let pure = sk.try_sign_with_rng(&mut rng, b"abc", b"ctx").unwrap();
+    assert!(all.iter().all(|ph| !pk.hash_verify(b"abc", &pure, b"ctx", ph)));
+
+    // The OID alone separates functions sharing a digest length, and vice versa
+    let sig = sk.try_hash_sign_digest_with_rng(&mut rng, b"abc", b"ctx", &DigestPh::sha3_256()).unwrap();
+    assert!(pk.hash_verify_digest(b"abc", &sig, b"ctx", &DigestPh::sha3_256()));
+    assert!(!pk.hash_verify(b"abc", &sig, b"ctx", &Ph::SHA256));
+    assert!(!pk.hash_verify(b"abc", &sig, b"ctx", &Ph::SHAKE128));
+    let sha256_oid = Ph::SHA256.oid();
+    let mislabeled = DigestPh::<sha3::Sha3_256>::try_new(&sha256_oid).unwrap();
+    assert!(!pk.hash_verify_digest(b"abc", &sig, b"ctx", &mislabeled));
+    let sig = sk.try_hash_sign_digest_with_rng(&mut rng, b"abc", b"ctx", &mislabeled).unwrap();
+    assert!(!pk.hash_verify(b"abc", &sig, b"ctx", &Ph::SHA256));
+    assert!(!pk.hash_verify_digest(b"abc", &sig, b"ctx", &DigestPh::sha3_256()));
+    assert!(pk.hash_verify_digest(b"abc", &sig, b"ctx", &mislabeled));
+
+    // SHA-512/256 is neither SHA-256 (of the same length) nor SHA-512 (of the same construction)
+    let sig = sk.try_hash_sign_digest_with_rng(&mut rng, b"abc", b"ctx", &DigestPh::sha512_256()).unwrap();
+    assert!(!pk.hash_verify_digest(b"abc", &sig, b"ctx", &DigestPh::sha256()));
+    assert!(!pk.hash_verify(b"abc", &sig, b"ctx", &Ph::SHA512));
+}
+
+
+// The signatures of each `DigestPh` verify against an independently formatted `M′`
+#[cfg(all(feature = "hazmat", not(feature = "fips-mode")))]
+#[test]
+fn test_digest_ph_formatted_message() {
+    use fips204::digest::Digest;
+    use fips204::traits::SerDes;
+
+    fn check<D: Digest>(pk: &ml_dsa_44::PublicKey, sk: &ml_dsa_44::PrivateKey, ph: &DigestPh<'_, D>) {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1216);
+        let sig = sk.try_hash_sign_digest_with_rng(&mut rng, b"abc", b"ctx", ph).unwrap();
+        let m_prime = [&[DOMAIN_PREHASH, 3][..], b"ctx", ph.oid(), &D::digest(b"abc")].concat();
+        let epk = ml_dsa_44::hazmat::verify_start(&pk.clone().into_bytes()).unwrap();
+        assert!(ml_dsa_44::hazmat::verify_finish(&epk, &mu_internal(&pk.tr(), &m_prime), &sig));
+    }
+
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1216);
+    let (pk, sk) = ml_dsa_44::try_keygen_with_rng(&mut rng).unwrap();
+    check(&pk, &sk, &DigestPh::sha224());
+    check(&pk, &sk, &DigestPh::sha256());
+    check(&pk, &sk, &DigestPh::sha384());
+    check(&pk, &sk, &DigestPh::sha512());
+    check(&pk, &sk, &DigestPh::sha512_224());
+    check(&pk, &sk, &DigestPh::sha512_256());
+    check(&pk, &sk, &DigestPh::sha3_224());
+    check(&pk, &sk, &DigestPh::sha3_256());
+    check(&pk, &sk, &DigestPh::sha3_384());
+    check(&pk, &sk, &DigestPh::sha3_512());
+}
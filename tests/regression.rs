@@ -0,0 +1,105 @@
+// This file checks every build against a recorded corpus of deterministic signatures, so that any
+// behavioral change (e.g., to sampling order or encodings) is caught before release.
+//
+// Each case of `tests/regression/deterministic.json` records a key generation seed, message,
+// context string and optional pre-hash function, along with the expected public key and the
+// deterministic (`rnd` = 0) signature. The corpus is only ever regenerated deliberately, and a
+// change to it signals a change of observable behavior:
+// ~~~
+// $ cargo test --test regression -- --ignored regenerate_corpus
+// ~~~
+#![cfg(all(feature = "ml-dsa-44", feature = "ml-dsa-65", feature = "ml-dsa-87"))]
+
+use fips204::traits::{KeyGen, SerDes, Verifier};
+use fips204::{ml_dsa_44, ml_dsa_65, ml_dsa_87, Ph};
+use hex::{decode, encode};
+use serde_json::{json, Value};
+
+const CORPUS: &str = "./tests/regression/deterministic.json";
+
+
+// The (message, context, pre-hash) inputs signed under each parameter set
+fn inputs() -> Vec<(Vec<u8>, Vec<u8>, Option<&'static str>)> {
+    vec![
+        (vec![], vec![], None),
+        ((0u8..33).collect(), b"regression".to_vec(), None),
+        ((0..1000u32).map(|i| (i * 7) as u8).collect(), vec![0xA5; 255], None),
+        (b"abc".to_vec(), b"hash".to_vec(), Some("SHA256")),
+        (b"abc".to_vec(), b"hash".to_vec(), Some("SHA512")),
+        (b"abc".to_vec(), vec![], Some("SHAKE128")),
+    ]
+}
+
+
+fn ph(name: &str) -> Ph {
+    match name {
+        "SHA256" => Ph::SHA256,
+        "SHA512" => Ph::SHA512,
+        "SHAKE128" => Ph::SHAKE128,
+        other => panic!("unexpected pre-hash function {other}"),
+    }
+}
+
+
+// Returns the public key and deterministic signature for the given inputs
+macro_rules! sign_case {
+    ($ml_dsa:ident, $seed:expr, $msg:expr, $ctx:expr, $ph:expr) => {{
+        let (pk, sk) = $ml_dsa::KG::keygen_from_seed($seed);
+        let sig = match $ph {
+            None => sk.try_sign_deterministic($msg, $ctx).unwrap(),
+            Some(name) => sk.try_hash_sign_deterministic($msg, $ctx, &ph(name)).unwrap(),
+        }
+        .into_signature();
+        let verified = match $ph {
+            None => pk.verify($msg, &sig, $ctx),
+            Some(name) => pk.hash_verify($msg, &sig, $ctx, &ph(name)),
+        };
+        assert!(verified);
+        (pk.into_bytes().to_vec(), sig.to_vec())
+    }};
+}
+
+
+fn sign(param_set: &str, seed: &[u8; 32], msg: &[u8], ctx: &[u8], ph: Option<&str>) -> (Vec<u8>, Vec<u8>) {
+    match param_set {
+        "ML-DSA-44" => sign_case!(ml_dsa_44, seed, msg, ctx, ph),
+        "ML-DSA-65" => sign_case!(ml_dsa_65, seed, msg, ctx, ph),
+        "ML-DSA-87" => sign_case!(ml_dsa_87, seed, msg, ctx, ph),
+        other => panic!("unexpected parameter set {other}"),
+    }
+}
+
+
+#[test]
+fn deterministic_corpus() {
+    let corpus: Value = serde_json::from_str(&std::fs::read_to_string(CORPUS).unwrap()).unwrap();
+    let cases = corpus["cases"].as_array().unwrap();
+    assert_eq!(cases.len(), 3 * inputs().len());
+    for case in cases {
+        let field = |name: &str| decode(case[name].as_str().unwrap()).unwrap();
+        let seed: [u8; 32] = field("seed").try_into().unwrap();
+        let param_set = case["param_set"].as_str().unwrap();
+        let (pk, sig) = sign(param_set, &seed, &field("message"), &field("ctx"), case["ph"].as_str());
+        assert_eq!(encode(pk), case["pk"].as_str().unwrap(), "{param_set} public key changed");
+        assert_eq!(encode(sig), case["sig"].as_str().unwrap(), "{param_set} signature changed");
+    }
+}
+
+
+#[test]
+#[ignore = "rewrites the recorded corpus; see the documentation at the top of this file"]
+fn regenerate_corpus() {
+    let mut cases = Vec::new();
+    for (p, param_set) in ["ML-DSA-44", "ML-DSA-65", "ML-DSA-87"].into_iter().enumerate() {
+        for (i, (msg, ctx, ph)) in inputs().into_iter().enumerate() {
+            let seed = [u8::try_from(16 * p + i).unwrap(); 32];
+            let (pk, sig) = sign(param_set, &seed, &msg, &ctx, ph);
+            cases.push(json!({
+                "param_set": param_set, "seed": encode(seed), "message": encode(msg),
+                "ctx": encode(ctx), "ph": ph, "pk": encode(pk), "sig": encode(sig),
+            }));
+        }
+    }
+    let corpus = json!({ "rnd": "all zero (deterministic signing)", "cases": cases });
+    std::fs::write(CORPUS, serde_json::to_string_pretty(&corpus).unwrap() + "\n").unwrap();
+}
@@ -0,0 +1,74 @@
+// Validates byte-identical keys and signatures across platforms, in particular big-endian
+// targets such as s390x and powerpc64 (see the `cross` job in `.github/workflows/test.yml`).
+// The expected digests were generated on a little-endian x86_64 host.
+
+use fips204::traits::{KeyGen, SerDes, Signer, Verifier};
+use fips204::Ph;
+use rand_chacha::rand_core::SeedableRng;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+
+
+fn digest(parts: &[&[u8]]) -> String {
+    let mut hasher = Shake256::default();
+    for part in parts {
+        hasher.update(part);
+    }
+    let mut out = [0u8; 32];
+    hasher.finalize_xof().read(&mut out);
+    hex::encode(out)
+}
+
+
+macro_rules! reproducible_test {
+    ($name:ident, $feature:literal, $module:ident, $expected_keys:literal, $expected_sig:literal, $expected_hash_sig:literal) => {
+        #[cfg(feature = $feature)]
+        #[test]
+        fn $name() {
+            use fips204::$module;
+            let message: Vec<u8> = (0..=255u8).collect();
+            let (pk, sk) = $module::KG::keygen_from_seed(&[0x42u8; 32]);
+            assert_eq!(
+                digest(&[&pk.clone().into_bytes(), &sk.duplicate().into_bytes()]),
+                $expected_keys,
+                "keys differ on target_endian = {}",
+                if cfg!(target_endian = "big") { "big" } else { "little" }
+            );
+
+            let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+            let sig = sk.try_sign_with_rng(&mut rng, &message, b"endian").unwrap();
+            assert!(pk.verify(&message, &sig, b"endian"));
+            assert_eq!(digest(&[&sig]), $expected_sig);
+
+            let sig = sk.try_hash_sign_with_rng(&mut rng, &message, b"endian", &Ph::SHA512).unwrap();
+            assert!(pk.hash_verify(&message, &sig, b"endian", &Ph::SHA512));
+            assert_eq!(digest(&[&sig]), $expected_hash_sig);
+        }
+    };
+}
+
+
+reproducible_test!(
+    reproducible_44,
+    "ml-dsa-44",
+    ml_dsa_44,
+    "dfab643ccb521c23186c3ec09a492ad3f49c7e4298c8c97b7e914e61df5cd4ba",
+    "87e4998c63d7613da8f3d1d5532c3fcb545a47d2913eb0e24f0e5d966470ee07",
+    "aebc70f698b56c526eef64b2ce7d89893f95eebcccc2434453012dd2d6990538"
+);
+reproducible_test!(
+    reproducible_65,
+    "ml-dsa-65",
+    ml_dsa_65,
+    "febf672faaef157b91933630a9bea4500c23bc0fdae4fd6f17730f9f52ca780e",
+    "164cba716c79f7db8b69730ae0938c94c91a9b7725336a28bd1ba2f7666aa58e",
+    "e652b944bcb5de3b5c98537d409695d41e897348fd2ea36f0080dfa536cdf9b0"
+);
+reproducible_test!(
+    reproducible_87,
+    "ml-dsa-87",
+    ml_dsa_87,
+    "e37af0a449d2395769280dd0fc8127f689be098790da77595eae5c82d3bdd87f",
+    "e51db916d83c79fe04b2f849e1b4374c3a5f2843f1aed56a209f0ef0df1f4019",
+    "44a840c584a43db9c43c6cda2636783a97c28b31c0bfc56d8a00b9c89c0d0307"
+);
@@ -0,0 +1,111 @@
+// Freezes how key generation and signing consume the provided random number generator: exactly
+// one `try_fill_bytes()` call for the 32-byte seed `ξ` or the 32-byte `rnd`, and nothing else.
+// Hence the outputs for a given generator (e.g., one replaying ACVP/NIST `seed` and `rnd`
+// values) are those of `keygen_from_seed()` and of the internal signing algorithm on those
+// bytes, which together with the pinned digests in `tests/reproducible.rs` keeps such vectors
+// reproducible across releases. A change to this behavior is a breaking change.
+
+use fips204::traits::{KeyGen, SerDes, Signer};
+use rand_chacha::rand_core::SeedableRng;
+use rand_core::{CryptoRng, RngCore};
+
+
+#[derive(Debug, PartialEq)]
+enum Call {
+    NextU32,
+    NextU64,
+    FillBytes(usize),
+    TryFillBytes(usize),
+}
+
+
+// Records every call made on the generator, and every byte supplied
+struct RecordingRng {
+    inner: rand_chacha::ChaCha8Rng,
+    calls: Vec<Call>,
+    output: Vec<u8>,
+}
+
+impl RecordingRng {
+    fn new(seed: u64) -> Self {
+        Self { inner: rand_chacha::ChaCha8Rng::seed_from_u64(seed), calls: Vec::new(), output: Vec::new() }
+    }
+
+    fn take(&mut self) -> (Vec<Call>, Vec<u8>) {
+        (core::mem::take(&mut self.calls), core::mem::take(&mut self.output))
+    }
+}
+
+impl RngCore for RecordingRng {
+    fn next_u32(&mut self) -> u32 {
+        self.calls.push(Call::NextU32);
+        self.inner.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.calls.push(Call::NextU64);
+        self.inner.next_u64()
+    }
+
+    fn fill_bytes(&mut self, out: &mut [u8]) {
+        self.calls.push(Call::FillBytes(out.len()));
+        self.inner.fill_bytes(out);
+        self.output.extend_from_slice(out);
+    }
+
+    fn try_fill_bytes(&mut self, out: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.calls.push(Call::TryFillBytes(out.len()));
+        self.inner.fill_bytes(out);
+        self.output.extend_from_slice(out);
+        Ok(())
+    }
+}
+
+impl CryptoRng for RecordingRng {}
+
+
+macro_rules! consumption_test {
+    ($name:ident, $feature:literal, $module:ident) => {
+        #[cfg(feature = $feature)]
+        #[test]
+        fn $name() {
+            use fips204::$module;
+            let mut rng = RecordingRng::new(1202);
+
+            // Key generation draws the seed ξ only
+            let (pk, sk) = $module::KG::try_keygen_with_rng(&mut rng).unwrap();
+            let (calls, xi) = rng.take();
+            assert_eq!(calls, [Call::TryFillBytes(32)]);
+            let (pk2, sk2) = $module::KG::keygen_from_seed(&xi.try_into().unwrap());
+            assert_eq!(pk.clone().into_bytes(), pk2.into_bytes());
+            assert_eq!(sk.duplicate().into_bytes(), sk2.into_bytes());
+
+            // Signing draws rnd only, whatever the message, context and pre-hash, and the
+            // signatures are those of the internal signing algorithm on that rnd
+            for (message, ctx) in [(&b""[..], &b""[..]), (&[0xA5u8; 3000][..], &[7u8; 255][..])] {
+                for ph in [None, Some(&fips204::Ph::SHA512)] {
+                    let sig = match ph {
+                        None => sk.try_sign_with_rng(&mut rng, message, ctx).unwrap(),
+                        Some(ph) => sk.try_hash_sign_with_rng(&mut rng, message, ctx, ph).unwrap(),
+                    };
+                    let (calls, rnd) = rng.take();
+                    assert_eq!(calls, [Call::TryFillBytes(32)]);
+                    #[cfg(all(feature = "hazmat", not(feature = "fips-mode")))]
+                    {
+                        let esk = $module::hazmat::sign_start(&sk.duplicate().into_bytes()).unwrap();
+                        let mu = fips204::message::mu(&$module::hazmat::private_key_tr(&esk), message, ctx, ph);
+                        let internal = $module::hazmat::sign_finish(&esk, &mu.unwrap(), rnd.try_into().unwrap());
+                        assert_eq!(internal, sig);
+                    }
+                    #[cfg(any(not(feature = "hazmat"), feature = "fips-mode"))]
+                    let _ = (sig, rnd);
+                }
+            }
+        }
+    };
+}
+
+
+consumption_test!(consumption_44, "ml-dsa-44", ml_dsa_44);
+consumption_test!(consumption_65, "ml-dsa-65", ml_dsa_65);
+consumption_test!(consumption_87, "ml-dsa-87", ml_dsa_87);
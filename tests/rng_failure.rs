@@ -0,0 +1,84 @@
+// Checks that every random number generator failure surfaces as an error, never as a panic or
+// a retry, using `rng::FailingRng` with every byte budget short of what an operation needs.
+//
+// Key generation draws the 32-byte seed `ξ` and signing the 32-byte `rnd`, each with a single
+// `try_fill_bytes()` call (see `tests/rng_consumption.rs`). The rejection sampling loops
+// (`ExpandA`, `ExpandS`, `ExpandMask` and `SampleInBall`, and the signing loop itself) draw
+// from SHAKE outputs derived from those values, never from the generator, so exhausting the
+// generator before each of these draws is covered by failing the single draw of each operation.
+
+use fips204::rng::FailingRng;
+use fips204::traits::{SerDes, Signer};
+use fips204::{Context, Ph};
+use rand_chacha::rand_core::SeedableRng;
+
+
+fn failing_rng(budget: usize) -> FailingRng<rand_chacha::ChaCha8Rng> {
+    FailingRng::new(rand_chacha::ChaCha8Rng::seed_from_u64(1209), budget)
+}
+
+
+macro_rules! failure_test {
+    ($name:ident, $feature:literal, $module:ident) => {
+        #[cfg(feature = $feature)]
+        #[test]
+        fn $name() {
+            use fips204::$module;
+
+            // Key generation fails for every budget short of the 32-byte seed, and then leaves
+            // caller-provided buffers untouched
+            for budget in 0..32 {
+                assert!($module::try_keygen_with_rng(&mut failing_rng(budget)).is_err());
+                assert!($module::Keypair::try_generate_with_rng(&mut failing_rng(budget)).is_err());
+                let (mut pk_out, mut sk_out) = ([0xA5u8; $module::PK_LEN], [0xA5u8; $module::SK_LEN]);
+                assert!($module::try_keygen_into_with_rng(&mut failing_rng(budget), &mut pk_out, &mut sk_out).is_err());
+                assert!(pk_out.iter().chain(sk_out.iter()).all(|&b| b == 0xA5));
+            }
+            let mut rng = failing_rng(32);
+            let (pk, sk) = $module::try_keygen_with_rng(&mut rng).unwrap();
+            assert_eq!(rng.remaining(), 0);
+
+            // Signing fails for every budget short of the 32-byte rnd, by every signing path
+            let sk_bytes = sk.duplicate().into_bytes();
+            let kp = $module::Keypair::try_from_bytes((pk.into_bytes(), sk_bytes)).unwrap();
+            for budget in 0..32 {
+                let mut rng = failing_rng(budget);
+                assert!(sk.try_sign_with_rng(&mut rng, b"msg", b"ctx").is_err());
+                assert!(sk.try_hash_sign_with_rng(&mut rng, b"msg", b"ctx", &Ph::SHA256).is_err());
+                let context = Context::new(b"ctx").with_hash(Ph::SHAKE128);
+                assert!(sk.try_sign_with_context_and_rng(&mut rng, b"msg", &context).is_err());
+                assert!(sk.try_sign_with_aux_rand(&mut rng, &[1u8; 32], b"msg", b"ctx").is_err());
+                assert!(sk.try_sign_fixed_ctx_with_rng(&mut rng, b"msg", b"ctx").is_err());
+                assert!(kp.try_sign_with_rng(&mut rng, b"msg", b"ctx").is_err());
+                assert!($module::try_sign_from_bytes_with_rng(&mut rng, &sk_bytes, b"msg", b"ctx").is_err());
+
+                let mut buf = [0x5Au8; 3 + $module::SIG_LEN];
+                assert!(sk.try_sign_in_place_with_rng(&mut failing_rng(budget), &mut buf, 3, b"ctx").is_err());
+                assert!(buf.iter().all(|&b| b == 0x5A));
+                #[cfg(feature = "alloc")]
+                assert!(sk.try_sign_many_with_rng(&mut failing_rng(budget), &[b"a", b"b"], b"ctx").is_err());
+            }
+
+            // A failure is reported as such, not as some other error
+            let err = sk.try_sign_with_rng(&mut failing_rng(0), b"msg", b"ctx").unwrap_err();
+            assert!(err.contains("random number generator failed"), "{err}");
+        }
+    };
+}
+
+
+failure_test!(failure_44, "ml-dsa-44", ml_dsa_44);
+failure_test!(failure_65, "ml-dsa-65", ml_dsa_65);
+failure_test!(failure_87, "ml-dsa-87", ml_dsa_87);
+
+
+// Splitting a seed draws one 32-byte coefficient vector per degree of the sharing polynomial;
+// a failure at any of them is an error
+#[cfg(feature = "sss")]
+#[test]
+fn failure_sss() {
+    for budget in (0..64).step_by(7) {
+        assert!(fips204::sss::try_split_seed_with_rng::<5>(&mut failing_rng(budget), &[7u8; 32], 3).is_err());
+    }
+    assert!(fips204::sss::try_split_seed_with_rng::<5>(&mut failing_rng(64), &[7u8; 32], 3).is_ok());
+}
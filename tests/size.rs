@@ -0,0 +1,61 @@
+//! Code size regression check for single parameter set builds.
+//!
+//! Builds `examples/size_probe.rs` (ML-DSA-65 key generation, signing and verification) in
+//! release mode with `default-features = false, features = ["ml-dsa-65"]`, then inspects the
+//! symbols of the resulting binary (requires `nm` on the path):
+//! * no code instantiated for the other parameter sets may remain; the generic engine is
+//!   monomorphized over `K, L`, which are `4, 4` for ML-DSA-44 and `8, 7` for ML-DSA-87, and
+//! * the code attributable to this crate, SHA-3 and the probe itself must stay within
+//!   `BUDGET` bytes.
+//!
+//! Run with:
+//! ~~~
+//! $ cargo test --test size -- --ignored --nocapture
+//! ~~~
+//!
+//! The figures depend upon the compiler version and target (measured with rustc 1.95 on
+//! `x86_64-unknown-linux-gnu`); revisit the budget when either changes.
+#![cfg(feature = "ml-dsa-65")]
+
+use std::process::Command;
+
+const BUDGET: usize = 28 * 1024;
+
+
+#[test]
+#[ignore = "runs a separate release build and requires nm; see the documentation at the top of this file"]
+fn single_param_set_size() {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let target_dir = format!("{manifest_dir}/target/size-probe");
+    let status = Command::new(env!("CARGO"))
+        .args(["build", "--release", "--example", "size_probe"])
+        .args(["--no-default-features", "--features", "ml-dsa-65", "--target-dir", &target_dir])
+        .current_dir(manifest_dir)
+        .status()
+        .expect("failed to run cargo");
+    assert!(status.success(), "size probe build failed");
+
+    let output = Command::new("nm")
+        .args(["--demangle", "--print-size"])
+        .arg(format!("{target_dir}/release/examples/size_probe"))
+        .output()
+        .expect("failed to run nm; is it installed?");
+    assert!(output.status.success(), "nm failed");
+    let symbols = String::from_utf8(output.stdout).unwrap();
+
+    let residue = regex::Regex::new(r"4_usize, ?4_usize|8_usize, ?7_usize|ml_dsa_44|ml_dsa_87").unwrap();
+    let (mut ml_dsa_65, mut size) = (false, 0);
+    for line in symbols.lines().filter(|l| l.contains("fips204") || l.contains("size_probe")) {
+        assert!(!residue.is_match(line), "code from another parameter set remains: {line}");
+        ml_dsa_65 |= line.contains("6_usize,5_usize") || line.contains("6_usize, 5_usize");
+    }
+    for line in symbols.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() > 3 && ["fips204", "sha3", "keccak", "size_probe"].iter().any(|c| line.contains(c)) {
+            size += usize::from_str_radix(fields[1], 16).unwrap();
+        }
+    }
+    assert!(ml_dsa_65, "no ML-DSA-65 code found; are symbols stripped?");
+    println!("ML-DSA-65 only: {size} bytes of code and tables (budget {BUDGET})");
+    assert!(size <= BUDGET, "code size {size} exceeds the budget of {BUDGET} bytes");
+}
@@ -0,0 +1,168 @@
+//! Upper bounds on the stack used by verification (and, with the `memory-report` feature, the
+//! reported bounds for key generation and signing), for integrators sizing RTOS task stacks.
+//!
+//! Each operation runs on a thread whose stack is limited to the budget; exceeding it
+//! overflows the stack and aborts the process. As an overflow cannot be caught, each run is
+//! performed in a separate child process of this test binary.
+//!
+//! The budgets apply to optimized builds; unoptimized builds use considerably more stack, so
+//! are checked against a larger multiple. To print the measured figures:
+//! ~~~
+//! $ cargo test --release --test stack -- --ignored --nocapture
+//! ~~~
+#![cfg(all(feature = "ml-dsa-44", feature = "ml-dsa-65", feature = "ml-dsa-87"))]
+
+use fips204::traits::{KeyGen, Signer, Verifier};
+use fips204::{ml_dsa_44, ml_dsa_65, ml_dsa_87};
+use rand_chacha::rand_core::SeedableRng;
+use std::process::Command;
+
+const PARAM_ENV: &str = "FIPS204_STACK_PARAM";
+const OP_ENV: &str = "FIPS204_STACK_OP";
+const SIZE_ENV: &str = "FIPS204_STACK_SIZE";
+
+// The published verify budgets (see README.md) in bytes, for optimized builds
+const BUDGETS: [(&str, usize); 3] =
+    [("ml_dsa_44", 60 * 1024), ("ml_dsa_65", 80 * 1024), ("ml_dsa_87", 104 * 1024)];
+
+const OPS: [&str; 3] = ["keygen", "sign", "verify"];
+
+
+macro_rules! run_on_stack {
+    ($ml_dsa:ident, $op:expr, $size:expr) => {{
+        let (pk, sk) = $ml_dsa::KG::keygen_from_seed(&[7; 32]);
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        let sig = sk.try_sign_with_rng(&mut rng, b"message", b"ctx").unwrap();
+        std::thread::scope(|s| {
+            let builder = std::thread::Builder::new().stack_size($size);
+            let handle = match $op {
+                "keygen" => builder.spawn_scoped(s, || $ml_dsa::try_keygen_with_rng(&mut rng).is_ok()),
+                "sign" => builder.spawn_scoped(s, || sk.try_sign_with_rng(&mut rng, b"message", b"ctx").is_ok()),
+                "verify" => builder.spawn_scoped(s, || pk.verify(b"message", &sig, b"ctx")),
+                _ => unreachable!(),
+            };
+            assert!(handle.unwrap().join().unwrap());
+        });
+    }};
+}
+
+
+// Child: run the operation with the given parameter set on a thread with the given stack size.
+// The self-test is run beforehand, so that with `fips-mode` the operation only checks the module
+// state (the self-test's own stack use is checked by `self_test_within_stack_budget`).
+fn child(param: &str, op: &str, size: usize) {
+    assert!(fips204::self_test().passed());
+    match param {
+        "self_test" => std::thread::scope(|s| {
+            let handle = std::thread::Builder::new().stack_size(size).spawn_scoped(s, fips204::self_test);
+            assert!(handle.unwrap().join().unwrap().passed());
+        }),
+        "ml_dsa_44" => run_on_stack!(ml_dsa_44, op, size),
+        "ml_dsa_65" => run_on_stack!(ml_dsa_65, op, size),
+        "ml_dsa_87" => run_on_stack!(ml_dsa_87, op, size),
+        _ => unreachable!(),
+    }
+}
+
+
+// Runs the child when this process is one, returning whether it was
+fn run_child() -> bool {
+    let (Ok(param), Ok(size)) = (std::env::var(PARAM_ENV), std::env::var(SIZE_ENV)) else { return false };
+    let op = std::env::var(OP_ENV).unwrap_or_else(|_| "verify".to_string());
+    child(&param, &op, size.parse().unwrap());
+    true
+}
+
+
+// Parent: returns whether the operation completed within `size` bytes of stack
+fn fits(test: &str, param: &str, op: &str, size: usize) -> bool {
+    Command::new(std::env::current_exe().unwrap())
+        .args([test, "--exact", "--include-ignored", "--test-threads=1"])
+        .env(PARAM_ENV, param)
+        .env(OP_ENV, op)
+        .env(SIZE_ENV, size.to_string())
+        .output()
+        .expect("failed to re-run the test binary")
+        .status
+        .success()
+}
+
+
+#[test]
+fn verify_within_stack_budget() {
+    if run_child() {
+        return;
+    }
+    let scale = if cfg!(debug_assertions) { 2 } else { 1 };
+    for (param, budget) in BUDGETS {
+        assert!(
+            fits("verify_within_stack_budget", param, "verify", budget * scale),
+            "{param} verify exceeds its {budget} byte stack budget (x{scale})"
+        );
+    }
+}
+
+
+// The stack consts reported per parameter set and operation
+#[cfg(feature = "memory-report")]
+#[test]
+fn operations_within_reported_stack() {
+    if run_child() {
+        return;
+    }
+    let reported = [
+        ("ml_dsa_44", [ml_dsa_44::KEYGEN_STACK_BYTES, ml_dsa_44::SIGN_STACK_BYTES, ml_dsa_44::VERIFY_STACK_BYTES]),
+        ("ml_dsa_65", [ml_dsa_65::KEYGEN_STACK_BYTES, ml_dsa_65::SIGN_STACK_BYTES, ml_dsa_65::VERIFY_STACK_BYTES]),
+        ("ml_dsa_87", [ml_dsa_87::KEYGEN_STACK_BYTES, ml_dsa_87::SIGN_STACK_BYTES, ml_dsa_87::VERIFY_STACK_BYTES]),
+    ];
+    let scale = if cfg!(debug_assertions) { 2 } else { 1 };
+    for ((param, bytes), (_, budget)) in reported.into_iter().zip(BUDGETS) {
+        assert_eq!(bytes[2], budget, "{param} verify const differs from the published budget");
+        for (op, bytes) in OPS.into_iter().zip(bytes) {
+            assert!(
+                fits("operations_within_reported_stack", param, op, bytes * scale),
+                "{param} {op} exceeds its reported {bytes} bytes of stack (x{scale})"
+            );
+        }
+    }
+    assert_eq!(ml_dsa_44::STATIC_BYTES, fips204::CONST_TABLE_BYTES);
+}
+
+
+#[cfg(feature = "fips-mode")]
+#[test]
+fn self_test_within_stack_budget() {
+    if run_child() {
+        return;
+    }
+    let scale = if cfg!(debug_assertions) { 2 } else { 1 };
+    let budget = fips204::fips::SELF_TEST_STACK_BYTES;
+    assert!(
+        fits("self_test_within_stack_budget", "self_test", "self_test", budget * scale),
+        "self_test() exceeds its {budget} byte stack budget (x{scale})"
+    );
+}
+
+
+#[test]
+#[ignore = "spawns many processes; see the documentation at the top of this file"]
+fn measure_stack() {
+    if run_child() {
+        return;
+    }
+    let params = ["ml_dsa_44", "ml_dsa_65", "ml_dsa_87"];
+    let cases = params.into_iter().flat_map(|param| OPS.map(|op| (param, op))).chain([("self_test", "self_test")]);
+    for (param, op) in cases {
+        // Binary search for the smallest stack, to within 256 bytes
+        let (mut lo, mut hi) = (0, 1 << 20);
+        while hi - lo > 256 {
+            let mid = (lo + hi) / 2;
+            if fits("measure_stack", param, op, mid) {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+        println!("{param} {op}: {hi} bytes");
+    }
+}
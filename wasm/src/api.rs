@@ -0,0 +1,159 @@
+// The typed JavaScript API of the npm package: one class per parameter set with static
+// keygen/sign/verify functions, plus a streaming verifier class for large inputs.
+use fips204::traits::{KeyGen, SerDes, Signer, Verifier};
+use fips204::{message, ml_dsa_44, ml_dsa_65, ml_dsa_87};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+use wasm_bindgen::prelude::*;
+
+
+/// An encoded public key and private key, as returned by `keygen()`.
+#[wasm_bindgen]
+pub struct KeyPair {
+    public_key: Vec<u8>,
+    private_key: Vec<u8>,
+}
+
+
+#[wasm_bindgen]
+impl KeyPair {
+    /// The encoded public key.
+    #[wasm_bindgen(getter, js_name = publicKey)]
+    pub fn public_key(&self) -> Vec<u8> { self.public_key.clone() }
+
+    /// The encoded private key.
+    #[wasm_bindgen(getter, js_name = privateKey)]
+    pub fn private_key(&self) -> Vec<u8> { self.private_key.clone() }
+}
+
+
+fn array<const N: usize>(bytes: &[u8], what: &str) -> Result<[u8; N], JsError> {
+    bytes.try_into().map_err(|_| JsError::new(&format!("{what} must be {N} bytes, got {}", bytes.len())))
+}
+
+
+macro_rules! js_api {
+    ($set:ident, $class:ident, $js_class:literal, $verifier:ident, $js_verifier:literal) => {
+        /// Key generation, signing and verification for one parameter set. Keys and signatures
+        /// use the FIPS 204 encodings, so interoperate with other implementations.
+        #[wasm_bindgen(js_name = $js_class)]
+        pub struct $class {}
+
+
+        #[wasm_bindgen(js_class = $js_class)]
+        impl $class {
+            /// Public key length in bytes.
+            #[wasm_bindgen(js_name = publicKeyLength)]
+            pub fn public_key_length() -> usize { $set::PK_LEN }
+
+            /// Private key length in bytes.
+            #[wasm_bindgen(js_name = privateKeyLength)]
+            pub fn private_key_length() -> usize { $set::SK_LEN }
+
+            /// Signature length in bytes.
+            #[wasm_bindgen(js_name = signatureLength)]
+            pub fn signature_length() -> usize { $set::SIG_LEN }
+
+            /// Generates a key pair using the platform's (Web Crypto) random number generator.
+            pub fn keygen() -> Result<KeyPair, JsError> {
+                let (pk, sk) = $set::try_keygen().map_err(JsError::new)?;
+                Ok(KeyPair { public_key: pk.into_bytes().to_vec(), private_key: sk.into_bytes().to_vec() })
+            }
+
+            /// Generates a key pair from a 32-byte seed, as per `ML-DSA.KeyGen_internal()`.
+            #[wasm_bindgen(js_name = keygenFromSeed)]
+            pub fn keygen_from_seed(seed: &[u8]) -> Result<KeyPair, JsError> {
+                let (pk, sk) = $set::KG::keygen_from_seed(&array(seed, "seed")?);
+                Ok(KeyPair { public_key: pk.into_bytes().to_vec(), private_key: sk.into_bytes().to_vec() })
+            }
+
+            /// Signs the message under the context string (of at most 255 bytes) with the
+            /// hedged variant, using the platform's random number generator.
+            pub fn sign(private_key: &[u8], message: &[u8], ctx: &[u8]) -> Result<Vec<u8>, JsError> {
+                let sk = $set::PrivateKey::try_from_bytes(array(private_key, "privateKey")?).map_err(JsError::new)?;
+                Ok(sk.try_sign(message, ctx).map_err(JsError::new)?.to_vec())
+            }
+
+            /// Signs the message under the context string with the deterministic variant, so
+            /// the same inputs always give the same signature.
+            #[wasm_bindgen(js_name = signDeterministic)]
+            pub fn sign_deterministic(private_key: &[u8], message: &[u8], ctx: &[u8]) -> Result<Vec<u8>, JsError> {
+                let esk = $set::hazmat::sign_start(&array(private_key, "privateKey")?).map_err(JsError::new)?;
+                let mu = message::mu(&$set::hazmat::private_key_tr(&esk), message, ctx, None).map_err(JsError::new)?;
+                Ok($set::hazmat::sign_finish(&esk, &mu, [0u8; 32]).to_vec())
+            }
+
+            /// Returns whether the signature over the message under the context string is
+            /// valid; a wrongly sized key or signature is an error.
+            pub fn verify(public_key: &[u8], message: &[u8], signature: &[u8], ctx: &[u8]) -> Result<bool, JsError> {
+                let pk = $set::PublicKey::try_from_bytes(array(public_key, "publicKey")?).map_err(JsError::new)?;
+                Ok(pk.verify(message, &array(signature, "signature")?, ctx))
+            }
+        }
+
+
+        /// Verifies a signature over a message supplied in chunks (e.g., read from a large
+        /// bundle file), without holding the whole message in memory.
+        #[wasm_bindgen(js_name = $js_verifier)]
+        pub struct $verifier {
+            pk: $set::PublicKey,
+            hasher: Shake256,
+        }
+
+
+        #[wasm_bindgen(js_class = $js_verifier)]
+        impl $verifier {
+            /// Starts verifying a message under the context string (of at most 255 bytes).
+            #[wasm_bindgen(constructor)]
+            pub fn new(public_key: &[u8], ctx: &[u8]) -> Result<$verifier, JsError> {
+                let pk = $set::PublicKey::try_from_bytes(array(public_key, "publicKey")?).map_err(JsError::new)?;
+                let mut prefix = [0u8; message::MAX_PREFIX_LEN];
+                let len = message::encode_prefix(ctx, None, &mut prefix).map_err(JsError::new)?;
+                let mut hasher = Shake256::default();
+                hasher.update(&pk.tr());
+                hasher.update(&prefix[..len]);
+                Ok(Self { pk, hasher })
+            }
+
+            /// Absorbs the next chunk of the message.
+            pub fn update(&mut self, chunk: &[u8]) { self.hasher.update(chunk); }
+
+            /// Returns whether the signature over all of the absorbed chunks is valid.
+            pub fn verify(self, signature: &[u8]) -> Result<bool, JsError> {
+                let mut mu = [0u8; 64];
+                self.hasher.finalize_xof().read(&mut mu);
+                Ok($set::hazmat::verify_finish(&self.pk, &mu, &array(signature, "signature")?))
+            }
+        }
+    };
+}
+
+
+js_api!(ml_dsa_44, MlDsa44, "MlDsa44", MlDsa44Verifier, "MlDsa44Verifier");
+js_api!(ml_dsa_65, MlDsa65, "MlDsa65", MlDsa65Verifier, "MlDsa65Verifier");
+js_api!(ml_dsa_87, MlDsa87, "MlDsa87", MlDsa87Verifier, "MlDsa87Verifier");
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_verify_streaming() {
+        let keys = MlDsa65::keygen_from_seed(&[7u8; 32]).unwrap();
+        let message: Vec<u8> = (0..10_000u32).map(|i| i.to_le_bytes()[0]).collect();
+        let sig = MlDsa65::sign(&keys.private_key(), &message, b"ctx").unwrap();
+        assert!(MlDsa65::verify(&keys.public_key(), &message, &sig, b"ctx").unwrap());
+
+        let det = MlDsa65::sign_deterministic(&keys.private_key(), &message, b"ctx").unwrap();
+        assert_eq!(det, MlDsa65::sign_deterministic(&keys.private_key(), &message, b"ctx").unwrap());
+        assert!(MlDsa65::verify(&keys.public_key(), &message, &det, b"ctx").unwrap());
+
+        let mut verifier = MlDsa65Verifier::new(&keys.public_key(), b"ctx").unwrap();
+        message.chunks(999).for_each(|chunk| verifier.update(chunk));
+        assert!(verifier.verify(&sig).unwrap());
+        let mut verifier = MlDsa65Verifier::new(&keys.public_key(), b"other").unwrap();
+        verifier.update(&message);
+        assert!(!verifier.verify(&sig).unwrap());
+    }
+}
@@ -1,4 +1,7 @@
 use wasm_bindgen::prelude::*;
+
+mod api;
+pub use api::*;
 use rand_chacha::rand_core::SeedableRng;
 use fips204::ml_dsa_44;
 use fips204::traits::{SerDes, Signer, Verifier};